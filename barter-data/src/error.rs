@@ -24,6 +24,9 @@ pub enum DataError {
         prev_last_update_id: u64,
         first_update_id: u64,
     },
+
+    #[error("exhausted {attempts} MarketStream re-connection attempts for exchange: {exchange}")]
+    ReconnectLimit { exchange: ExchangeId, attempts: u32 },
 }
 
 impl DataError {
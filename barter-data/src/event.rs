@@ -43,6 +43,12 @@ pub struct MarketEvent<InstrumentId = Instrument, T = DataKind> {
     pub exchange: Exchange,
     pub instrument: InstrumentId,
     pub kind: T,
+    /// Monotonically increasing sequence number assigned by the
+    /// [`consume`](crate::streams::consumer::consume) loop of the exchange stream that produced
+    /// the event, independent of the timestamps. Downstream data handlers preserve it, enabling
+    /// consumers to enforce strict ordering & detect dropped events. Zero until assigned.
+    #[serde(default)]
+    pub sequence: u64,
 }
 
 /// Available kinds of normalised Barter [`MarketEvent<T>`](MarketEvent).
@@ -74,6 +80,7 @@ impl<InstrumentId> From<MarketEvent<InstrumentId, PublicTrade>>
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
+            sequence: event.sequence,
             kind: DataKind::Trade(event.kind),
         }
     }
@@ -88,6 +95,7 @@ impl<InstrumentId> From<MarketEvent<InstrumentId, OrderBookL1>>
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
+            sequence: event.sequence,
             kind: DataKind::OrderBookL1(event.kind),
         }
     }
@@ -102,6 +110,7 @@ impl<InstrumentId> From<MarketEvent<InstrumentId, OrderBook>>
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
+            sequence: event.sequence,
             kind: DataKind::OrderBook(event.kind),
         }
     }
@@ -114,6 +123,7 @@ impl<InstrumentId> From<MarketEvent<InstrumentId, Candle>> for MarketEvent<Instr
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
+            sequence: event.sequence,
             kind: DataKind::Candle(event.kind),
         }
     }
@@ -128,6 +138,7 @@ impl<InstrumentId> From<MarketEvent<InstrumentId, Liquidation>>
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
+            sequence: event.sequence,
             kind: DataKind::Liquidation(event.kind),
         }
     }
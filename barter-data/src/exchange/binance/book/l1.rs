@@ -78,6 +78,7 @@ impl<InstrumentId> From<(ExchangeId, InstrumentId, BinanceOrderBookL1)>
                 best_bid: Level::new(book.best_bid_price, book.best_bid_amount),
                 best_ask: Level::new(book.best_ask_price, book.best_ask_amount),
             },
+            sequence: 0,
         })])
     }
 }
@@ -98,6 +98,7 @@ impl<InstrumentId> From<(ExchangeId, InstrumentId, BinanceLiquidation)>
                 quantity: liquidation.order.quantity,
                 time: liquidation.order.time,
             },
+            sequence: 0,
         })])
     }
 }
@@ -92,6 +92,7 @@ impl<InstrumentId> From<(ExchangeId, InstrumentId, BinanceTrade)>
                 amount: trade.amount,
                 side: trade.side,
             },
+            sequence: 0,
         })])
     }
 }
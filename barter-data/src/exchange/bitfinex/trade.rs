@@ -60,6 +60,7 @@ impl<InstrumentId> From<(ExchangeId, InstrumentId, BitfinexTrade)>
                 amount: trade.amount,
                 side: trade.side,
             },
+            sequence: 0,
         })])
     }
 }
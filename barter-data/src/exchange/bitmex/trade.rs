@@ -69,6 +69,7 @@ impl<InstrumentId: Clone> From<(ExchangeId, InstrumentId, BitmexTrade)>
                             amount: trade.amount,
                             side: trade.side,
                         },
+                        sequence: 0,
                     })
                 })
                 .collect(),
@@ -63,6 +63,7 @@ impl<InstrumentId> From<(ExchangeId, InstrumentId, CoinbaseTrade)>
                 amount: trade.amount,
                 side: trade.side,
             },
+            sequence: 0,
         })])
     }
 }
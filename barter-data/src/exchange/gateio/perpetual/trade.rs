@@ -96,6 +96,7 @@ impl<InstrumentId: Clone> From<(ExchangeId, InstrumentId, GateioFuturesTrades)>
                             Side::Sell
                         },
                     },
+                    sequence: 0,
                 })
             })
             .collect()
@@ -68,6 +68,7 @@ impl<InstrumentId> From<(ExchangeId, InstrumentId, GateioSpotTrade)>
                 amount: trade.data.amount,
                 side: trade.data.side,
             },
+            sequence: 0,
         })])
     }
 }
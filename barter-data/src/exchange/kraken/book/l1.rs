@@ -70,6 +70,7 @@ impl<InstrumentId> From<(ExchangeId, InstrumentId, KrakenOrderBookL1)>
                     best_bid: Level::new(book.spread.best_bid_price, book.spread.best_bid_amount),
                     best_ask: Level::new(book.spread.best_ask_price, book.spread.best_ask_amount),
                 },
+                sequence: 0,
             })]),
             KrakenOrderBookL1::Event(_) => MarketIter(vec![]),
         }
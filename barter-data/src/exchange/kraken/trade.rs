@@ -78,6 +78,7 @@ impl<InstrumentId: Clone> From<(ExchangeId, InstrumentId, KrakenTrades)>
                             amount: trade.amount,
                             side: trade.side,
                         },
+                        sequence: 0,
                     })
                 })
                 .collect(),
@@ -110,6 +110,7 @@ impl<InstrumentId: Clone> From<(ExchangeId, InstrumentId, OkxTrades)>
                         amount: trade.amount,
                         side: trade.side,
                     },
+                    sequence: 0,
                 })
             })
             .collect()
@@ -1,4 +1,7 @@
-use super::{consumer::consume, Streams};
+use super::{
+    consumer::{consume_with_policy, ReconnectPolicy},
+    Streams,
+};
 use crate::{
     error::DataError,
     event::MarketEvent,
@@ -30,6 +33,7 @@ where
 {
     pub channels: HashMap<ExchangeId, ExchangeChannel<MarketEvent<Instrument, Kind::Event>>>,
     pub futures: Vec<SubscribeFuture>,
+    pub reconnect_policy: ReconnectPolicy,
 }
 
 impl<Kind> Debug for StreamBuilder<Kind>
@@ -40,6 +44,7 @@ where
         f.debug_struct("StreamBuilder<SubscriptionKind>")
             .field("channels", &self.channels)
             .field("num_futures", &self.futures.len())
+            .field("reconnect_policy", &self.reconnect_policy)
             .finish()
     }
 }
@@ -53,6 +58,17 @@ where
         Self {
             channels: HashMap::new(),
             futures: Vec::new(),
+            reconnect_policy: ReconnectPolicy::default(),
+        }
+    }
+
+    /// Configure the [`ReconnectPolicy`] used to re-connect each [`MarketStream`](crate::MarketStream)
+    /// of the [`Subscription`]s subsequently added via the [`subscribe()`](StreamBuilder::subscribe())
+    /// method.
+    pub fn reconnect_policy(self, reconnect_policy: ReconnectPolicy) -> Self {
+        Self {
+            reconnect_policy,
+            ..self
         }
     }
 
@@ -77,6 +93,7 @@ where
         // Acquire channel Sender to send Market<Kind::Event> from consumer loop to user
         // '--> Add ExchangeChannel Entry if this Exchange <--> SubscriptionKind combination is new
         let exchange_tx = self.channels.entry(Exchange::ID).or_default().tx.clone();
+        let reconnect_policy = self.reconnect_policy;
 
         // Add Future that once awaited will yield the Result<(), SocketError> of subscribing
         self.futures.push(Box::pin(async move {
@@ -88,7 +105,11 @@ where
             subscriptions.dedup();
 
             // Spawn a MarketStream consumer loop with these Subscriptions<Exchange, Kind>
-            tokio::spawn(consume(subscriptions, exchange_tx));
+            tokio::spawn(consume_with_policy(
+                subscriptions,
+                exchange_tx,
+                reconnect_policy,
+            ));
 
             Ok(())
         }));
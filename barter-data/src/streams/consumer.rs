@@ -1,13 +1,13 @@
 use crate::{
     error::DataError,
     event::MarketEvent,
-    exchange::StreamSelector,
+    exchange::{ExchangeId, StreamSelector},
     instrument::InstrumentData,
     subscription::{Subscription, SubscriptionKind},
     Identifier, MarketStream,
 };
-use futures::StreamExt;
-use std::time::Duration;
+use futures::{Stream, StreamExt};
+use std::{future::Future, time::Duration};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
@@ -16,14 +16,79 @@ use tracing::{debug, error, info, warn};
 /// of repeated disconnections with re-initialisation failures.
 pub const STARTING_RECONNECT_BACKOFF_MS: u64 = 125;
 
+/// Default maximum duration the [`consume`] function waits between attempts to re-initialise a
+/// [`MarketStream`], capping the exponential backoff.
+pub const MAX_RECONNECT_BACKOFF_MS: u64 = 60_000;
+
+/// Policy used by a [`MarketStream`] consumer loop to re-connect after it's [`MarketStream`] is
+/// dropped by the exchange, or ends with a terminal [`DataError`].
+///
+/// Each consecutive re-connection attempt waits for an exponential backoff of the `base_delay`
+/// (ie/ `base_delay`, `2 * base_delay`, `4 * base_delay`, etc), capped at the `max_delay`. The
+/// attempt count is reset once a [`MarketStream`] is successfully re-initialised.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct ReconnectPolicy {
+    /// Delay before the first re-connection attempt.
+    pub base_delay: Duration,
+    /// Maximum delay between consecutive re-connection attempts, capping the exponential backoff.
+    pub max_delay: Duration,
+    /// Maximum number of consecutive re-connection attempts before the consumer loop gives up.
+    /// `None` retries forever.
+    pub max_retries: Option<u32>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(STARTING_RECONNECT_BACKOFF_MS),
+            max_delay: Duration::from_millis(MAX_RECONNECT_BACKOFF_MS),
+            max_retries: None,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Backoff to wait before the provided (1-indexed) consecutive re-connection attempt.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        2_u32
+            .checked_pow(attempt.saturating_sub(1))
+            .and_then(|multiplier| self.base_delay.checked_mul(multiplier))
+            .map_or(self.max_delay, |backoff| backoff.min(self.max_delay))
+    }
+}
+
+/// Central [`MarketEvent<T>`](MarketEvent) consumer loop, re-connecting using the default
+/// [`ReconnectPolicy`]. See [`consume_with_policy`].
+pub async fn consume<Exchange, Instrument, Kind>(
+    subscriptions: Vec<Subscription<Exchange, Instrument, Kind>>,
+    exchange_tx: mpsc::UnboundedSender<MarketEvent<Instrument::Id, Kind::Event>>,
+) -> Result<(), DataError>
+where
+    Exchange: StreamSelector<Instrument, Kind>,
+    Kind: SubscriptionKind,
+    Instrument: InstrumentData,
+    Subscription<Exchange, Instrument, Kind>:
+        Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    consume_with_policy(subscriptions, exchange_tx, ReconnectPolicy::default()).await
+}
+
 /// Central [`MarketEvent<T>`](MarketEvent) consumer loop.
 ///
 /// Initialises an exchange [`MarketStream`] using a collection of [`Subscription`]s. Consumed
-/// events are distributed downstream via the `exchange_tx mpsc::UnboundedSender`. A re-connection
-/// mechanism with an exponential backoff policy is utilised to ensure maximum up-time.
-pub async fn consume<Exchange, Instrument, Kind>(
+/// events are assigned the next sequence number of this consumer loop (starting from one, and
+/// continuing across re-connections), then distributed downstream via the
+/// `exchange_tx mpsc::UnboundedSender`. If the
+/// [`MarketStream`] ends or yields a terminal [`DataError`], it is re-initialised according to
+/// the [`ReconnectPolicy`] to ensure maximum up-time.
+///
+/// Returns a [`DataError`] if the initial [`MarketStream`] initialisation fails, or a
+/// [`DataError::ReconnectLimit`] once the `max_retries` of the [`ReconnectPolicy`] are
+/// exhausted. In either case the `exchange_tx` is dropped, disconnecting the downstream receiver.
+pub async fn consume_with_policy<Exchange, Instrument, Kind>(
     subscriptions: Vec<Subscription<Exchange, Instrument, Kind>>,
     exchange_tx: mpsc::UnboundedSender<MarketEvent<Instrument::Id, Kind::Event>>,
+    policy: ReconnectPolicy,
 ) -> Result<(), DataError>
 where
     Exchange: StreamSelector<Instrument, Kind>,
@@ -38,35 +103,84 @@ where
     info!(
         %exchange,
         ?subscriptions,
-        policy = "retry connection with exponential backoff",
+        ?policy,
         "MarketStream consumer loop running",
     );
 
-    // Consumer loop retry parameters
+    let subscriptions = &subscriptions;
+    consume_stream(
+        exchange,
+        || Exchange::Stream::init(subscriptions),
+        exchange_tx,
+        policy,
+    )
+    .await
+}
+
+/// Consumer loop driving [`consume_with_policy`], generic over the `init` function that
+/// (re-)initialises the [`MarketStream`].
+async fn consume_stream<Init, InitFut, EventStream, InstrumentId, T>(
+    exchange: ExchangeId,
+    mut init: Init,
+    exchange_tx: mpsc::UnboundedSender<MarketEvent<InstrumentId, T>>,
+    policy: ReconnectPolicy,
+) -> Result<(), DataError>
+where
+    Init: FnMut() -> InitFut,
+    InitFut: Future<Output = Result<EventStream, DataError>>,
+    EventStream: Stream<Item = Result<MarketEvent<InstrumentId, T>, DataError>> + Unpin,
+    InstrumentId: std::fmt::Debug,
+    T: std::fmt::Debug,
+{
+    // Consecutive re-connection attempt since the MarketStream was last initialised (0 if never)
     let mut attempt: u32 = 0;
-    let mut backoff_ms: u64 = STARTING_RECONNECT_BACKOFF_MS;
+
+    // Sequence number of the latest MarketEvent distributed downstream (0 if none)
+    let mut sequence: u64 = 0;
 
     'retry: loop {
-        // Increment retry parameters at start of every iteration
-        attempt += 1;
-        backoff_ms *= 2;
+        if attempt > 0 {
+            // Give up once the ReconnectPolicy max_retries are exhausted
+            if let Some(max_retries) = policy.max_retries.filter(|max| attempt > *max) {
+                error!(
+                    %exchange,
+                    max_retries,
+                    action = "shutting down Stream",
+                    "exhausted MarketStream re-connection attempts"
+                );
+                break Err(DataError::ReconnectLimit {
+                    exchange,
+                    attempts: max_retries,
+                });
+            }
+
+            let backoff = policy.backoff(attempt);
+            warn!(
+                %exchange,
+                attempt,
+                ?backoff,
+                action = "attempt re-connection after backoff",
+                "re-connecting MarketStream"
+            );
+            tokio::time::sleep(backoff).await;
+        }
+
         info!(%exchange, attempt, "attempting to initialise MarketStream");
 
-        // Attempt to initialise MarketStream: if it fails on first attempt return DataError
-        let mut stream = match Exchange::Stream::init(&subscriptions).await {
+        // Attempt to initialise MarketStream: if the initial connection fails return DataError
+        let mut stream = match init().await {
             Ok(stream) => {
                 info!(%exchange, attempt, "successfully initialised MarketStream");
-                attempt = 0;
-                backoff_ms = STARTING_RECONNECT_BACKOFF_MS;
                 stream
             }
             Err(error) => {
                 error!(%exchange, attempt, ?error, "failed to initialise MarketStream");
 
-                // Exit function if Stream::init failed the first attempt, else retry
-                if attempt == 1 {
+                // Exit function if the initial Stream::init failed, else retry after backoff
+                if attempt == 0 {
                     return Err(error);
                 } else {
+                    attempt += 1;
                     continue;
                 }
             }
@@ -76,7 +190,10 @@ where
         while let Some(event_result) = stream.next().await {
             match event_result {
                 // If Ok: send MarketEvent<T> to exchange receiver
-                Ok(market_event) => {
+                Ok(mut market_event) => {
+                    sequence += 1;
+                    market_event.sequence = sequence;
+
                     if let Err(error) = exchange_tx.send(market_event) {
                         debug!(
                             payload = ?error.0,
@@ -111,13 +228,116 @@ where
             }
         }
 
-        // If MarketStream ends unexpectedly, attempt re-connection after backoff_ms
+        // If MarketStream ends unexpectedly, attempt re-connection after backoff
+        // '--> consecutive attempt count restarts since the MarketStream was initialised
         warn!(
             %exchange,
-            backoff_ms,
             action = "attempt re-connection after backoff",
             "exchange MarketStream unexpectedly ended"
         );
-        tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+        attempt = 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::error::SocketError;
+
+    #[test]
+    fn test_reconnect_policy_backoff() {
+        let policy = ReconnectPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+            max_retries: Some(3),
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+
+        // Backoff is capped at the max_delay, rather than growing unbounded or overflowing
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(300));
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn market_event() -> Result<MarketEvent<u64, ()>, DataError> {
+        Ok(MarketEvent {
+            exchange_time: chrono::Utc::now(),
+            received_time: chrono::Utc::now(),
+            exchange: barter_integration::model::Exchange::from(ExchangeId::BinanceSpot),
+            instrument: 1,
+            kind: (),
+            sequence: 0,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_consume_stream_retries_failed_reinitialisation() {
+        // Scripted outcome of each MarketStream initialisation attempt
+        let mut inits = std::collections::VecDeque::from([
+            Ok(vec![market_event(), market_event()]),
+            Err(()),
+            Ok(vec![market_event()]),
+            Err(()),
+            Err(()),
+        ]);
+        let mut init_count = 0;
+
+        let (exchange_tx, mut exchange_rx) = mpsc::unbounded_channel();
+        let policy = ReconnectPolicy {
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            max_retries: Some(2),
+        };
+
+        let result = consume_stream(
+            ExchangeId::BinanceSpot,
+            || {
+                init_count += 1;
+                let init = inits.pop_front().expect("unexpected MarketStream init");
+                async move {
+                    init.map(futures::stream::iter)
+                        .map_err(|_| DataError::Socket(SocketError::Sink))
+                }
+            },
+            exchange_tx,
+            policy,
+        )
+        .await;
+
+        // Failed re-initialisation after the MarketStream ended is retried rather than returned,
+        // until consecutive failures exhaust the max_retries
+        assert!(matches!(
+            result,
+            Err(DataError::ReconnectLimit { attempts: 2, .. })
+        ));
+        assert_eq!(init_count, 5);
+
+        // Events are sequenced across re-connections
+        let mut sequences = Vec::new();
+        while let Ok(event) = exchange_rx.try_recv() {
+            sequences.push(event.sequence);
+        }
+        assert_eq!(sequences, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_consume_stream_returns_error_if_initial_initialisation_fails() {
+        let (exchange_tx, _exchange_rx) = mpsc::unbounded_channel::<MarketEvent<u64, ()>>();
+
+        let result = consume_stream(
+            ExchangeId::BinanceSpot,
+            || async {
+                Err::<futures::stream::Empty<Result<MarketEvent<u64, ()>, DataError>>, _>(
+                    DataError::Socket(SocketError::Sink),
+                )
+            },
+            exchange_tx,
+            ReconnectPolicy::default(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(DataError::Socket(SocketError::Sink))));
     }
 }
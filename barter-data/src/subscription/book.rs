@@ -87,6 +87,16 @@ impl OrderBook {
         self.clone()
     }
 
+    /// Best bid [`Level`], if the bids are not empty.
+    pub fn best_bid(&self) -> Option<Level> {
+        self.bids.levels.first().copied()
+    }
+
+    /// Best ask [`Level`], if the asks are not empty.
+    pub fn best_ask(&self) -> Option<Level> {
+        self.asks.levels.first().copied()
+    }
+
     /// Calculate the mid price by taking the average of the best bid and ask prices.
     ///
     /// See Docs: <https://www.quantstart.com/articles/high-frequency-trading-ii-limit-order-book>
@@ -283,6 +293,7 @@ impl<InstrumentId> From<(ExchangeId, InstrumentId, OrderBook)>
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: book,
+            sequence: 0,
         })])
     }
 }
@@ -386,6 +397,24 @@ mod tests {
     mod order_book {
         use super::*;
 
+        #[test]
+        fn test_best_bid_and_ask() {
+            let book = OrderBook {
+                last_update_time: Default::default(),
+                bids: OrderBookSide {
+                    side: Side::Buy,
+                    levels: vec![Level::new(100.0, 1.0), Level::new(50.0, 1.0)],
+                },
+                asks: OrderBookSide {
+                    side: Side::Sell,
+                    levels: vec![],
+                },
+            };
+
+            assert_eq!(book.best_bid(), Some(Level::new(100.0, 1.0)));
+            assert_eq!(book.best_ask(), None);
+        }
+
         #[test]
         fn test_mid_price() {
             struct TestCase {
@@ -62,6 +62,8 @@ async fn main() {
                 starting_equity: 10_000.0,
                 trading_days_per_year: 365,
                 risk_free_return: 0.0,
+                omega_threshold_return: 0.0,
+                return_kind: Default::default(),
             })
             .build_and_init()
             .expect("failed to build & initialise MetaPortfolio"),
@@ -83,13 +85,20 @@ async fn main() {
             .data(historical::MarketFeed::new(
                 load_json_market_event_candles().into_iter(),
             ))
-            .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+            .strategy(
+                RSIStrategy::new(StrategyConfig {
+                    rsi_period: 14,
+                    ..StrategyConfig::default()
+                })
+                .unwrap(),
+            )
             .execution(SimulatedExecution::new(ExecutionConfig {
                 simulated_fees_pct: Fees {
                     exchange: 0.1,
                     slippage: 0.05,
                     network: 0.0,
                 },
+                ..Default::default()
             }))
             .build()
             .expect("failed to build trader"),
@@ -109,6 +118,8 @@ async fn main() {
             starting_equity: 1000.0,
             trading_days_per_year: 365,
             risk_free_return: 0.0,
+            omega_threshold_return: 0.0,
+            return_kind: Default::default(),
         }))
         .build()
         .expect("failed to build engine");
@@ -132,6 +143,7 @@ fn load_json_market_event_candles() -> Vec<MarketEvent<Instrument, DataKind>> {
             exchange: Exchange::from("binance"),
             instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
             kind: DataKind::Candle(candle),
+            sequence: 0,
         })
         .collect()
 }
@@ -148,6 +160,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // Signal Event occurred in Engine
                 println!("{signal:?}");
             }
+            Event::SignalOutcome(signal_outcome) => {
+                // SignalOutcome Event occurred in Engine
+                println!("{signal_outcome:?}");
+            }
             Event::SignalForceExit(_) => {
                 // SignalForceExit Event occurred in Engine
             }
@@ -158,6 +174,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
             Event::OrderUpdate => {
                 // OrderUpdate Event occurred in Engine
             }
+            Event::OrderExpired(expired_order) => {
+                // OrderExpired Event occurred in Engine
+                println!("{expired_order:?}");
+            }
             Event::Fill(fill_event) => {
                 // Fill Event occurred in Engine
                 println!("{fill_event:?}");
@@ -178,6 +198,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // Balance update Event occurred in Engine
                 println!("{balance_update:?}");
             }
+            Event::StatisticUpdate(statistic_update) => {
+                // StatisticUpdate Event occurred in Engine
+                println!("{statistic_update:?}");
+            }
         }
     }
 }
@@ -17,10 +17,10 @@ use barter::{
     strategy::example::{Config as StrategyConfig, RSIStrategy},
 };
 use barter_data::{
-    event::{DataKind, MarketEvent},
+    event::MarketEvent,
     exchange::{binance::spot::BinanceSpot, ExchangeId},
     streams::Streams,
-    subscription::trade::PublicTrades,
+    subscription::trade::{PublicTrade, PublicTrades},
 };
 use barter_integration::model::{
     instrument::{kind::InstrumentKind, Instrument},
@@ -63,6 +63,8 @@ async fn main() {
                 starting_equity: 10_000.0,
                 trading_days_per_year: 365,
                 risk_free_return: 0.0,
+                omega_threshold_return: 0.0,
+                return_kind: Default::default(),
             })
             .build_and_init()
             .expect("failed to build & initialise MetaPortfolio"),
@@ -81,14 +83,21 @@ async fn main() {
             .command_rx(trader_command_rx)
             .event_tx(event_tx.clone())
             .portfolio(Arc::clone(&portfolio))
-            .data(live::MarketFeed::new(stream_market_event_trades().await))
-            .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+            .data(live::TradeFeed::new(stream_market_event_trades().await))
+            .strategy(
+                RSIStrategy::new(StrategyConfig {
+                    rsi_period: 14,
+                    ..StrategyConfig::default()
+                })
+                .unwrap(),
+            )
             .execution(SimulatedExecution::new(ExecutionConfig {
                 simulated_fees_pct: Fees {
                     exchange: 0.1,
                     slippage: 0.05,
                     network: 0.0,
                 },
+                ..Default::default()
             }))
             .build()
             .expect("failed to build trader"),
@@ -108,6 +117,8 @@ async fn main() {
             starting_equity: 1000.0,
             trading_days_per_year: 365,
             risk_free_return: 0.0,
+            omega_threshold_return: 0.0,
+            return_kind: Default::default(),
         }))
         .build()
         .expect("failed to build engine");
@@ -118,8 +129,8 @@ async fn main() {
     let _ = tokio::time::timeout(ENGINE_RUN_TIMEOUT, engine.run()).await;
 }
 
-async fn stream_market_event_trades() -> mpsc::UnboundedReceiver<MarketEvent<Instrument, DataKind>>
-{
+async fn stream_market_event_trades(
+) -> mpsc::UnboundedReceiver<MarketEvent<Instrument, PublicTrade>> {
     // Initialise PublicTrades Streams for BinanceSpot
     // '--> each call to StreamBuilder::subscribe() creates a separate WebSocket connection
     let mut streams = Streams::<PublicTrades>::builder()
@@ -147,17 +158,7 @@ async fn stream_market_event_trades() -> mpsc::UnboundedReceiver<MarketEvent<Ins
     // Notes:
     //  - Use `streams.select(ExchangeId)` to interact with the individual exchange streams!
     //  - Use `streams.join()` to join all exchange streams into a single mpsc::UnboundedReceiver!
-    let mut trade_rx = streams.select(ExchangeId::BinanceSpot).unwrap();
-
-    let (tx, rx) = mpsc::unbounded_channel();
-
-    tokio::spawn(async move {
-        while let Some(trade) = trade_rx.recv().await {
-            let _ = tx.send(MarketEvent::from(trade));
-        }
-    });
-
-    rx
+    streams.select(ExchangeId::BinanceSpot).unwrap()
 }
 
 // Listen to Events that occur in the Engine. These can be used for updating event-sourcing,
@@ -173,6 +174,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // Signal Event occurred in Engine
                 println!("{signal:?}");
             }
+            Event::SignalOutcome(signal_outcome) => {
+                // SignalOutcome Event occurred in Engine
+                println!("{signal_outcome:?}");
+            }
             Event::SignalForceExit(_) => {
                 // SignalForceExit Event occurred in Engine
             }
@@ -183,6 +188,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
             Event::OrderUpdate => {
                 // OrderUpdate Event occurred in Engine
             }
+            Event::OrderExpired(expired_order) => {
+                // OrderExpired Event occurred in Engine
+                println!("{expired_order:?}");
+            }
             Event::Fill(fill_event) => {
                 // Fill Event occurred in Engine
                 println!("{fill_event:?}");
@@ -203,6 +212,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // Balance update Event occurred in Engine
                 println!("{balance_update:?}");
             }
+            Event::StatisticUpdate(statistic_update) => {
+                // StatisticUpdate Event occurred in Engine
+                println!("{statistic_update:?}");
+            }
         }
     }
 }
@@ -1,4 +1,5 @@
 use barter_integration::error::SocketError;
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 
 /// All errors generated in the barter::data module.
@@ -10,9 +11,30 @@ pub enum DataError {
     #[error("Failed to build struct due to missing attributes: {0}")]
     BuilderIncomplete(&'static str),
 
+    #[error("Invalid config: {0}")]
+    InvalidConfig(&'static str),
+
     #[error("Socket: {0}")]
     Socket(#[from] SocketError),
 
     #[error("Barter-Data: {0}")]
     Data(#[from] barter_data::error::DataError),
+
+    #[error("IO: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to deserialise market event on line {line}: {error}")]
+    Deserialise {
+        line: usize,
+        error: serde_json::Error,
+    },
+
+    #[error("invalid bar violating OHLC invariants at {timestamp}")]
+    InvalidBar { timestamp: DateTime<Utc> },
+
+    #[error("gap detected between bars: expected next bar at {expected}, got {actual}")]
+    GapDetected {
+        expected: DateTime<Utc>,
+        actual: DateTime<Utc>,
+    },
 }
@@ -1,22 +1,39 @@
-use crate::data::{Feed, MarketGenerator};
+use crate::data::{error::DataError, Feed, MarketGenerator, Sequenced, Sequencer};
+use barter_data::event::MarketEvent;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "compression")]
+use flate2::read::MultiGzDecoder;
+use serde::de::DeserializeOwned;
+use std::{
+    ffi::OsStr,
+    fmt::{Debug, Formatter},
+    fs::File,
+    io::{BufRead, BufReader, Lines, Read},
+    marker::PhantomData,
+    path::Path,
+};
+use tracing::warn;
 
-/// Historical [`Feed`] of market events.
+/// Historical [`Feed`] of market events. Each market event yielded is assigned the next
+/// [`Sequencer`] sequence number.
 #[derive(Debug)]
 pub struct MarketFeed<Iter, Event>
 where
     Iter: Iterator<Item = Event>,
 {
     pub market_iterator: Iter,
+    sequencer: Sequencer,
 }
 
 impl<Iter, Event> MarketGenerator<Event> for MarketFeed<Iter, Event>
 where
     Iter: Iterator<Item = Event>,
+    Event: Sequenced,
 {
     fn next(&mut self) -> Feed<Event> {
-        self.market_iterator
-            .next()
-            .map_or(Feed::Finished, Feed::Next)
+        self.market_iterator.next().map_or(Feed::Finished, |event| {
+            Feed::Next(self.sequencer.sequence(event))
+        })
     }
 }
 
@@ -32,6 +49,411 @@ where
     {
         Self {
             market_iterator: market_iterator.into_iter(),
+            sequencer: Sequencer::default(),
         }
     }
 }
+
+/// Historical [`Feed`] of [`MarketEvent`]s held in memory that can be rewound & repositioned,
+/// enabling the same data to be replayed from different start points (eg/ walk-forward testing)
+/// without reading it again.
+///
+/// Market events must be provided in chronological `exchange_time` order. Each market event
+/// yielded is assigned the next [`Sequencer`] sequence number, and the [`Sequencer`] restarts
+/// whenever the [`ReplayFeed`] is repositioned, as with a freshly constructed feed.
+#[derive(Debug)]
+pub struct ReplayFeed<InstrumentId, Kind> {
+    events: Vec<MarketEvent<InstrumentId, Kind>>,
+    cursor: usize,
+    sequencer: Sequencer,
+}
+
+impl<InstrumentId, Kind> MarketGenerator<MarketEvent<InstrumentId, Kind>>
+    for ReplayFeed<InstrumentId, Kind>
+where
+    InstrumentId: Clone,
+    Kind: Clone,
+{
+    fn next(&mut self) -> Feed<MarketEvent<InstrumentId, Kind>> {
+        match self.events.get(self.cursor) {
+            Some(event) => {
+                self.cursor += 1;
+                Feed::Next(self.sequencer.sequence(event.clone()))
+            }
+            None => Feed::Finished,
+        }
+    }
+}
+
+impl<InstrumentId, Kind> ReplayFeed<InstrumentId, Kind> {
+    /// Construct a historical [`ReplayFeed`] that yields the chronologically ordered
+    /// [`MarketEvent`]s provided.
+    pub fn new<IntoIter>(events: IntoIter) -> Self
+    where
+        IntoIter: IntoIterator<Item = MarketEvent<InstrumentId, Kind>>,
+    {
+        Self {
+            events: events.into_iter().collect(),
+            cursor: 0,
+            sequencer: Sequencer::default(),
+        }
+    }
+
+    /// Reposition the [`ReplayFeed`] so the next market event yielded is the first at or after
+    /// the provided timestamp. Seeking past the last market event finishes the feed.
+    pub fn seek_to(&mut self, timestamp: DateTime<Utc>) {
+        self.cursor = self
+            .events
+            .partition_point(|event| event.exchange_time < timestamp);
+        self.sequencer = Sequencer::default();
+    }
+
+    /// Rewind the [`ReplayFeed`] to the first market event.
+    pub fn reset(&mut self) {
+        self.cursor = 0;
+        self.sequencer = Sequencer::default();
+    }
+
+    /// Returns true while the [`ReplayFeed`] has market events remaining.
+    pub fn should_continue(&self) -> bool {
+        self.cursor < self.events.len()
+    }
+}
+
+/// Historical [`Feed`] of market events lazily deserialised from newline-delimited JSON (eg/ a
+/// `.json` or `.ndjson` file), with one market event per line. Blank lines are skipped.
+///
+/// Only the current line is held in memory, so large files are streamed rather than loaded up
+/// front. A line that cannot be read or deserialised yields [`Feed::Unhealthy`] (see
+/// [`Self::try_next`] for the associated [`DataError`]), and the next line is read on the
+/// following call. Each market event yielded is assigned the next [`Sequencer`] sequence number.
+#[derive(Debug)]
+pub struct JsonLinesFeed<Reader, Event> {
+    lines: Lines<Reader>,
+    line: usize,
+    sequencer: Sequencer,
+    _event_marker: PhantomData<Event>,
+}
+
+impl<Reader, Event> MarketGenerator<Event> for JsonLinesFeed<Reader, Event>
+where
+    Reader: BufRead,
+    Event: DeserializeOwned + Sequenced,
+{
+    fn next(&mut self) -> Feed<Event> {
+        match self.try_next() {
+            Ok(Some(event)) => Feed::Next(event),
+            Ok(None) => Feed::Finished,
+            Err(error) => {
+                warn!(%error, "historical JsonLinesFeed failed to yield next market event");
+                Feed::Unhealthy
+            }
+        }
+    }
+}
+
+impl<Reader, Event> JsonLinesFeed<Reader, Event>
+where
+    Reader: BufRead,
+    Event: DeserializeOwned + Sequenced,
+{
+    /// Construct a historical [`JsonLinesFeed`] that lazily yields market events from the
+    /// newline-delimited JSON [`BufRead`] provided.
+    pub fn new(reader: Reader) -> Self {
+        Self {
+            lines: reader.lines(),
+            line: 0,
+            sequencer: Sequencer::default(),
+            _event_marker: PhantomData,
+        }
+    }
+
+    /// Attempt to read & deserialise the next market event, returning `Ok(None)` once the
+    /// [`BufRead`] is exhausted.
+    #[allow(clippy::result_large_err)]
+    pub fn try_next(&mut self) -> Result<Option<Event>, DataError> {
+        loop {
+            let line = match self.lines.next() {
+                Some(line) => line?,
+                None => return Ok(None),
+            };
+            self.line += 1;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            return serde_json::from_str(&line)
+                .map(|event| Some(self.sequencer.sequence(event)))
+                .map_err(|error| DataError::Deserialise {
+                    line: self.line,
+                    error,
+                });
+        }
+    }
+}
+
+impl<Event> JsonLinesFeed<DataFile, Event>
+where
+    Event: DeserializeOwned + Sequenced,
+{
+    /// Construct a historical [`JsonLinesFeed`] that lazily yields market events from the
+    /// newline-delimited JSON file at the provided path, which is transparently decompressed if
+    /// it is compressed. See [`DataFile`].
+    #[allow(clippy::result_large_err)]
+    pub fn from_path<P>(path: P) -> Result<Self, DataError>
+    where
+        P: AsRef<Path>,
+    {
+        DataFile::open(path).map(Self::new).map_err(DataError::from)
+    }
+}
+
+/// Historical data file reader that transparently decompresses gzip (`.gz`) & zstd (`.zst`)
+/// files, as determined by the file extension. Other files are read as is.
+///
+/// Decompression is streamed as the file is read, so memory use stays bounded regardless of the
+/// file size. Decompression requires the `compression` feature, without which opening a `.gz` or
+/// `.zst` file fails.
+pub enum DataFile {
+    Plain(BufReader<File>),
+    #[cfg(feature = "compression")]
+    Gzip(BufReader<MultiGzDecoder<File>>),
+    #[cfg(feature = "compression")]
+    Zstd(BufReader<zstd::Decoder<'static, BufReader<File>>>),
+}
+
+impl DataFile {
+    /// Open the data file at the provided path, decompressing it according to it's extension.
+    pub fn open<P>(path: P) -> std::io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+
+        match path.extension().and_then(OsStr::to_str) {
+            #[cfg(feature = "compression")]
+            Some("gz") => Ok(Self::Gzip(BufReader::new(MultiGzDecoder::new(file)))),
+            #[cfg(feature = "compression")]
+            Some("zst") => {
+                zstd::Decoder::new(file).map(|decoder| Self::Zstd(BufReader::new(decoder)))
+            }
+            #[cfg(not(feature = "compression"))]
+            Some(extension @ ("gz" | "zst")) => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("decompressing .{extension} data files requires the compression feature"),
+            )),
+            _ => Ok(Self::Plain(BufReader::new(file))),
+        }
+    }
+}
+
+impl Debug for DataFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plain(reader) => f.debug_tuple("Plain").field(reader).finish(),
+            #[cfg(feature = "compression")]
+            Self::Gzip(reader) => f.debug_tuple("Gzip").field(reader).finish(),
+            #[cfg(feature = "compression")]
+            Self::Zstd(_) => f.debug_tuple("Zstd").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl Read for DataFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(reader) => reader.read(buf),
+            #[cfg(feature = "compression")]
+            Self::Gzip(reader) => reader.read(buf),
+            #[cfg(feature = "compression")]
+            Self::Zstd(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl BufRead for DataFile {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        match self {
+            Self::Plain(reader) => reader.fill_buf(),
+            #[cfg(feature = "compression")]
+            Self::Gzip(reader) => reader.fill_buf(),
+            #[cfg(feature = "compression")]
+            Self::Zstd(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self {
+            Self::Plain(reader) => reader.consume(amt),
+            #[cfg(feature = "compression")]
+            Self::Gzip(reader) => reader.consume(amt),
+            #[cfg(feature = "compression")]
+            Self::Zstd(reader) => reader.consume(amt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle;
+    use barter_data::event::DataKind;
+    use barter_integration::model::instrument::Instrument;
+    use chrono::Duration;
+    use std::ops::Add;
+
+    fn replay_feed(base_time: DateTime<Utc>) -> ReplayFeed<Instrument, DataKind> {
+        ReplayFeed::new((0..4).map(|minutes| {
+            let mut event = market_event_candle();
+            event.exchange_time = base_time.add(Duration::minutes(minutes));
+            event
+        }))
+    }
+
+    fn next_minute(feed: &mut ReplayFeed<Instrument, DataKind>, base_time: DateTime<Utc>) -> i64 {
+        match feed.next() {
+            Feed::Next(event) => (event.exchange_time - base_time).num_minutes(),
+            feed => panic!("expected Feed::Next, got {feed:?}"),
+        }
+    }
+
+    #[test]
+    fn replay_feed_seeks_to_first_event_at_or_after_timestamp() {
+        let base_time = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+        let mut feed = replay_feed(base_time);
+        assert_eq!(next_minute(&mut feed, base_time), 0);
+
+        // Seek to an exact event timestamp, restarting the sequence numbers
+        feed.seek_to(base_time.add(Duration::minutes(2)));
+        assert!(matches!(feed.next(), Feed::Next(event) if event.sequence == 1));
+
+        // Seek between event timestamps, including backwards
+        feed.seek_to(base_time.add(Duration::seconds(30)));
+        assert_eq!(next_minute(&mut feed, base_time), 1);
+
+        // Seek past the last event
+        feed.seek_to(base_time.add(Duration::minutes(10)));
+        assert!(!feed.should_continue());
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn replay_feed_reset_rewinds_to_first_event() {
+        let base_time = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+        let mut feed = replay_feed(base_time);
+        while let Feed::Next(_) = feed.next() {}
+        assert!(!feed.should_continue());
+
+        feed.reset();
+        assert!(feed.should_continue());
+        assert!(matches!(
+            feed.next(),
+            Feed::Next(event) if event.sequence == 1 && event.exchange_time == base_time
+        ));
+    }
+
+    #[test]
+    fn json_lines_feed_lazily_yields_market_events_and_skips_malformed_lines() {
+        let event = market_event_candle();
+        let line = serde_json::to_string(&event).unwrap();
+        let input = format!("{line}\n\n{{\"not\": \"a market event\"}}\n{line}\n");
+
+        let mut feed = JsonLinesFeed::<_, MarketEvent<Instrument, DataKind>>::new(input.as_bytes());
+
+        assert_eq!(
+            feed.next(),
+            Feed::Next(MarketEvent {
+                sequence: 1,
+                ..event.clone()
+            })
+        );
+
+        // Malformed line surfaces as a DataError, rather than panicking
+        assert!(matches!(
+            feed.try_next(),
+            Err(DataError::Deserialise { line: 3, .. })
+        ));
+
+        assert_eq!(
+            feed.next(),
+            Feed::Next(MarketEvent {
+                sequence: 2,
+                ..event
+            })
+        );
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn json_lines_feed_yields_unhealthy_for_malformed_line() {
+        let mut feed =
+            JsonLinesFeed::<_, MarketEvent<Instrument, DataKind>>::new("not json\n".as_bytes());
+
+        assert_eq!(feed.next(), Feed::Unhealthy);
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn json_lines_feed_from_path_decompresses_gzip_and_zstd_files() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let events = [market_event_candle(), market_event_candle()];
+        let input = events
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap() + "\n")
+            .collect::<String>();
+
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(input.as_bytes()).unwrap();
+
+        let files = [
+            ("ndjson", input.clone().into_bytes()),
+            ("ndjson.gz", gzip.finish().unwrap()),
+            ("ndjson.zst", zstd::encode_all(input.as_bytes(), 0).unwrap()),
+        ];
+
+        for (extension, contents) in files {
+            let path = std::env::temp_dir().join(format!("{}.{extension}", uuid::Uuid::new_v4()));
+            std::fs::write(&path, contents).unwrap();
+
+            let mut feed =
+                JsonLinesFeed::<_, MarketEvent<Instrument, DataKind>>::from_path(&path).unwrap();
+            for (sequence, event) in (1..).zip(events.iter().cloned()) {
+                assert_eq!(feed.next(), Feed::Next(MarketEvent { sequence, ..event }));
+            }
+            assert_eq!(feed.next(), Feed::Finished, "failed for .{extension}");
+
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn json_lines_feed_from_compressed_path_fails_without_compression_feature() {
+        for extension in ["ndjson.gz", "ndjson.zst"] {
+            let path = std::env::temp_dir().join(format!("{}.{extension}", uuid::Uuid::new_v4()));
+            std::fs::write(&path, "").unwrap();
+
+            let result = JsonLinesFeed::<_, MarketEvent<Instrument, DataKind>>::from_path(&path);
+            assert!(
+                matches!(&result, Err(DataError::Io(error)) if error.kind() == std::io::ErrorKind::Unsupported),
+                "failed for .{extension}"
+            );
+
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn json_lines_feed_from_missing_path_fails() {
+        let result = JsonLinesFeed::<_, MarketEvent<Instrument, DataKind>>::from_path(
+            "does/not/exist.ndjson",
+        );
+
+        assert!(matches!(result, Err(DataError::Io(_))));
+    }
+}
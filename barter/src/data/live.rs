@@ -1,19 +1,61 @@
 use super::{Feed, MarketGenerator};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::{candle::Candle, trade::PublicTrade},
+};
+use barter_integration::model::{instrument::Instrument, MarketId};
+use chrono::{DateTime, Utc};
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc;
+use tracing::warn;
 
-/// Live [`Feed`] of market events.
+/// Live [`Feed`] of market events. Market events are yielded as received, preserving the sequence
+/// number assigned upstream by the `Barter-Data` consumer loop that produced them.
 #[derive(Debug)]
 pub struct MarketFeed<Event> {
     pub market_rx: mpsc::UnboundedReceiver<Event>,
+    /// Optional soft heartbeat. If configured, the [`MarketFeed`] is considered dead & yields
+    /// [`Feed::Finished`] if no market event is received within this grace period.
+    pub heartbeat_timeout: Option<Duration>,
+    /// Time the latest market event was received (or the [`MarketFeed`] was constructed).
+    pub last_event_time: Instant,
+    shutdown: FeedShutdown,
+}
+
+/// Handle to gracefully shut down a live [`MarketFeed`] from another thread or task, eg/ when a
+/// [`Command::Terminate`](crate::engine::Command::Terminate) is received.
+///
+/// Once shut down, the [`MarketFeed`] yields [`Feed::Finished`] and closes it's
+/// [`mpsc::UnboundedReceiver`]. The `Barter-Data` stream task feeding the receiver then fails to
+/// send it's next market event and exits, disconnecting it's exchange websocket.
+#[derive(Clone, Debug, Default)]
+pub struct FeedShutdown(Arc<AtomicBool>);
+
+impl FeedShutdown {
+    /// Signal the associated [`MarketFeed`] to shut down.
+    pub fn shutdown(&self) {
+        self.0.store(true, atomic::Ordering::Release);
+    }
+
+    /// Determines if the associated [`MarketFeed`] has been signalled to shut down.
+    pub fn is_shutdown(&self) -> bool {
+        self.0.load(atomic::Ordering::Acquire)
+    }
 }
 
 impl<Event> MarketGenerator<Event> for MarketFeed<Event> {
     fn next(&mut self) -> Feed<Event> {
         loop {
-            match self.market_rx.try_recv() {
-                Ok(event) => break Feed::Next(event),
-                Err(mpsc::error::TryRecvError::Empty) => continue,
-                Err(mpsc::error::TryRecvError::Disconnected) => break Feed::Finished,
+            if let Some(feed) = self.poll_next() {
+                break feed;
             }
         }
     }
@@ -30,7 +72,524 @@ impl<Event> MarketFeed<Event> {
     ///  2. Use [`Streams::join`](barter_data::streams::Streams::join) to join all exchange
     ///     [`mpsc::UnboundedReceiver`] streams into a unified [`mpsc::UnboundedReceiver`].
     ///  3. Construct [`Self`] with the unified [`mpsc::UnboundedReceiver`].
+    ///
+    /// If an exchange drops it's websocket, the `Barter-Data` consumer loop re-connects according
+    /// to it's [`ReconnectPolicy`](barter_data::streams::consumer::ReconnectPolicy). The
+    /// [`MarketFeed`] keeps waiting for market events whilst it re-connects (unless a heartbeat
+    /// timeout elapses), and yields [`Feed::Finished`] once every consumer loop feeding it has
+    /// given up.
     pub fn new(market_rx: mpsc::UnboundedReceiver<Event>) -> Self {
-        Self { market_rx }
+        Self {
+            market_rx,
+            heartbeat_timeout: None,
+            last_event_time: Instant::now(),
+            shutdown: FeedShutdown::default(),
+        }
+    }
+
+    /// Configure a soft heartbeat grace period. A brief gap in market events is tolerated, but if
+    /// no market event is received within the `timeout` the [`MarketFeed`] is considered dead and
+    /// yields [`Feed::Finished`]. The grace period starts from construction, so it should allow
+    /// enough time for the first market event to arrive.
+    ///
+    /// By default there is no heartbeat, and the [`MarketFeed`] only finishes once the
+    /// [`mpsc::UnboundedReceiver`] is disconnected.
+    pub fn with_heartbeat_timeout(self, timeout: Duration) -> Self {
+        Self {
+            heartbeat_timeout: Some(timeout),
+            last_event_time: Instant::now(),
+            ..self
+        }
+    }
+
+    /// [`FeedShutdown`] handle that gracefully shuts down this [`MarketFeed`].
+    pub fn shutdown_handle(&self) -> FeedShutdown {
+        self.shutdown.clone()
+    }
+
+    /// Returns false once this [`MarketFeed`] has been signalled to shut down.
+    pub fn should_continue(&self) -> bool {
+        !self.shutdown.is_shutdown()
+    }
+
+    /// Attempt to generate the next [`Feed`] without waiting, returning `None` if no market event
+    /// is available yet.
+    fn poll_next(&mut self) -> Option<Feed<Event>> {
+        if self.shutdown.is_shutdown() {
+            self.market_rx.close();
+            return Some(Feed::Finished);
+        }
+
+        match self.market_rx.try_recv() {
+            Ok(event) => {
+                self.last_event_time = Instant::now();
+                Some(Feed::Next(event))
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {
+                // Transient gaps within the heartbeat grace period keep waiting for events
+                match self.heartbeat_timeout {
+                    Some(timeout) if self.last_event_time.elapsed() >= timeout => {
+                        warn!(
+                            ?timeout,
+                            "live MarketFeed received no market events within heartbeat timeout"
+                        );
+                        Some(Feed::Finished)
+                    }
+                    _ => None,
+                }
+            }
+            Err(mpsc::error::TryRecvError::Disconnected) => Some(Feed::Finished),
+        }
+    }
+}
+
+/// Live trade-driven [`Feed`] that yields each [`PublicTrade`] from a `Barter-Data` trade stream
+/// as a [`DataKind::Trade`] [`MarketEvent`].
+///
+/// This enables tick-level strategies that react to every trade. To instead drive a strategy
+/// off bars built from trades, wrap the [`TradeFeed`] in a
+/// [`VolumeBarResampler`](super::volume::VolumeBarResampler).
+#[derive(Debug)]
+pub struct TradeFeed {
+    feed: MarketFeed<MarketEvent<Instrument, PublicTrade>>,
+}
+
+impl MarketGenerator<MarketEvent<Instrument, DataKind>> for TradeFeed {
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        match self.feed.next() {
+            Feed::Next(trade) => Feed::Next(MarketEvent::from(trade)),
+            Feed::Unhealthy => Feed::Unhealthy,
+            Feed::Finished => Feed::Finished,
+        }
+    }
+}
+
+impl TradeFeed {
+    /// Initialises a live [`TradeFeed`] that yields [`PublicTrade`] [`MarketEvent`]s from the
+    /// provided [`mpsc::UnboundedReceiver`], eg/ a joined `Barter-Data`
+    /// [`PublicTrades`](barter_data::subscription::trade::PublicTrades) stream.
+    pub fn new(trade_rx: mpsc::UnboundedReceiver<MarketEvent<Instrument, PublicTrade>>) -> Self {
+        Self {
+            feed: MarketFeed::new(trade_rx),
+        }
+    }
+
+    /// Configure a soft heartbeat grace period. See [`MarketFeed::with_heartbeat_timeout`].
+    pub fn with_heartbeat_timeout(self, timeout: Duration) -> Self {
+        Self {
+            feed: self.feed.with_heartbeat_timeout(timeout),
+        }
+    }
+
+    /// [`FeedShutdown`] handle that gracefully shuts down this [`TradeFeed`].
+    pub fn shutdown_handle(&self) -> FeedShutdown {
+        self.feed.shutdown_handle()
+    }
+}
+
+/// Live candle-driven [`Feed`] that yields each [`Candle`] from a candle stream as a
+/// [`DataKind::Candle`] [`MarketEvent`].
+///
+/// Live candle streams send many updates of a still forming candle before it closes, so a
+/// strategy acting on every update reacts to provisional data that may repaint. If
+/// `only_closed_bars` is enabled, the latest update of each forming candle is held back until the
+/// candle is confirmed closed, and the superseded updates are discarded. Since a [`Candle`] has no
+/// closed flag, a candle is confirmed closed by it's interval boundary, ie/ once either:
+///  - An update is received at or after it's `close_time`.
+///  - A candle of the same market with a later `close_time` is received.
+///  - It's `close_time` elapses whilst waiting for the next update, eg/ on an idle market.
+///
+/// Each candle yielded preserves the upstream sequence number of it's latest update, so the
+/// sequence numbers of the superseded updates are skipped.
+#[derive(Debug)]
+pub struct CandleFeed {
+    feed: MarketFeed<MarketEvent<Instrument, Candle>>,
+    only_closed_bars: bool,
+    forming: HashMap<MarketId, MarketEvent<Instrument, Candle>>,
+    last_closed: HashMap<MarketId, DateTime<Utc>>,
+    closed: VecDeque<MarketEvent<Instrument, DataKind>>,
+}
+
+impl MarketGenerator<MarketEvent<Instrument, DataKind>> for CandleFeed {
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            if self.only_closed_bars {
+                self.close_elapsed_candles(Utc::now());
+            }
+
+            if let Some(candle) = self.closed.pop_front() {
+                break Feed::Next(candle);
+            }
+
+            let candle = match self.feed.poll_next() {
+                Some(Feed::Next(candle)) => candle,
+                Some(Feed::Unhealthy) => break Feed::Unhealthy,
+                Some(Feed::Finished) => break Feed::Finished,
+                None => continue,
+            };
+
+            match self.only_closed_bars {
+                true => self.update_candle(candle, Utc::now()),
+                false => break Feed::Next(MarketEvent::from(candle)),
+            }
+        }
+    }
+}
+
+impl CandleFeed {
+    /// Initialises a live [`CandleFeed`] that yields every [`Candle`] [`MarketEvent`] update
+    /// from the provided [`mpsc::UnboundedReceiver`].
+    pub fn new(candle_rx: mpsc::UnboundedReceiver<MarketEvent<Instrument, Candle>>) -> Self {
+        Self {
+            feed: MarketFeed::new(candle_rx),
+            only_closed_bars: false,
+            forming: HashMap::new(),
+            last_closed: HashMap::new(),
+            closed: VecDeque::new(),
+        }
+    }
+
+    /// Configure whether only confirmed closed candles are yielded, rather than every update of
+    /// a forming candle. Disabled by default.
+    pub fn with_only_closed_bars(self, only_closed_bars: bool) -> Self {
+        Self {
+            only_closed_bars,
+            ..self
+        }
+    }
+
+    /// Configure a soft heartbeat grace period. See [`MarketFeed::with_heartbeat_timeout`].
+    pub fn with_heartbeat_timeout(self, timeout: Duration) -> Self {
+        Self {
+            feed: self.feed.with_heartbeat_timeout(timeout),
+            ..self
+        }
+    }
+
+    /// [`FeedShutdown`] handle that gracefully shuts down this [`CandleFeed`].
+    pub fn shutdown_handle(&self) -> FeedShutdown {
+        self.feed.shutdown_handle()
+    }
+
+    /// Update the forming candle of the [`Candle`] update's market, queueing any candle that is
+    /// now confirmed closed.
+    fn update_candle(&mut self, candle: MarketEvent<Instrument, Candle>, now: DateTime<Utc>) {
+        let market_id = MarketId::new(&candle.exchange, &candle.instrument);
+
+        // Discard late updates of candles that have already been yielded as closed
+        if let Some(last_closed) = self.last_closed.get(&market_id) {
+            if candle.kind.close_time <= *last_closed {
+                return;
+            }
+        }
+
+        // Forming candle is closed once a candle with a later close_time is received, and late
+        // updates of an earlier candle are discarded
+        if let Entry::Occupied(forming) = self.forming.entry(market_id.clone()) {
+            match forming.get().kind.close_time.cmp(&candle.kind.close_time) {
+                Ordering::Less => {
+                    let forming = forming.remove();
+                    self.close_candle(market_id.clone(), forming);
+                }
+                Ordering::Equal => {}
+                Ordering::Greater => return,
+            }
+        }
+
+        // Candle is closed once an update is received at or after it's close_time
+        match candle.kind.close_time <= now {
+            true => {
+                self.forming.remove(&market_id);
+                self.close_candle(market_id, candle);
+            }
+            false => {
+                self.forming.insert(market_id, candle);
+            }
+        }
+    }
+
+    /// Queue every forming candle whose `close_time` has elapsed, so a closed candle is not held
+    /// back indefinitely whilst it's market is idle.
+    fn close_elapsed_candles(&mut self, now: DateTime<Utc>) {
+        let mut elapsed = self
+            .forming
+            .iter()
+            .filter(|(_, candle)| candle.kind.close_time <= now)
+            .map(|(market_id, candle)| (candle.kind.close_time, market_id.clone()))
+            .collect::<Vec<_>>();
+        elapsed.sort_by_key(|(close_time, _)| *close_time);
+
+        for (_, market_id) in elapsed {
+            if let Some(candle) = self.forming.remove(&market_id) {
+                self.close_candle(market_id, candle);
+            }
+        }
+    }
+
+    /// Queue a confirmed closed [`Candle`] to be yielded.
+    fn close_candle(&mut self, market_id: MarketId, candle: MarketEvent<Instrument, Candle>) {
+        self.last_closed.insert(market_id, candle.kind.close_time);
+        self.closed.push_back(MarketEvent::from(candle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::volume::{self, VolumeBarResampler},
+        test_util::market_event_trade,
+    };
+    use barter_integration::model::{instrument::kind::InstrumentKind, Side};
+
+    #[test]
+    fn market_feed_yields_events_received_within_heartbeat_timeout() {
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        let mut feed = MarketFeed::new(market_rx).with_heartbeat_timeout(Duration::from_secs(5));
+
+        market_tx.send(1).unwrap();
+        assert_eq!(feed.next(), Feed::Next(1));
+    }
+
+    #[test]
+    fn market_feed_finishes_if_no_event_within_heartbeat_timeout() {
+        // Keep market_tx in scope so the receiver does not disconnect
+        let (_market_tx, market_rx) = mpsc::unbounded_channel::<u64>();
+        let mut feed = MarketFeed::new(market_rx).with_heartbeat_timeout(Duration::from_millis(10));
+
+        let start = Instant::now();
+        assert_eq!(feed.next(), Feed::Finished);
+        assert!(start.elapsed() >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn market_feed_tolerates_gap_shorter_than_heartbeat_timeout() {
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        let mut feed =
+            MarketFeed::new(market_rx).with_heartbeat_timeout(Duration::from_millis(500));
+
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            market_tx.send(1).unwrap();
+        });
+
+        assert_eq!(feed.next(), Feed::Next(1));
+    }
+
+    #[test]
+    fn market_feed_shutdown_finishes_feed_and_stops_stream_task() {
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        let mut feed = MarketFeed::new(market_rx);
+        let shutdown = feed.shutdown_handle();
+
+        // Stream task sends market events until the MarketFeed receiver is closed
+        let (exited_tx, exited_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            while market_tx.send(trade("btc", 100.0, 1.0)).is_ok() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            exited_tx.send(()).unwrap();
+        });
+
+        assert!(matches!(feed.next(), Feed::Next(_)));
+        assert!(feed.should_continue());
+
+        shutdown.shutdown();
+        assert!(!feed.should_continue());
+        assert_eq!(feed.next(), Feed::Finished);
+
+        assert!(exited_rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    fn trade(base: &str, price: f64, amount: f64) -> MarketEvent<Instrument, PublicTrade> {
+        let event = market_event_trade(Side::Buy);
+        MarketEvent {
+            exchange_time: event.exchange_time,
+            received_time: event.received_time,
+            exchange: event.exchange,
+            instrument: Instrument::from((base, "usdt", InstrumentKind::Spot)),
+            kind: PublicTrade {
+                id: "trade_id".to_string(),
+                price,
+                amount,
+                side: Side::Buy,
+            },
+            sequence: 0,
+        }
+    }
+
+    #[test]
+    fn trade_feed_forwards_each_trade_as_market_event() {
+        let (trade_tx, trade_rx) = mpsc::unbounded_channel();
+        let mut feed = TradeFeed::new(trade_rx);
+
+        // Upstream sequence numbers are preserved, including any gaps
+        for (sequence, base, price) in [(7, "btc", 100.0), (9, "eth", 10.0)] {
+            trade_tx
+                .send(MarketEvent {
+                    sequence,
+                    ..trade(base, price, 1.0)
+                })
+                .unwrap();
+        }
+        drop(trade_tx);
+
+        for (sequence, (base, price)) in [(7, ("btc", 100.0)), (9, ("eth", 10.0))] {
+            match feed.next() {
+                Feed::Next(event) => {
+                    assert_eq!(event.sequence, sequence);
+                    assert_eq!(
+                        event.instrument,
+                        Instrument::from((base, "usdt", InstrumentKind::Spot))
+                    );
+                    match event.kind {
+                        DataKind::Trade(trade) => assert_eq!(trade.price, price),
+                        kind => panic!("expected DataKind::Trade, got {kind:?}"),
+                    }
+                }
+                feed => panic!("expected Feed::Next, got {feed:?}"),
+            }
+        }
+
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn trade_feed_aggregates_trades_into_volume_bars() {
+        let (trade_tx, trade_rx) = mpsc::unbounded_channel();
+        let mut feed = VolumeBarResampler::new(
+            volume::Config {
+                volume_threshold: 3.0,
+            },
+            TradeFeed::new(trade_rx),
+        )
+        .unwrap();
+
+        trade_tx.send(trade("btc", 100.0, 1.0)).unwrap();
+        trade_tx.send(trade("btc", 110.0, 1.0)).unwrap();
+        trade_tx.send(trade("btc", 105.0, 1.0)).unwrap();
+        drop(trade_tx);
+
+        match feed.next() {
+            Feed::Next(MarketEvent {
+                kind: DataKind::Candle(candle),
+                ..
+            }) => {
+                assert_eq!(candle.open, 100.0);
+                assert_eq!(candle.high, 110.0);
+                assert_eq!(candle.close, 105.0);
+                assert_eq!(candle.volume, 3.0);
+                assert_eq!(candle.trade_count, 3);
+            }
+            feed => panic!("expected Candle volume bar, got {feed:?}"),
+        }
+
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    fn candle(
+        base: &str,
+        close_time: DateTime<Utc>,
+        close: f64,
+    ) -> MarketEvent<Instrument, Candle> {
+        let event = trade(base, close, 1.0);
+        MarketEvent {
+            exchange_time: event.exchange_time,
+            received_time: event.received_time,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            kind: Candle {
+                close_time,
+                open: 100.0,
+                high: close.max(100.0),
+                low: close.min(100.0),
+                close,
+                volume: 1.0,
+                trade_count: 1,
+            },
+            sequence: 0,
+        }
+    }
+
+    fn next_candle(feed: &mut CandleFeed) -> (u64, Candle) {
+        match feed.next() {
+            Feed::Next(MarketEvent {
+                sequence,
+                kind: DataKind::Candle(candle),
+                ..
+            }) => (sequence, candle),
+            feed => panic!("expected Candle MarketEvent, got {feed:?}"),
+        }
+    }
+
+    #[test]
+    fn candle_feed_yields_every_candle_update_by_default() {
+        let (candle_tx, candle_rx) = mpsc::unbounded_channel();
+        let mut feed = CandleFeed::new(candle_rx);
+
+        let close_time = Utc::now() + chrono::Duration::hours(1);
+        candle_tx.send(candle("btc", close_time, 101.0)).unwrap();
+        candle_tx.send(candle("btc", close_time, 102.0)).unwrap();
+        drop(candle_tx);
+
+        assert_eq!(next_candle(&mut feed).1.close, 101.0);
+        assert_eq!(next_candle(&mut feed).1.close, 102.0);
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn candle_feed_with_only_closed_bars_suppresses_forming_candles() {
+        let (candle_tx, candle_rx) = mpsc::unbounded_channel();
+        let mut feed = CandleFeed::new(candle_rx).with_only_closed_bars(true);
+
+        let closed_time = Utc::now() - chrono::Duration::minutes(1);
+        let forming_time = Utc::now() + chrono::Duration::hours(1);
+        let next_forming_time = forming_time + chrono::Duration::hours(1);
+
+        let updates = [
+            // Final update received after the close_time interval boundary is yielded immediately
+            candle("btc", closed_time, 99.0),
+            // Late update of a candle already yielded as closed is discarded
+            candle("btc", closed_time, 98.0),
+            // Forming candle updates are held back until a later candle of the same market arrives
+            candle("btc", forming_time, 101.0),
+            candle("eth", forming_time, 10.0),
+            candle("btc", forming_time, 102.0),
+            candle("btc", next_forming_time, 103.0),
+        ];
+        for (sequence, update) in (1..).zip(updates) {
+            candle_tx.send(MarketEvent { sequence, ..update }).unwrap();
+        }
+        drop(candle_tx);
+
+        let (sequence, closed) = next_candle(&mut feed);
+        assert_eq!(
+            (sequence, closed.close_time, closed.close),
+            (1, closed_time, 99.0)
+        );
+
+        let (sequence, closed) = next_candle(&mut feed);
+        assert_eq!(
+            (sequence, closed.close_time, closed.close),
+            (5, forming_time, 102.0)
+        );
+
+        // Candles still forming when the feed finishes are never yielded
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn candle_feed_with_only_closed_bars_flushes_candle_once_close_time_elapses() {
+        // Keep candle_tx in scope so the idle market does not disconnect the receiver
+        let (candle_tx, candle_rx) = mpsc::unbounded_channel();
+        let mut feed = CandleFeed::new(candle_rx).with_only_closed_bars(true);
+
+        let close_time = Utc::now() + chrono::Duration::milliseconds(20);
+        candle_tx.send(candle("btc", close_time, 101.0)).unwrap();
+
+        // No further updates are received, but the candle is yielded once it's close_time elapses
+        let (_, closed) = next_candle(&mut feed);
+        assert_eq!((closed.close_time, closed.close), (close_time, 101.0));
+        assert!(Utc::now() >= close_time);
     }
 }
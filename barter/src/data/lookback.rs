@@ -0,0 +1,155 @@
+use super::{error::DataError, HistoryProvider};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_integration::model::{instrument::Instrument, Market};
+use std::collections::{vec_deque, HashMap, VecDeque};
+
+/// Bounded lookback window of the most recent [`Candle`]s of each [`Market`], exposed via
+/// [`HistoryProvider`]. Saves every strategy from maintaining it's own buffer of recent bars.
+///
+/// Owned by the strategy side, either directly or via a
+/// [`LookbackStrategy`](crate::strategy::lookback::LookbackStrategy), and updated with every
+/// [`MarketEvent`] the strategy consumes. Only [`Candle`] [`MarketEvent`]s are retained, and once a
+/// [`Market`]'s window is full the oldest [`Candle`] is evicted.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CandleHistory {
+    lookback: usize,
+    bars: HashMap<Market, VecDeque<Candle>>,
+}
+
+impl HistoryProvider for CandleHistory {
+    fn recent_bars(&self, market: &Market, n: usize) -> vec_deque::Iter<'_, Candle> {
+        self.bars
+            .get(market)
+            .map(|bars| bars.range(bars.len().saturating_sub(n)..))
+            .unwrap_or_default()
+    }
+}
+
+impl CandleHistory {
+    /// Constructs a new [`CandleHistory`] that retains up to `lookback` [`Candle`]s for each
+    /// [`Market`].
+    ///
+    /// Returns a [`DataError::InvalidConfig`] if `lookback` is 0.
+    #[allow(clippy::result_large_err)]
+    pub fn new(lookback: usize) -> Result<Self, DataError> {
+        if lookback == 0 {
+            return Err(DataError::InvalidConfig(
+                "CandleHistory lookback must be positive",
+            ));
+        }
+
+        Ok(Self {
+            lookback,
+            bars: HashMap::new(),
+        })
+    }
+
+    /// Retain the [`Candle`] of the provided [`MarketEvent`], ignoring any other [`DataKind`]s.
+    pub fn update(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        let DataKind::Candle(candle) = &market.kind else {
+            return;
+        };
+
+        let bars = self
+            .bars
+            .entry(Market::new(
+                market.exchange.clone(),
+                market.instrument.clone(),
+            ))
+            .or_insert_with(|| VecDeque::with_capacity(self.lookback));
+
+        if bars.len() == self.lookback {
+            bars.pop_front();
+        }
+        bars.push_back(*candle);
+    }
+
+    /// Maximum number of [`Candle`]s retained for each [`Market`].
+    pub fn lookback(&self) -> usize {
+        self.lookback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{market_event_candle, market_event_trade};
+    use barter_integration::model::{instrument::kind::InstrumentKind, Side};
+
+    fn candle_event(base: &str, close: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        event.instrument = Instrument::from((base, "usdt", InstrumentKind::Spot));
+        if let DataKind::Candle(candle) = &mut event.kind {
+            candle.close = close;
+        }
+        event
+    }
+
+    fn market(base: &str) -> Market {
+        Market::new(
+            market_event_candle().exchange,
+            Instrument::from((base, "usdt", InstrumentKind::Spot)),
+        )
+    }
+
+    fn closes<Provider>(provider: &Provider, market: &Market, n: usize) -> Vec<f64>
+    where
+        Provider: HistoryProvider,
+    {
+        provider
+            .recent_bars(market, n)
+            .map(|candle| candle.close)
+            .collect()
+    }
+
+    #[test]
+    fn candle_history_retains_bounded_window_of_recent_candles() {
+        let mut history = CandleHistory::new(3).unwrap();
+        let btc = market("btc");
+        assert_eq!(closes(&history, &btc, 3), Vec::<f64>::new());
+
+        history.update(&candle_event("btc", 1.0));
+        assert_eq!(closes(&history, &btc, 3), vec![1.0]);
+
+        for market in [
+            candle_event("btc", 2.0),
+            market_event_trade(Side::Buy),
+            candle_event("btc", 3.0),
+            candle_event("btc", 4.0),
+        ] {
+            history.update(&market);
+        }
+
+        // Oldest Candle evicted once the window is full, and non-Candle events are not retained
+        assert_eq!(closes(&history, &btc, 3), vec![2.0, 3.0, 4.0]);
+        assert_eq!(closes(&history, &btc, 2), vec![3.0, 4.0]);
+        assert_eq!(closes(&history, &btc, 10), vec![2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn candle_history_retains_candles_of_each_market_independently() {
+        let mut history = CandleHistory::new(5).unwrap();
+        for market in [
+            candle_event("btc", 100.0),
+            candle_event("eth", 10.0),
+            candle_event("btc", 101.0),
+        ] {
+            history.update(&market);
+        }
+
+        assert_eq!(closes(&history, &market("btc"), 5), vec![100.0, 101.0]);
+        assert_eq!(closes(&history, &market("eth"), 5), vec![10.0]);
+        assert_eq!(closes(&history, &market("sol"), 5), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn candle_history_with_zero_lookback_returns_error() {
+        assert!(matches!(
+            CandleHistory::new(0),
+            Err(DataError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+use super::{Feed, MarketGenerator, Sequencer};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+
+/// [`MarketGenerator`] that merges several underlying feeds (eg/ one per symbol) into a single
+/// stream ordered by [`MarketEvent`] `exchange_time`. Enables strategies that need multiple
+/// symbols interleaved chronologically, such as pairs trading.
+///
+/// The next [`MarketEvent`] of every underlying feed is buffered, and the earliest is yielded on
+/// each call to `next()`. Events with identical timestamps are yielded deterministically in
+/// [`Instrument`] order, followed by [`Exchange`](barter_integration::model::Exchange) &
+/// underlying feed order. The merged feed is only [`Feed::Finished`] once every underlying feed
+/// is finished. If any unfinished feed is [`Feed::Unhealthy`], the merged feed is unhealthy
+/// since the next event in time order cannot be determined.
+///
+/// Merged events are sequenced by the [`MergedDataHandler`]'s own [`Sequencer`], independently
+/// of the underlying feed sequence numbers.
+#[derive(Debug)]
+pub struct MergedDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    feeds: Vec<MergedFeed<Generator>>,
+    sequencer: Sequencer,
+}
+
+/// Underlying feed of a [`MergedDataHandler`], along with its buffered next [`MarketEvent`].
+#[derive(Debug)]
+struct MergedFeed<Generator> {
+    feed: Generator,
+    next: Option<MarketEvent<Instrument, DataKind>>,
+    finished: bool,
+}
+
+impl<Generator> MarketGenerator<MarketEvent<Instrument, DataKind>> for MergedDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        // Ensure the next MarketEvent of each unfinished feed is available
+        for merged in self.feeds.iter_mut() {
+            if merged.finished || merged.next.is_some() {
+                continue;
+            }
+
+            match merged.feed.next() {
+                Feed::Next(event) => merged.next = Some(event),
+                Feed::Unhealthy => return Feed::Unhealthy,
+                Feed::Finished => merged.finished = true,
+            }
+        }
+
+        // Yield the earliest buffered MarketEvent, breaking ties deterministically
+        let earliest = self
+            .feeds
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, merged)| merged.next.is_some())
+            .min_by(|(a_index, a), (b_index, b)| {
+                let (a, b) = (a.next.as_ref().unwrap(), b.next.as_ref().unwrap());
+                a.exchange_time
+                    .cmp(&b.exchange_time)
+                    .then_with(|| a.instrument.cmp(&b.instrument))
+                    .then_with(|| a.exchange.cmp(&b.exchange))
+                    .then_with(|| a_index.cmp(b_index))
+            })
+            .and_then(|(_, merged)| merged.next.take());
+
+        match earliest {
+            Some(event) => Feed::Next(self.sequencer.sequence(event)),
+            None => Feed::Finished,
+        }
+    }
+}
+
+impl<Generator> MergedDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    /// Constructs a new [`MergedDataHandler`] from the provided underlying feeds.
+    pub fn new<Feeds>(feeds: Feeds) -> Self
+    where
+        Feeds: IntoIterator<Item = Generator>,
+    {
+        Self {
+            feeds: feeds
+                .into_iter()
+                .map(|feed| MergedFeed {
+                    feed,
+                    next: None,
+                    finished: false,
+                })
+                .collect(),
+            sequencer: Sequencer::default(),
+        }
+    }
+
+    /// Returns true while any underlying feed may still yield a [`MarketEvent`].
+    pub fn should_continue(&self) -> bool {
+        self.feeds
+            .iter()
+            .any(|merged| !merged.finished || merged.next.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::historical, test_util::market_event_candle};
+    use barter_integration::model::instrument::kind::InstrumentKind;
+    use chrono::{DateTime, Duration, Utc};
+    use std::ops::Add;
+
+    fn event(
+        base_time: DateTime<Utc>,
+        minutes: i64,
+        base: &str,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        event.exchange_time = base_time.add(Duration::minutes(minutes));
+        event.instrument = Instrument::from((base, "usdt", InstrumentKind::Spot));
+        event
+    }
+
+    fn next<Generator>(merged: &mut MergedDataHandler<Generator>) -> (i64, String, u64)
+    where
+        Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+    {
+        match merged.next() {
+            Feed::Next(event) => (
+                event.exchange_time.timestamp() / 60,
+                event.instrument.base.to_string(),
+                event.sequence,
+            ),
+            feed => panic!("expected Feed::Next, got {feed:?}"),
+        }
+    }
+
+    #[test]
+    fn merged_data_handler_yields_events_in_timestamp_order() {
+        let base_time = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+        let eth = historical::MarketFeed::new(vec![
+            event(base_time, 1, "eth"),
+            event(base_time, 2, "eth"),
+            event(base_time, 4, "eth"),
+        ]);
+        let btc = historical::MarketFeed::new(vec![
+            event(base_time, 0, "btc"),
+            event(base_time, 3, "btc"),
+        ]);
+
+        let mut merged = MergedDataHandler::new([eth, btc]);
+
+        assert_eq!(next(&mut merged), (0, "btc".to_owned(), 1));
+        assert_eq!(next(&mut merged), (1, "eth".to_owned(), 2));
+        assert_eq!(next(&mut merged), (2, "eth".to_owned(), 3));
+        assert_eq!(next(&mut merged), (3, "btc".to_owned(), 4));
+        assert!(merged.should_continue());
+        assert_eq!(next(&mut merged), (4, "eth".to_owned(), 5));
+        assert_eq!(merged.next(), Feed::Finished);
+        assert!(!merged.should_continue());
+    }
+
+    #[test]
+    fn merged_data_handler_breaks_timestamp_ties_by_instrument() {
+        let base_time = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+        let sol = historical::MarketFeed::new(vec![event(base_time, 0, "sol")]);
+        let eth = historical::MarketFeed::new(vec![event(base_time, 0, "eth")]);
+        let btc = historical::MarketFeed::new(vec![event(base_time, 0, "btc")]);
+
+        let mut merged = MergedDataHandler::new([sol, eth, btc]);
+
+        assert_eq!(next(&mut merged).1, "btc");
+        assert_eq!(next(&mut merged).1, "eth");
+        assert_eq!(next(&mut merged).1, "sol");
+        assert_eq!(merged.next(), Feed::Finished);
+    }
+}
@@ -1,5 +1,8 @@
+use barter_data::{event::MarketEvent, subscription::candle::Candle};
+use barter_integration::model::Market;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::vec_deque;
 
 /// Barter data module specific errors.
 pub mod error;
@@ -10,12 +13,40 @@ pub mod live;
 /// Historical market event feed for backtesting.
 pub mod historical;
 
+/// Bounded lookback window of recent candles, retained on the strategy side.
+pub mod lookback;
+
+/// Market event feed merging several underlying feeds into a single timestamp ordered stream.
+pub mod merged;
+
+/// Resampled market event feed that aggregates an underlying candle feed into a coarser timeframe.
+pub mod resample;
+
+/// Synthetic spread market event feed derived from two underlying feeds (eg/ for pairs trading).
+pub mod spread;
+
+/// Market event feed wrapper that throttles a historical feed to simulate live timing.
+pub mod throttle;
+
+/// Market event feed wrapper that validates the market events of an underlying feed.
+pub mod validate;
+
+/// Volume bar market event feed that aggregates an underlying feed by cumulative traded volume.
+pub mod volume;
+
 /// Generates the next `Event`. Acts as the system heartbeat.
 pub trait MarketGenerator<Event> {
     /// Return the next market `Event`.
     fn next(&mut self) -> Feed<Event>;
 }
 
+/// Provides a lookback window of the most recently generated [`Candle`]s.
+pub trait HistoryProvider {
+    /// Return up to the `n` most recently generated [`Candle`]s of the provided [`Market`],
+    /// ordered from oldest to newest.
+    fn recent_bars(&self, market: &Market, n: usize) -> vec_deque::Iter<'_, Candle>;
+}
+
 /// Communicates the state of the [`Feed`] as well as the next event.
 #[derive(Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub enum Feed<Event> {
@@ -24,6 +55,53 @@ pub enum Feed<Event> {
     Finished,
 }
 
+/// Market event that can be assigned a sequence number by the source feed that yields it.
+pub trait Sequenced {
+    /// Assign the provided sequence number to the market event.
+    fn set_sequence(&mut self, sequence: u64);
+}
+
+impl<InstrumentId, T> Sequenced for MarketEvent<InstrumentId, T> {
+    fn set_sequence(&mut self, sequence: u64) {
+        self.sequence = sequence;
+    }
+}
+
+/// Per source feed counter that assigns a monotonically increasing sequence number, starting
+/// from one, to every market event the feed yields. Enables downstream consumers to enforce
+/// strict ordering and detect dropped events, independently of the event timestamps.
+///
+/// Only source feeds (eg/ the [`historical`] feeds) assign sequence numbers. Live market events
+/// are sequenced upstream by the `Barter-Data` consumer loop, and data handlers wrapping another
+/// feed preserve the upstream sequence numbers rather than re-sequencing.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Sequencer {
+    next: u64,
+}
+
+impl Default for Sequencer {
+    fn default() -> Self {
+        Self { next: 1 }
+    }
+}
+
+impl Sequencer {
+    /// Assign the next sequence number to the provided market event.
+    pub fn sequence<Event>(&mut self, mut event: Event) -> Event
+    where
+        Event: Sequenced,
+    {
+        event.set_sequence(self.next);
+        self.next += 1;
+        event
+    }
+
+    /// Sequence number that will be assigned to the next market event.
+    pub fn next_sequence(&self) -> u64 {
+        self.next
+    }
+}
+
 /// Metadata detailing the [`Candle`](barter_data::subscription::candle::Candle) or
 /// [`Trade`](barter_data::subscription::trade::PublicTrade) close price & it's associated
 /// timestamp. Used to propagate key market information in downstream Events.
@@ -33,6 +111,21 @@ pub struct MarketMeta {
     pub close: f64,
     /// Exchange timestamp from the source market event.
     pub time: DateTime<Utc>,
+    /// Optional volatility estimate of the market in price units (eg/ ATR), provided by a strategy
+    /// to enable volatility adjusted position sizing. See
+    /// [`VolatilityAllocator`](crate::portfolio::allocator::VolatilityAllocator).
+    #[serde(default)]
+    pub volatility: Option<f64>,
+    /// Optional volume of the source market event (eg/ Candle volume), used to model the market
+    /// impact of an order. See
+    /// [`VolumeProportional`](crate::execution::slippage::VolumeProportional).
+    #[serde(default)]
+    pub volume: Option<f64>,
+    /// Optional quoted bid-ask spread of the market in price units (ie/ best ask - best bid),
+    /// used to model the cost of crossing the spread. See
+    /// [`SpreadBased`](crate::execution::slippage::SpreadBased).
+    #[serde(default)]
+    pub spread: Option<f64>,
 }
 
 impl Default for MarketMeta {
@@ -40,6 +133,9 @@ impl Default for MarketMeta {
         Self {
             close: 100.0,
             time: Utc::now(),
+            volatility: None,
+            volume: None,
+            spread: None,
         }
     }
 }
@@ -0,0 +1,301 @@
+use super::{error::DataError, Feed, MarketGenerator};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_integration::model::{instrument::Instrument, Market};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Configuration for constructing a [`ResamplingDataHandler`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Number of input [`Candle`]s aggregated into each output [`Candle`] (eg/ 15 to resample
+    /// 1-minute candles into 15-minute candles).
+    pub bars: usize,
+}
+
+/// [`MarketGenerator`] that resamples an underlying [`Candle`] feed into a coarser timeframe by
+/// aggregating every N input [`Candle`]s into one output [`Candle`]: open is the first open, high
+/// is the max high, low is the min low, close is the last close, and volume & trade count are
+/// summed.
+///
+/// Candles are accumulated independently for each [`Market`], and the aggregated [`Candle`]
+/// [`MarketEvent`] is only yielded once its window of N input [`Candle`]s closes. Other
+/// [`DataKind`]s are passed through unchanged. When the underlying feed finishes, any partially
+/// accumulated [`Candle`]s are flushed in [`Market`] order before the feed finishes.
+///
+/// Events passed through keep their upstream sequence number, and each aggregated [`Candle`]
+/// takes the sequence number of the latest input [`Candle`] in it's window.
+#[derive(Debug)]
+pub struct ResamplingDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    bars: usize,
+    feed: Generator,
+    windows: BTreeMap<Market, ResampleWindow>,
+}
+
+/// Output [`Candle`] being accumulated for a [`Market`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct ResampleWindow {
+    exchange_time: DateTime<Utc>,
+    received_time: DateTime<Utc>,
+    sequence: u64,
+    candle: Candle,
+    bars: usize,
+}
+
+impl ResampleWindow {
+    /// Constructs a new [`ResampleWindow`] from the first [`Candle`] of the window.
+    fn new(event: &MarketEvent<Instrument, DataKind>, candle: Candle) -> Self {
+        Self {
+            exchange_time: event.exchange_time,
+            received_time: event.received_time,
+            sequence: event.sequence,
+            candle,
+            bars: 1,
+        }
+    }
+
+    /// Aggregates the next [`Candle`] into the [`ResampleWindow`].
+    fn update(&mut self, event: &MarketEvent<Instrument, DataKind>, next: Candle) {
+        self.exchange_time = event.exchange_time;
+        self.received_time = event.received_time;
+        self.sequence = event.sequence;
+        self.candle.close_time = next.close_time;
+        self.candle.high = self.candle.high.max(next.high);
+        self.candle.low = self.candle.low.min(next.low);
+        self.candle.close = next.close;
+        self.candle.volume += next.volume;
+        self.candle.trade_count += next.trade_count;
+        self.bars += 1;
+    }
+
+    /// Consumes the [`ResampleWindow`], returning the aggregated [`Candle`] [`MarketEvent`].
+    fn into_event(self, market: Market) -> MarketEvent<Instrument, DataKind> {
+        MarketEvent {
+            exchange_time: self.exchange_time,
+            received_time: self.received_time,
+            exchange: market.exchange,
+            instrument: market.instrument,
+            kind: DataKind::Candle(self.candle),
+            sequence: self.sequence,
+        }
+    }
+}
+
+impl<Generator> MarketGenerator<MarketEvent<Instrument, DataKind>>
+    for ResamplingDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            let event = match self.feed.next() {
+                Feed::Next(event) => event,
+                Feed::Unhealthy => return Feed::Unhealthy,
+                Feed::Finished => {
+                    // Flush any partial windows before finishing
+                    return match self.windows.pop_first() {
+                        Some((market, window)) => Feed::Next(window.into_event(market)),
+                        None => Feed::Finished,
+                    };
+                }
+            };
+
+            let candle = match &event.kind {
+                DataKind::Candle(candle) => *candle,
+                _ => return Feed::Next(event),
+            };
+
+            if let Some(resampled) = self.update(&event, candle) {
+                return Feed::Next(resampled);
+            }
+        }
+    }
+}
+
+impl<Generator> ResamplingDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    /// Constructs a new [`ResamplingDataHandler`] that resamples the provided feed.
+    ///
+    /// Returns a [`DataError::InvalidConfig`] if `bars` is 0.
+    #[allow(clippy::result_large_err)]
+    pub fn new(config: Config, feed: Generator) -> Result<Self, DataError> {
+        if config.bars == 0 {
+            return Err(DataError::InvalidConfig(
+                "ResamplingDataHandler bars must be positive",
+            ));
+        }
+
+        Ok(Self {
+            bars: config.bars,
+            feed,
+            windows: BTreeMap::new(),
+        })
+    }
+
+    /// Aggregates the [`Candle`] into the [`Market`]'s current [`ResampleWindow`], returning the
+    /// aggregated [`MarketEvent`] if the window has closed.
+    fn update(
+        &mut self,
+        event: &MarketEvent<Instrument, DataKind>,
+        candle: Candle,
+    ) -> Option<MarketEvent<Instrument, DataKind>> {
+        let market = Market::new(event.exchange.clone(), event.instrument.clone());
+
+        let window = self
+            .windows
+            .entry(market.clone())
+            .and_modify(|window| window.update(event, candle))
+            .or_insert_with(|| ResampleWindow::new(event, candle));
+
+        if window.bars < self.bars {
+            return None;
+        }
+
+        self.windows
+            .remove(&market)
+            .map(|window| window.into_event(market))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::historical, test_util::market_event_candle};
+    use barter_integration::model::instrument::kind::InstrumentKind;
+    use chrono::Duration;
+    use std::ops::Add;
+
+    fn candle_event(
+        base: &str,
+        base_time: DateTime<Utc>,
+        minutes: i64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        event.instrument = Instrument::from((base, "usdt", InstrumentKind::Spot));
+        event.exchange_time = base_time.add(Duration::minutes(minutes));
+        event.kind = DataKind::Candle(Candle {
+            close_time: event.exchange_time,
+            open,
+            high,
+            low,
+            close,
+            volume: 2.0,
+            trade_count: 1,
+        });
+        event
+    }
+
+    fn candle(event: &MarketEvent<Instrument, DataKind>) -> Candle {
+        match event.kind {
+            DataKind::Candle(candle) => candle,
+            _ => panic!("expected Candle MarketEvent"),
+        }
+    }
+
+    #[test]
+    fn resampling_data_handler_aggregates_n_candles_and_flushes_partial_window() {
+        let base_time = Utc::now();
+        let feed = historical::MarketFeed::new(vec![
+            candle_event("btc", base_time, 0, 100.0, 110.0, 95.0, 105.0),
+            candle_event("btc", base_time, 1, 105.0, 120.0, 100.0, 115.0),
+            candle_event("btc", base_time, 2, 115.0, 116.0, 90.0, 92.0),
+            candle_event("btc", base_time, 3, 92.0, 94.0, 91.0, 93.0),
+        ]);
+
+        let mut resampler = ResamplingDataHandler::new(Config { bars: 3 }, feed).unwrap();
+
+        // Window closes after 3 input candles
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.exchange_time, base_time.add(Duration::minutes(2)));
+        assert_eq!(event.sequence, 3);
+        assert_eq!(
+            candle(&event),
+            Candle {
+                close_time: base_time.add(Duration::minutes(2)),
+                open: 100.0,
+                high: 120.0,
+                low: 90.0,
+                close: 92.0,
+                volume: 6.0,
+                trade_count: 3,
+            }
+        );
+
+        // Partial final window flushed when the underlying feed finishes
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.sequence, 4);
+        assert_eq!(candle(&event).open, 92.0);
+        assert_eq!(candle(&event).close, 93.0);
+        assert_eq!(candle(&event).volume, 2.0);
+
+        assert_eq!(resampler.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn resampling_data_handler_accumulates_each_market_independently() {
+        let base_time = Utc::now();
+        let feed = historical::MarketFeed::new(vec![
+            candle_event("eth", base_time, 0, 10.0, 10.0, 10.0, 10.0),
+            candle_event("btc", base_time, 0, 100.0, 100.0, 100.0, 100.0),
+            candle_event("eth", base_time, 1, 11.0, 11.0, 11.0, 11.0),
+            candle_event("btc", base_time, 1, 101.0, 101.0, 101.0, 101.0),
+            candle_event("eth", base_time, 2, 12.0, 12.0, 12.0, 12.0),
+            candle_event("btc", base_time, 2, 102.0, 102.0, 102.0, 102.0),
+        ]);
+
+        let mut resampler = ResamplingDataHandler::new(Config { bars: 2 }, feed).unwrap();
+
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.instrument.base, "eth".into());
+        assert_eq!(candle(&event).close, 11.0);
+
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.instrument.base, "btc".into());
+        assert_eq!(candle(&event).open, 100.0);
+
+        // Partial windows flushed in Market order
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.instrument.base, "btc".into());
+        assert_eq!(candle(&event).close, 102.0);
+
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.instrument.base, "eth".into());
+        assert_eq!(candle(&event).close, 12.0);
+
+        assert_eq!(resampler.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn resampling_data_handler_rejects_zero_bars() {
+        let resampler = ResamplingDataHandler::new(
+            Config { bars: 0 },
+            historical::MarketFeed::new(std::iter::empty()),
+        );
+
+        assert!(matches!(resampler, Err(DataError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,357 @@
+use super::{Feed, MarketGenerator};
+use crate::statistic::metric::beta::RollingBeta;
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_integration::model::{instrument::Instrument, Exchange};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Configuration for constructing a [`SpreadDataHandler`] via the new() constructor method.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// [`Exchange`] the synthetic spread [`MarketEvent`]s are attributed to.
+    pub exchange: Exchange,
+    /// Synthetic spread [`Instrument`] the spread [`MarketEvent`]s are attributed to.
+    pub instrument: Instrument,
+    /// Hedge ratio β applied to the secondary leg, where spread = primary - β * secondary.
+    pub hedge_ratio: f64,
+}
+
+/// [`MarketGenerator`] that consumes two underlying [`Candle`] feeds and yields a synthetic
+/// instrument equal to their linear combination: spread = primary - β * secondary
+/// (eg/ ETH - β * BTC). Enables existing strategies to trade the spread as if it were a normal
+/// symbol.
+///
+/// Legs are aligned on [`MarketEvent`] `exchange_time`. If one leg is behind the other, its
+/// events are discarded until both legs have a bar for the same time. Non-[`Candle`] events are
+/// ignored.
+///
+/// Since the intra-bar path of each leg is unknown, the spread [`Candle`] `high` & `low` are the
+/// max & min of the spread `open` & `close`. Volume & trade count are not meaningful for a
+/// synthetic instrument and are set to zero. Spread events take the upstream sequence number of
+/// the primary leg [`Candle`] they are derived from.
+#[derive(Debug)]
+pub struct SpreadDataHandler<Primary, Secondary>
+where
+    Primary: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+    Secondary: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    exchange: Exchange,
+    instrument: Instrument,
+    hedge_ratio: f64,
+    hedge_ratio_estimator: Option<RollingBeta>,
+    primary: Primary,
+    secondary: Secondary,
+    primary_next: Option<(DateTime<Utc>, u64, Candle)>,
+    secondary_next: Option<(DateTime<Utc>, u64, Candle)>,
+}
+
+impl<Primary, Secondary> MarketGenerator<MarketEvent<Instrument, DataKind>>
+    for SpreadDataHandler<Primary, Secondary>
+where
+    Primary: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+    Secondary: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            // Ensure the next Candle of each leg is available
+            let (primary_time, primary_sequence, primary) = match self.primary_next {
+                Some(next) => next,
+                None => match next_candle(&mut self.primary) {
+                    Feed::Next(next) => *self.primary_next.insert(next),
+                    Feed::Unhealthy => return Feed::Unhealthy,
+                    Feed::Finished => return Feed::Finished,
+                },
+            };
+            let (secondary_time, _, secondary) = match self.secondary_next {
+                Some(next) => next,
+                None => match next_candle(&mut self.secondary) {
+                    Feed::Next(next) => *self.secondary_next.insert(next),
+                    Feed::Unhealthy => return Feed::Unhealthy,
+                    Feed::Finished => return Feed::Finished,
+                },
+            };
+
+            // Discard the Candle of any leg that is behind, until both legs are aligned
+            match primary_time.cmp(&secondary_time) {
+                Ordering::Less => self.primary_next = None,
+                Ordering::Greater => self.secondary_next = None,
+                Ordering::Equal => {
+                    self.primary_next = None;
+                    self.secondary_next = None;
+
+                    // Calculate spread before updating the hedge ratio to avoid look-ahead
+                    let spread =
+                        self.spread_event(primary_time, primary_sequence, &primary, &secondary);
+                    self.update_hedge_ratio(&primary, &secondary);
+
+                    return Feed::Next(spread);
+                }
+            }
+        }
+    }
+}
+
+impl<Primary, Secondary> SpreadDataHandler<Primary, Secondary>
+where
+    Primary: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+    Secondary: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    /// Constructs a new [`SpreadDataHandler`] from the provided primary & secondary leg feeds.
+    pub fn new(config: Config, primary: Primary, secondary: Secondary) -> Self {
+        Self {
+            exchange: config.exchange,
+            instrument: config.instrument,
+            hedge_ratio: config.hedge_ratio,
+            hedge_ratio_estimator: None,
+            primary,
+            secondary,
+            primary_next: None,
+            secondary_next: None,
+        }
+    }
+
+    /// Dynamically estimate the hedge ratio β using the provided [`RollingBeta`], regressing the
+    /// primary leg close on the secondary leg close every bar.
+    ///
+    /// The configured hedge ratio is used until the [`RollingBeta`] has warmed up. Each spread
+    /// bar uses the β estimated from the preceding bars only, so the spread never looks ahead.
+    pub fn with_rolling_beta(self, estimator: RollingBeta) -> Self {
+        Self {
+            hedge_ratio_estimator: Some(estimator),
+            ..self
+        }
+    }
+
+    /// Current hedge ratio β applied to the secondary leg.
+    pub fn hedge_ratio(&self) -> f64 {
+        self.hedge_ratio
+    }
+
+    /// Updates the hedge ratio β applied to the secondary leg for all subsequent spread bars.
+    pub fn set_hedge_ratio(&mut self, hedge_ratio: f64) {
+        self.hedge_ratio = hedge_ratio;
+    }
+
+    /// Update the hedge ratio β with the latest [`RollingBeta`] estimate, if one is configured &
+    /// warmed up.
+    fn update_hedge_ratio(&mut self, primary: &Candle, secondary: &Candle) {
+        if let Some(beta) = self
+            .hedge_ratio_estimator
+            .as_mut()
+            .and_then(|estimator| estimator.update(primary.close, secondary.close))
+        {
+            self.hedge_ratio = beta;
+        }
+    }
+
+    /// Calculate the spread [`MarketEvent`] from the aligned primary & secondary [`Candle`]s.
+    fn spread_event(
+        &self,
+        exchange_time: DateTime<Utc>,
+        sequence: u64,
+        primary: &Candle,
+        secondary: &Candle,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let open = primary.open - self.hedge_ratio * secondary.open;
+        let close = primary.close - self.hedge_ratio * secondary.close;
+
+        MarketEvent {
+            exchange_time,
+            received_time: Utc::now(),
+            exchange: self.exchange.clone(),
+            instrument: self.instrument.clone(),
+            kind: DataKind::Candle(Candle {
+                close_time: primary.close_time,
+                open,
+                high: open.max(close),
+                low: open.min(close),
+                close,
+                volume: 0.0,
+                trade_count: 0,
+            }),
+            sequence,
+        }
+    }
+}
+
+/// Retrieve the next [`Candle`] [`MarketEvent`] from the provided feed, ignoring any other
+/// [`DataKind`]s.
+fn next_candle<Generator>(feed: &mut Generator) -> Feed<(DateTime<Utc>, u64, Candle)>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    loop {
+        match feed.next() {
+            Feed::Next(MarketEvent {
+                exchange_time,
+                kind: DataKind::Candle(candle),
+                sequence,
+                ..
+            }) => return Feed::Next((exchange_time, sequence, candle)),
+            Feed::Next(_) => continue,
+            Feed::Unhealthy => return Feed::Unhealthy,
+            Feed::Finished => return Feed::Finished,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::historical, statistic::metric::beta, test_util::market_event_candle};
+    use barter_integration::model::instrument::kind::InstrumentKind;
+    use chrono::Duration;
+    use std::ops::Add;
+
+    fn candle_event(
+        base_time: DateTime<Utc>,
+        minutes: i64,
+        open: f64,
+        close: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        event.exchange_time = base_time.add(Duration::minutes(minutes));
+        event.kind = DataKind::Candle(Candle {
+            close_time: event.exchange_time,
+            open,
+            high: open.max(close),
+            low: open.min(close),
+            close,
+            volume: 1.0,
+            trade_count: 1,
+        });
+        event
+    }
+
+    fn config(hedge_ratio: f64) -> Config {
+        Config {
+            exchange: Exchange::from("synthetic"),
+            instrument: Instrument::from(("eth_btc_spread", "usdt", InstrumentKind::Spot)),
+            hedge_ratio,
+        }
+    }
+
+    fn candle(event: &MarketEvent<Instrument, DataKind>) -> Candle {
+        match event.kind {
+            DataKind::Candle(candle) => candle,
+            _ => panic!("expected Candle MarketEvent"),
+        }
+    }
+
+    #[test]
+    fn spread_data_handler_yields_spread_of_aligned_candles() {
+        let base_time = Utc::now();
+        let primary = historical::MarketFeed::new(vec![
+            candle_event(base_time, 0, 2000.0, 2100.0),
+            candle_event(base_time, 1, 2100.0, 2050.0),
+        ]);
+        let secondary = historical::MarketFeed::new(vec![
+            candle_event(base_time, 0, 30000.0, 31000.0),
+            candle_event(base_time, 1, 31000.0, 30000.0),
+        ]);
+
+        let mut spread = SpreadDataHandler::new(config(0.05), primary, secondary);
+
+        let Feed::Next(first) = spread.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(first.exchange_time, base_time);
+        assert_eq!(first.exchange, Exchange::from("synthetic"));
+        let first = candle(&first);
+        assert_eq!(first.open, 2000.0 - 0.05 * 30000.0);
+        assert_eq!(first.close, 2100.0 - 0.05 * 31000.0);
+        assert_eq!(first.high, first.close);
+        assert_eq!(first.low, first.open);
+
+        let Feed::Next(second) = spread.next() else {
+            panic!("expected Feed::Next")
+        };
+        let second = candle(&second);
+        assert_eq!(second.open, 2100.0 - 0.05 * 31000.0);
+        assert_eq!(second.close, 2050.0 - 0.05 * 30000.0);
+
+        assert_eq!(spread.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn spread_data_handler_discards_unaligned_candles() {
+        let base_time = Utc::now();
+        let primary = historical::MarketFeed::new(vec![
+            candle_event(base_time, 0, 100.0, 100.0),
+            candle_event(base_time, 2, 102.0, 102.0),
+        ]);
+        let secondary = historical::MarketFeed::new(vec![
+            candle_event(base_time, 1, 10.0, 10.0),
+            candle_event(base_time, 2, 12.0, 12.0),
+        ]);
+
+        let mut spread = SpreadDataHandler::new(config(2.0), primary, secondary);
+
+        let Feed::Next(event) = spread.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.exchange_time, base_time.add(Duration::minutes(2)));
+        assert_eq!(candle(&event).close, 102.0 - 2.0 * 12.0);
+
+        // Spread takes the upstream sequence number of the primary leg Candle
+        assert_eq!(event.sequence, 2);
+        assert_eq!(spread.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn spread_data_handler_applies_updated_hedge_ratio() {
+        let base_time = Utc::now();
+        let primary = historical::MarketFeed::new(vec![candle_event(base_time, 0, 100.0, 100.0)]);
+        let secondary = historical::MarketFeed::new(vec![candle_event(base_time, 0, 10.0, 10.0)]);
+
+        let mut spread = SpreadDataHandler::new(config(1.0), primary, secondary);
+        spread.set_hedge_ratio(3.0);
+
+        let Feed::Next(event) = spread.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(candle(&event).close, 70.0);
+    }
+
+    #[test]
+    fn spread_data_handler_with_rolling_beta_updates_hedge_ratio_after_warm_up() {
+        let base_time = Utc::now();
+        let primary = historical::MarketFeed::new(vec![
+            candle_event(base_time, 0, 20.0, 20.0),
+            candle_event(base_time, 1, 40.0, 40.0),
+            candle_event(base_time, 2, 60.0, 60.0),
+        ]);
+        let secondary = historical::MarketFeed::new(vec![
+            candle_event(base_time, 0, 10.0, 10.0),
+            candle_event(base_time, 1, 20.0, 20.0),
+            candle_event(base_time, 2, 30.0, 30.0),
+        ]);
+
+        let mut spread = SpreadDataHandler::new(config(1.0), primary, secondary)
+            .with_rolling_beta(RollingBeta::new(beta::Config { window: 2 }).unwrap());
+
+        // Bar 0: estimator warming up, so configured hedge ratio is used
+        let Feed::Next(event) = spread.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(candle(&event).close, 20.0 - 1.0 * 10.0);
+        assert_eq!(spread.hedge_ratio(), 1.0);
+
+        // Bar 1: spread uses β from preceding bars, then β is estimated as 2.0
+        let Feed::Next(event) = spread.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(candle(&event).close, 40.0 - 1.0 * 20.0);
+        assert!((spread.hedge_ratio() - 2.0).abs() < 1e-10);
+
+        // Bar 2: spread uses estimated β
+        let Feed::Next(event) = spread.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert!(candle(&event).close.abs() < 1e-10);
+    }
+}
@@ -0,0 +1,147 @@
+use super::{error::DataError, Feed, MarketGenerator};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Configuration for constructing a [`ThrottledDataHandler`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Replay speed relative to wall-clock time (eg/ 1.0 is real time, and 60.0 compresses an
+    /// hour of market events into a minute). A speed of 0.0 disables throttling.
+    pub speed: f64,
+}
+
+/// [`MarketGenerator`] that wraps a historical feed and throttles it to simulate live timing
+/// (eg/ when demoing a strategy), rather than yielding market events as fast as possible.
+///
+/// Before yielding each market event, the [`ThrottledDataHandler`] sleeps until the
+/// `exchange_time` delta since the previous market event, divided by the configured speed, has
+/// elapsed since the previous market event was yielded. Time spent processing each market event
+/// therefore counts towards the delay. Market events that are not later than the previous market
+/// event are yielded immediately. Every event is passed through unchanged, including its sequence
+/// number.
+#[derive(Debug)]
+pub struct ThrottledDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    speed: f64,
+    feed: Generator,
+    previous: Option<(DateTime<Utc>, Instant)>,
+}
+
+impl<Generator> MarketGenerator<MarketEvent<Instrument, DataKind>>
+    for ThrottledDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        let event = match self.feed.next() {
+            Feed::Next(event) => event,
+            feed => return feed,
+        };
+
+        if let Some(delay) = self.delay(event.exchange_time) {
+            std::thread::sleep(delay);
+        }
+
+        self.previous = Some((event.exchange_time, Instant::now()));
+        Feed::Next(event)
+    }
+}
+
+impl<Generator> ThrottledDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    /// Constructs a new [`ThrottledDataHandler`] that throttles the provided feed.
+    ///
+    /// Returns a [`DataError::InvalidConfig`] if the `speed` is negative or NaN.
+    #[allow(clippy::result_large_err)]
+    pub fn new(config: Config, feed: Generator) -> Result<Self, DataError> {
+        if config.speed.is_nan() || config.speed < 0.0 {
+            return Err(DataError::InvalidConfig(
+                "ThrottledDataHandler speed must not be negative or NaN",
+            ));
+        }
+
+        Ok(Self {
+            speed: config.speed,
+            feed,
+            previous: None,
+        })
+    }
+
+    /// Calculate the remaining delay before a market event with the provided `exchange_time`
+    /// should be yielded, if any.
+    fn delay(&self, exchange_time: DateTime<Utc>) -> Option<Duration> {
+        if self.speed == 0.0 {
+            return None;
+        }
+
+        let (previous_time, previous_yielded) = self.previous?;
+        let delta = (exchange_time - previous_time).to_std().ok()?;
+
+        Duration::try_from_secs_f64(delta.as_secs_f64() / self.speed)
+            .ok()?
+            .checked_sub(previous_yielded.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::historical, test_util::market_event_candle};
+    use std::ops::Add;
+
+    fn events(seconds: &[i64]) -> Vec<MarketEvent<Instrument, DataKind>> {
+        let base_time = market_event_candle().exchange_time;
+        seconds
+            .iter()
+            .map(|seconds| {
+                let mut event = market_event_candle();
+                event.exchange_time = base_time.add(chrono::Duration::seconds(*seconds));
+                event
+            })
+            .collect()
+    }
+
+    #[test]
+    fn throttled_data_handler_sleeps_for_scaled_exchange_time_delta() {
+        // 6 seconds of market events at 60x speed takes at least 100ms
+        let feed = historical::MarketFeed::new(events(&[0, 3, 6]));
+        let mut handler = ThrottledDataHandler::new(Config { speed: 60.0 }, feed).unwrap();
+
+        let start = Instant::now();
+        while let Feed::Next(_) = handler.next() {}
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn throttled_data_handler_with_zero_speed_does_not_sleep() {
+        let feed = historical::MarketFeed::new(events(&[0, 3600, 7200]));
+        let mut handler = ThrottledDataHandler::new(Config { speed: 0.0 }, feed).unwrap();
+
+        let start = Instant::now();
+        let mut sequence = 0;
+        while let Feed::Next(event) = handler.next() {
+            sequence = event.sequence;
+        }
+        assert_eq!(sequence, 3);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn throttled_data_handler_rejects_negative_or_nan_speed() {
+        for speed in [-1.0, f64::NAN] {
+            let handler = ThrottledDataHandler::new(
+                Config { speed },
+                historical::MarketFeed::new(std::iter::empty()),
+            );
+
+            assert!(matches!(handler, Err(DataError::InvalidConfig(_))));
+        }
+    }
+}
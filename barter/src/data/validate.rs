@@ -0,0 +1,435 @@
+use super::{error::DataError, Feed, MarketGenerator};
+use crate::statistic::{de_duration_from_secs, se_duration_as_secs};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_integration::model::{instrument::Instrument, Market};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use tracing::warn;
+
+/// Configuration for constructing a [`ValidatingDataHandler`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    /// Validate the OHLC invariants of every [`Candle`], such that `low <= open, close, high` and
+    /// `high >= open, close`. Disabled by default.
+    #[serde(default)]
+    pub validate: bool,
+    /// Detect gaps between consecutive [`Candle`]s of each [`Market`]. Disabled by default.
+    #[serde(default)]
+    pub gaps: Option<GapConfig>,
+}
+
+/// Configuration for detecting gaps between consecutive [`Candle`]s (eg/ missing bars due to an
+/// exchange outage).
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct GapConfig {
+    /// Expected interval between consecutive [`Candle`] timestamps of a [`Market`].
+    #[serde(
+        deserialize_with = "de_duration_from_secs",
+        serialize_with = "se_duration_as_secs"
+    )]
+    pub interval: Duration,
+    #[serde(default)]
+    pub policy: GapPolicy,
+}
+
+/// Behaviour when consecutive [`Candle`] timestamps of a [`Market`] differ by more than the
+/// expected [`GapConfig`] interval.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum GapPolicy {
+    /// Return a [`DataError::GapDetected`]. The [`Candle`] after the gap is still yielded on the
+    /// following call.
+    Error,
+    /// Log a warning & yield the [`Candle`] after the gap.
+    #[default]
+    Warn,
+    /// Yield the [`Candle`] after the gap without reporting it.
+    Ignore,
+    /// Fill the gap with a flat [`Candle`] at every missing interval, each with the previous
+    /// close price and zero volume, before yielding the [`Candle`] after the gap.
+    FillForward,
+}
+
+/// [`MarketGenerator`] that wraps an underlying feed and validates the market events it yields,
+/// protecting a backtest from silently consuming corrupt or incomplete vendor data.
+///
+/// An event that fails validation yields [`Feed::Unhealthy`] (see [`Self::try_next`] for the
+/// associated [`DataError`]), and the next event is validated on the following call. Gaps are
+/// handled according to the configured [`GapPolicy`], where the timestamp of each [`Candle`] is
+/// compared with the previous valid [`Candle`] of the same [`Market`].
+///
+/// Events yielded keep their upstream sequence number, so a discarded invalid event leaves a
+/// sequence gap. Gap filling [`Candle`]s take the sequence number of the [`Candle`] that revealed
+/// the gap.
+#[derive(Debug)]
+pub struct ValidatingDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    validate: bool,
+    gaps: Option<GapConfig>,
+    feed: Generator,
+    last_bars: HashMap<Market, (DateTime<Utc>, Candle)>,
+    pending: VecDeque<MarketEvent<Instrument, DataKind>>,
+}
+
+impl<Generator> MarketGenerator<MarketEvent<Instrument, DataKind>>
+    for ValidatingDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        match self.try_next() {
+            Ok(feed) => feed,
+            Err(error) => {
+                warn!(%error, "ValidatingDataHandler failed to validate market event");
+                Feed::Unhealthy
+            }
+        }
+    }
+}
+
+impl<Generator> ValidatingDataHandler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    /// Constructs a new [`ValidatingDataHandler`] that validates the provided feed.
+    ///
+    /// Returns a [`DataError::InvalidConfig`] if the [`GapConfig`] interval is not positive.
+    #[allow(clippy::result_large_err)]
+    pub fn new(config: Config, feed: Generator) -> Result<Self, DataError> {
+        if let Some(gaps) = config.gaps {
+            if gaps.interval <= Duration::zero() {
+                return Err(DataError::InvalidConfig(
+                    "ValidatingDataHandler gap interval must be positive",
+                ));
+            }
+        }
+
+        Ok(Self {
+            validate: config.validate,
+            gaps: config.gaps,
+            feed,
+            last_bars: HashMap::new(),
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Attempt to generate the next market event, returning a [`DataError`] if it fails
+    /// validation or a gap is detected with [`GapPolicy::Error`].
+    #[allow(clippy::result_large_err)]
+    pub fn try_next(&mut self) -> Result<Feed<MarketEvent<Instrument, DataKind>>, DataError> {
+        if self.pending.is_empty() {
+            match self.feed.next() {
+                Feed::Next(event) => self.validate_event(event)?,
+                feed => return Ok(feed),
+            }
+        }
+
+        Ok(self.pending.pop_front().map_or(Feed::Unhealthy, Feed::Next))
+    }
+
+    /// Validate the market event, queueing it (and any gap filling [`Candle`]s) to be yielded.
+    #[allow(clippy::result_large_err)]
+    fn validate_event(
+        &mut self,
+        event: MarketEvent<Instrument, DataKind>,
+    ) -> Result<(), DataError> {
+        let candle = match &event.kind {
+            DataKind::Candle(candle) => *candle,
+            _ => {
+                self.pending.push_back(event);
+                return Ok(());
+            }
+        };
+
+        if self.validate && !is_valid_bar(&candle) {
+            return Err(DataError::InvalidBar {
+                timestamp: event.exchange_time,
+            });
+        }
+
+        let Some(gaps) = self.gaps else {
+            self.pending.push_back(event);
+            return Ok(());
+        };
+
+        let previous = self.last_bars.insert(
+            Market::new(event.exchange.clone(), event.instrument.clone()),
+            (event.exchange_time, candle),
+        );
+        let Some((previous_time, previous)) = previous else {
+            self.pending.push_back(event);
+            return Ok(());
+        };
+
+        let expected = previous_time + gaps.interval;
+        let actual = event.exchange_time;
+        if actual <= expected {
+            self.pending.push_back(event);
+            return Ok(());
+        }
+
+        match gaps.policy {
+            GapPolicy::Error => {
+                self.pending.push_back(event);
+                return Err(DataError::GapDetected { expected, actual });
+            }
+            GapPolicy::Warn => {
+                warn!(
+                    exchange = %event.exchange,
+                    instrument = %event.instrument,
+                    %expected,
+                    %actual,
+                    "ValidatingDataHandler detected gap between consecutive candles"
+                );
+            }
+            GapPolicy::Ignore => {}
+            GapPolicy::FillForward => {
+                let mut time = expected;
+                while time < actual {
+                    self.pending.push_back(MarketEvent {
+                        exchange_time: time,
+                        received_time: event.received_time,
+                        exchange: event.exchange.clone(),
+                        instrument: event.instrument.clone(),
+                        kind: DataKind::Candle(Candle {
+                            close_time: time,
+                            open: previous.close,
+                            high: previous.close,
+                            low: previous.close,
+                            close: previous.close,
+                            volume: 0.0,
+                            trade_count: 0,
+                        }),
+                        sequence: event.sequence,
+                    });
+                    time += gaps.interval;
+                }
+            }
+        }
+
+        self.pending.push_back(event);
+        Ok(())
+    }
+}
+
+/// Determine if the [`Candle`] satisfies the OHLC invariants. Any NaN price is invalid.
+fn is_valid_bar(candle: &Candle) -> bool {
+    [candle.open, candle.close, candle.high]
+        .into_iter()
+        .all(|price| candle.low <= price)
+        && [candle.open, candle.close]
+            .into_iter()
+            .all(|price| candle.high >= price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::historical, test_util::market_event_candle};
+    use std::ops::Add;
+
+    fn start() -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp(0, 0).unwrap()
+    }
+
+    fn candle_event(
+        minutes: i64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        event.exchange_time = start().add(Duration::minutes(minutes));
+        event.kind = DataKind::Candle(Candle {
+            close_time: event.exchange_time,
+            open,
+            high,
+            low,
+            close,
+            volume: 1.0,
+            trade_count: 1,
+        });
+        event
+    }
+
+    #[test]
+    fn is_valid_bar_checks_ohlc_invariants() {
+        let candle = |open, high, low, close| match candle_event(0, open, high, low, close).kind {
+            DataKind::Candle(candle) => candle,
+            _ => unreachable!(),
+        };
+
+        assert!(is_valid_bar(&candle(100.0, 110.0, 90.0, 105.0)));
+        assert!(is_valid_bar(&candle(100.0, 100.0, 100.0, 100.0)));
+        assert!(!is_valid_bar(&candle(100.0, 110.0, 101.0, 105.0)));
+        assert!(!is_valid_bar(&candle(100.0, 110.0, 90.0, 111.0)));
+        assert!(!is_valid_bar(&candle(100.0, 80.0, 90.0, 85.0)));
+        assert!(!is_valid_bar(&candle(f64::NAN, 110.0, 90.0, 105.0)));
+    }
+
+    #[test]
+    fn validating_data_handler_returns_invalid_bar_error_when_enabled() {
+        let invalid = candle_event(1, 100.0, 110.0, 101.0, 105.0);
+        let invalid_time = invalid.exchange_time;
+        let feed = historical::MarketFeed::new(vec![
+            candle_event(0, 100.0, 110.0, 90.0, 105.0),
+            invalid,
+            candle_event(2, 100.0, 110.0, 90.0, 105.0),
+        ]);
+
+        let mut handler = ValidatingDataHandler::new(
+            Config {
+                validate: true,
+                gaps: None,
+            },
+            feed,
+        )
+        .unwrap();
+
+        assert!(matches!(handler.try_next(), Ok(Feed::Next(_))));
+        assert!(matches!(
+            handler.try_next(),
+            Err(DataError::InvalidBar { timestamp }) if timestamp == invalid_time
+        ));
+
+        // Invalid bar is discarded, leaving an upstream sequence gap, & subsequent events are
+        // still validated
+        assert!(matches!(handler.next(), Feed::Next(event) if event.sequence == 3));
+        assert_eq!(handler.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn validating_data_handler_passes_invalid_bars_through_when_disabled() {
+        let feed = historical::MarketFeed::new(vec![candle_event(0, 100.0, 110.0, 101.0, 105.0)]);
+
+        let mut handler = ValidatingDataHandler::new(Config::default(), feed).unwrap();
+
+        assert!(matches!(handler.next(), Feed::Next(_)));
+        assert_eq!(handler.next(), Feed::Finished);
+    }
+
+    fn gap_handler(
+        policy: GapPolicy,
+    ) -> ValidatingDataHandler<
+        historical::MarketFeed<
+            std::vec::IntoIter<MarketEvent<Instrument, DataKind>>,
+            MarketEvent<Instrument, DataKind>,
+        >,
+    > {
+        let feed = historical::MarketFeed::new(vec![
+            candle_event(0, 100.0, 110.0, 90.0, 105.0),
+            candle_event(1, 105.0, 110.0, 90.0, 106.0),
+            candle_event(4, 106.0, 110.0, 90.0, 107.0),
+        ]);
+
+        ValidatingDataHandler::new(
+            Config {
+                validate: false,
+                gaps: Some(GapConfig {
+                    interval: Duration::minutes(1),
+                    policy,
+                }),
+            },
+            feed,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn validating_data_handler_rejects_non_positive_gap_interval() {
+        for interval in [Duration::zero(), Duration::minutes(-1)] {
+            let handler = ValidatingDataHandler::new(
+                Config {
+                    validate: true,
+                    gaps: Some(GapConfig {
+                        interval,
+                        policy: GapPolicy::Error,
+                    }),
+                },
+                historical::MarketFeed::new(std::iter::empty()),
+            );
+
+            assert!(matches!(handler, Err(DataError::InvalidConfig(_))));
+        }
+    }
+
+    fn minutes(feed: Feed<MarketEvent<Instrument, DataKind>>, start: DateTime<Utc>) -> i64 {
+        match feed {
+            Feed::Next(event) => (event.exchange_time - start).num_minutes(),
+            feed => panic!("expected Feed::Next, got {feed:?}"),
+        }
+    }
+
+    #[test]
+    fn validating_data_handler_gap_policy_error_reports_gap_then_yields_candle() {
+        let start = start();
+        let mut handler = gap_handler(GapPolicy::Error);
+
+        assert_eq!(minutes(handler.next(), start), 0);
+        assert_eq!(minutes(handler.next(), start), 1);
+        assert!(matches!(
+            handler.try_next(),
+            Err(DataError::GapDetected { expected, actual })
+                if (expected - start).num_minutes() == 2 && (actual - start).num_minutes() == 4
+        ));
+        assert_eq!(minutes(handler.next(), start), 4);
+        assert_eq!(handler.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn validating_data_handler_gap_policy_warn_and_ignore_yield_candle_after_gap() {
+        for policy in [GapPolicy::Warn, GapPolicy::Ignore] {
+            let start = start();
+            let mut handler = gap_handler(policy);
+
+            assert_eq!(minutes(handler.next(), start), 0);
+            assert_eq!(minutes(handler.next(), start), 1);
+            assert_eq!(minutes(handler.next(), start), 4);
+            assert_eq!(handler.next(), Feed::Finished);
+        }
+    }
+
+    #[test]
+    fn validating_data_handler_gap_policy_fill_forward_yields_flat_candles() {
+        let start = start();
+        let mut handler = gap_handler(GapPolicy::FillForward);
+
+        assert_eq!(minutes(handler.next(), start), 0);
+        assert_eq!(minutes(handler.next(), start), 1);
+
+        for minute in [2, 3] {
+            let Feed::Next(event) = handler.next() else {
+                panic!("expected Feed::Next")
+            };
+            assert_eq!((event.exchange_time - start).num_minutes(), minute);
+            assert_eq!(event.sequence, 3);
+            assert_eq!(
+                event.kind,
+                DataKind::Candle(Candle {
+                    close_time: event.exchange_time,
+                    open: 106.0,
+                    high: 106.0,
+                    low: 106.0,
+                    close: 106.0,
+                    volume: 0.0,
+                    trade_count: 0,
+                })
+            );
+        }
+
+        let Feed::Next(event) = handler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!((event.exchange_time - start).num_minutes(), 4);
+        assert_eq!(event.sequence, 3);
+        assert_eq!(handler.next(), Feed::Finished);
+    }
+}
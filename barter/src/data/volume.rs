@@ -0,0 +1,310 @@
+use super::{error::DataError, Feed, MarketGenerator};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_integration::model::{instrument::Instrument, Market};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Configuration for constructing a [`VolumeBarResampler`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Cumulative volume that must be traded before a volume bar closes.
+    pub volume_threshold: f64,
+}
+
+/// [`MarketGenerator`] that aggregates an underlying feed of [`Candle`] and/or
+/// [`PublicTrade`](barter_data::subscription::trade::PublicTrade) [`MarketEvent`]s into volume
+/// bars, rather than time bars. Each volume bar closes, and is yielded as an aggregated [`Candle`]
+/// [`MarketEvent`], once its cumulative volume reaches the configured threshold.
+///
+/// Bars are accumulated independently for each [`Market`], so the underlying feed may contain
+/// multiple [`Market`]s. Incoming events are not split, so the event that crosses the threshold
+/// is included in full, and a volume bar may slightly exceed the threshold. Other [`DataKind`]s
+/// are passed through unchanged.
+///
+/// Any partially accumulated volume bars are discarded when the underlying feed finishes.
+///
+/// Events passed through keep their upstream sequence number, and each volume bar takes the
+/// sequence number of the underlying event that closed it.
+#[derive(Debug)]
+pub struct VolumeBarResampler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    volume_threshold: f64,
+    feed: Generator,
+    bars: HashMap<Market, VolumeBar>,
+}
+
+/// Volume bar being accumulated for a [`Market`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct VolumeBar {
+    exchange_time: DateTime<Utc>,
+    candle: Candle,
+}
+
+impl VolumeBar {
+    /// Constructs a new [`VolumeBar`] from the first [`Candle`] of the bar.
+    fn new(exchange_time: DateTime<Utc>, candle: Candle) -> Self {
+        Self {
+            exchange_time,
+            candle,
+        }
+    }
+
+    /// Aggregates the next [`Candle`] into the [`VolumeBar`].
+    fn update(&mut self, exchange_time: DateTime<Utc>, next: Candle) {
+        self.exchange_time = exchange_time;
+        self.candle.close_time = next.close_time;
+        self.candle.high = self.candle.high.max(next.high);
+        self.candle.low = self.candle.low.min(next.low);
+        self.candle.close = next.close;
+        self.candle.volume += next.volume;
+        self.candle.trade_count += next.trade_count;
+    }
+}
+
+impl<Generator> MarketGenerator<MarketEvent<Instrument, DataKind>> for VolumeBarResampler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            let event = match self.feed.next() {
+                Feed::Next(event) => event,
+                Feed::Unhealthy => return Feed::Unhealthy,
+                Feed::Finished => return Feed::Finished,
+            };
+
+            // Normalise volume contributing events into a Candle, passing through anything else
+            let candle = match &event.kind {
+                DataKind::Candle(candle) => *candle,
+                DataKind::Trade(trade) => Candle {
+                    close_time: event.exchange_time,
+                    open: trade.price,
+                    high: trade.price,
+                    low: trade.price,
+                    close: trade.price,
+                    volume: trade.amount.abs(),
+                    trade_count: 1,
+                },
+                _ => return Feed::Next(event),
+            };
+
+            if let Some(volume_bar) = self.update(&event, candle) {
+                return Feed::Next(volume_bar);
+            }
+        }
+    }
+}
+
+impl<Generator> VolumeBarResampler<Generator>
+where
+    Generator: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    /// Constructs a new [`VolumeBarResampler`] that aggregates the provided feed.
+    ///
+    /// Returns a [`DataError::InvalidConfig`] if the `volume_threshold` is not positive.
+    #[allow(clippy::result_large_err)]
+    pub fn new(config: Config, feed: Generator) -> Result<Self, DataError> {
+        if config.volume_threshold.is_nan() || config.volume_threshold <= 0.0 {
+            return Err(DataError::InvalidConfig(
+                "VolumeBarResampler volume_threshold must be positive",
+            ));
+        }
+
+        Ok(Self {
+            volume_threshold: config.volume_threshold,
+            feed,
+            bars: HashMap::new(),
+        })
+    }
+
+    /// Aggregates the [`Candle`] into the [`Market`]'s current [`VolumeBar`], returning the
+    /// aggregated [`MarketEvent`] if the volume threshold has been reached.
+    fn update(
+        &mut self,
+        event: &MarketEvent<Instrument, DataKind>,
+        candle: Candle,
+    ) -> Option<MarketEvent<Instrument, DataKind>> {
+        let market = Market::new(event.exchange.clone(), event.instrument.clone());
+
+        let volume_bar = self
+            .bars
+            .entry(market.clone())
+            .and_modify(|volume_bar| volume_bar.update(event.exchange_time, candle))
+            .or_insert_with(|| VolumeBar::new(event.exchange_time, candle));
+
+        if volume_bar.candle.volume < self.volume_threshold {
+            return None;
+        }
+
+        let volume_bar = self.bars.remove(&market)?;
+        Some(MarketEvent {
+            exchange_time: volume_bar.exchange_time,
+            received_time: event.received_time,
+            exchange: market.exchange,
+            instrument: market.instrument,
+            kind: DataKind::Candle(volume_bar.candle),
+            sequence: event.sequence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::historical,
+        test_util::{market_event_candle, market_event_trade},
+    };
+    use barter_integration::model::{instrument::kind::InstrumentKind, Side};
+    use chrono::Duration;
+    use std::ops::Add;
+
+    fn trade_event(
+        base: &str,
+        base_time: DateTime<Utc>,
+        seconds: i64,
+        price: f64,
+        amount: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_trade(Side::Buy);
+        event.instrument = Instrument::from((base, "usdt", InstrumentKind::Spot));
+        event.exchange_time = base_time.add(Duration::seconds(seconds));
+        if let DataKind::Trade(trade) = &mut event.kind {
+            trade.price = price;
+            trade.amount = amount;
+        }
+        event
+    }
+
+    fn candle(event: &MarketEvent<Instrument, DataKind>) -> Candle {
+        match event.kind {
+            DataKind::Candle(candle) => candle,
+            _ => panic!("expected Candle MarketEvent"),
+        }
+    }
+
+    #[test]
+    fn volume_bar_resampler_aggregates_trades_until_volume_threshold() {
+        let base_time = Utc::now();
+        let feed = historical::MarketFeed::new(vec![
+            trade_event("btc", base_time, 0, 100.0, 4.0),
+            trade_event("btc", base_time, 1, 105.0, 3.0),
+            trade_event("btc", base_time, 2, 95.0, 4.0),
+            trade_event("btc", base_time, 3, 98.0, 10.0),
+            trade_event("btc", base_time, 4, 99.0, 1.0),
+        ]);
+
+        let mut resampler = VolumeBarResampler::new(
+            Config {
+                volume_threshold: 10.0,
+            },
+            feed,
+        )
+        .unwrap();
+
+        // Bar 0: crossing trade included in full, so volume exceeds the threshold
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.exchange_time, base_time.add(Duration::seconds(2)));
+        assert_eq!(event.sequence, 3);
+        assert_eq!(
+            candle(&event),
+            Candle {
+                close_time: base_time.add(Duration::seconds(2)),
+                open: 100.0,
+                high: 105.0,
+                low: 95.0,
+                close: 95.0,
+                volume: 11.0,
+                trade_count: 3,
+            }
+        );
+
+        // Bar 1: single trade meets the threshold
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(candle(&event).volume, 10.0);
+        assert_eq!(candle(&event).trade_count, 1);
+
+        // Volume bars take the upstream sequence number of the event that closed them
+        assert_eq!(event.sequence, 4);
+
+        // Partial volume bar discarded when the feed finishes
+        assert_eq!(resampler.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn volume_bar_resampler_accumulates_each_market_independently() {
+        let base_time = Utc::now();
+        let feed = historical::MarketFeed::new(vec![
+            trade_event("btc", base_time, 0, 100.0, 6.0),
+            trade_event("eth", base_time, 1, 10.0, 6.0),
+            trade_event("eth", base_time, 2, 11.0, 6.0),
+            trade_event("btc", base_time, 3, 101.0, 6.0),
+        ]);
+
+        let mut resampler = VolumeBarResampler::new(
+            Config {
+                volume_threshold: 10.0,
+            },
+            feed,
+        )
+        .unwrap();
+
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.instrument.base, "eth".into());
+        assert_eq!(candle(&event).close, 11.0);
+
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.instrument.base, "btc".into());
+        assert_eq!(candle(&event).open, 100.0);
+        assert_eq!(candle(&event).close, 101.0);
+    }
+
+    #[test]
+    fn volume_bar_resampler_aggregates_candles() {
+        let first = market_event_candle();
+        let mut second = market_event_candle();
+        second.exchange_time = first.exchange_time.add(Duration::minutes(1));
+        let first_candle = candle(&first);
+
+        let mut resampler = VolumeBarResampler::new(
+            Config {
+                volume_threshold: first_candle.volume * 2.0,
+            },
+            historical::MarketFeed::new(vec![first, second.clone()]),
+        )
+        .unwrap();
+
+        let Feed::Next(event) = resampler.next() else {
+            panic!("expected Feed::Next")
+        };
+        assert_eq!(event.exchange_time, second.exchange_time);
+        assert_eq!(candle(&event).volume, first_candle.volume * 2.0);
+        assert_eq!(candle(&event).open, first_candle.open);
+    }
+
+    #[test]
+    fn volume_bar_resampler_rejects_non_positive_volume_threshold() {
+        for volume_threshold in [0.0, -1.0, f64::NAN] {
+            let resampler = VolumeBarResampler::new(
+                Config { volume_threshold },
+                historical::MarketFeed::new(std::iter::empty()),
+            );
+
+            assert!(matches!(resampler, Err(DataError::InvalidConfig(_))));
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use super::error::EngineError;
+use barter_integration::model::{Market, MarketId};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Layered configuration for a multi-[`Trader`](super::trader::Trader) setup. A base `Config` is
+/// shared by every [`Market`], and optionally merged with a per-[`Market`] override that only
+/// specifies the parameters that differ (eg/ a tighter stop for a volatile symbol).
+///
+/// Overrides are partial JSON objects whose fields are merged recursively into the serialised
+/// base `Config`. Every overridden field must exist in the serialised base `Config`, so a typo in
+/// an override is surfaced as an error rather than silently ignored.
+///
+/// Since merging operates on the serialised base `Config`, fields that are not serialised cannot
+/// be overridden: fields marked `#[serde(skip_serializing)]` (or `#[serde(skip)]`), and fields
+/// omitted by `#[serde(skip_serializing_if = "...")]` for the base value (eg/ a `None`).
+///
+/// eg/ `{"rsi_period": 7}` overrides only the `rsi_period` of an example strategy
+/// [`Config`](crate::strategy::example::Config).
+#[derive(Clone, PartialEq, Debug)]
+pub struct LayeredConfig<Config> {
+    pub base: Config,
+    pub overrides: HashMap<Market, Value>,
+}
+
+impl<Config> LayeredConfig<Config>
+where
+    Config: Clone + Serialize + DeserializeOwned,
+{
+    /// Constructs a new [`LayeredConfig`] with the provided base `Config` and no overrides.
+    pub fn new(base: Config) -> Self {
+        Self {
+            base,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Adds a partial JSON override to be merged into the base `Config` for the provided
+    /// [`Market`]. Replaces any existing override for that [`Market`].
+    pub fn with_override<M>(mut self, market: M, config_override: Value) -> Self
+    where
+        M: Into<Market>,
+    {
+        self.overrides.insert(market.into(), config_override);
+        self
+    }
+
+    /// Resolves the `Config` for the provided [`Market`] by merging its override (if any) into
+    /// the base `Config`.
+    pub fn resolve(&self, market: &Market) -> Result<Config, EngineError> {
+        let config_override = match self.overrides.get(market) {
+            None => return Ok(self.base.clone()),
+            Some(config_override) => config_override,
+        };
+
+        let config_error = |reason: String| EngineError::ConfigOverride {
+            market_id: MarketId::from(market),
+            reason,
+        };
+
+        let mut config =
+            serde_json::to_value(&self.base).map_err(|e| config_error(e.to_string()))?;
+        merge(&mut config, config_override, "").map_err(config_error)?;
+
+        serde_json::from_value(config).map_err(|e| config_error(e.to_string()))
+    }
+}
+
+/// Recursively merge the override [`Value`] into the base [`Value`]. Override objects must only
+/// contain fields that already exist in the base object.
+fn merge(base: &mut Value, config_override: &Value, path: &str) -> Result<(), String> {
+    match (base, config_override) {
+        (Value::Object(base), Value::Object(config_override)) => {
+            merge_objects(base, config_override, path)
+        }
+        (base, config_override) => {
+            *base = config_override.clone();
+            Ok(())
+        }
+    }
+}
+
+fn merge_objects(
+    base: &mut Map<String, Value>,
+    config_override: &Map<String, Value>,
+    path: &str,
+) -> Result<(), String> {
+    config_override.iter().try_for_each(|(key, value)| {
+        let field_path = match path.is_empty() {
+            true => key.clone(),
+            false => format!("{path}.{key}"),
+        };
+
+        match base.get_mut(key) {
+            Some(base_value) => merge(base_value, value, &field_path),
+            None => Err(format!(
+                "unknown config field: {field_path} (only fields present in the serialised base \
+                 config can be overridden, so skip_serializing fields cannot be)"
+            )),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::instrument::kind::InstrumentKind;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+    struct TestConfig {
+        period: usize,
+        risk: TestRiskConfig,
+    }
+
+    #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+    struct TestRiskConfig {
+        stop_loss_pct: f64,
+        take_profit_pct: f64,
+    }
+
+    fn base() -> TestConfig {
+        TestConfig {
+            period: 14,
+            risk: TestRiskConfig {
+                stop_loss_pct: 0.05,
+                take_profit_pct: 0.10,
+            },
+        }
+    }
+
+    fn market(base: &str) -> Market {
+        Market::new("binance", (base, "usdt", InstrumentKind::Spot))
+    }
+
+    #[test]
+    fn resolve_market_without_override_returns_base() {
+        let config = LayeredConfig::new(base()).with_override(market("sol"), json!({"period": 7}));
+
+        assert_eq!(config.resolve(&market("btc")).unwrap(), base());
+    }
+
+    #[test]
+    fn resolve_market_with_nested_override_merges_into_base() {
+        let config = LayeredConfig::new(base())
+            .with_override(market("sol"), json!({"risk": {"stop_loss_pct": 0.02}}));
+
+        let expected = TestConfig {
+            period: 14,
+            risk: TestRiskConfig {
+                stop_loss_pct: 0.02,
+                take_profit_pct: 0.10,
+            },
+        };
+
+        assert_eq!(config.resolve(&market("sol")).unwrap(), expected);
+    }
+
+    #[test]
+    fn resolve_market_with_unknown_override_field_fails() {
+        let config = LayeredConfig::new(base())
+            .with_override(market("sol"), json!({"risk": {"stop_los_pct": 0.02}}));
+
+        match config.resolve(&market("sol")) {
+            Err(EngineError::ConfigOverride { reason, .. }) => {
+                assert!(reason.starts_with("unknown config field: risk.stop_los_pct"))
+            }
+            result => panic!("expected ConfigOverride error, got: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn resolve_market_with_override_of_skip_serializing_field_fails_with_reason() {
+        #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+        struct SkippedConfig {
+            period: usize,
+            #[serde(default, skip_serializing)]
+            warmup: usize,
+        }
+
+        let config = LayeredConfig::new(SkippedConfig {
+            period: 14,
+            warmup: 0,
+        })
+        .with_override(market("sol"), json!({"warmup": 10}));
+
+        match config.resolve(&market("sol")) {
+            Err(EngineError::ConfigOverride { reason, .. }) => {
+                assert!(reason.starts_with("unknown config field: warmup"));
+                assert!(reason.contains("skip_serializing fields cannot be"));
+            }
+            result => panic!("expected ConfigOverride error, got: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn resolve_market_with_invalid_override_type_fails() {
+        let config =
+            LayeredConfig::new(base()).with_override(market("sol"), json!({"period": "seven"}));
+
+        assert!(matches!(
+            config.resolve(&market("sol")),
+            Err(EngineError::ConfigOverride { .. })
+        ));
+    }
+}
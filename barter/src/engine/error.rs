@@ -1,4 +1,5 @@
-use crate::portfolio::repository::error::RepositoryError;
+use crate::{portfolio::repository::error::RepositoryError, strategy::error::StrategyError};
+use barter_integration::model::{Market, MarketId};
 use thiserror::Error;
 
 /// All errors generated in barter-engine.
@@ -7,6 +8,24 @@ pub enum EngineError {
     #[error("Failed to build struct due to missing attributes: {0}")]
     BuilderIncomplete(&'static str),
 
+    #[error(
+        "Trader markets do not match trader_command_txs markets - \
+         Traders without a command_tx: {missing_command_tx:?}, \
+         command_txs without a Trader: {missing_trader:?}, \
+         duplicate Trader markets: {duplicate_trader:?}"
+    )]
+    TraderMarketMismatch {
+        missing_command_tx: Vec<Market>,
+        missing_trader: Vec<Market>,
+        duplicate_trader: Vec<Market>,
+    },
+
+    #[error("Failed to apply config override for market {market_id}: {reason}")]
+    ConfigOverride { market_id: MarketId, reason: String },
+
     #[error("Failed to interact with repository")]
     RepositoryInteractionError(#[from] RepositoryError),
+
+    #[error("Failed to construct strategy: {0}")]
+    Strategy(#[from] StrategyError),
 }
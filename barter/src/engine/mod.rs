@@ -6,21 +6,29 @@ use crate::{
     portfolio::{
         position::Position,
         repository::{PositionHandler, StatisticHandler},
-        FillUpdater, MarketUpdater, OrderGenerator,
+        ExposureReporter, FillUpdater, MarketUpdater, OrderGenerator, ReturnReporter,
     },
-    statistic::summary::{PositionSummariser, TableBuilder},
+    statistic::summary::{session::SessionSummary, PositionSummariser, TableBuilder, TableFormat},
     strategy::SignalGenerator,
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{instrument::Instrument, Market, MarketId};
 use parking_lot::Mutex;
-use prettytable::Table;
 use serde::Serialize;
-use std::{collections::HashMap, fmt::Debug, sync::Arc, thread};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+};
 use tokio::sync::{mpsc, oneshot};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+/// Layered base & per-[`Market`] override configuration for multi-[`Trader`] setups.
+pub mod config;
+
 /// Barter Engine module specific errors.
 pub mod error;
 
@@ -36,6 +44,10 @@ pub enum Command {
     /// `oneshot::Sender`. Involves the [`Engine`] only.
     FetchOpenPositions(oneshot::Sender<Result<Vec<Position>, EngineError>>),
 
+    /// Lists every [`Market`] managed by the [`Engine`] (ie/ one per [`Trader`]) and sends them
+    /// on the provided `oneshot::Sender`. Involves the [`Engine`] only.
+    ListMarkets(oneshot::Sender<Vec<Market>>),
+
     /// Terminate every running [`Trader`] associated with this [`Engine`]. Involves all [`Trader`]s.
     Terminate(String),
 
@@ -45,6 +57,15 @@ pub enum Command {
     /// Exit a [`Position`]. Uses the [`Market`] provided to route this [`Command`] to the relevant
     /// [`Trader`] instance. Involves one [`Trader`].
     ExitPosition(Market),
+
+    /// Inject a synthetic [`MarketEvent`] into the trading loop of the relevant [`Trader`], which
+    /// handles it as if it came from it's [`MarketGenerator`]. Uses the [`MarketEvent`]
+    /// exchange & instrument to route this [`Command`]. Involves one [`Trader`].
+    ///
+    /// Enables scenario-based testing of the full trading pipeline without a data file. Only
+    /// available in tests, or with the `test-utils` feature enabled.
+    #[cfg(any(test, feature = "test-utils"))]
+    InjectMarket(MarketEvent<Instrument, DataKind>),
 }
 
 /// Lego components for constructing an [`Engine`] via the new() constructor method.
@@ -73,6 +94,10 @@ where
     /// Uses trading session's exited [`Position`]s to calculate an average statistical summary
     /// across all [`Market`]s traded.
     pub statistics_summary: Statistic,
+    /// [`TableFormat`] used to print the trading session summary.
+    pub summary_format: TableFormat,
+    /// Optional path to export the full-precision trading session summary to as JSON.
+    pub summary_export_path: Option<PathBuf>,
 }
 
 /// Multi-threaded Trading Engine capable of trading with an arbitrary number of [`Trader`]s, one
@@ -92,6 +117,8 @@ where
         + MarketUpdater
         + OrderGenerator
         + FillUpdater
+        + ExposureReporter
+        + ReturnReporter
         + Send
         + 'static,
     Data: MarketGenerator<MarketEvent<Instrument, DataKind>> + Send + 'static,
@@ -114,6 +141,10 @@ where
     /// Uses trading session's exited [`Position`]s to calculate an average statistical summary
     /// across all [`Market`]s traded.
     statistics_summary: Statistic,
+    /// [`TableFormat`] used to print the trading session summary.
+    summary_format: TableFormat,
+    /// Optional path to export the full-precision trading session summary to as JSON.
+    summary_export_path: Option<PathBuf>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -126,6 +157,8 @@ where
         + MarketUpdater
         + OrderGenerator
         + FillUpdater
+        + ExposureReporter
+        + ReturnReporter
         + Send
         + 'static,
     Data: MarketGenerator<MarketEvent<Instrument, DataKind>> + Send,
@@ -145,6 +178,8 @@ where
             traders: lego.traders,
             trader_command_txs: lego.trader_command_txs,
             statistics_summary: lego.statistics_summary,
+            summary_format: lego.summary_format,
+            summary_export_path: lego.summary_export_path,
         }
     }
 
@@ -157,8 +192,9 @@ where
     /// receives [`Command`]s via the `command_rx` and actions them
     /// (eg/ terminate_traders, fetch_open_positions). If all of the [`Trader`]s stop organically
     /// (eg/ due to a finished [`MarketGenerator`]), the [`Engine`] terminates & prints a summary
-    /// for the trading session.
-    pub async fn run(mut self) {
+    /// for the trading session. The [`SessionSummary`] is also returned so it can be compared
+    /// with the summaries of other sessions (see [`SessionSummary::diff`]).
+    pub async fn run(mut self) -> SessionSummary<Statistic> {
         // Run Traders on threads & send notification when they have stopped organically
         let mut notify_traders_stopped = self.run_traders().await;
 
@@ -175,6 +211,9 @@ where
                             Command::FetchOpenPositions(positions_tx) => {
                                 self.fetch_open_positions(positions_tx).await;
                             },
+                            Command::ListMarkets(markets_tx) => {
+                                self.list_markets(markets_tx).await;
+                            },
                             Command::Terminate(message) => {
                                 self.terminate_traders(message).await;
                                 break;
@@ -185,6 +224,10 @@ where
                             Command::ExitAllPositions => {
                                 self.exit_all_positions().await;
                             },
+                            #[cfg(any(test, feature = "test-utils"))]
+                            Command::InjectMarket(market) => {
+                                self.inject_market(market).await;
+                            },
                         }
                     } else {
                         // Terminate traders due to dropped receiver
@@ -194,8 +237,18 @@ where
             }
         }
 
-        // Print Trading Session Summary
-        self.generate_session_summary().printstd();
+        // Print Trading Session Summary, & optionally export it at full precision
+        let summary_format = self.summary_format;
+        let summary_export_path = self.summary_export_path.take();
+        let session_summary = self.generate_session_summary();
+
+        session_summary.table(summary_format).printstd();
+
+        if let Some(path) = summary_export_path {
+            export_session_summary(&path, &session_summary);
+        }
+
+        session_summary
     }
 
     /// Runs each [`Trader`] it's own thread. Sends a message on the returned `mpsc::Receiver<bool>`
@@ -251,6 +304,19 @@ where
         }
     }
 
+    /// Lists every [`Market`] managed by the [`Engine`] and sends them on the provided
+    /// `oneshot::Sender`.
+    async fn list_markets(&self, markets_tx: oneshot::Sender<Vec<Market>>) {
+        let markets = self.trader_command_txs.keys().cloned().collect();
+
+        if markets_tx.send(markets).is_err() {
+            warn!(
+                why = "oneshot receiver dropped",
+                "cannot action Command::ListMarkets"
+            );
+        }
+    }
+
     /// Terminate every running [`Trader`] associated with this [`Engine`].
     async fn terminate_traders(&self, message: String) {
         // Firstly, exit all Positions
@@ -314,15 +380,49 @@ where
         }
     }
 
-    /// Generate a trading session summary. Uses the Portfolio's statistics per [`Market`] in
-    /// combination with the average statistics across all [`Market`]s traded.
-    fn generate_session_summary(mut self) -> Table {
+    /// Inject a synthetic [`MarketEvent`] into the trading loop of the [`Trader`] associated with
+    /// it's exchange & instrument.
+    #[cfg(any(test, feature = "test-utils"))]
+    async fn inject_market(&self, market: MarketEvent<Instrument, DataKind>) {
+        let market_key = Market::new(market.exchange.clone(), market.instrument.clone());
+
+        if let Some(command_tx) = self.trader_command_txs.get(&market_key) {
+            if command_tx
+                .send(Command::InjectMarket(market))
+                .await
+                .is_err()
+            {
+                error!(
+                    market = &*format!("{:?}", market_key),
+                    why = "dropped receiver",
+                    "failed to send Command::InjectMarket to Trader command_rx"
+                );
+            }
+        } else {
+            warn!(
+                market = &*format!("{:?}", market_key),
+                why = "Engine has no trader_command_tx associated with provided Market",
+                "failed to inject MarketEvent"
+            );
+        }
+    }
+
+    /// Generate a trading session summary. Uses the Portfolio's statistics & time-in-market per
+    /// [`Market`] in combination with the average statistics across all [`Market`]s traded.
+    fn generate_session_summary(mut self) -> SessionSummary<Statistic> {
+        // Fetch time-in-market per Market & in total, and the Portfolio time-weighted return
+        let time_in_market = self.portfolio.lock().time_in_market();
+        let time_weighted_return = self.portfolio.lock().time_weighted_return();
+
         // Fetch statistics for each Market
         let stats_per_market = self.trader_command_txs.into_keys().filter_map(|market| {
             let market_id = MarketId::from(&market);
 
             match self.portfolio.lock().get_statistics(&market_id) {
-                Ok(statistics) => Some((market_id.0, statistics)),
+                Ok(statistics) => {
+                    let market_time_in_market = time_in_market.market(&market_id);
+                    Some((market_id.0, (statistics, (market_time_in_market, None))))
+                }
                 Err(error) => {
                     error!(
                         ?error,
@@ -349,10 +449,37 @@ where
                 );
             });
 
-        // Combine Total & Per-Market Statistics Into Table
-        crate::statistic::summary::combine(
-            stats_per_market.chain([("Total".to_owned(), self.statistics_summary)]),
-        )
+        // Combine Total & Per-Market Statistics
+        SessionSummary {
+            summaries: stats_per_market
+                .chain([(
+                    "Total".to_owned(),
+                    (
+                        self.statistics_summary,
+                        (time_in_market.total, Some(time_weighted_return)),
+                    ),
+                )])
+                .collect(),
+        }
+    }
+}
+
+/// Export the full-precision trading session summary to the provided path as JSON.
+fn export_session_summary<Statistic>(path: &Path, session_summary: &SessionSummary<Statistic>)
+where
+    Statistic: TableBuilder + Serialize + Clone,
+{
+    let export = session_summary
+        .export_json()
+        .map_err(|error| error.to_string())
+        .and_then(|json| std::fs::write(path, json).map_err(|error| error.to_string()));
+
+    if let Err(error) = export {
+        error!(
+            %error,
+            ?path,
+            "failed to export trading session summary"
+        );
     }
 }
 
@@ -373,6 +500,8 @@ where
     traders: Option<Vec<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>>,
     trader_command_txs: Option<HashMap<Market, mpsc::Sender<Command>>>,
     statistics_summary: Option<Statistic>,
+    summary_format: Option<TableFormat>,
+    summary_export_path: Option<PathBuf>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -385,6 +514,8 @@ where
         + MarketUpdater
         + OrderGenerator
         + FillUpdater
+        + ExposureReporter
+        + ReturnReporter
         + Send,
     Data: MarketGenerator<MarketEvent<Instrument, DataKind>> + Send,
     Strategy: SignalGenerator + Send,
@@ -398,6 +529,8 @@ where
             traders: None,
             trader_command_txs: None,
             statistics_summary: None,
+            summary_format: None,
+            summary_export_path: None,
         }
     }
 
@@ -446,9 +579,33 @@ where
         }
     }
 
+    pub fn summary_format(self, value: TableFormat) -> Self {
+        Self {
+            summary_format: Some(value),
+            ..self
+        }
+    }
+
+    pub fn summary_export_path(self, value: PathBuf) -> Self {
+        Self {
+            summary_export_path: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(
         self,
     ) -> Result<Engine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
+        let traders = self
+            .traders
+            .ok_or(EngineError::BuilderIncomplete("traders"))?;
+        let trader_command_txs = self
+            .trader_command_txs
+            .ok_or(EngineError::BuilderIncomplete("trader_command_txs"))?;
+
+        // Validate every Trader has exactly one associated command_tx, and vice versa
+        validate_trader_markets(traders.iter().map(Trader::market), &trader_command_txs)?;
+
         Ok(Engine {
             engine_id: self
                 .engine_id
@@ -459,15 +616,178 @@ where
             portfolio: self
                 .portfolio
                 .ok_or(EngineError::BuilderIncomplete("portfolio"))?,
-            traders: self
-                .traders
-                .ok_or(EngineError::BuilderIncomplete("traders"))?,
-            trader_command_txs: self
-                .trader_command_txs
-                .ok_or(EngineError::BuilderIncomplete("trader_command_txs"))?,
+            traders,
+            trader_command_txs,
             statistics_summary: self
                 .statistics_summary
                 .ok_or(EngineError::BuilderIncomplete("statistics_summary"))?,
+            summary_format: self.summary_format.unwrap_or_default(),
+            summary_export_path: self.summary_export_path,
         })
     }
 }
+
+/// Validates the [`Market`]s of the provided [`Trader`]s exactly correspond to the [`Market`]s of
+/// the `trader_command_txs`. Returns an [`EngineError::TraderMarketMismatch`] describing any
+/// [`Trader`] without a command_tx, command_tx without a [`Trader`], or duplicate [`Trader`]
+/// [`Market`].
+fn validate_trader_markets<'a, Markets, T>(
+    trader_markets: Markets,
+    trader_command_txs: &HashMap<Market, T>,
+) -> Result<(), EngineError>
+where
+    Markets: Iterator<Item = &'a Market>,
+{
+    let mut seen = HashSet::new();
+    let mut missing_command_tx = Vec::new();
+    let mut duplicate_trader = Vec::new();
+
+    for market in trader_markets {
+        if !seen.insert(market) {
+            duplicate_trader.push(market.clone());
+        } else if !trader_command_txs.contains_key(market) {
+            missing_command_tx.push(market.clone());
+        }
+    }
+
+    let missing_trader = trader_command_txs
+        .keys()
+        .filter(|market| !seen.contains(market))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    match missing_command_tx.is_empty() && missing_trader.is_empty() && duplicate_trader.is_empty()
+    {
+        true => Ok(()),
+        false => Err(EngineError::TraderMarketMismatch {
+            missing_command_tx,
+            missing_trader,
+            duplicate_trader,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::historical,
+        event::EventTx,
+        execution::simulated::SimulatedExecution,
+        portfolio::{
+            allocator::DefaultAllocator, portfolio::MetaPortfolio,
+            repository::in_memory::InMemoryRepository, risk::DefaultRisk,
+        },
+        statistic::summary::{
+            trading::{Config as StatisticConfig, TradingSummary},
+            Initialiser,
+        },
+        strategy::example::RSIStrategy,
+    };
+    use barter_integration::model::{instrument::kind::InstrumentKind, Exchange};
+
+    type TestPortfolio = MetaPortfolio<
+        InMemoryRepository<TradingSummary>,
+        DefaultAllocator,
+        DefaultRisk,
+        TradingSummary,
+    >;
+
+    type TestEngine = Engine<
+        EventTx,
+        TradingSummary,
+        TestPortfolio,
+        historical::MarketFeed<
+            std::vec::IntoIter<MarketEvent<Instrument, DataKind>>,
+            MarketEvent<Instrument, DataKind>,
+        >,
+        RSIStrategy,
+        SimulatedExecution,
+    >;
+
+    fn market(base: &str) -> Market {
+        Market::new(
+            Exchange::from("binance"),
+            Instrument::from((base, "usdt", InstrumentKind::Spot)),
+        )
+    }
+
+    fn engine(markets: Vec<Market>) -> TestEngine {
+        let engine_id = Uuid::new_v4();
+        let statistic_config = StatisticConfig {
+            starting_equity: 10_000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+            omega_threshold_return: 0.0,
+            return_kind: Default::default(),
+        };
+
+        let portfolio = MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(markets.clone())
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .unwrap();
+
+        let trader_command_txs = markets
+            .into_iter()
+            .map(|market| (market, mpsc::channel(10).0))
+            .collect();
+
+        Engine::new(EngineLego {
+            engine_id,
+            command_rx: mpsc::channel(10).1,
+            portfolio: Arc::new(Mutex::new(portfolio)),
+            traders: Vec::new(),
+            trader_command_txs,
+            statistics_summary: TradingSummary::init(statistic_config),
+            summary_format: TableFormat::default(),
+            summary_export_path: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn list_markets_sends_every_market_managed_by_the_engine() {
+        let engine = engine(vec![market("btc"), market("eth")]);
+
+        let (markets_tx, markets_rx) = oneshot::channel();
+        engine.list_markets(markets_tx).await;
+
+        let mut markets = markets_rx.await.unwrap();
+        markets.sort_by_key(|market| market.instrument.base.clone());
+        assert_eq!(markets, vec![market("btc"), market("eth")]);
+    }
+
+    #[test]
+    fn validate_trader_markets_with_matching_markets() {
+        let trader_markets = [market("btc"), market("eth")];
+        let command_txs = HashMap::from([(market("btc"), ()), (market("eth"), ())]);
+
+        assert!(validate_trader_markets(trader_markets.iter(), &command_txs).is_ok());
+    }
+
+    #[test]
+    fn validate_trader_markets_with_mismatched_markets() {
+        let trader_markets = [market("btc"), market("eth"), market("eth")];
+        let command_txs = HashMap::from([(market("btc"), ()), (market("sol"), ())]);
+
+        match validate_trader_markets(trader_markets.iter(), &command_txs) {
+            Err(EngineError::TraderMarketMismatch {
+                missing_command_tx,
+                missing_trader,
+                duplicate_trader,
+            }) => {
+                assert_eq!(missing_command_tx, vec![market("eth")]);
+                assert_eq!(missing_trader, vec![market("sol")]);
+                assert_eq!(duplicate_trader, vec![market("eth")]);
+            }
+            result => panic!("expected TraderMarketMismatch, got: {result:?}"),
+        }
+    }
+}
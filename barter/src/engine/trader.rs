@@ -3,8 +3,8 @@ use crate::{
     data::{Feed, MarketGenerator},
     event::{Event, MessageTransmitter},
     execution::ExecutionClient,
-    portfolio::{FillUpdater, MarketUpdater, OrderGenerator},
-    strategy::{SignalForceExit, SignalGenerator},
+    portfolio::{error::PortfolioError, FillUpdater, MarketUpdater, OrderGenerator},
+    strategy::{error::StrategyError, Signal, SignalForceExit, SignalGenerator, SignalOutcome},
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{instrument::Instrument, Market};
@@ -12,9 +12,38 @@ use parking_lot::Mutex;
 use serde::Serialize;
 use std::{collections::VecDeque, fmt::Debug, marker::PhantomData, sync::Arc};
 use tokio::sync::mpsc;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Capacity of the [`Command`] channel created by [`Trader::from_configs`].
+pub const COMMAND_CHANNEL_CAPACITY: usize = 10;
+
+/// Component configurations for constructing a [`Trader`] & it's [`Command`] transmitter via
+/// the [`Trader::from_configs`] constructor method. The Strategy component is constructed from
+/// its configuration via [`TryFrom`], and the Execution component via [`From`].
+#[derive(Debug)]
+pub struct TraderConfig<EventTx, Portfolio, Data, StrategyConfig, ExecutionConfig> {
+    /// Identifier for the [`Engine`](super::Engine) the [`Trader`] is associated with.
+    pub engine_id: Uuid,
+    /// Unique [`Market`] the [`Trader`] is bartering on.
+    pub market: Market,
+    /// [`Event`] transmitter for sending every [`Event`] the [`Trader`] encounters to an external
+    /// sink.
+    pub event_tx: EventTx,
+    /// Shared Portfolio instance.
+    pub portfolio: Arc<Mutex<Portfolio>>,
+    /// [`MarketGenerator`] of the [`Market`].
+    pub data: Data,
+    /// Configuration the [`SignalGenerator`] is constructed from.
+    pub strategy: StrategyConfig,
+    /// Configuration the [`ExecutionClient`] is constructed from.
+    pub execution: ExecutionConfig,
+    /// Discard any [`Signal`](crate::strategy::Signal) generated on the first market event.
+    pub skip_first_bar_signal: bool,
+    /// Emit an [`Event::SignalOutcome`] for every [`Signal`](crate::strategy::Signal) generated.
+    pub emit_signal_outcomes: bool,
+}
+
 /// Lego components for constructing a [`Trader`] via the new() constructor method.
 #[derive(Debug)]
 pub struct TraderLego<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -44,6 +73,14 @@ where
     pub strategy: Strategy,
     /// Execution handler that implements [`ExecutionClient`].
     pub execution: Execution,
+    /// Discard any [`Signal`](crate::strategy::Signal) generated on the first [`MarketEvent`],
+    /// which often has a degenerate [`MarketMeta`](crate::data::MarketMeta) (eg/ no prior close).
+    /// The Strategy still observes the first [`MarketEvent`]. Defaults to false.
+    pub skip_first_bar_signal: bool,
+    /// Emit an [`Event::SignalOutcome`] for every [`Signal`](crate::strategy::Signal) the
+    /// Strategy generates, tagging whether it resulted in an
+    /// [`OrderEvent`](crate::portfolio::OrderEvent). Defaults to false.
+    pub emit_signal_outcomes: bool,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -83,6 +120,12 @@ where
     strategy: Strategy,
     /// Execution handler that implements [`ExecutionClient`].
     execution: Execution,
+    /// Discard any [`Signal`](crate::strategy::Signal) generated on the first [`MarketEvent`].
+    skip_first_bar_signal: bool,
+    /// Emit an [`Event::SignalOutcome`] for every [`Signal`](crate::strategy::Signal) generated.
+    emit_signal_outcomes: bool,
+    /// Determines if the first [`MarketEvent`] has been handled by the trading loop.
+    first_bar_handled: bool,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -114,18 +157,57 @@ where
             data: lego.data,
             strategy: lego.strategy,
             execution: lego.execution,
+            skip_first_bar_signal: lego.skip_first_bar_signal,
+            emit_signal_outcomes: lego.emit_signal_outcomes,
+            first_bar_handled: false,
             _statistic_marker: PhantomData,
         }
     }
 
+    /// Constructs a new [`Trader`] from the provided [`TraderConfig`], returning it alongside the
+    /// [`Command`] transmitter for the [`Engine`](super::Engine)'s `trader_command_txs`.
+    ///
+    /// Returns an [`EngineError::Strategy`] if the Strategy cannot be constructed from its
+    /// configuration (eg/ an invalid indicator period).
+    pub fn from_configs<StrategyConfig, ExecutionConfig>(
+        config: TraderConfig<EventTx, Portfolio, Data, StrategyConfig, ExecutionConfig>,
+    ) -> Result<(Self, mpsc::Sender<Command>), EngineError>
+    where
+        Strategy: TryFrom<StrategyConfig, Error = StrategyError>,
+        Execution: From<ExecutionConfig>,
+    {
+        let (command_tx, command_rx) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
+
+        let trader = Self::new(TraderLego {
+            engine_id: config.engine_id,
+            market: config.market,
+            command_rx,
+            event_tx: config.event_tx,
+            portfolio: config.portfolio,
+            data: config.data,
+            strategy: Strategy::try_from(config.strategy)?,
+            execution: Execution::from(config.execution),
+            skip_first_bar_signal: config.skip_first_bar_signal,
+            emit_signal_outcomes: config.emit_signal_outcomes,
+            _statistic_marker: PhantomData,
+        });
+
+        Ok((trader, command_tx))
+    }
+
+    /// Returns the unique [`Market`] this [`Trader`] is bartering on.
+    pub fn market(&self) -> &Market {
+        &self.market
+    }
+
     /// Builder to construct [`Trader`] instances.
     pub fn builder() -> TraderBuilder<EventTx, Statistic, Portfolio, Data, Strategy, Execution> {
         TraderBuilder::new()
     }
 
     /// Run the trading event-loop for this [`Trader`] instance. Loop will run until [`Trader`]
-    /// receives a [`Command::Terminate`] via the mpsc::Receiver command_rx, or the
-    /// [`MarketGenerator`] yields [`Feed::Finished`].
+    /// receives a [`Command::Terminate`] via the mpsc::Receiver command_rx, the
+    /// [`MarketGenerator`] yields [`Feed::Finished`], or the Portfolio repository is unavailable.
     pub fn run(mut self) {
         // Run trading loop for this Trader instance
         'trading: loop {
@@ -137,26 +219,38 @@ where
                         self.event_q
                             .push_back(Event::SignalForceExit(SignalForceExit::from(market)));
                     }
+                    #[cfg(any(test, feature = "test-utils"))]
+                    Command::InjectMarket(market) => {
+                        self.event_tx.send(Event::Market(market.clone()));
+                        self.event_q.push_back(Event::Market(market));
+                    }
                     _ => continue,
                 }
             }
 
             // If the Feed<MarketEvent> yields, populate event_q with the next MarketEvent
-            match self.data.next() {
-                Feed::Next(market) => {
-                    self.event_tx.send(Event::Market(market.clone()));
-                    self.event_q.push_back(Event::Market(market));
-                }
-                Feed::Unhealthy => {
-                    warn!(
-                        engine_id = %self.engine_id,
-                        market = ?self.market,
-                        action = "continuing while waiting for healthy Feed",
-                        "MarketFeed unhealthy"
-                    );
-                    continue 'trading;
+            // '--> An injected MarketEvent is handled in place of the next MarketEvent
+            if !self
+                .event_q
+                .iter()
+                .any(|event| matches!(event, Event::Market(_)))
+            {
+                match self.data.next() {
+                    Feed::Next(market) => {
+                        self.event_tx.send(Event::Market(market.clone()));
+                        self.event_q.push_back(Event::Market(market));
+                    }
+                    Feed::Unhealthy => {
+                        warn!(
+                            engine_id = %self.engine_id,
+                            market = ?self.market,
+                            action = "continuing while waiting for healthy Feed",
+                            "MarketFeed unhealthy"
+                        );
+                        continue 'trading;
+                    }
+                    Feed::Finished => break 'trading,
                 }
-                Feed::Finished => break 'trading,
             }
 
             // Handle Events in the event_q
@@ -164,63 +258,161 @@ where
             while let Some(event) = self.event_q.pop_front() {
                 match event {
                     Event::Market(market) => {
+                        match self.execution.update_from_market(&market) {
+                            Ok(fills) => {
+                                for fill in fills {
+                                    self.event_tx.send(Event::Fill(fill.clone()));
+                                    self.event_q.push_back(Event::Fill(fill));
+                                }
+                            }
+                            Err(error) => {
+                                warn!(
+                                    engine_id = %self.engine_id,
+                                    market = ?self.market,
+                                    ?error,
+                                    action = "skipping Execution fills for this MarketEvent",
+                                    "failed to update Execution from market"
+                                );
+                            }
+                        }
+
+                        self.queue_expired_orders();
+
+                        let first_bar = !std::mem::replace(&mut self.first_bar_handled, true);
+
                         if let Some(signal) = self.strategy.generate_signal(&market) {
-                            self.event_tx.send(Event::Signal(signal.clone()));
-                            self.event_q.push_back(Event::Signal(signal));
+                            if first_bar && self.skip_first_bar_signal {
+                                debug!(
+                                    engine_id = %self.engine_id,
+                                    market = ?self.market,
+                                    "skipping Signal generated on the first MarketEvent"
+                                );
+                                self.send_signal_outcome(signal, false);
+                            } else {
+                                self.event_tx.send(Event::Signal(signal.clone()));
+                                self.event_q.push_back(Event::Signal(signal));
+                            }
+                        }
+
+                        let position_updates = self.portfolio.lock().update_from_market(&market);
+                        match position_updates {
+                            Ok(position_updates) => {
+                                for position_update in position_updates {
+                                    self.event_tx.send(Event::PositionUpdate(position_update));
+                                }
+                            }
+                            Err(error) => {
+                                self.halt_and_flatten(
+                                    error,
+                                    "failed to update Portfolio from market",
+                                );
+                                break 'trading;
+                            }
                         }
 
-                        if let Some(position_update) = self
-                            .portfolio
-                            .lock()
-                            .update_from_market(&market)
-                            .expect("failed to update Portfolio from market")
-                        {
-                            self.event_tx.send(Event::PositionUpdate(position_update));
+                        let delayed_orders = self.portfolio.lock().generate_delayed_orders(&market);
+                        match delayed_orders {
+                            Ok(delayed_orders) => {
+                                for order in delayed_orders {
+                                    self.event_tx.send(Event::OrderNew(order.clone()));
+                                    self.event_q.push_back(Event::OrderNew(order));
+                                }
+                            }
+                            Err(error) => {
+                                self.halt_and_flatten(error, "failed to generate delayed orders");
+                                break 'trading;
+                            }
                         }
                     }
 
                     Event::Signal(signal) => {
-                        if let Some(order) = self
-                            .portfolio
-                            .lock()
-                            .generate_order(&signal)
-                            .expect("failed to generate order")
-                        {
-                            self.event_tx.send(Event::OrderNew(order.clone()));
-                            self.event_q.push_back(Event::OrderNew(order));
+                        let order = self.portfolio.lock().generate_order(&signal);
+                        match order {
+                            Ok(Some(order)) => {
+                                self.send_signal_outcome(signal, true);
+                                self.event_tx.send(Event::OrderNew(order.clone()));
+                                self.event_q.push_back(Event::OrderNew(order));
+                            }
+                            Ok(None) => self.send_signal_outcome(signal, false),
+                            Err(error) => {
+                                self.halt_and_flatten(error, "failed to generate order");
+                                break 'trading;
+                            }
                         }
                     }
 
                     Event::SignalForceExit(signal_force_exit) => {
-                        if let Some(order) = self
-                            .portfolio
-                            .lock()
-                            .generate_exit_order(signal_force_exit)
-                            .expect("failed to generate forced exit order")
-                        {
-                            self.event_tx.send(Event::OrderNew(order.clone()));
-                            self.event_q.push_back(Event::OrderNew(order));
+                        let order = self.portfolio.lock().generate_exit_order(signal_force_exit);
+                        match order {
+                            Ok(Some(order)) => {
+                                self.event_tx.send(Event::OrderNew(order.clone()));
+                                self.event_q.push_back(Event::OrderNew(order));
+                            }
+                            Ok(None) => {}
+                            Err(error) => {
+                                self.halt_and_flatten(
+                                    error,
+                                    "failed to generate forced exit order",
+                                );
+                                break 'trading;
+                            }
                         }
                     }
 
                     Event::OrderNew(order) => {
-                        let fill = self
-                            .execution
-                            .generate_fill(&order)
-                            .expect("failed to generate Fill");
+                        let submitted = self.execution.submit_order(&order);
+
+                        // Orders replaced by the submitted OrderEvent will never fill further
+                        self.queue_replaced_fills();
+                        self.queue_expired_orders();
 
-                        self.event_tx.send(Event::Fill(fill.clone()));
-                        self.event_q.push_back(Event::Fill(fill));
+                        match submitted {
+                            Ok(Some(fill)) => {
+                                self.event_tx.send(Event::Fill(fill.clone()));
+                                self.event_q.push_back(Event::Fill(fill));
+                            }
+                            Ok(None) => {}
+                            Err(error) => {
+                                // Rejected OrderEvents will never fill, so handle them as expired
+                                warn!(
+                                    engine_id = %self.engine_id,
+                                    market = ?self.market,
+                                    ?error,
+                                    ?order,
+                                    action = "handling rejected OrderEvent as expired",
+                                    "failed to submit OrderEvent to Execution"
+                                );
+                                self.event_tx.send(Event::OrderExpired(order.clone()));
+                                self.event_q.push_back(Event::OrderExpired(order));
+                            }
+                        }
                     }
 
-                    Event::Fill(fill) => {
-                        let fill_side_effect_events = self
-                            .portfolio
-                            .lock()
-                            .update_from_fill(&fill)
-                            .expect("failed to update Portfolio from fill");
+                    Event::OrderExpired(order) => {
+                        let updated = self.portfolio.lock().update_from_expired_order(&order);
+                        if let Err(error) = updated {
+                            self.halt_and_flatten(
+                                error,
+                                "failed to update Portfolio from expired order",
+                            );
+                            break 'trading;
+                        }
+                    }
 
-                        self.event_tx.send_many(fill_side_effect_events);
+                    Event::Fill(fill) => {
+                        let fill_side_effect_events = self.portfolio.lock().update_from_fill(&fill);
+                        match fill_side_effect_events {
+                            Ok(fill_side_effect_events) => {
+                                self.event_tx.send_many(fill_side_effect_events);
+                            }
+                            Err(error) => {
+                                self.halt_and_flatten(
+                                    error,
+                                    "failed to update Portfolio from fill",
+                                );
+                                break 'trading;
+                            }
+                        }
                     }
                     _ => {}
                 }
@@ -234,6 +426,113 @@ where
         }
     }
 
+    /// Sends & queues every [`OrderEvent`](crate::portfolio::OrderEvent) the execution handler
+    /// has expired (eg/ replaced working orders), so the Portfolio releases the cash reserved
+    /// for them.
+    fn queue_expired_orders(&mut self) {
+        for order in self.execution.take_expired_orders() {
+            self.event_tx.send(Event::OrderExpired(order.clone()));
+            self.event_q.push_back(Event::OrderExpired(order));
+        }
+    }
+
+    /// Sends & queues the [`FillEvent`](crate::execution::FillEvent)s for the quantity already
+    /// filled of working orders replaced by the execution handler, so the Portfolio applies them
+    /// before the unfilled remainder is expired.
+    fn queue_replaced_fills(&mut self) {
+        for fill in self.execution.take_replaced_fills() {
+            self.event_tx.send(Event::Fill(fill.clone()));
+            self.event_q.push_back(Event::Fill(fill));
+        }
+    }
+
+    /// Halts trading if the Portfolio repository is unavailable (see
+    /// [`FailurePolicy`](crate::portfolio::repository::resilient::FailurePolicy)), flattening
+    /// this [`Trader`]'s open [`Position`](crate::portfolio::position::Position) so it is not left
+    /// unmanaged. Panics on any other [`PortfolioError`].
+    ///
+    /// The flattening [`Event::Fill`] is always sent, so it can be reconciled if the Portfolio
+    /// cannot be updated with it.
+    fn halt_and_flatten(&mut self, error: PortfolioError, context: &str) {
+        if !error.is_repository_unavailable() {
+            panic!("{context}: {error:?}");
+        }
+
+        error!(
+            engine_id = %self.engine_id,
+            market = ?self.market,
+            ?error,
+            action = "halting Trader & flattening open Position",
+            "{context}"
+        );
+
+        let exit_order = self
+            .portfolio
+            .lock()
+            .generate_exit_order(SignalForceExit::from(self.market.clone()));
+        let order = match exit_order {
+            Ok(Some(order)) => order,
+            Ok(None) => return,
+            Err(error) => {
+                error!(?error, "failed to generate flattening exit order");
+                return;
+            }
+        };
+        self.event_tx.send(Event::OrderNew(order.clone()));
+
+        let submitted = self.execution.submit_order(&order);
+
+        // Apply the filled quantity of any working orders replaced by the flattening exit order
+        for replaced in self.execution.take_replaced_fills() {
+            self.event_tx.send(Event::Fill(replaced.clone()));
+            if let Err(error) = self.portfolio.lock().update_from_fill(&replaced) {
+                error!(
+                    ?error,
+                    "failed to update Portfolio from replaced order Fill"
+                );
+            }
+        }
+
+        // Release the cash reserved for any orders replaced by the flattening exit order
+        for expired in self.execution.take_expired_orders() {
+            self.event_tx.send(Event::OrderExpired(expired.clone()));
+            if let Err(error) = self.portfolio.lock().update_from_expired_order(&expired) {
+                error!(?error, "failed to update Portfolio from expired order");
+            }
+        }
+
+        let fill = match submitted {
+            Ok(Some(fill)) => fill,
+            Ok(None) => {
+                warn!("flattening exit order is working rather than filled");
+                return;
+            }
+            Err(error) => {
+                error!(?error, "failed to execute flattening exit order");
+                return;
+            }
+        };
+        self.event_tx.send(Event::Fill(fill.clone()));
+
+        match self.portfolio.lock().update_from_fill(&fill) {
+            Ok(fill_side_effect_events) => self.event_tx.send_many(fill_side_effect_events),
+            Err(error) => error!(
+                ?error,
+                "failed to update Portfolio from flattening Fill, which must be reconciled"
+            ),
+        }
+    }
+
+    /// Sends an [`Event::SignalOutcome`] for the [`Signal`], if configured to do so.
+    fn send_signal_outcome(&mut self, signal: Signal, order_generated: bool) {
+        if self.emit_signal_outcomes {
+            self.event_tx.send(Event::SignalOutcome(SignalOutcome {
+                signal,
+                order_generated,
+            }));
+        }
+    }
+
     /// Returns a [`Command`] if one has been received.
     fn receive_remote_command(&mut self) -> Option<Command> {
         match self.command_rx.try_recv() {
@@ -281,6 +580,8 @@ where
     data: Option<Data>,
     strategy: Option<Strategy>,
     execution: Option<Execution>,
+    skip_first_bar_signal: Option<bool>,
+    emit_signal_outcomes: Option<bool>,
     _statistic_marker: Option<PhantomData<Statistic>>,
 }
 
@@ -304,6 +605,8 @@ where
             data: None,
             strategy: None,
             execution: None,
+            skip_first_bar_signal: None,
+            emit_signal_outcomes: None,
             _statistic_marker: None,
         }
     }
@@ -364,6 +667,24 @@ where
         }
     }
 
+    /// Discard any [`Signal`](crate::strategy::Signal) generated on the first [`MarketEvent`].
+    /// Defaults to false if not provided.
+    pub fn skip_first_bar_signal(self, value: bool) -> Self {
+        Self {
+            skip_first_bar_signal: Some(value),
+            ..self
+        }
+    }
+
+    /// Emit an [`Event::SignalOutcome`] for every [`Signal`](crate::strategy::Signal) generated.
+    /// Defaults to false if not provided.
+    pub fn emit_signal_outcomes(self, value: bool) -> Self {
+        Self {
+            emit_signal_outcomes: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(
         self,
     ) -> Result<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
@@ -391,7 +712,256 @@ where
             execution: self
                 .execution
                 .ok_or(EngineError::BuilderIncomplete("execution"))?,
+            skip_first_bar_signal: self.skip_first_bar_signal.unwrap_or_default(),
+            emit_signal_outcomes: self.emit_signal_outcomes.unwrap_or_default(),
+            first_bar_handled: false,
             _statistic_marker: PhantomData,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::historical,
+        event::EventTx,
+        execution::simulated::{Config as ExecutionConfig, SimulatedExecution},
+        portfolio::{
+            allocator::DefaultAllocator, portfolio::MetaPortfolio,
+            repository::in_memory::InMemoryRepository, risk::DefaultRisk, OrderType,
+        },
+        statistic::summary::trading::{Config as StatisticConfig, TradingSummary},
+        strategy::{Decision, SignalStrength},
+        test_util::{market_event_trade, order_event, signal},
+    };
+    use barter_integration::model::{instrument::kind::InstrumentKind, Side};
+
+    type TestPortfolio = MetaPortfolio<
+        InMemoryRepository<TradingSummary>,
+        DefaultAllocator,
+        DefaultRisk,
+        TradingSummary,
+    >;
+
+    type TestTrader<Data> =
+        Trader<EventTx, TradingSummary, TestPortfolio, Data, AlwaysSignal, SimulatedExecution>;
+
+    /// Strategy that generates a long [`Signal`] for every [`MarketEvent`].
+    struct AlwaysSignal;
+
+    impl TryFrom<()> for AlwaysSignal {
+        type Error = StrategyError;
+
+        fn try_from(_: ()) -> Result<Self, Self::Error> {
+            Ok(Self)
+        }
+    }
+
+    impl SignalGenerator for AlwaysSignal {
+        fn generate_signal(&mut self, _: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+            let mut signal = signal();
+            signal.signals.insert(Decision::Long, SignalStrength(1.0));
+            Some(signal)
+        }
+    }
+
+    fn trader<Data>(
+        data: Data,
+        skip_first_bar_signal: bool,
+    ) -> (
+        TestTrader<Data>,
+        mpsc::Sender<Command>,
+        mpsc::UnboundedReceiver<Event>,
+    )
+    where
+        Data: MarketGenerator<MarketEvent<Instrument, DataKind>> + Send,
+    {
+        let engine_id = Uuid::new_v4();
+        let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+        let portfolio = MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+                omega_threshold_return: 0.0,
+                return_kind: Default::default(),
+            })
+            .build_and_init()
+            .unwrap();
+
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        let (trader, command_tx) = Trader::from_configs(TraderConfig {
+            engine_id,
+            market,
+            event_tx: EventTx::new(event_tx),
+            portfolio: Arc::new(Mutex::new(portfolio)),
+            data,
+            strategy: (),
+            execution: ExecutionConfig::default(),
+            skip_first_bar_signal,
+            emit_signal_outcomes: false,
+        })
+        .unwrap();
+
+        (trader, command_tx, event_rx)
+    }
+
+    fn count_signals(event_rx: &mut mpsc::UnboundedReceiver<Event>) -> usize {
+        std::iter::from_fn(|| event_rx.try_recv().ok())
+            .filter(|event| matches!(event, Event::Signal(_)))
+            .count()
+    }
+
+    #[test]
+    fn trader_handles_injected_market_event_as_if_from_data_handler() {
+        // No data file: the MarketGenerator finishes immediately
+        let (trader, command_tx, mut event_rx) =
+            trader(historical::MarketFeed::new(std::iter::empty()), false);
+
+        let injected = market_event_trade(Side::Buy);
+        command_tx
+            .try_send(Command::InjectMarket(injected.clone()))
+            .unwrap();
+
+        trader.run();
+
+        match event_rx.try_recv() {
+            Ok(Event::Market(market)) => assert_eq!(market, injected),
+            other => panic!("expected injected Event::Market, found: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn trader_forwards_first_bar_signal_by_default() {
+        let markets = [market_event_trade(Side::Buy), market_event_trade(Side::Buy)];
+        let (trader, _command_tx, mut event_rx) =
+            trader(historical::MarketFeed::new(markets), false);
+
+        trader.run();
+
+        assert_eq!(count_signals(&mut event_rx), 2);
+    }
+
+    #[test]
+    fn trader_skips_first_bar_signal_if_configured() {
+        let markets = [market_event_trade(Side::Buy), market_event_trade(Side::Buy)];
+        let (trader, _command_tx, mut event_rx) =
+            trader(historical::MarketFeed::new(markets), true);
+
+        trader.run();
+
+        assert_eq!(count_signals(&mut event_rx), 1);
+    }
+
+    #[test]
+    fn trader_emits_signal_outcomes_if_configured() {
+        let markets = [
+            market_event_trade(Side::Buy),
+            market_event_trade(Side::Buy),
+            market_event_trade(Side::Buy),
+        ];
+        let (mut trader, _command_tx, mut event_rx) =
+            trader(historical::MarketFeed::new(markets), true);
+        trader.emit_signal_outcomes = true;
+
+        trader.run();
+
+        // First bar Signal is skipped, second enters a Position, third is vetoed whilst it's open
+        let outcomes = std::iter::from_fn(|| event_rx.try_recv().ok())
+            .filter_map(|event| match event {
+                Event::SignalOutcome(outcome) => Some(outcome.order_generated),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(outcomes, [false, true, false]);
+    }
+
+    #[test]
+    fn trader_emits_no_signal_outcomes_by_default() {
+        let markets = [market_event_trade(Side::Buy), market_event_trade(Side::Buy)];
+        let (trader, _command_tx, mut event_rx) =
+            trader(historical::MarketFeed::new(markets), false);
+
+        trader.run();
+
+        assert!(std::iter::from_fn(|| event_rx.try_recv().ok())
+            .all(|event| !matches!(event, Event::SignalOutcome(_))));
+    }
+
+    #[test]
+    fn trader_handles_order_rejected_by_execution_as_expired() {
+        let markets = [market_event_trade(Side::Buy)];
+        let (mut trader, _command_tx, mut event_rx) =
+            trader(historical::MarketFeed::new(markets), false);
+
+        // Limit OrderEvent without a limit_price is rejected by the SimulatedExecution
+        let mut rejected = order_event();
+        rejected.order_type = OrderType::Limit;
+        rejected.limit_price = None;
+        trader.event_q.push_back(Event::OrderNew(rejected.clone()));
+
+        trader.run();
+
+        assert!(std::iter::from_fn(|| event_rx.try_recv().ok())
+            .any(|event| matches!(event, Event::OrderExpired(order) if order == rejected)));
+    }
+
+    #[test]
+    fn trader_releases_reserved_cash_of_replaced_order_before_replacement_fills() {
+        let markets = [market_event_trade(Side::Buy)];
+        let (mut trader, _command_tx, mut event_rx) =
+            trader(historical::MarketFeed::new(markets), false);
+        let portfolio = Arc::clone(&trader.portfolio);
+
+        // Entry OrderEvent reserves cash, & is submitted as a non-marketable limit order
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+        let mut working = portfolio
+            .lock()
+            .generate_order(&input_signal)
+            .unwrap()
+            .unwrap();
+        working.order_type = OrderType::Limit;
+        working.limit_price = Some(working.market_meta.close * 0.5);
+        assert!(portfolio.lock().reserved_cash() > 0.0);
+
+        // Market OrderEvent for the same market replaces the working order & fills immediately
+        let mut replacement = working.clone();
+        replacement.order_type = OrderType::Market;
+        replacement.limit_price = None;
+        trader.event_q.push_back(Event::OrderNew(working.clone()));
+        trader.event_q.push_back(Event::OrderNew(replacement));
+
+        trader.run();
+
+        // Replaced order is reported expired before the replacement's Fill
+        let events = std::iter::from_fn(|| event_rx.try_recv().ok())
+            .filter_map(|event| match event {
+                Event::OrderExpired(order) => Some(order == working),
+                Event::Fill(_) => Some(false),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(events.first(), Some(&true));
+
+        let mut portfolio = portfolio.lock();
+        assert_eq!(portfolio.reserved_cash(), 0.0);
+        assert_eq!(
+            portfolio.available_cash().unwrap(),
+            portfolio.current_cash().unwrap()
+        );
+    }
+}
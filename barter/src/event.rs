@@ -4,7 +4,8 @@ use crate::{
         position::{Position, PositionExit, PositionUpdate},
         Balance, OrderEvent,
     },
-    strategy::{Signal, SignalForceExit},
+    statistic::StatisticUpdate,
+    strategy::{Signal, SignalForceExit, SignalOutcome},
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::instrument::Instrument;
@@ -21,14 +22,17 @@ use tracing::warn;
 pub enum Event {
     Market(MarketEvent<Instrument, DataKind>),
     Signal(Signal),
+    SignalOutcome(SignalOutcome),
     SignalForceExit(SignalForceExit),
     OrderNew(OrderEvent),
     OrderUpdate,
+    OrderExpired(OrderEvent),
     Fill(FillEvent),
     PositionNew(Position),
     PositionUpdate(PositionUpdate),
     PositionExit(PositionExit),
     Balance(Balance),
+    StatisticUpdate(StatisticUpdate),
 }
 
 /// Message transmitter for sending Barter messages to downstream consumers.
@@ -86,3 +90,155 @@ impl EventTx {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::{position::PositionExiter, OrderType},
+        strategy::{Decision, SignalStrength},
+        test_util::{
+            fill_event, market_event_candle, market_event_trade, order_event, position, signal,
+        },
+    };
+    use barter_data::subscription::{
+        book::{Level, OrderBook, OrderBookL1, OrderBookSide},
+        liquidation::Liquidation,
+    };
+    use barter_integration::model::{MarketId, Side};
+    use chrono::Utc;
+
+    fn assert_round_trip(event: Event) {
+        let json = serde_json::to_string(&event).unwrap();
+        let actual: Event = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, event, "Event failed to round trip via JSON: {json}");
+    }
+
+    #[test]
+    fn market_events_of_every_data_kind_round_trip() {
+        let mut order_book_l1 = market_event_trade(Side::Buy);
+        order_book_l1.kind = DataKind::OrderBookL1(OrderBookL1 {
+            last_update_time: Utc::now(),
+            best_bid: Level::new(99.0, 1.5),
+            best_ask: Level::new(101.0, 2.5),
+        });
+
+        let mut order_book = market_event_trade(Side::Buy);
+        order_book.kind = DataKind::OrderBook(OrderBook {
+            last_update_time: Utc::now(),
+            bids: OrderBookSide::new(Side::Buy, [(99.0, 1.0), (98.0, 2.0)]),
+            asks: OrderBookSide::new(Side::Sell, [(101.0, 1.0), (102.0, 2.0)]),
+        });
+
+        let mut liquidation = market_event_trade(Side::Buy);
+        liquidation.kind = DataKind::Liquidation(Liquidation {
+            side: Side::Sell,
+            price: 100.0,
+            quantity: 3.0,
+            time: Utc::now(),
+        });
+
+        let mut sequenced = market_event_candle();
+        sequenced.sequence = 42;
+
+        for market in [
+            market_event_trade(Side::Buy),
+            market_event_trade(Side::Sell),
+            market_event_candle(),
+            order_book_l1,
+            order_book,
+            liquidation,
+            sequenced,
+        ] {
+            assert_round_trip(Event::Market(market));
+        }
+    }
+
+    #[test]
+    fn signal_events_round_trip() {
+        let mut signal = signal();
+        signal.signals.insert(Decision::Long, SignalStrength(0.75));
+        signal
+            .signals
+            .insert(Decision::CloseShort, SignalStrength(1.0));
+        signal.tag = Some("trend".to_owned());
+
+        let mut signal_force_exit =
+            SignalForceExit::new(signal.exchange.clone(), signal.instrument.clone());
+        signal_force_exit.tag = Some("trend".to_owned());
+
+        assert_round_trip(Event::Signal(signal.clone()));
+        assert_round_trip(Event::SignalOutcome(SignalOutcome {
+            signal,
+            order_generated: true,
+        }));
+        assert_round_trip(Event::SignalForceExit(signal_force_exit));
+    }
+
+    #[test]
+    fn order_events_round_trip() {
+        let mut limit_order = order_event();
+        limit_order.order_type = OrderType::Limit;
+        limit_order.limit_price = Some(99.5);
+        limit_order.quantity = -2.5;
+        limit_order.tag = Some("mean_reversion".to_owned());
+
+        assert_round_trip(Event::OrderNew(order_event()));
+        assert_round_trip(Event::OrderNew(limit_order.clone()));
+        assert_round_trip(Event::OrderExpired(limit_order));
+        assert_round_trip(Event::OrderUpdate);
+    }
+
+    #[test]
+    fn fill_events_round_trip() {
+        let mut fill = fill_event();
+        fill.fees.exchange = 0.1;
+        fill.fees.slippage = 0.05;
+        fill.tag = Some("trend".to_owned());
+
+        assert_round_trip(Event::Fill(fill_event()));
+        assert_round_trip(Event::Fill(fill));
+    }
+
+    #[test]
+    fn events_serialised_without_defaulted_fields_deserialise() {
+        let mut market = serde_json::to_value(Event::Market(market_event_candle())).unwrap();
+        market["Market"].as_object_mut().unwrap().remove("sequence");
+        let market: Event = serde_json::from_value(market).unwrap();
+        assert!(matches!(market, Event::Market(market) if market.sequence == 0));
+
+        let expected = Event::Fill(fill_event());
+        let mut fill = serde_json::to_value(&expected).unwrap();
+        fill["Fill"].as_object_mut().unwrap().remove("tag");
+        let fill: Event = serde_json::from_value(fill).unwrap();
+        assert_eq!(fill, expected);
+    }
+
+    #[test]
+    fn position_and_balance_events_round_trip() {
+        let mut position = position();
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        let balance = Balance {
+            time: Utc::now(),
+            total: 1000.0,
+            available: 900.0,
+        };
+
+        assert_round_trip(Event::PositionNew(position.clone()));
+        assert_round_trip(Event::PositionUpdate(PositionUpdate::from(&mut position)));
+        assert_round_trip(Event::PositionExit(
+            position.exit(balance, &exit_fill).unwrap(),
+        ));
+        assert_round_trip(Event::Balance(balance));
+        assert_round_trip(Event::StatisticUpdate(StatisticUpdate {
+            time: Utc::now(),
+            market_id: MarketId::new(&position.exchange, &position.instrument),
+            statistics: serde_json::json!({ "pnl": 10.5, "trades": 2 }),
+        }));
+
+        // Exited Position
+        assert_round_trip(Event::PositionNew(position));
+    }
+}
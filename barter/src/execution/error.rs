@@ -5,4 +5,7 @@ use thiserror::Error;
 pub enum ExecutionError {
     #[error("Failed to build struct due to missing attributes: {0}")]
     BuilderIncomplete(&'static str),
+
+    #[error("Limit OrderEvent provided without a limit_price")]
+    LimitPriceMissing,
 }
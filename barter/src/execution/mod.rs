@@ -1,4 +1,9 @@
-use crate::{data::MarketMeta, portfolio::OrderEvent, strategy::Decision};
+use crate::{
+    data::MarketMeta,
+    portfolio::{default_contract_multiplier, ContractType, OrderEvent},
+    strategy::Decision,
+};
+use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{instrument::Instrument, Exchange};
 use chrono::{DateTime, Utc};
 use error::ExecutionError;
@@ -10,10 +15,53 @@ pub mod error;
 /// Handlers for simulated and live [`OrderEvent`] execution.
 pub mod simulated;
 
+/// Pluggable models of the slippage incurred by simulated fills.
+pub mod slippage;
+
 /// Generates a result [`FillEvent`] by executing an [`OrderEvent`].
 pub trait ExecutionClient {
     /// Return a [`FillEvent`] from executing the input [`OrderEvent`].
     fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError>;
+
+    /// Submit an [`OrderEvent`] for execution. Returns a [`FillEvent`] if the [`OrderEvent`] is
+    /// filled immediately, or `None` if it is held as a working order to be filled by a
+    /// subsequent [`MarketEvent`] (see [`ExecutionClient::update_from_market`]).
+    ///
+    /// Defaults to immediately filling every [`OrderEvent`] via
+    /// [`ExecutionClient::generate_fill`].
+    fn submit_order(&mut self, order: &OrderEvent) -> Result<Option<FillEvent>, ExecutionError> {
+        self.generate_fill(order).map(Some)
+    }
+
+    /// Evaluate any working orders against the latest [`MarketEvent`], returning a [`FillEvent`]
+    /// for every working order that has now filled.
+    ///
+    /// Defaults to no working orders.
+    fn update_from_market(
+        &mut self,
+        _market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<FillEvent>, ExecutionError> {
+        Ok(Vec::new())
+    }
+
+    /// Take the working orders that have expired unfilled since this was last called. These
+    /// [`OrderEvent`]s will never generate a [`FillEvent`]. If only part of an order will never
+    /// fill (eg/ the remainder of a partially filled order), the expired [`OrderEvent`] has the
+    /// unfilled quantity.
+    ///
+    /// Defaults to no expired orders.
+    fn take_expired_orders(&mut self) -> Vec<OrderEvent> {
+        Vec::new()
+    }
+
+    /// Take the [`FillEvent`]s for the quantity already filled of working orders replaced via
+    /// [`ExecutionClient::submit_order`] since this was last called. The unfilled remainder of
+    /// each replaced order is yielded via [`ExecutionClient::take_expired_orders`].
+    ///
+    /// Defaults to no replaced fills.
+    fn take_replaced_fills(&mut self) -> Vec<FillEvent> {
+        Vec::new()
+    }
 }
 
 /// Fills are journals of work done by an Execution handler. These are sent back to the portfolio
@@ -29,10 +77,20 @@ pub struct FillEvent {
     pub decision: Decision,
     /// +ve or -ve Quantity depending on Decision
     pub quantity: f64,
-    /// abs(Quantity) * ClosePrice, excluding TotalFees
+    /// Notional value of the fill excluding TotalFees, ie/ abs(Quantity) * ClosePrice *
+    /// ContractMultiplier for [`ContractType::Linear`] contracts. See [`ContractType::notional`].
     pub fill_value_gross: f64,
     /// All fee types incurred when executing an [`OrderEvent`], and their associated [`FeeAmount`].
     pub fees: Fees,
+    /// Units of the underlying per contract, propagated from the source [`OrderEvent`].
+    #[serde(default = "default_contract_multiplier")]
+    pub contract_multiplier: f64,
+    /// Linear or Inverse, propagated from the source [`OrderEvent`].
+    #[serde(default)]
+    pub contract_type: ContractType,
+    /// Optional sub-strategy tag, propagated from the source [`OrderEvent`].
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
 impl FillEvent {
@@ -76,6 +134,9 @@ pub struct FillEventBuilder {
     pub quantity: Option<f64>,
     pub fill_value_gross: Option<f64>,
     pub fees: Option<Fees>,
+    pub contract_multiplier: Option<f64>,
+    pub contract_type: Option<ContractType>,
+    pub tag: Option<String>,
 }
 
 impl FillEventBuilder {
@@ -139,6 +200,27 @@ impl FillEventBuilder {
         }
     }
 
+    pub fn contract_multiplier(self, value: f64) -> Self {
+        Self {
+            contract_multiplier: Some(value),
+            ..self
+        }
+    }
+
+    pub fn contract_type(self, value: ContractType) -> Self {
+        Self {
+            contract_type: Some(value),
+            ..self
+        }
+    }
+
+    pub fn tag(self, value: String) -> Self {
+        Self {
+            tag: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<FillEvent, ExecutionError> {
         Ok(FillEvent {
             time: self.time.ok_or(ExecutionError::BuilderIncomplete("time"))?,
@@ -161,6 +243,11 @@ impl FillEventBuilder {
                 .fill_value_gross
                 .ok_or(ExecutionError::BuilderIncomplete("fill_value_gross"))?,
             fees: self.fees.ok_or(ExecutionError::BuilderIncomplete("fees"))?,
+            contract_multiplier: self
+                .contract_multiplier
+                .unwrap_or_else(default_contract_multiplier),
+            contract_type: self.contract_type.unwrap_or_default(),
+            tag: self.tag,
         })
     }
 }
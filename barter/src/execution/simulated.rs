@@ -1,23 +1,381 @@
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    execution::{error::ExecutionError, ExecutionClient, Fees, FillEvent},
-    portfolio::OrderEvent,
+    data::MarketMeta,
+    execution::{error::ExecutionError, slippage::SlippageModel, ExecutionClient, Fees, FillEvent},
+    portfolio::{OrderEvent, OrderType},
+    statistic::{de_duration_from_secs, se_duration_as_secs},
 };
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::{instrument::Instrument, Market, MarketId};
+use std::collections::{HashMap, VecDeque};
+use tracing::{info, warn};
 
 /// Configuration for constructing a [`SimulatedExecution`] via the new() constructor method.
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
 pub struct Config {
     /// Simulated fee percentage to be used for each [`Fees`] field in decimal form (eg/ 0.01 for 1%)
     pub simulated_fees_pct: Fees,
+    /// Per-[`Market`] overrides of the `simulated_fees_pct`. [`Market`]s without an override use
+    /// the global `simulated_fees_pct`.
+    #[serde(default)]
+    pub fee_overrides: Vec<FeeOverride>,
+    /// Model used to fill working [`OrderType::Limit`] orders.
+    #[serde(default)]
+    pub limit_fill_model: LimitFillModel,
+    /// Price marketable [`OrderType::Limit`] orders are filled at.
+    #[serde(default)]
+    pub limit_fill_price: LimitFillPrice,
+    /// Number of [`MarketEvent`] bars a working [`OrderType::Limit`] order is evaluated against
+    /// before it expires unfilled. `None` keeps working orders until they fill (GTC).
+    #[serde(default)]
+    pub expiry_bars: Option<u64>,
+    /// Bounds the modelled slippage is clamped to, so a single extreme bar cannot dominate.
+    #[serde(default)]
+    pub slippage_clamp: SlippageClamp,
+    /// Limit on the quantity of entry orders relative to the recent average bar volume of their
+    /// [`Market`]. `None` does not limit order quantities.
+    #[serde(default)]
+    pub volume_limit: Option<VolumeLimit>,
+    /// Delay between an [`OrderEvent`] being submitted & it being executed. Defaults to no delay.
+    #[serde(default)]
+    pub execution_delay: ExecutionDelay,
+    /// Liquidity cap splitting large market orders across the fills of multiple bars. `None`
+    /// fills market orders in full at a single price.
+    #[serde(default)]
+    pub partial_fill: Option<PartialFill>,
+}
+
+/// Caps the quantity of a market [`OrderEvent`] filled against each bar to a fraction of the bar
+/// volume. Orders exceeding the cap are split across multiple [`FillEvent`]s, with the remaining
+/// quantity filled against subsequent bars of their [`Market`] at successively worse prices,
+/// modelling a large order walking the book.
+///
+/// Order quantities & bar volumes are assumed to be in the same units. If the volume of a bar is
+/// unknown, the remaining quantity is filled in full against it.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct PartialFill {
+    /// Maximum quantity filled per bar as a fraction of the bar volume in decimal form (eg/ 0.1
+    /// for 10%).
+    pub max_fill_ratio: f64,
+    /// Price concession of each subsequent fill of an order in basis points of the bar close
+    /// (eg/ 5.0 fills the second tranche of a buy order 0.05% above the close, the third 0.1%
+    /// above, etc).
+    pub price_step_bps: f64,
+}
+
+/// Market [`OrderEvent`] held by a [`SimulatedExecution`] whilst it is partially filled across
+/// bars due to a [`PartialFill`] liquidity cap.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct PartialOrder {
+    pub order: OrderEvent,
+    /// Absolute quantity still to be filled.
+    pub remaining_quantity: f64,
+    /// Number of [`FillEvent`]s generated for the order so far.
+    pub fill_count: u64,
+}
+
+impl PartialOrder {
+    /// Determines if the partial order is fully filled.
+    fn is_filled(&self) -> bool {
+        self.remaining_quantity <= 0.0
+    }
+}
+
+/// Delay between an [`OrderEvent`] being submitted to a [`SimulatedExecution`] & it being
+/// executed, modelling the latency of a live trading system. Without a delay, an order generated
+/// from a bar is executed at the close of that same bar, which flatters backtest results.
+#[derive(Copy, Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum ExecutionDelay {
+    /// Execute orders immediately at the [`MarketMeta`] they were generated from.
+    #[default]
+    None,
+    /// Execute orders against the Nth subsequent bar of their [`Market`] (ie/ an order generated
+    /// on bar T executes against bar T+N).
+    Bars(u64),
+    /// Execute orders against the first subsequent bar of their [`Market`] at least this long
+    /// (in seconds) after the time of the [`MarketMeta`] they were generated from.
+    Duration(
+        #[serde(
+            deserialize_with = "de_duration_from_secs",
+            serialize_with = "se_duration_as_secs"
+        )]
+        Duration,
+    ),
+}
+
+impl ExecutionDelay {
+    /// Determines if the delay is zero, in which case orders are executed immediately.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::None => true,
+            Self::Bars(bars) => *bars == 0,
+            Self::Duration(duration) => *duration <= Duration::zero(),
+        }
+    }
 }
 
+/// [`OrderEvent`] held by a [`SimulatedExecution`] until it's [`ExecutionDelay`] has elapsed.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct DelayedOrder {
+    pub order: OrderEvent,
+    /// Number of subsequent [`MarketEvent`] bars of the order's [`Market`] still to elapse.
+    pub bars_remaining: u64,
+    /// Earliest [`MarketEvent`] exchange time the order can be executed at.
+    pub eligible_time: DateTime<Utc>,
+}
+
+/// Limits the quantity of entry [`OrderEvent`]s to a fraction of the average volume of the
+/// trailing `window_bars` bars of their [`Market`], scaling down any order that exceeds it. This
+/// keeps backtests honest by not assuming orders can be filled regardless of liquidity.
+///
+/// The limit is not applied until a [`Market`] has `window_bars` bars of volume history, or if
+/// that history has no volume. Order quantities & bar volumes are assumed to be in the same
+/// units. The excess quantity of a scaled order is discarded rather than carried over, so exit
+/// orders are never scaled, since that would leave part of the Position open with no order
+/// working to close it. To model orders, including exits, that fill across bars due to limited
+/// liquidity, configure a [`PartialFill`] instead.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct VolumeLimit {
+    /// Maximum order quantity as a fraction of the average bar volume in decimal form (eg/ 0.1
+    /// for 10%).
+    pub max_volume_pct: f64,
+    /// Number of trailing bars the average bar volume is calculated over.
+    pub window_bars: usize,
+}
+
+/// Volume of the trailing bars of a [`Market`], used to apply a [`VolumeLimit`].
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+struct RollingVolume {
+    volumes: VecDeque<f64>,
+}
+
+impl RollingVolume {
+    /// Update with the volume of the next bar, discarding bars outside of the window.
+    fn update(&mut self, volume: f64, window_bars: usize) {
+        self.volumes.push_back(volume);
+        while self.volumes.len() > window_bars {
+            self.volumes.pop_front();
+        }
+    }
+
+    /// Average bar volume of the window, or `None` if there are fewer than `window_bars` bars.
+    fn average(&self, window_bars: usize) -> Option<f64> {
+        (window_bars > 0 && self.volumes.len() >= window_bars)
+            .then(|| self.volumes.iter().sum::<f64>() / self.volumes.len() as f64)
+    }
+}
+
+/// Floor & ceiling the proportional slippage modelled by [`SimulatedExecution`] is clamped
+/// between. If the floor exceeds the ceiling, the ceiling takes precedence.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct SlippageClamp {
+    pub floor: Option<SlippageLimit>,
+    pub ceiling: Option<SlippageLimit>,
+}
+
+impl SlippageClamp {
+    /// Clamp the slippage of a fill with the provided gross fill value.
+    pub fn clamp(&self, slippage: f64, fill_value_gross: f64) -> f64 {
+        let slippage = match self.floor {
+            Some(floor) => slippage.max(floor.amount(fill_value_gross)),
+            None => slippage,
+        };
+
+        match self.ceiling {
+            Some(ceiling) => slippage.min(ceiling.amount(fill_value_gross)),
+            None => slippage,
+        }
+    }
+}
+
+/// Slippage limit used by a [`SlippageClamp`].
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub enum SlippageLimit {
+    /// Basis points of the gross fill value (eg/ 5.0 for 0.05%).
+    Bps(f64),
+    /// Absolute amount in the quote currency.
+    Absolute(f64),
+}
+
+impl SlippageLimit {
+    /// Slippage amount of this limit for a fill with the provided gross fill value.
+    pub fn amount(&self, fill_value_gross: f64) -> f64 {
+        match self {
+            Self::Bps(bps) => fill_value_gross * bps / 10_000.0,
+            Self::Absolute(amount) => *amount,
+        }
+    }
+}
+
+/// Price at which [`SimulatedExecution`] fills a marketable [`OrderType::Limit`] order (ie/ a buy
+/// limit at or above the market price, or a sell limit at or below it).
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum LimitFillPrice {
+    /// Always fill at the limit price.
+    #[default]
+    Limit,
+    /// Fill at the market price if it is better than the limit price, since a marketable limit
+    /// order crossing the spread takes the best available price up to its limit.
+    PriceImprovement,
+}
+
+/// Simulated fee percentages of a [`Market`], overriding the global
+/// [`Config::simulated_fees_pct`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct FeeOverride {
+    pub market: Market,
+    /// Simulated fee percentage to be used for each [`Fees`] field in decimal form (eg/ 0.01 for 1%)
+    pub simulated_fees_pct: Fees,
+}
+
+/// Model used by [`SimulatedExecution`] to determine when a working [`OrderType::Limit`] order
+/// fills against subsequent [`MarketEvent`]s.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub enum LimitFillModel {
+    /// Fill the entire order as soon as a bar's price range touches the limit price.
+    #[default]
+    Touch,
+    /// Fill the entire order once a bar's price range trades through the limit price (ie/ strictly
+    /// below a buy limit, or strictly above a sell limit). More conservative than
+    /// [`LimitFillModel::Touch`], since trades exactly at the limit price may not have reached
+    /// the order in the queue.
+    TradeThrough,
+    /// Model the order's queue priority at the limit price. See [`QueueModel`].
+    Queue(QueueModel),
+}
+
+/// Queue priority model for working [`OrderType::Limit`] orders. An order only fills once the
+/// cumulative volume traded at (or through) its limit price exceeds the volume queued ahead of
+/// it. Volume traded beyond the queue ahead fills the order proportionally across bars.
+///
+/// Bars do not provide volume-at-price, so it is approximated as a fraction of the volume of each
+/// bar that touches the limit price.
+///
+/// The quantity filled across bars is tracked in [`WorkingOrder::filled_quantity`], & yielded as a
+/// single [`FillEvent`] at the limit price once the order is fully filled. If the order is
+/// replaced or expires first, the quantity filled so far is yielded as a [`FillEvent`] (via
+/// [`ExecutionClient::take_replaced_fills`] or [`ExecutionClient::update_from_market`]
+/// respectively), & only the unfilled remainder is expired.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct QueueModel {
+    /// Volume assumed queued ahead of the order at its limit price when it is placed.
+    pub queue_ahead: f64,
+    /// Fraction of a bar's volume assumed traded at (or through) a touched limit price, in decimal
+    /// form (eg/ 0.1 for 10%).
+    pub volume_at_price_pct: f64,
+}
+
+/// [`OrderType::Limit`] order held by a [`SimulatedExecution`] until it fills.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct WorkingOrder {
+    pub order: OrderEvent,
+    pub limit_price: f64,
+    /// Remaining volume queued ahead of the order at its limit price.
+    pub queue_ahead: f64,
+    /// Absolute quantity filled so far.
+    pub filled_quantity: f64,
+    /// Number of [`MarketEvent`] bars the order has been evaluated against without filling.
+    pub bars_elapsed: u64,
+}
+
+impl WorkingOrder {
+    /// Determines if the working order is a buy (+ve quantity) rather than a sell.
+    fn is_buy(&self) -> bool {
+        self.order.quantity.is_sign_positive()
+    }
+
+    /// Determines if the working order is fully filled.
+    fn is_filled(&self) -> bool {
+        self.filled_quantity >= self.order.quantity.abs()
+    }
+}
+
+/// Price range & volume of a [`MarketEvent`] used to evaluate working orders.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+struct Bar {
+    low: f64,
+    high: f64,
+    close: f64,
+    volume: f64,
+}
+
+impl Bar {
+    /// Extract the [`Bar`] from a [`MarketEvent`] Candle or Trade.
+    fn from_market(market: &MarketEvent<Instrument, DataKind>) -> Option<Self> {
+        match &market.kind {
+            DataKind::Candle(candle) => Some(Self {
+                low: candle.low,
+                high: candle.high,
+                close: candle.close,
+                volume: candle.volume,
+            }),
+            DataKind::Trade(trade) => Some(Self {
+                low: trade.price,
+                high: trade.price,
+                close: trade.price,
+                volume: trade.amount,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 /// Simulated execution handler that executes [`OrderEvent`]s to generate [`FillEvent`]s via a
 /// simulated broker interaction.
+///
+/// [`OrderType::Limit`] orders submitted via [`ExecutionClient::submit_order`] that are not
+/// marketable are held as [`WorkingOrder`]s, and filled against subsequent [`MarketEvent`]s
+/// according to the configured [`LimitFillModel`]. A new order for a market replaces any
+/// existing working order for that market. If configured, working orders that remain unfilled
+/// after `expiry_bars` bars are cancelled. Both replaced & cancelled working orders are yielded
+/// via [`ExecutionClient::take_expired_orders`], since they will never fill. If a replaced
+/// working order has partially filled, the filled quantity is yielded via
+/// [`ExecutionClient::take_replaced_fills`] & only the unfilled remainder is expired. If configured, the
+/// quantity of submitted entry orders is limited by a [`VolumeLimit`], & the quantity scaled
+/// away is also yielded as expired.
+///
+/// If an [`ExecutionDelay`] is configured, submitted orders are held as [`DelayedOrder`]s until
+/// the delay has elapsed, and are then executed against the price of the bar that released them
+/// (eg/ market orders fill at it's close, and limit orders become working orders unless they are
+/// marketable at it's close). Fills of delayed orders are yielded via
+/// [`ExecutionClient::update_from_market`], & delayed orders that fail to execute are yielded as
+/// expired.
+///
+/// If a [`PartialFill`] liquidity cap is configured, market orders exceeding it are held as
+/// [`PartialOrder`]s, and filled across subsequent [`MarketEvent`]s. A new order for a market
+/// also replaces the unfilled remainder of any partial order for that market, which is yielded
+/// as expired with the remaining quantity.
+///
+/// The slippage of each fill is the constant `simulated_fees_pct.slippage` of it's [`Market`],
+/// unless a [`SlippageModel`] is injected via [`SimulatedExecution::with_slippage_model`]. In
+/// either case the slippage is clamped by the configured [`SlippageClamp`]. The injected
+/// [`SlippageModel`] is provided the spread of the latest
+/// [`OrderBookL1`](barter_data::subscription::book::OrderBookL1) consumed for the [`Market`], if
+/// the [`MarketMeta`] of the fill has none.
 pub struct SimulatedExecution {
     fees_pct: Fees,
+    fee_overrides: Vec<FeeOverride>,
+    limit_fill_model: LimitFillModel,
+    limit_fill_price: LimitFillPrice,
+    expiry_bars: Option<u64>,
+    slippage_clamp: SlippageClamp,
+    #[serde(skip)]
+    slippage_model: Option<Box<dyn SlippageModel + Send>>,
+    volume_limit: Option<VolumeLimit>,
+    execution_delay: ExecutionDelay,
+    partial_fill: Option<PartialFill>,
+    rolling_volumes: HashMap<MarketId, RollingVolume>,
+    quoted_spreads: HashMap<MarketId, f64>,
+    delayed_orders: Vec<DelayedOrder>,
+    partial_orders: Vec<PartialOrder>,
+    working_orders: Vec<WorkingOrder>,
+    expired_orders: Vec<OrderEvent>,
+    replaced_fills: Vec<FillEvent>,
 }
 
 impl ExecutionClient for SimulatedExecution {
@@ -33,9 +391,185 @@ impl ExecutionClient for SimulatedExecution {
             decision: order.decision,
             quantity: order.quantity,
             fill_value_gross,
-            fees: self.calculate_fees(&fill_value_gross),
+            fees: self.calculate_fees(order, &fill_value_gross),
+            contract_multiplier: order.contract_multiplier,
+            contract_type: order.contract_type,
+            tag: order.tag.clone(),
         })
     }
+
+    fn submit_order(&mut self, order: &OrderEvent) -> Result<Option<FillEvent>, ExecutionError> {
+        // Hold the order until it's ExecutionDelay has elapsed
+        let (bars_remaining, eligible_time) = match self.execution_delay {
+            delay if delay.is_zero() => return self.execute_order(order),
+            ExecutionDelay::Duration(delay) => (0, order.market_meta.time + delay),
+            ExecutionDelay::Bars(bars) => (bars, order.market_meta.time),
+            ExecutionDelay::None => (0, order.market_meta.time),
+        };
+
+        // Validate upfront, rather than failing once the delay has elapsed
+        if order.order_type == OrderType::Limit && order.limit_price.is_none() {
+            return Err(ExecutionError::LimitPriceMissing);
+        }
+
+        self.delayed_orders.push(DelayedOrder {
+            order: order.clone(),
+            bars_remaining,
+            eligible_time,
+        });
+
+        Ok(None)
+    }
+
+    fn update_from_market(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<FillEvent>, ExecutionError> {
+        // Track the latest quoted spread of the market provided to the SlippageModel
+        if let DataKind::OrderBookL1(book_l1) = &market.kind {
+            self.quoted_spreads.insert(
+                MarketId::new(&market.exchange, &market.instrument),
+                book_l1.best_ask.price - book_l1.best_bid.price,
+            );
+        }
+
+        let bar = match Bar::from_market(market) {
+            Some(bar) => bar,
+            None => return Ok(Vec::new()),
+        };
+
+        // Track the rolling volume of the market used to apply the VolumeLimit
+        if let Some(volume_limit) = self.volume_limit {
+            self.rolling_volumes
+                .entry(MarketId::new(&market.exchange, &market.instrument))
+                .or_default()
+                .update(bar.volume, volume_limit.window_bars);
+        }
+
+        let market_meta = MarketMeta {
+            close: bar.close,
+            time: market.exchange_time,
+            volatility: None,
+            volume: Some(bar.volume),
+            spread: None,
+        };
+
+        let mut fills = Vec::new();
+        let mut expired_orders = Vec::new();
+        let mut working_orders = std::mem::take(&mut self.working_orders);
+
+        working_orders.retain_mut(|working| {
+            if working.order.exchange != market.exchange
+                || working.order.instrument != market.instrument
+            {
+                return true;
+            }
+
+            if self.evaluate_working_order(working, &bar) {
+                fills.push(self.generate_fill_at(&working.order, working.limit_price, market_meta));
+                return false;
+            }
+
+            // Cancel the working order if it is still not fully filled after expiry_bars bars,
+            // filling the quantity already filled & expiring the unfilled remainder
+            working.bars_elapsed += 1;
+            match self.expiry_bars {
+                Some(expiry_bars) if working.bars_elapsed >= expiry_bars => {
+                    info!(
+                        exchange = %working.order.exchange,
+                        instrument = %working.order.instrument,
+                        limit_price = working.limit_price,
+                        filled_quantity = working.filled_quantity,
+                        expiry_bars,
+                        "SimulatedExecution expired unfilled remainder of working limit order"
+                    );
+                    let (fill, remainder) = self.split_working_order(working, market_meta);
+                    fills.extend(fill);
+                    expired_orders.push(remainder);
+                    false
+                }
+                _ => true,
+            }
+        });
+
+        self.working_orders = working_orders;
+        self.expired_orders.extend(expired_orders);
+
+        // Fill the next tranche of the market's partial orders against this bar
+        if let Some(partial_fill) = self.partial_fill {
+            let mut partial_orders = std::mem::take(&mut self.partial_orders);
+            partial_orders.retain_mut(|partial| {
+                if partial.order.exchange != market.exchange
+                    || partial.order.instrument != market.instrument
+                {
+                    return true;
+                }
+
+                fills.extend(self.fill_tranche(partial, partial_fill, market_meta));
+                !partial.is_filled()
+            });
+            self.partial_orders = partial_orders;
+        }
+
+        // Release delayed orders of the market whose ExecutionDelay has elapsed with this bar
+        let mut released = Vec::new();
+        self.delayed_orders.retain_mut(|delayed| {
+            if delayed.order.exchange != market.exchange
+                || delayed.order.instrument != market.instrument
+            {
+                return true;
+            }
+
+            delayed.bars_remaining = delayed.bars_remaining.saturating_sub(1);
+            if delayed.bars_remaining > 0 || market.exchange_time < delayed.eligible_time {
+                return true;
+            }
+
+            released.push(delayed.order.clone());
+            false
+        });
+
+        // Execute released orders against the price of this bar. Rejected orders will never
+        // fill, so are expired without discarding the fills of the other orders
+        for mut order in released {
+            order.market_meta = MarketMeta {
+                volatility: order.market_meta.volatility,
+                ..market_meta
+            };
+
+            let executed = self.execute_order(&order);
+            fills.append(&mut self.replaced_fills);
+            match executed {
+                Ok(fill) => fills.extend(fill),
+                Err(error) => {
+                    warn!(
+                        exchange = %order.exchange,
+                        instrument = %order.instrument,
+                        ?error,
+                        action = "handling rejected delayed order as expired",
+                        "SimulatedExecution failed to execute delayed order"
+                    );
+                    self.expired_orders.push(order);
+                }
+            }
+        }
+
+        Ok(fills)
+    }
+
+    fn take_expired_orders(&mut self) -> Vec<OrderEvent> {
+        std::mem::take(&mut self.expired_orders)
+    }
+
+    fn take_replaced_fills(&mut self) -> Vec<FillEvent> {
+        std::mem::take(&mut self.replaced_fills)
+    }
+}
+
+impl From<Config> for SimulatedExecution {
+    fn from(cfg: Config) -> Self {
+        Self::new(cfg)
+    }
 }
 
 impl SimulatedExecution {
@@ -43,28 +577,409 @@ impl SimulatedExecution {
     pub fn new(cfg: Config) -> Self {
         Self {
             fees_pct: cfg.simulated_fees_pct,
+            fee_overrides: cfg.fee_overrides,
+            limit_fill_model: cfg.limit_fill_model,
+            limit_fill_price: cfg.limit_fill_price,
+            expiry_bars: cfg.expiry_bars,
+            slippage_clamp: cfg.slippage_clamp,
+            slippage_model: None,
+            volume_limit: cfg.volume_limit,
+            execution_delay: cfg.execution_delay,
+            partial_fill: cfg.partial_fill,
+            rolling_volumes: HashMap::new(),
+            quoted_spreads: HashMap::new(),
+            delayed_orders: Vec::new(),
+            partial_orders: Vec::new(),
+            working_orders: Vec::new(),
+            expired_orders: Vec::new(),
+            replaced_fills: Vec::new(),
+        }
+    }
+
+    /// Use the provided [`SlippageModel`] to calculate the slippage of each fill, rather than the
+    /// constant `simulated_fees_pct.slippage`.
+    pub fn with_slippage_model<Slippage>(self, slippage_model: Slippage) -> Self
+    where
+        Slippage: SlippageModel + Send + 'static,
+    {
+        Self {
+            slippage_model: Some(Box::new(slippage_model)),
+            ..self
+        }
+    }
+
+    /// Working [`OrderType::Limit`] orders that have not yet filled.
+    pub fn working_orders(&self) -> &[WorkingOrder] {
+        &self.working_orders
+    }
+
+    /// Submitted orders that are waiting for their [`ExecutionDelay`] to elapse.
+    pub fn delayed_orders(&self) -> &[DelayedOrder] {
+        &self.delayed_orders
+    }
+
+    /// Market orders that have been partially filled due to the [`PartialFill`] liquidity cap.
+    pub fn partial_orders(&self) -> &[PartialOrder] {
+        &self.partial_orders
+    }
+
+    /// Execute an [`OrderEvent`] at it's [`MarketMeta`]. Market orders are filled immediately,
+    /// as are marketable [`OrderType::Limit`] orders. Other limit orders are held as
+    /// [`WorkingOrder`]s until a subsequent [`MarketEvent`] fills them.
+    fn execute_order(&mut self, order: &OrderEvent) -> Result<Option<FillEvent>, ExecutionError> {
+        // Scale down entry orders that exceed the VolumeLimit
+        let order = &self.apply_volume_limit(order);
+
+        // Replace any existing working or partial order for the same market, filling the quantity
+        // already filled of working orders & reporting the unfilled remainder of both as expired
+        // since it will never fill
+        let mut working_orders = std::mem::take(&mut self.working_orders);
+        working_orders.retain(|working| {
+            if working.order.exchange != order.exchange
+                || working.order.instrument != order.instrument
+            {
+                return true;
+            }
+
+            info!(
+                exchange = %working.order.exchange,
+                instrument = %working.order.instrument,
+                limit_price = working.limit_price,
+                filled_quantity = working.filled_quantity,
+                "SimulatedExecution replaced unfilled working limit order"
+            );
+            let (fill, remainder) = self.split_working_order(working, order.market_meta);
+            self.replaced_fills.extend(fill);
+            self.expired_orders.push(remainder);
+            false
+        });
+        self.working_orders = working_orders;
+
+        let expired_orders = &mut self.expired_orders;
+        self.partial_orders.retain(|partial| {
+            if partial.order.exchange != order.exchange
+                || partial.order.instrument != order.instrument
+            {
+                return true;
+            }
+
+            info!(
+                exchange = %partial.order.exchange,
+                instrument = %partial.order.instrument,
+                remaining_quantity = partial.remaining_quantity,
+                "SimulatedExecution replaced unfilled remainder of partial order"
+            );
+            let mut remainder = partial.order.clone();
+            remainder.quantity = partial.remaining_quantity.copysign(partial.order.quantity);
+            expired_orders.push(remainder);
+            false
+        });
+
+        if order.order_type != OrderType::Limit {
+            let partial_fill = match self.partial_fill {
+                Some(partial_fill) => partial_fill,
+                None => return self.generate_fill(order).map(Some),
+            };
+
+            // Fill up to the liquidity cap of the current bar, holding any remaining quantity
+            let mut partial = PartialOrder {
+                order: order.clone(),
+                remaining_quantity: order.quantity.abs(),
+                fill_count: 0,
+            };
+            let fill = self.fill_tranche(&mut partial, partial_fill, order.market_meta);
+
+            if !partial.is_filled() {
+                info!(
+                    exchange = %order.exchange,
+                    instrument = %order.instrument,
+                    quantity = order.quantity,
+                    remaining_quantity = partial.remaining_quantity,
+                    "SimulatedExecution partially filled market order"
+                );
+                self.partial_orders.push(partial);
+            }
+
+            return Ok(fill);
+        }
+
+        let limit_price = order.limit_price.ok_or(ExecutionError::LimitPriceMissing)?;
+
+        // Marketable limit orders take liquidity, so fill immediately
+        let marketable = match order.quantity.is_sign_positive() {
+            true => limit_price >= order.market_meta.close,
+            false => limit_price <= order.market_meta.close,
+        };
+        if marketable {
+            let fill_price = match self.limit_fill_price {
+                LimitFillPrice::Limit => limit_price,
+                LimitFillPrice::PriceImprovement => order.market_meta.close,
+            };
+
+            return Ok(Some(self.generate_fill_at(
+                order,
+                fill_price,
+                order.market_meta,
+            )));
+        }
+
+        // Otherwise hold as a working order until a subsequent MarketEvent fills it
+        let queue_ahead = match self.limit_fill_model {
+            LimitFillModel::Touch | LimitFillModel::TradeThrough => 0.0,
+            LimitFillModel::Queue(queue) => queue.queue_ahead,
+        };
+
+        info!(
+            exchange = %order.exchange,
+            instrument = %order.instrument,
+            limit_price,
+            "SimulatedExecution holding working limit order"
+        );
+
+        self.working_orders.push(WorkingOrder {
+            order: order.clone(),
+            limit_price,
+            queue_ahead,
+            filled_quantity: 0.0,
+            bars_elapsed: 0,
+        });
+
+        Ok(None)
+    }
+
+    /// Scale down the quantity of an entry [`OrderEvent`] to the configured [`VolumeLimit`], if
+    /// it's [`Market`] has enough volume history & the quantity exceeds the limit. The quantity
+    /// scaled away is reported as expired, since it will never fill.
+    fn apply_volume_limit(&mut self, order: &OrderEvent) -> OrderEvent {
+        let mut order = order.clone();
+
+        let volume_limit = match self.volume_limit {
+            Some(volume_limit) if order.decision.is_entry() => volume_limit,
+            _ => return order,
+        };
+
+        let average_volume = match self
+            .rolling_volumes
+            .get(&MarketId::new(&order.exchange, &order.instrument))
+            .and_then(|rolling| rolling.average(volume_limit.window_bars))
+        {
+            Some(average_volume) if average_volume > 0.0 => average_volume,
+            _ => return order,
+        };
+
+        let max_quantity = average_volume * volume_limit.max_volume_pct;
+        if order.quantity.abs() > max_quantity {
+            info!(
+                exchange = %order.exchange,
+                instrument = %order.instrument,
+                quantity = order.quantity,
+                max_quantity,
+                "SimulatedExecution scaled down order quantity to volume limit"
+            );
+            let mut scaled_away = order.clone();
+            scaled_away.quantity = (order.quantity.abs() - max_quantity).copysign(order.quantity);
+            self.expired_orders.push(scaled_away);
+            order.quantity = max_quantity.copysign(order.quantity);
+        }
+
+        order
+    }
+
+    /// Fill the next tranche of a [`PartialOrder`] against a bar with the provided [`MarketMeta`],
+    /// capped by the [`PartialFill`] liquidity of the bar & priced at the bar close worsened by
+    /// the price step of each previous fill. Returns `None` if the bar has no liquidity.
+    fn fill_tranche(
+        &self,
+        partial: &mut PartialOrder,
+        partial_fill: PartialFill,
+        market_meta: MarketMeta,
+    ) -> Option<FillEvent> {
+        let capacity = match market_meta.volume {
+            Some(volume) => volume.max(0.0) * partial_fill.max_fill_ratio,
+            None => f64::INFINITY,
+        };
+
+        let quantity = partial.remaining_quantity.min(capacity);
+        if quantity <= 0.0 {
+            return None;
+        }
+
+        // Buy orders fill at successively higher prices, & sell orders at successively lower
+        let concession = partial.fill_count as f64 * partial_fill.price_step_bps / 10_000.0;
+        let fill_price = market_meta.close * (1.0 + concession.copysign(partial.order.quantity));
+
+        let mut tranche = partial.order.clone();
+        tranche.quantity = quantity.copysign(partial.order.quantity);
+        partial.remaining_quantity -= quantity;
+        partial.fill_count += 1;
+
+        Some(self.generate_fill_at(&tranche, fill_price, market_meta))
+    }
+
+    /// Evaluate a [`WorkingOrder`] against the next [`Bar`] using the configured
+    /// [`LimitFillModel`], returning true if the order is now fully filled.
+    fn evaluate_working_order(&self, working: &mut WorkingOrder, bar: &Bar) -> bool {
+        let touched = match (self.limit_fill_model, working.is_buy()) {
+            (LimitFillModel::TradeThrough, true) => bar.low < working.limit_price,
+            (LimitFillModel::TradeThrough, false) => bar.high > working.limit_price,
+            (_, true) => bar.low <= working.limit_price,
+            (_, false) => bar.high >= working.limit_price,
+        };
+
+        if !touched {
+            return false;
+        }
+
+        match self.limit_fill_model {
+            LimitFillModel::Touch | LimitFillModel::TradeThrough => {
+                working.filled_quantity = working.order.quantity.abs();
+            }
+            LimitFillModel::Queue(queue) => {
+                // Volume traded at the limit price first consumes the queue ahead of the order
+                let traded_at_price = bar.volume * queue.volume_at_price_pct;
+                let available = (traded_at_price - working.queue_ahead).max(0.0);
+                working.queue_ahead = (working.queue_ahead - traded_at_price).max(0.0);
+
+                let remaining = working.order.quantity.abs() - working.filled_quantity;
+                working.filled_quantity += available.min(remaining);
+            }
+        }
+
+        working.is_filled()
+    }
+
+    /// Split a [`WorkingOrder`] that will never fill in full into a [`FillEvent`] for the quantity
+    /// already filled at it's limit price (if any), & an [`OrderEvent`] for the unfilled remainder.
+    fn split_working_order(
+        &self,
+        working: &WorkingOrder,
+        market_meta: MarketMeta,
+    ) -> (Option<FillEvent>, OrderEvent) {
+        let mut remainder = working.order.clone();
+        remainder.quantity = (working.order.quantity.abs() - working.filled_quantity)
+            .copysign(working.order.quantity);
+
+        if working.filled_quantity <= 0.0 {
+            return (None, remainder);
+        }
+
+        let mut filled = working.order.clone();
+        filled.quantity = working.filled_quantity.copysign(working.order.quantity);
+
+        (
+            Some(self.generate_fill_at(&filled, working.limit_price, market_meta)),
+            remainder,
+        )
+    }
+
+    /// Generate a [`FillEvent`] for an [`OrderEvent`] filled at the provided price.
+    fn generate_fill_at(
+        &self,
+        order: &OrderEvent,
+        fill_price: f64,
+        market_meta: MarketMeta,
+    ) -> FillEvent {
+        let fill_value_gross =
+            order
+                .contract_type
+                .notional(order.quantity, fill_price, order.contract_multiplier);
+
+        FillEvent {
+            time: Utc::now(),
+            exchange: order.exchange.clone(),
+            instrument: order.instrument.clone(),
+            market_meta,
+            decision: order.decision,
+            quantity: order.quantity,
+            fill_value_gross,
+            fees: self.calculate_fees_at(order, &market_meta, &fill_value_gross),
+            contract_multiplier: order.contract_multiplier,
+            contract_type: order.contract_type,
+            tag: order.tag.clone(),
         }
     }
 
     /// Calculates the simulated gross fill value (excluding TotalFees) based on the input [`OrderEvent`].
     fn calculate_fill_value_gross(order: &OrderEvent) -> f64 {
-        order.quantity.abs() * order.market_meta.close
+        order.contract_type.notional(
+            order.quantity,
+            order.market_meta.close,
+            order.contract_multiplier,
+        )
     }
 
     /// Calculates the simulated [`Fees`] a [`FillEvent`] will incur, based on the input [`OrderEvent`].
-    fn calculate_fees(&self, fill_value_gross: &f64) -> Fees {
+    fn calculate_fees(&self, order: &OrderEvent, fill_value_gross: &f64) -> Fees {
+        self.calculate_fees_at(order, &order.market_meta, fill_value_gross)
+    }
+
+    /// Calculates the simulated [`Fees`] a [`FillEvent`] of the input [`OrderEvent`] will incur
+    /// when filled at the provided [`MarketMeta`].
+    fn calculate_fees_at(
+        &self,
+        order: &OrderEvent,
+        market_meta: &MarketMeta,
+        fill_value_gross: &f64,
+    ) -> Fees {
+        let fees_pct = self.fees_pct(order);
+        let slippage_pct = match &self.slippage_model {
+            Some(slippage_model) => {
+                slippage_model.apply(order, &self.with_quoted_spread(order, market_meta))
+            }
+            None => fees_pct.slippage,
+        };
+
         Fees {
-            exchange: self.fees_pct.exchange * fill_value_gross,
-            slippage: self.fees_pct.slippage * fill_value_gross,
-            network: self.fees_pct.network * fill_value_gross,
+            exchange: fees_pct.exchange * fill_value_gross,
+            slippage: self
+                .slippage_clamp
+                .clamp(slippage_pct * fill_value_gross, *fill_value_gross),
+            network: fees_pct.network * fill_value_gross,
         }
     }
+
+    /// Provides the latest quoted spread of the [`OrderEvent`]'s [`Market`] in the [`MarketMeta`],
+    /// if it does not already have one.
+    fn with_quoted_spread(&self, order: &OrderEvent, market_meta: &MarketMeta) -> MarketMeta {
+        MarketMeta {
+            spread: market_meta.spread.or_else(|| {
+                self.quoted_spreads
+                    .get(&MarketId::new(&order.exchange, &order.instrument))
+                    .copied()
+            }),
+            ..*market_meta
+        }
+    }
+
+    /// Simulated fee percentages of the [`OrderEvent`]'s [`Market`], falling back to the global
+    /// fee percentages if the [`Market`] has no [`FeeOverride`].
+    fn fees_pct(&self, order: &OrderEvent) -> Fees {
+        self.fee_overrides
+            .iter()
+            .find(|fee_override| {
+                fee_override.market.exchange == order.exchange
+                    && fee_override.market.instrument == order.instrument
+            })
+            .map_or(self.fees_pct, |fee_override| {
+                fee_override.simulated_fees_pct
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::order_event;
+    use crate::{
+        execution::slippage::{SpreadBased, VolumeProportional},
+        portfolio::ContractType,
+        strategy::Decision,
+        test_util::{market_event_candle, order_event},
+    };
+    use barter_data::subscription::{
+        book::{Level, OrderBookL1},
+        candle::Candle,
+    };
+    use barter_integration::model::instrument::kind::InstrumentKind;
 
     #[test]
     fn should_generate_ok_fill_event_with_valid_order_event_provided() {
@@ -74,6 +989,7 @@ mod tests {
                 slippage: 0.05,
                 network: 0.0,
             },
+            ..Default::default()
         });
 
         let mut input_order = order_event();
@@ -121,6 +1037,41 @@ mod tests {
         assert_eq!(actual, expected)
     }
 
+    #[test]
+    fn should_calculate_fill_value_gross_correctly_with_contract_multiplier_provided() {
+        let mut input_order = order_event();
+        input_order.quantity = 2.0;
+        input_order.market_meta.close = 10.0;
+        input_order.contract_multiplier = 50.0;
+
+        let actual = SimulatedExecution::calculate_fill_value_gross(&input_order);
+
+        let expected = 2.0 * 10.0 * 50.0;
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn should_calculate_fill_value_gross_of_inverse_contracts_in_base_asset() {
+        let mut input_order = order_event();
+        input_order.quantity = -2_000.0;
+        input_order.market_meta.close = 20_000.0;
+        input_order.contract_multiplier = 100.0;
+        input_order.contract_type = ContractType::Inverse;
+
+        let actual = SimulatedExecution::calculate_fill_value_gross(&input_order);
+
+        // 2,000 contracts * 100 USD face value / 20,000 USD/BTC = 10 BTC
+        assert_eq!(actual, 10.0);
+        assert_eq!(
+            SimulatedExecution::new(Config::default())
+                .generate_fill(&input_order)
+                .unwrap()
+                .contract_type,
+            ContractType::Inverse
+        );
+    }
+
     #[test]
     fn should_calculate_simulated_fees_correctly() {
         let simulated_execution = SimulatedExecution::new(Config {
@@ -129,11 +1080,13 @@ mod tests {
                 slippage: 0.1,
                 network: 0.001,
             },
+            ..Default::default()
         });
 
         let input_fill_value_gross = 100.0;
 
-        let actual_result = simulated_execution.calculate_fees(&input_fill_value_gross);
+        let actual_result =
+            simulated_execution.calculate_fees(&order_event(), &input_fill_value_gross);
 
         let expected = Fees {
             exchange: 50.0,
@@ -143,4 +1096,838 @@ mod tests {
 
         assert_eq!(actual_result, expected)
     }
+
+    #[test]
+    fn should_clamp_simulated_slippage_between_floor_and_ceiling() {
+        let simulated_execution = |slippage: f64| {
+            SimulatedExecution::new(Config {
+                simulated_fees_pct: Fees {
+                    exchange: 0.0,
+                    slippage,
+                    network: 0.0,
+                },
+                slippage_clamp: SlippageClamp {
+                    floor: Some(SlippageLimit::Bps(10.0)),
+                    ceiling: Some(SlippageLimit::Absolute(5.0)),
+                },
+                ..Default::default()
+            })
+        };
+        let input_fill_value_gross = 1000.0;
+
+        // Slippage below the floor of 10bps is raised to 1.0
+        let actual =
+            simulated_execution(0.0001).calculate_fees(&order_event(), &input_fill_value_gross);
+        assert_eq!(actual.slippage, 1.0);
+
+        // Slippage within the bounds is unchanged
+        let actual =
+            simulated_execution(0.002).calculate_fees(&order_event(), &input_fill_value_gross);
+        assert_eq!(actual.slippage, 2.0);
+
+        // Slippage above the absolute ceiling is lowered to 5.0
+        let actual =
+            simulated_execution(0.5).calculate_fees(&order_event(), &input_fill_value_gross);
+        assert_eq!(actual.slippage, 5.0);
+    }
+
+    #[test]
+    fn should_calculate_simulated_slippage_using_injected_slippage_model() {
+        let config = Config {
+            simulated_fees_pct: Fees {
+                exchange: 0.0,
+                slippage: 0.1,
+                network: 0.0,
+            },
+            ..Default::default()
+        };
+
+        // Default slippage is the constant simulated_fees_pct.slippage
+        let fill = SimulatedExecution::new(config.clone())
+            .generate_fill(&order_event())
+            .unwrap();
+        assert_eq!(fill.fees.slippage, 0.1 * fill.fill_value_gross);
+
+        // Injected SlippageModel replaces the constant, & is still clamped
+        let mut execution = SimulatedExecution::new(Config {
+            slippage_clamp: SlippageClamp {
+                floor: None,
+                ceiling: Some(SlippageLimit::Absolute(1.0)),
+            },
+            ..config
+        })
+        .with_slippage_model(VolumeProportional { impact_bps: 100.0 });
+
+        // Working limit order of 2.0 fills on a bar with volume of 20.0: 10bps of 180.0
+        execution.submit_order(&limit_order(2.0, 90.0)).unwrap();
+        let fills = execution
+            .update_from_market(&candle(85.0, 99.0, 20.0))
+            .unwrap();
+        assert!((fills[0].fees.slippage - 0.18).abs() < 1e-10);
+
+        // Market order with unknown volume assumes 100% participation: 100bps of 200.0, clamped
+        let mut order = order_event();
+        order.quantity = 2.0;
+        let fill = execution.generate_fill(&order).unwrap();
+        assert_eq!(fill.fees.slippage, 1.0);
+    }
+
+    #[test]
+    fn should_calculate_spread_based_slippage_from_latest_quoted_spread() {
+        let mut execution = SimulatedExecution::new(Config {
+            simulated_fees_pct: Fees::default(),
+            ..Default::default()
+        })
+        .with_slippage_model(SpreadBased);
+
+        // Market order of 1.0 at a close of 100.0
+        let mut order = order_event();
+        order.exchange = market_event_candle().exchange;
+        order.instrument = market_event_candle().instrument;
+        order.quantity = 1.0;
+        order.market_meta.close = 100.0;
+
+        // No slippage until a spread has been quoted for the market
+        assert_eq!(execution.generate_fill(&order).unwrap().fees.slippage, 0.0);
+
+        // Half of the quoted 0.2 spread is 10bps of 100.0
+        let mut quote = market_event_candle();
+        quote.kind = DataKind::OrderBookL1(OrderBookL1 {
+            last_update_time: quote.exchange_time,
+            best_bid: Level::new(99.9, 1.0),
+            best_ask: Level::new(100.1, 1.0),
+        });
+        assert!(execution.update_from_market(&quote).unwrap().is_empty());
+
+        let fill = execution.generate_fill(&order).unwrap();
+        assert!((fill.fees.slippage - 0.1).abs() < 1e-10);
+    }
+
+    #[test]
+    fn should_calculate_simulated_fees_using_per_market_overrides() {
+        let btc_usdt = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+        let simulated_execution = SimulatedExecution::new(Config {
+            simulated_fees_pct: Fees {
+                exchange: 0.001,
+                slippage: 0.0005,
+                network: 0.0,
+            },
+            fee_overrides: vec![FeeOverride {
+                market: btc_usdt.clone(),
+                simulated_fees_pct: Fees {
+                    exchange: 0.0002,
+                    slippage: 0.0001,
+                    network: 0.0,
+                },
+            }],
+            ..Default::default()
+        });
+
+        // Identical orders for an overridden & a non-overridden market
+        let mut btc_order = order_event();
+        btc_order.exchange = btc_usdt.exchange;
+        btc_order.instrument = btc_usdt.instrument;
+        btc_order.quantity = 10.0;
+        btc_order.market_meta.close = 100.0;
+
+        let mut alt_order = btc_order.clone();
+        alt_order.instrument = Instrument::from(("alt", "usdt", InstrumentKind::Spot));
+
+        let btc_fill = simulated_execution.generate_fill(&btc_order).unwrap();
+        let alt_fill = simulated_execution.generate_fill(&alt_order).unwrap();
+
+        assert_eq!(btc_fill.fill_value_gross, alt_fill.fill_value_gross);
+        assert!((btc_fill.fees.exchange - 0.2).abs() < 1e-10);
+        assert!((btc_fill.fees.slippage - 0.1).abs() < 1e-10);
+        assert!((alt_fill.fees.exchange - 1.0).abs() < 1e-10);
+        assert!((alt_fill.fees.slippage - 0.5).abs() < 1e-10);
+    }
+
+    fn limit_order(quantity: f64, limit_price: f64) -> OrderEvent {
+        let mut order = order_event();
+        order.exchange = market_event_candle().exchange;
+        order.instrument = market_event_candle().instrument;
+        order.order_type = OrderType::Limit;
+        order.quantity = quantity;
+        order.limit_price = Some(limit_price);
+        order.market_meta.close = 100.0;
+        order
+    }
+
+    fn candle(low: f64, high: f64, volume: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_candle();
+        market.kind = DataKind::Candle(Candle {
+            close_time: market.exchange_time,
+            open: high,
+            high,
+            low,
+            close: low,
+            volume,
+            trade_count: 10,
+        });
+        market
+    }
+
+    #[test]
+    fn new_order_for_market_replaces_working_limit_order_and_reports_it_expired() {
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        let replaced = limit_order(1.0, 90.0);
+        assert!(execution.submit_order(&replaced).unwrap().is_none());
+        assert!(execution.take_expired_orders().is_empty());
+
+        assert!(execution
+            .submit_order(&limit_order(1.0, 80.0))
+            .unwrap()
+            .is_none());
+        assert_eq!(execution.working_orders().len(), 1);
+        assert_eq!(execution.working_orders()[0].limit_price, 80.0);
+
+        let expired = execution.take_expired_orders();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].limit_price, replaced.limit_price);
+    }
+
+    #[test]
+    fn submit_market_order_fills_immediately() {
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        let fill = execution.submit_order(&order_event()).unwrap();
+
+        assert!(fill.is_some());
+        assert!(execution.working_orders().is_empty());
+    }
+
+    #[test]
+    fn submit_limit_order_without_limit_price_fails() {
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        let mut order = limit_order(1.0, 90.0);
+        order.limit_price = None;
+
+        assert!(matches!(
+            execution.submit_order(&order),
+            Err(ExecutionError::LimitPriceMissing)
+        ));
+    }
+
+    #[test]
+    fn submit_marketable_limit_order_fills_immediately_at_limit_price() {
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        let fill = execution
+            .submit_order(&limit_order(2.0, 105.0))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(fill.fill_value_gross, 2.0 * 105.0);
+        assert!(execution.working_orders().is_empty());
+    }
+
+    #[test]
+    fn submit_marketable_buy_limit_with_price_improvement_fills_at_lower_market_price() {
+        let mut execution = SimulatedExecution::new(Config {
+            limit_fill_price: LimitFillPrice::PriceImprovement,
+            ..Default::default()
+        });
+
+        // Buy limit @ 105.0 above market price of 100.0
+        let fill = execution
+            .submit_order(&limit_order(2.0, 105.0))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(fill.fill_value_gross, 2.0 * 100.0);
+    }
+
+    #[test]
+    fn submit_marketable_sell_limit_with_price_improvement_fills_at_higher_market_price() {
+        let mut execution = SimulatedExecution::new(Config {
+            limit_fill_price: LimitFillPrice::PriceImprovement,
+            ..Default::default()
+        });
+
+        // Sell limit @ 95.0 below market price of 100.0
+        let fill = execution
+            .submit_order(&limit_order(-2.0, 95.0))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(fill.quantity, -2.0);
+        assert_eq!(fill.fill_value_gross, 2.0 * 100.0);
+    }
+
+    #[test]
+    fn working_limit_with_price_improvement_fills_at_limit_price() {
+        let mut execution = SimulatedExecution::new(Config {
+            limit_fill_price: LimitFillPrice::PriceImprovement,
+            ..Default::default()
+        });
+
+        // Resting buy limit is filled by the market trading down to it, so no improvement
+        execution.submit_order(&limit_order(2.0, 90.0)).unwrap();
+        let fills = execution
+            .update_from_market(&candle(85.0, 99.0, 100.0))
+            .unwrap();
+
+        assert_eq!(fills[0].fill_value_gross, 2.0 * 90.0);
+    }
+
+    #[test]
+    fn touch_model_fills_working_buy_limit_when_bar_touches_limit_price() {
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        assert!(execution
+            .submit_order(&limit_order(2.0, 90.0))
+            .unwrap()
+            .is_none());
+        assert_eq!(execution.working_orders().len(), 1);
+
+        // Bar does not reach limit price
+        assert!(execution
+            .update_from_market(&candle(91.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+
+        // Bar touches limit price
+        let fills = execution
+            .update_from_market(&candle(90.0, 99.0, 100.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 2.0);
+        assert_eq!(fills[0].fill_value_gross, 2.0 * 90.0);
+        assert_eq!(fills[0].market_meta.close, 90.0);
+        assert!(execution.working_orders().is_empty());
+    }
+
+    #[test]
+    fn touch_model_fills_working_sell_limit_when_bar_touches_limit_price() {
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        execution.submit_order(&limit_order(-1.0, 110.0)).unwrap();
+
+        assert!(execution
+            .update_from_market(&candle(100.0, 109.0, 100.0))
+            .unwrap()
+            .is_empty());
+
+        let fills = execution
+            .update_from_market(&candle(100.0, 111.0, 100.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].fill_value_gross, 110.0);
+    }
+
+    #[test]
+    fn trade_through_model_fills_working_limit_only_when_bar_trades_through_limit_price() {
+        let mut execution = SimulatedExecution::new(Config {
+            limit_fill_model: LimitFillModel::TradeThrough,
+            ..Default::default()
+        });
+
+        // Buy limit: bar touching the limit price does not fill
+        execution.submit_order(&limit_order(2.0, 90.0)).unwrap();
+        assert!(execution
+            .update_from_market(&candle(90.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+        assert_eq!(execution.working_orders().len(), 1);
+
+        // Bar trading through the limit price fills at the limit price
+        let fills = execution
+            .update_from_market(&candle(89.5, 99.0, 100.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].fill_value_gross, 2.0 * 90.0);
+        assert!(execution.working_orders().is_empty());
+
+        // Sell limit: same behaviour above the limit price
+        execution.submit_order(&limit_order(-1.0, 110.0)).unwrap();
+        assert!(execution
+            .update_from_market(&candle(100.0, 110.0, 100.0))
+            .unwrap()
+            .is_empty());
+        let fills = execution
+            .update_from_market(&candle(100.0, 110.5, 100.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].fill_value_gross, 110.0);
+    }
+
+    #[test]
+    fn queue_model_fills_working_limit_once_volume_ahead_is_consumed() {
+        let mut execution = SimulatedExecution::new(Config {
+            limit_fill_model: LimitFillModel::Queue(QueueModel {
+                queue_ahead: 15.0,
+                volume_at_price_pct: 0.1,
+            }),
+            ..Default::default()
+        });
+
+        execution.submit_order(&limit_order(10.0, 90.0)).unwrap();
+
+        // 10.0 traded at price: consumes 10.0 of the 15.0 queued ahead
+        assert!(execution
+            .update_from_market(&candle(90.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+        assert_eq!(execution.working_orders()[0].queue_ahead, 5.0);
+
+        // Bar doesn't touch the limit price, so no volume traded at price
+        assert!(execution
+            .update_from_market(&candle(95.0, 99.0, 1000.0))
+            .unwrap()
+            .is_empty());
+
+        // 10.0 traded at price: consumes remaining 5.0 queued ahead & fills 5.0
+        assert!(execution
+            .update_from_market(&candle(90.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+        assert_eq!(execution.working_orders()[0].filled_quantity, 5.0);
+
+        // 10.0 traded at price: fills remaining 5.0
+        let fills = execution
+            .update_from_market(&candle(89.0, 99.0, 100.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 10.0);
+        assert_eq!(fills[0].fill_value_gross, 10.0 * 90.0);
+        assert!(execution.working_orders().is_empty());
+    }
+
+    #[test]
+    fn replacing_partially_filled_queue_order_fills_filled_quantity_and_expires_remainder() {
+        let mut execution = SimulatedExecution::new(Config {
+            limit_fill_model: LimitFillModel::Queue(QueueModel {
+                queue_ahead: 0.0,
+                volume_at_price_pct: 0.1,
+            }),
+            ..Default::default()
+        });
+
+        // 4.0 traded at price fills 4.0 of the 10.0 working order
+        execution.submit_order(&limit_order(10.0, 90.0)).unwrap();
+        assert!(execution
+            .update_from_market(&candle(90.0, 99.0, 40.0))
+            .unwrap()
+            .is_empty());
+        assert!(execution.take_replaced_fills().is_empty());
+
+        // Replacing the working order fills the 4.0 filled at it's limit price
+        execution.submit_order(&limit_order(10.0, 80.0)).unwrap();
+        let fills = execution.take_replaced_fills();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 4.0);
+        assert_eq!(fills[0].fill_value_gross, 4.0 * 90.0);
+        assert!(execution.take_replaced_fills().is_empty());
+
+        // Only the unfilled remainder is expired
+        let expired = execution.take_expired_orders();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].quantity, 6.0);
+        assert_eq!(expired[0].limit_price, Some(90.0));
+    }
+
+    #[test]
+    fn submit_order_replaces_working_order_for_same_market() {
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        execution.submit_order(&limit_order(1.0, 90.0)).unwrap();
+        execution.submit_order(&limit_order(1.0, 80.0)).unwrap();
+
+        assert_eq!(execution.working_orders().len(), 1);
+        assert_eq!(execution.working_orders()[0].limit_price, 80.0);
+    }
+
+    #[test]
+    fn working_limit_expires_unfilled_at_exactly_expiry_bars() {
+        let mut execution = SimulatedExecution::new(Config {
+            expiry_bars: Some(2),
+            ..Default::default()
+        });
+
+        let order = limit_order(2.0, 90.0);
+        execution.submit_order(&order).unwrap();
+
+        // First bar does not reach limit price, so the order is still working
+        assert!(execution
+            .update_from_market(&candle(91.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+        assert_eq!(execution.working_orders().len(), 1);
+        assert_eq!(execution.working_orders()[0].bars_elapsed, 1);
+        assert!(execution.take_expired_orders().is_empty());
+
+        // Second bar does not reach limit price, so the order expires
+        assert!(execution
+            .update_from_market(&candle(91.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+        assert!(execution.working_orders().is_empty());
+        assert_eq!(execution.take_expired_orders(), vec![order]);
+        assert!(execution.take_expired_orders().is_empty());
+
+        // Expired order can no longer fill
+        assert!(execution
+            .update_from_market(&candle(85.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn partially_filled_queue_order_fills_filled_quantity_and_expires_remainder() {
+        let mut execution = SimulatedExecution::new(Config {
+            limit_fill_model: LimitFillModel::Queue(QueueModel {
+                queue_ahead: 0.0,
+                volume_at_price_pct: 0.1,
+            }),
+            expiry_bars: Some(2),
+            ..Default::default()
+        });
+
+        // 3.0 traded at price fills 3.0 of the 10.0 working order
+        execution.submit_order(&limit_order(10.0, 90.0)).unwrap();
+        assert!(execution
+            .update_from_market(&candle(90.0, 99.0, 30.0))
+            .unwrap()
+            .is_empty());
+        assert!(execution.take_expired_orders().is_empty());
+
+        // Expiring bar doesn't touch the limit price, so the 3.0 filled is filled at the limit
+        let fills = execution
+            .update_from_market(&candle(95.0, 99.0, 30.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 3.0);
+        assert_eq!(fills[0].fill_value_gross, 3.0 * 90.0);
+        assert!(execution.working_orders().is_empty());
+
+        // Only the unfilled remainder is expired
+        let expired = execution.take_expired_orders();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].quantity, 7.0);
+    }
+
+    #[test]
+    fn working_limit_fills_on_final_bar_before_expiry() {
+        let mut execution = SimulatedExecution::new(Config {
+            expiry_bars: Some(2),
+            ..Default::default()
+        });
+
+        execution.submit_order(&limit_order(2.0, 90.0)).unwrap();
+
+        assert!(execution
+            .update_from_market(&candle(91.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+
+        let fills = execution
+            .update_from_market(&candle(90.0, 99.0, 100.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert!(execution.working_orders().is_empty());
+        assert!(execution.take_expired_orders().is_empty());
+    }
+
+    #[test]
+    fn working_limit_without_expiry_bars_is_good_till_cancelled() {
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        execution.submit_order(&limit_order(2.0, 90.0)).unwrap();
+
+        for _ in 0..100 {
+            execution
+                .update_from_market(&candle(91.0, 99.0, 100.0))
+                .unwrap();
+        }
+
+        assert_eq!(execution.working_orders().len(), 1);
+        assert!(execution.take_expired_orders().is_empty());
+    }
+
+    fn market_order(quantity: f64) -> OrderEvent {
+        let mut order = limit_order(quantity, 100.0);
+        order.order_type = OrderType::Market;
+        order.limit_price = None;
+        order
+    }
+
+    #[test]
+    fn execution_delay_in_bars_fills_orders_against_the_nth_subsequent_bar() {
+        let mut execution = SimulatedExecution::new(Config {
+            execution_delay: ExecutionDelay::Bars(2),
+            ..Config::default()
+        });
+
+        // Order generated on bar T is held rather than filled at it's close of 100.0
+        assert_eq!(execution.submit_order(&market_order(2.0)).unwrap(), None);
+        assert_eq!(execution.delayed_orders().len(), 1);
+
+        // Bars of other markets do not count towards the delay
+        let mut other_market = candle(50.0, 60.0, 100.0);
+        other_market.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+        assert!(execution
+            .update_from_market(&other_market)
+            .unwrap()
+            .is_empty());
+
+        // Bar T+1
+        assert!(execution
+            .update_from_market(&candle(90.0, 99.0, 100.0))
+            .unwrap()
+            .is_empty());
+
+        // Bar T+2 fills the order at it's close
+        let fills = execution
+            .update_from_market(&candle(80.0, 95.0, 100.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].market_meta.close, 80.0);
+        assert_eq!(fills[0].fill_value_gross, 160.0);
+        assert!(execution.delayed_orders().is_empty());
+    }
+
+    #[test]
+    fn execution_delay_expires_rejected_delayed_order_without_discarding_other_fills() {
+        let mut execution = SimulatedExecution::new(Config {
+            execution_delay: ExecutionDelay::Bars(1),
+            ..Config::default()
+        });
+
+        // Valid order is released ahead of a limit order that is missing it's limit price
+        execution.submit_order(&market_order(2.0)).unwrap();
+        let mut rejected = limit_order(1.0, 90.0);
+        rejected.limit_price = None;
+        execution.delayed_orders.push(DelayedOrder {
+            order: rejected.clone(),
+            bars_remaining: 1,
+            eligible_time: rejected.market_meta.time,
+        });
+
+        let fills = execution
+            .update_from_market(&candle(80.0, 95.0, 100.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].fill_value_gross, 2.0 * 80.0);
+        assert!(execution.delayed_orders().is_empty());
+
+        let expired = execution.take_expired_orders();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].quantity, 1.0);
+        assert_eq!(expired[0].limit_price, None);
+    }
+
+    #[test]
+    fn execution_delay_duration_releases_orders_once_eligible() {
+        let mut execution = SimulatedExecution::new(Config {
+            execution_delay: ExecutionDelay::Duration(Duration::seconds(60)),
+            ..Config::default()
+        });
+        let candle_at = |seconds: i64, low: f64, high: f64| {
+            let mut market = candle(low, high, 100.0);
+            market.exchange_time += Duration::seconds(seconds);
+            market
+        };
+
+        // Non-marketable limit order generated at the time of the first candle
+        let mut order = limit_order(1.0, 90.0);
+        order.market_meta.time = candle_at(0, 0.0, 0.0).exchange_time;
+        assert_eq!(execution.submit_order(&order).unwrap(), None);
+
+        // Bar before the order is eligible cannot fill it, despite touching the limit price
+        assert!(execution
+            .update_from_market(&candle_at(30, 85.0, 99.0))
+            .unwrap()
+            .is_empty());
+        assert_eq!(execution.delayed_orders().len(), 1);
+
+        // Once eligible, the limit order is not marketable at the close so becomes a working order
+        assert!(execution
+            .update_from_market(&candle_at(60, 95.0, 99.0))
+            .unwrap()
+            .is_empty());
+        assert!(execution.delayed_orders().is_empty());
+        assert_eq!(execution.working_orders().len(), 1);
+
+        // Subsequent bar touching the limit price fills it
+        let fills = execution
+            .update_from_market(&candle_at(120, 89.0, 99.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].fill_value_gross, 90.0);
+    }
+
+    fn partial_fill_execution() -> SimulatedExecution {
+        SimulatedExecution::new(Config {
+            partial_fill: Some(PartialFill {
+                max_fill_ratio: 0.1,
+                price_step_bps: 10.0,
+            }),
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn partial_fill_splits_market_orders_across_bars_at_worsening_prices() {
+        let mut execution = partial_fill_execution();
+        let fill_price = |fill: &FillEvent| fill.fill_value_gross / fill.quantity.abs();
+
+        // Liquidity cap of 10.0 on the current bar volume of 100.0
+        let mut order = market_order(25.0);
+        order.market_meta.volume = Some(100.0);
+
+        let mut fills = vec![execution.submit_order(&order).unwrap().unwrap()];
+        assert_eq!(fills[0].quantity, 10.0);
+        assert_eq!(execution.partial_orders().len(), 1);
+
+        // Subsequent bars fill the remaining quantity up to their own liquidity cap
+        for volume in [100.0, 50.0] {
+            fills.extend(
+                execution
+                    .update_from_market(&candle(100.0, 110.0, volume))
+                    .unwrap(),
+            );
+        }
+        assert!(execution.partial_orders().is_empty());
+        assert_eq!(
+            fills.iter().map(|fill| fill.quantity).collect::<Vec<_>>(),
+            vec![10.0, 10.0, 5.0]
+        );
+
+        // Summed fills equal the order quantity, & each tranche is filled at a worse price
+        assert_eq!(fills.iter().map(|fill| fill.quantity).sum::<f64>(), 25.0);
+        assert_eq!(fill_price(&fills[0]), 100.0);
+        assert!(fill_price(&fills[1]) > fill_price(&fills[0]));
+        assert!(fill_price(&fills[2]) > fill_price(&fills[1]));
+
+        // Sell orders fill at successively lower prices
+        let mut order = market_order(-15.0);
+        order.decision = Decision::Short;
+        order.market_meta.volume = Some(100.0);
+
+        let mut fills = vec![execution.submit_order(&order).unwrap().unwrap()];
+        fills.extend(
+            execution
+                .update_from_market(&candle(100.0, 110.0, 100.0))
+                .unwrap(),
+        );
+        assert_eq!(fills.iter().map(|fill| fill.quantity).sum::<f64>(), -15.0);
+        assert!(fill_price(&fills[1]) < fill_price(&fills[0]));
+    }
+
+    #[test]
+    fn partial_fill_fills_in_full_without_volume_and_is_replaced_by_new_orders() {
+        let mut execution = partial_fill_execution();
+
+        // Liquidity of a bar with unknown volume is not capped
+        let fill = execution
+            .submit_order(&market_order(25.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(fill.quantity, 25.0);
+        assert!(execution.partial_orders().is_empty());
+
+        // New order for the market replaces the unfilled remainder of a partial order
+        let mut order = market_order(25.0);
+        order.market_meta.volume = Some(100.0);
+        execution.submit_order(&order).unwrap();
+        assert_eq!(execution.partial_orders().len(), 1);
+
+        let mut exit = market_order(-10.0);
+        exit.decision = Decision::CloseLong;
+        exit.market_meta.volume = Some(100.0);
+        assert_eq!(
+            execution.submit_order(&exit).unwrap().unwrap().quantity,
+            -10.0
+        );
+        assert!(execution.partial_orders().is_empty());
+        assert!(execution
+            .update_from_market(&candle(100.0, 110.0, 100.0))
+            .unwrap()
+            .is_empty());
+
+        // Replaced remainder is reported as expired with the quantity that will never fill
+        let expired = execution.take_expired_orders();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].decision, Decision::Long);
+        assert_eq!(expired[0].quantity, 15.0);
+    }
+
+    #[test]
+    fn volume_limit_scales_down_entry_orders_once_volume_history_exists() {
+        let mut execution = SimulatedExecution::new(Config {
+            volume_limit: Some(VolumeLimit {
+                max_volume_pct: 0.1,
+                window_bars: 3,
+            }),
+            ..Config::default()
+        });
+
+        let order = |decision: Decision, quantity: f64| {
+            let mut order = limit_order(quantity, 100.0);
+            order.order_type = OrderType::Market;
+            order.limit_price = None;
+            order.decision = decision;
+            order
+        };
+        let fill_quantity = |execution: &mut SimulatedExecution, order: OrderEvent| {
+            execution.submit_order(&order).unwrap().unwrap().quantity
+        };
+
+        // Limit is not applied until there is window_bars of volume history
+        execution
+            .update_from_market(&candle(90.0, 99.0, 100.0))
+            .unwrap();
+        execution
+            .update_from_market(&candle(90.0, 99.0, 200.0))
+            .unwrap();
+        assert_eq!(
+            fill_quantity(&mut execution, order(Decision::Long, 50.0)),
+            50.0
+        );
+
+        // Average volume of 200.0 limits entries to 20.0
+        execution
+            .update_from_market(&candle(90.0, 99.0, 300.0))
+            .unwrap();
+        assert_eq!(
+            fill_quantity(&mut execution, order(Decision::Long, 50.0)),
+            20.0
+        );
+        assert_eq!(
+            fill_quantity(&mut execution, order(Decision::Short, -50.0)),
+            -20.0
+        );
+
+        // Quantity scaled away is reported as expired, since it will never fill
+        assert_eq!(
+            execution
+                .take_expired_orders()
+                .iter()
+                .map(|expired| expired.quantity)
+                .collect::<Vec<_>>(),
+            vec![30.0, -30.0]
+        );
+        assert_eq!(
+            fill_quantity(&mut execution, order(Decision::Long, 10.0)),
+            10.0
+        );
+
+        // Exit orders are never scaled down
+        assert_eq!(
+            fill_quantity(&mut execution, order(Decision::CloseLong, -50.0)),
+            -50.0
+        );
+
+        // Oldest bar rolls out of the window: average volume of 400.0 limits entries to 40.0
+        execution
+            .update_from_market(&candle(90.0, 99.0, 700.0))
+            .unwrap();
+        assert_eq!(
+            fill_quantity(&mut execution, order(Decision::Long, 50.0)),
+            40.0
+        );
+    }
 }
@@ -0,0 +1,104 @@
+use crate::{data::MarketMeta, portfolio::OrderEvent};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Models the slippage incurred when filling an [`OrderEvent`], used by the
+/// [`SimulatedExecution`](super::simulated::SimulatedExecution) to calculate the slippage
+/// component of the [`Fees`](super::Fees) of each fill.
+pub trait SlippageModel: Debug {
+    /// Returns the slippage of filling the [`OrderEvent`] at the provided [`MarketMeta`], as a
+    /// fraction of the gross fill value in decimal form (eg/ 0.001 for 0.1%).
+    fn apply(&self, order: &OrderEvent, market: &MarketMeta) -> f64;
+}
+
+/// [`SlippageModel`] charging a fixed number of basis points of the gross fill value.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct FixedBps {
+    /// Slippage in basis points (eg/ 5.0 for 0.05%).
+    pub bps: f64,
+}
+
+impl SlippageModel for FixedBps {
+    fn apply(&self, _: &OrderEvent, _: &MarketMeta) -> f64 {
+        self.bps / 10_000.0
+    }
+}
+
+/// [`SlippageModel`] charging slippage proportional to the participation of the [`OrderEvent`]
+/// in the volume of the bar it fills on (ie/ abs(quantity) / [`MarketMeta::volume`]), modelling
+/// the market impact of larger orders.
+///
+/// If the bar volume is unknown or not positive, the [`OrderEvent`] is conservatively assumed to
+/// be the entire bar volume.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct VolumeProportional {
+    /// Slippage in basis points at 100% participation (eg/ 50.0 charges 5bps for an order of 10%
+    /// of the bar volume).
+    pub impact_bps: f64,
+}
+
+impl SlippageModel for VolumeProportional {
+    fn apply(&self, order: &OrderEvent, market: &MarketMeta) -> f64 {
+        let participation = match market.volume {
+            Some(volume) if volume > 0.0 => order.quantity.abs() / volume,
+            _ => 1.0,
+        };
+
+        participation * self.impact_bps / 10_000.0
+    }
+}
+
+/// [`SlippageModel`] charging half of the quoted bid-ask spread ([`MarketMeta::spread`]) as a
+/// fraction of the [`MarketMeta`] close, which is the cost of a market order crossing the spread
+/// relative to the mid price the close approximates.
+///
+/// The [`SimulatedExecution`](super::simulated::SimulatedExecution) provides the spread of the
+/// latest [`OrderBookL1`](barter_data::subscription::book::OrderBookL1) it has consumed for the
+/// market. If the spread is unknown, no slippage is charged.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct SpreadBased;
+
+impl SlippageModel for SpreadBased {
+    fn apply(&self, _: &OrderEvent, market: &MarketMeta) -> f64 {
+        match market.spread {
+            Some(spread) if market.close > 0.0 => spread.max(0.0) / 2.0 / market.close,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::order_event;
+
+    #[test]
+    fn slippage_models_return_fraction_of_gross_fill_value() {
+        let mut order = order_event();
+        order.quantity = -5.0;
+        let market = |volume| MarketMeta {
+            volume,
+            ..Default::default()
+        };
+
+        assert_eq!(FixedBps { bps: 5.0 }.apply(&order, &market(None)), 0.0005);
+
+        // Half of a 0.1 spread is 5bps of a 100.0 close, & no slippage without a quoted spread
+        let quoted = MarketMeta {
+            close: 100.0,
+            spread: Some(0.1),
+            ..Default::default()
+        };
+        assert!((SpreadBased.apply(&order, &quoted) - 0.0005).abs() < 1e-12);
+        assert_eq!(SpreadBased.apply(&order, &market(None)), 0.0);
+
+        // 5.0 quantity is 10% participation in a bar volume of 50.0
+        let volume_proportional = VolumeProportional { impact_bps: 50.0 };
+        assert_eq!(
+            volume_proportional.apply(&order, &market(Some(50.0))),
+            0.0005
+        );
+        assert_eq!(volume_proportional.apply(&order, &market(None)), 0.005);
+        assert_eq!(volume_proportional.apply(&order, &market(Some(0.0))), 0.005);
+    }
+}
@@ -61,16 +61,25 @@
 //! ### Strategy
 //! ```
 //! use barter::{
-//!     strategy::{SignalGenerator, example::{Config as StrategyConfig, RSIStrategy}},
+//!     strategy::{
+//!         SignalGenerator, TradeDirection,
+//!         example::{Config as StrategyConfig, RSIStrategy},
+//!     },
 //!     test_util,
 //! };
 //! use barter_integration::model::Side;
 //!
 //! let config = StrategyConfig {
 //!     rsi_period: 14,
+//!     oversold: 40.0,
+//!     overbought: 60.0,
+//!     scale_signal_strength: false,
+//!     warm_up: None,
+//!     direction: TradeDirection::Both,
+//!     cooldown_bars: 0,
 //! };
 //!
-//! let mut strategy = RSIStrategy::new(config);
+//! let mut strategy = RSIStrategy::new(config).unwrap();
 //!
 //! let market_event = test_util::market_event_trade(Side::Buy);
 //!
@@ -95,7 +104,7 @@
 //!     test_util,
 //! };
 //! use barter_integration::model::{Market, instrument::kind::InstrumentKind};
-//! use std::marker::PhantomData;
+//! use std::{collections::HashMap, marker::PhantomData};
 //! use uuid::Uuid;
 //!
 //! let components = PortfolioLego {
@@ -105,10 +114,21 @@
 //!     allocator: DefaultAllocator{ default_order_value: 100.0 },
 //!     risk: DefaultRisk{},
 //!     starting_cash: 10000.0,
+//!     leverage: 1.0,
+//!     fx_conversion: None,
+//!     contract_multipliers: HashMap::new(),
+//!     contract_types: HashMap::new(),
+//!     max_positions_per_market: 1,
+//!     max_adds: 0,
+//!     entry_delay_bars: 0,
+//!     mark_price: Default::default(),
+//!     statistic_exclusions: vec![],
 //!     statistic_config: StatisticConfig {
 //!         starting_equity: 10000.0 ,
 //!         trading_days_per_year: 365,
-//!         risk_free_return: 0.0
+//!         risk_free_return: 0.0,
+//!         omega_threshold_return: 0.0,
+//!         return_kind: Default::default()
 //!     },
 //!     _statistic_marker: PhantomData::<TradingSummary>::default()
 //! };
@@ -150,7 +170,8 @@
 //!         exchange: 0.1,
 //!         slippage: 0.05, // Simulated slippage modelled as a Fee
 //!         network: 0.0,
-//!     }
+//!     },
+//!     ..Default::default()
 //! };
 //!
 //! let mut execution = SimulatedExecution::new(config);
@@ -178,6 +199,8 @@
 //!     starting_equity: 10000.0,
 //!     trading_days_per_year: 253,
 //!     risk_free_return: 0.5,
+//!     omega_threshold_return: 0.0,
+//!     return_kind: Default::default(),
 //! };
 //!
 //! let mut trading_summary = TradingSummary::init(config);
@@ -249,7 +272,7 @@ pub mod test_util {
     use crate::{
         data::MarketMeta,
         execution::{Fees, FillEvent},
-        portfolio::{position::Position, OrderEvent, OrderType},
+        portfolio::{position::Position, Balance, ContractType, OrderEvent, OrderType},
         strategy::{Decision, Signal},
     };
     use barter_data::{
@@ -277,6 +300,7 @@ pub mod test_util {
                 amount: 1.0,
                 side,
             }),
+            sequence: 0,
         }
     }
 
@@ -297,6 +321,7 @@ pub mod test_util {
                 volume: 100000.0,
                 trade_count: 1000,
             }),
+            sequence: 0,
         }
     }
 
@@ -308,6 +333,9 @@ pub mod test_util {
             instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
             signals: Default::default(),
             market_meta: Default::default(),
+            tag: None,
+            stop_loss: None,
+            take_profit: None,
         }
     }
 
@@ -321,6 +349,10 @@ pub mod test_util {
             decision: Decision::default(),
             quantity: 1.0,
             order_type: OrderType::default(),
+            limit_price: None,
+            contract_multiplier: 1.0,
+            contract_type: ContractType::Linear,
+            tag: None,
         }
     }
 
@@ -335,6 +367,9 @@ pub mod test_util {
             quantity: 1.0,
             fill_value_gross: 100.0,
             fees: Fees::default(),
+            contract_multiplier: 1.0,
+            contract_type: ContractType::Linear,
+            tag: None,
         }
     }
 
@@ -342,11 +377,14 @@ pub mod test_util {
     pub fn position() -> Position {
         Position {
             position_id: "engine_id_trader_{}_{}_position".to_owned(),
+            tag: None,
             exchange: Exchange::from("binance"),
             instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
             meta: Default::default(),
             side: Side::Buy,
             quantity: 1.0,
+            contract_multiplier: 1.0,
+            contract_type: ContractType::Linear,
             enter_fees: Default::default(),
             enter_fees_total: 0.0,
             enter_avg_price_gross: 100.0,
@@ -359,6 +397,21 @@ pub mod test_util {
             current_value_gross: 100.0,
             unrealised_profit_loss: 0.0,
             realised_profit_loss: 0.0,
+            max_favorable: 0.0,
+            max_adverse: 0.0,
+            adds: 0,
         }
     }
+
+    /// Build a closed [`Position`] with the provided realised PnL.
+    pub fn closed_position(realised_profit_loss: f64) -> Position {
+        let mut position = position();
+        position.realised_profit_loss = realised_profit_loss;
+        position.meta.exit_balance = Some(Balance {
+            time: Utc::now(),
+            total: 1000.0,
+            available: 1000.0,
+        });
+        position
+    }
 }
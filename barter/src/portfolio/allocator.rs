@@ -1,23 +1,35 @@
 use crate::{
-    portfolio::{position::Position, OrderEvent},
+    portfolio::{position::Position, risk::EquitySnapshot, OrderEvent},
+    statistic::summary::win_loss::WinLossSummary,
     strategy::{Decision, SignalStrength},
 };
 use serde::{Deserialize, Serialize};
 
 /// Allocates an appropriate [`OrderEvent`] quantity.
 pub trait OrderAllocator {
-    /// Returns an [`OrderEvent`] with a calculated order quantity based on the input order,
-    /// [`SignalStrength`] and potential existing [`Position`].
-    fn allocate_order(
-        &self,
-        order: &mut OrderEvent,
-        position: Option<&Position>,
-        signal_strength: SignalStrength,
-    );
+    /// Returns an [`OrderEvent`] with a calculated order quantity based on the input order & the
+    /// Portfolio state provided by the [`AllocationContext`].
+    fn allocate_order(&self, order: &mut OrderEvent, context: AllocationContext<'_>);
+}
+
+/// Portfolio state an [`OrderAllocator`] may use to size an [`OrderEvent`]. New sizing inputs are
+/// added here, rather than to the [`OrderAllocator::allocate_order`] signature.
+#[derive(Copy, Clone, Debug)]
+pub struct AllocationContext<'a> {
+    /// Existing [`Position`] in the [`OrderEvent`] market, if any.
+    pub position: Option<&'a Position>,
+    /// [`SignalStrength`] advised for the [`OrderEvent`] decision.
+    pub signal_strength: SignalStrength,
+    /// Snapshot of the Portfolio equity.
+    pub equity: &'a EquitySnapshot,
+    /// [`WinLossSummary`] of the Positions closed by the Portfolio. It is held in memory, so it
+    /// only covers the Positions closed since the Portfolio was constructed & resets on a process
+    /// restart (unless restored from a Portfolio snapshot).
+    pub trades: &'a WinLossSummary,
 }
 
 /// Default allocation manager that implements [`OrderAllocator`]. Order size is calculated by
-/// using the default_order_value, symbol close value, and [`SignalStrength`].
+/// using the default_order_value, symbol close value, contract multiplier, and [`SignalStrength`].
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
 pub struct DefaultAllocator {
     pub default_order_value: f64,
@@ -27,11 +39,18 @@ impl OrderAllocator for DefaultAllocator {
     fn allocate_order(
         &self,
         order: &mut OrderEvent,
-        position: Option<&Position>,
-        signal_strength: SignalStrength,
+        AllocationContext {
+            position,
+            signal_strength,
+            ..
+        }: AllocationContext<'_>,
     ) {
         // Calculate exact order_size, then round it to a more appropriate decimal place
-        let default_order_size = self.default_order_value / order.market_meta.close;
+        let default_order_size = order.contract_type.quantity(
+            self.default_order_value,
+            order.market_meta.close,
+            order.contract_multiplier,
+        );
         let default_order_size = (default_order_size * 10000.0).floor() / 10000.0;
 
         match order.decision {
@@ -47,10 +66,185 @@ impl OrderAllocator for DefaultAllocator {
     }
 }
 
+/// Percent-of-equity allocation manager that implements [`OrderAllocator`]. Order size is
+/// calculated using the configured percentage of the current Portfolio equity, symbol close value,
+/// contract multiplier, and [`SignalStrength`], so position sizes scale as the account grows or
+/// shrinks. No quantity is allocated to entries whilst the current equity is not positive.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct PercentEquityAllocator {
+    /// Fraction of the current equity allocated to each entry order (eg/ 0.1 for 10%).
+    pub order_equity_pct: f64,
+}
+
+impl OrderAllocator for PercentEquityAllocator {
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        AllocationContext {
+            position,
+            signal_strength,
+            equity,
+            ..
+        }: AllocationContext<'_>,
+    ) {
+        // Calculate exact order_size from the current equity, then round it to a more appropriate
+        // decimal place
+        let order_value = equity.current.max(0.0) * self.order_equity_pct;
+        let order_size = order.contract_type.quantity(
+            order_value,
+            order.market_meta.close,
+            order.contract_multiplier,
+        );
+        let order_size = (order_size * 10000.0).floor() / 10000.0;
+
+        match order.decision {
+            // Entry
+            Decision::Long => order.quantity = order_size * signal_strength.0,
+
+            // Entry
+            Decision::Short => order.quantity = -order_size * signal_strength.0,
+
+            // Exit
+            _ => order.quantity = 0.0 - position.as_ref().unwrap().quantity,
+        }
+    }
+}
+
+/// Volatility adjusted allocation manager that implements [`OrderAllocator`]. Order size is
+/// calculated as the risk_per_trade divided by the [`MarketMeta`](crate::data::MarketMeta)
+/// volatility estimate (eg/ ATR), scaled by the [`SignalStrength`], so positions are smaller in
+/// volatile markets. For linear contracts, a move of one volatility unit against the position
+/// loses approximately the risk_per_trade.
+///
+/// No quantity is allocated to entries without a positive volatility estimate.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct VolatilityAllocator {
+    /// Value risked per entry order for a move of one volatility unit.
+    pub risk_per_trade: f64,
+}
+
+impl OrderAllocator for VolatilityAllocator {
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        AllocationContext {
+            position,
+            signal_strength,
+            ..
+        }: AllocationContext<'_>,
+    ) {
+        // Calculate the units of the underlying risking risk_per_trade per volatility unit, then
+        // convert to an exact order_size & round it to a more appropriate decimal place
+        let units = match order.market_meta.volatility {
+            Some(volatility) if volatility.is_finite() && volatility > 0.0 => {
+                self.risk_per_trade / volatility
+            }
+            _ => 0.0,
+        };
+        let order_size = order.contract_type.quantity(
+            units * order.market_meta.close,
+            order.market_meta.close,
+            order.contract_multiplier,
+        );
+        let order_size = (order_size * 10000.0).floor() / 10000.0;
+
+        match order.decision {
+            // Entry
+            Decision::Long => order.quantity = order_size * signal_strength.0,
+
+            // Entry
+            Decision::Short => order.quantity = -order_size * signal_strength.0,
+
+            // Exit
+            _ => order.quantity = 0.0 - position.as_ref().unwrap().quantity,
+        }
+    }
+}
+
+/// Fractional Kelly criterion allocation manager that implements [`OrderAllocator`].
+///
+/// Order size is calculated using a fraction of the current Portfolio equity (see
+/// [`EquitySnapshot::current`]), symbol close value, contract multiplier, and
+/// [`SignalStrength`]. The fraction of equity is the kelly_fraction of the Kelly criterion
+/// f* = W - (1 - W) / R, where the win rate W & payoff ratio R are read from the
+/// [`WinLossSummary`] of the Portfolio's closed trades (see [`AllocationContext::trades`]). Since
+/// that summary is held in memory, a restarted process sizes entries with the fallback until
+/// min_trades Positions have closed again.
+///
+/// Until min_trades closed trades exist, the conservative fallback_equity_pct is used instead. A
+/// record without losses has f* = W, and an estimated negative edge allocates no quantity to
+/// entries. The Kelly fraction of equity is capped at 1.0.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KellyAllocator {
+    /// Fraction of the full Kelly criterion allocated (eg/ 0.5 for half-Kelly).
+    pub kelly_fraction: f64,
+    /// Number of closed trades required before the Kelly criterion is trusted.
+    pub min_trades: u64,
+    /// Fraction of the current equity allocated to each entry order before min_trades closed
+    /// trades exist (eg/ 0.01 for 1%).
+    pub fallback_equity_pct: f64,
+}
+
+impl OrderAllocator for KellyAllocator {
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        AllocationContext {
+            position,
+            signal_strength,
+            equity,
+            trades,
+        }: AllocationContext<'_>,
+    ) {
+        // Calculate exact order_size from the fraction of current equity, then round it to a more
+        // appropriate decimal place
+        let order_value = equity.current.max(0.0) * self.equity_pct(trades);
+        let order_size = order.contract_type.quantity(
+            order_value,
+            order.market_meta.close,
+            order.contract_multiplier,
+        );
+        let order_size = (order_size * 10000.0).floor() / 10000.0;
+
+        match order.decision {
+            // Entry
+            Decision::Long => order.quantity = order_size * signal_strength.0,
+
+            // Entry
+            Decision::Short => order.quantity = -order_size * signal_strength.0,
+
+            // Exit
+            _ => order.quantity = 0.0 - position.as_ref().unwrap().quantity,
+        }
+    }
+}
+
+impl KellyAllocator {
+    /// Fraction of the current equity allocated to an entry order, given the [`WinLossSummary`]
+    /// of the Portfolio's closed trades.
+    pub fn equity_pct(&self, trades: &WinLossSummary) -> f64 {
+        if trades.trades() < self.min_trades.max(1) {
+            return self.fallback_equity_pct;
+        }
+
+        let win_rate = trades.win_rate();
+        let kelly = match (trades.losses, trades.payoff_ratio()) {
+            (0, _) => win_rate,
+            (_, Some(payoff_ratio)) => win_rate - (1.0 - win_rate) / payoff_ratio,
+            (_, None) => 0.0,
+        };
+
+        (self.kelly_fraction * kelly).clamp(0.0, 1.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::{order_event, position};
+    use crate::{
+        statistic::summary::PositionSummariser,
+        test_util::{closed_position, order_event, position},
+    };
 
     #[test]
     fn should_allocate_order_to_exit_open_long_position() {
@@ -68,8 +262,12 @@ mod tests {
 
         allocator.allocate_order(
             &mut input_order,
-            Some(&input_position),
-            input_signal_strength,
+            AllocationContext {
+                position: Some(&input_position),
+                signal_strength: input_signal_strength,
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
         );
 
         let actual_result = input_order.quantity;
@@ -94,8 +292,12 @@ mod tests {
 
         allocator.allocate_order(
             &mut input_order,
-            Some(&input_position),
-            input_signal_strength,
+            AllocationContext {
+                position: Some(&input_position),
+                signal_strength: input_signal_strength,
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
         );
 
         let actual_result = input_order.quantity;
@@ -118,7 +320,15 @@ mod tests {
 
         let input_signal_strength = SignalStrength(1.0);
 
-        allocator.allocate_order(&mut input_order, None, input_signal_strength);
+        allocator.allocate_order(
+            &mut input_order,
+            AllocationContext {
+                position: None,
+                signal_strength: input_signal_strength,
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
+        );
 
         let actual_result = input_order.quantity;
         let expected_result = (default_order_value / order_close) * input_signal_strength.0 as f64;
@@ -140,7 +350,15 @@ mod tests {
 
         let input_signal_strength = SignalStrength(1.0);
 
-        allocator.allocate_order(&mut input_order, None, input_signal_strength);
+        allocator.allocate_order(
+            &mut input_order,
+            AllocationContext {
+                position: None,
+                signal_strength: input_signal_strength,
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
+        );
 
         let actual_result = input_order.quantity;
         let expected_order_size = ((default_order_value / order_close) * 10000.0).floor() / 10000.0;
@@ -164,7 +382,15 @@ mod tests {
 
         let input_signal_strength = SignalStrength(1.0);
 
-        allocator.allocate_order(&mut input_order, None, input_signal_strength);
+        allocator.allocate_order(
+            &mut input_order,
+            AllocationContext {
+                position: None,
+                signal_strength: input_signal_strength,
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
+        );
 
         let actual_result = input_order.quantity;
         let expected_result = -(default_order_value / order_close) * input_signal_strength.0 as f64;
@@ -186,7 +412,15 @@ mod tests {
 
         let input_signal_strength = SignalStrength(1.0);
 
-        allocator.allocate_order(&mut input_order, None, input_signal_strength);
+        allocator.allocate_order(
+            &mut input_order,
+            AllocationContext {
+                position: None,
+                signal_strength: input_signal_strength,
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
+        );
 
         let actual_result = input_order.quantity;
         let expected_order_size = ((default_order_value / order_close) * 10000.0).floor() / 10000.0;
@@ -195,4 +429,233 @@ mod tests {
         assert_ne!(actual_result, 0.0);
         assert_eq!(actual_result, expected_result)
     }
+
+    #[test]
+    fn should_allocate_order_to_enter_long_position_in_contracts_with_contract_multiplier() {
+        let allocator = DefaultAllocator {
+            default_order_value: 10000.0,
+        };
+
+        let mut input_order = order_event();
+        input_order.market_meta.close = 100.0;
+        input_order.contract_multiplier = 50.0;
+        input_order.decision = Decision::Long;
+
+        allocator.allocate_order(
+            &mut input_order,
+            AllocationContext {
+                position: None,
+                signal_strength: SignalStrength(1.0),
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
+        );
+
+        // 10000.0 / (100.0 * 50.0) = 2.0 contracts
+        assert_eq!(input_order.quantity, 2.0)
+    }
+
+    #[test]
+    fn should_allocate_order_as_percent_of_current_equity() {
+        let allocator = PercentEquityAllocator {
+            order_equity_pct: 0.1,
+        };
+        let allocate = |decision, current_equity| {
+            let mut input_order = order_event();
+            input_order.market_meta.close = 10.0;
+            input_order.decision = decision;
+
+            allocator.allocate_order(
+                &mut input_order,
+                AllocationContext {
+                    position: None,
+                    signal_strength: SignalStrength(0.5),
+                    equity: &EquitySnapshot {
+                        current: current_equity,
+                        peak: current_equity,
+                        ..Default::default()
+                    },
+                    trades: &WinLossSummary::default(),
+                },
+            );
+            input_order.quantity
+        };
+
+        // 10% of 1000.0 equity = 100.0 order value, at 10.0 close & 0.5 signal strength
+        assert_eq!(allocate(Decision::Long, 1000.0), 5.0);
+        assert_eq!(allocate(Decision::Short, 1000.0), -5.0);
+
+        // Position size scales with the account
+        assert_eq!(allocate(Decision::Long, 2000.0), 10.0);
+        assert_eq!(allocate(Decision::Long, 500.0), 2.5);
+        assert_eq!(allocate(Decision::Long, -100.0), 0.0);
+    }
+
+    #[test]
+    fn should_allocate_percent_equity_order_to_exit_open_position() {
+        let allocator = PercentEquityAllocator {
+            order_equity_pct: 0.1,
+        };
+
+        let mut input_order = order_event();
+        input_order.decision = Decision::CloseLong;
+
+        let mut input_position = position();
+        input_position.quantity = 3.0;
+
+        allocator.allocate_order(
+            &mut input_order,
+            AllocationContext {
+                position: Some(&input_position),
+                signal_strength: SignalStrength(1.0),
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
+        );
+
+        assert_eq!(input_order.quantity, -3.0)
+    }
+
+    #[test]
+    fn should_allocate_smaller_volatility_adjusted_order_in_high_volatility_market() {
+        let allocator = VolatilityAllocator {
+            risk_per_trade: 100.0,
+        };
+        let allocate = |decision, volatility| {
+            let mut input_order = order_event();
+            input_order.market_meta.close = 1000.0;
+            input_order.market_meta.volatility = volatility;
+            input_order.decision = decision;
+
+            allocator.allocate_order(
+                &mut input_order,
+                AllocationContext {
+                    position: None,
+                    signal_strength: SignalStrength(1.0),
+                    equity: &EquitySnapshot::default(),
+                    trades: &WinLossSummary::default(),
+                },
+            );
+            input_order.quantity
+        };
+
+        // Risk 100.0 per trade / ATR of 20.0 = 5.0 units, vs / ATR of 80.0 = 1.25 units
+        let low_volatility = allocate(Decision::Long, Some(20.0));
+        let high_volatility = allocate(Decision::Long, Some(80.0));
+        assert_eq!(low_volatility, 5.0);
+        assert_eq!(high_volatility, 1.25);
+        assert!(high_volatility < low_volatility);
+        assert_eq!(allocate(Decision::Short, Some(80.0)), -1.25);
+
+        // No volatility estimate allocates no quantity
+        assert_eq!(allocate(Decision::Long, None), 0.0);
+        assert_eq!(allocate(Decision::Long, Some(0.0)), 0.0);
+    }
+
+    #[test]
+    fn should_allocate_volatility_adjusted_order_in_contracts_with_contract_multiplier() {
+        let allocator = VolatilityAllocator {
+            risk_per_trade: 1000.0,
+        };
+
+        let mut input_order = order_event();
+        input_order.market_meta.close = 100.0;
+        input_order.market_meta.volatility = Some(2.0);
+        input_order.contract_multiplier = 50.0;
+        input_order.decision = Decision::Long;
+
+        allocator.allocate_order(
+            &mut input_order,
+            AllocationContext {
+                position: None,
+                signal_strength: SignalStrength(1.0),
+                equity: &EquitySnapshot::default(),
+                trades: &WinLossSummary::default(),
+            },
+        );
+
+        // 1000.0 / 2.0 = 500.0 units of the underlying = 10.0 contracts of 50.0 units
+        assert_eq!(input_order.quantity, 10.0)
+    }
+
+    #[test]
+    fn kelly_allocator_uses_fallback_until_min_trades_exist() {
+        let allocator = KellyAllocator {
+            kelly_fraction: 0.5,
+            min_trades: 10,
+            fallback_equity_pct: 0.01,
+        };
+
+        let trades = WinLossSummary {
+            wins: 5,
+            losses: 4,
+            gross_profit: 100.0,
+            gross_loss: 40.0,
+        };
+        assert_eq!(allocator.equity_pct(&WinLossSummary::default()), 0.01);
+        assert_eq!(allocator.equity_pct(&trades), 0.01);
+    }
+
+    #[test]
+    fn kelly_allocator_sizes_orders_with_fractional_kelly_criterion() {
+        let allocator = KellyAllocator {
+            kelly_fraction: 0.5,
+            min_trades: 8,
+            fallback_equity_pct: 0.01,
+        };
+
+        // W = 0.75, R = (120.0 / 6) / (20.0 / 2) = 2.0, f* = 0.75 - 0.25 / 2.0 = 0.625
+        let mut trades = WinLossSummary::default();
+        for realised_profit_loss in [20.0, 20.0, 20.0, 20.0, 20.0, 20.0, -10.0, -10.0] {
+            trades.update(&closed_position(realised_profit_loss));
+        }
+        assert_eq!(allocator.equity_pct(&trades), 0.3125);
+
+        let mut input_order = order_event();
+        input_order.market_meta.close = 10.0;
+        input_order.decision = Decision::Long;
+        allocator.allocate_order(
+            &mut input_order,
+            AllocationContext {
+                position: None,
+                signal_strength: SignalStrength(1.0),
+                equity: &EquitySnapshot {
+                    current: 1000.0,
+                    peak: 1000.0,
+                    ..Default::default()
+                },
+                trades: &trades,
+            },
+        );
+
+        // Half-Kelly 31.25% of 1000.0 equity = 312.5 order value, at 10.0 close
+        assert_eq!(input_order.quantity, 31.25);
+    }
+
+    #[test]
+    fn kelly_allocator_allocates_nothing_with_negative_edge() {
+        let allocator = KellyAllocator {
+            kelly_fraction: 1.0,
+            min_trades: 2,
+            fallback_equity_pct: 0.01,
+        };
+
+        // W = 0.25, R = 1.0, f* = 0.25 - 0.75 = -0.5
+        let losing = WinLossSummary {
+            wins: 1,
+            losses: 3,
+            gross_profit: 10.0,
+            gross_loss: 30.0,
+        };
+        assert_eq!(allocator.equity_pct(&losing), 0.0);
+
+        // Without losses f* = W = 1.0
+        let winning = WinLossSummary {
+            wins: 4,
+            losses: 0,
+            gross_profit: 40.0,
+            gross_loss: 0.0,
+        };
+        assert_eq!(allocator.equity_pct(&winning), 1.0);
+    }
 }
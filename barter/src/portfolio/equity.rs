@@ -0,0 +1,406 @@
+use crate::{
+    portfolio::{position::PositionId, risk::EquitySnapshot},
+    statistic::metric::{returns::TimeWeightedReturn, EquityPoint},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, VecDeque},
+};
+
+/// In-memory mark-to-market equity curve of a Portfolio. Equity is marked once per bar as the
+/// realised total equity plus the unrealised P&L of every open
+/// [`Position`](super::position::Position). Market updates of several markets sharing the same
+/// bar time update a single mark.
+///
+/// If `max_marks` is configured, only the most recent marks are retained so the memory used by
+/// a long running (eg/ live) Portfolio is bounded.
+///
+/// External [`CashFlow`]s (deposits & withdrawals) change equity without being trading P&L, so
+/// they are recorded alongside the marks & excluded from the [`EquityCurve::bar_returns`].
+///
+/// Note: the curve is tracked for the current trading session only, and is not persisted in the
+/// Portfolio's repository.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct EquityCurve {
+    /// Total equity excluding open [`Position`](super::position::Position)s - equates to
+    /// [`Balance.total`](super::Balance).
+    pub realised_total: f64,
+    /// Latest unrealised P&L of every open [`Position`](super::position::Position).
+    pub unrealised: HashMap<PositionId, f64>,
+    /// Equity marks in the order they were recorded.
+    pub marks: VecDeque<EquityPoint>,
+    /// Maximum number of marks retained, evicting the oldest marks first. `None` retains every
+    /// mark.
+    #[serde(default)]
+    pub max_marks: Option<usize>,
+    /// Number of marks evicted from the front of `marks` due to the `max_marks` limit.
+    #[serde(default)]
+    pub marks_evicted: usize,
+    /// External [`CashFlow`]s in the order they were applied.
+    pub cash_flows: Vec<CashFlow>,
+    /// [`TimeWeightedReturn`] of the marked equity, chained around each [`CashFlow`].
+    pub time_weighted_return: TimeWeightedReturn,
+    /// Highest marked equity, starting at the starting equity & shifted by each [`CashFlow`] so
+    /// deposits & withdrawals are not counted as drawdown. See [`EquityCurve::peak`].
+    pub peak_marked: f64,
+    /// Realised P&L since the last UTC midnight, as determined by fill timestamps.
+    #[serde(default)]
+    pub daily_profit_loss: DailyProfitLoss,
+}
+
+/// Realised P&L of a Portfolio during a single UTC day, reset by the first P&L realised on the
+/// next day.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct DailyProfitLoss {
+    /// UTC day the P&L was realised on, or `None` if no P&L has been realised.
+    pub day: Option<NaiveDate>,
+    pub realised: f64,
+}
+
+impl DailyProfitLoss {
+    /// Accumulates P&L realised at the provided time, resetting the realised P&L if the time is
+    /// on a later UTC day. P&L realised on an earlier UTC day is ignored.
+    pub fn update(&mut self, time: DateTime<Utc>, profit_loss: f64) {
+        let day = time.date_naive();
+        match self.day.map(|current| day.cmp(&current)) {
+            Some(Ordering::Less) => {}
+            Some(Ordering::Equal) => self.realised += profit_loss,
+            None | Some(Ordering::Greater) => {
+                self.day = Some(day);
+                self.realised = profit_loss;
+            }
+        }
+    }
+
+    /// P&L realised on the UTC day of the provided time, which is 0.0 if the last P&L was
+    /// realised on another day.
+    pub fn realised_on(&self, time: DateTime<Utc>) -> f64 {
+        match self.day == Some(time.date_naive()) {
+            true => self.realised,
+            false => 0.0,
+        }
+    }
+}
+
+/// External cash deposit (+ve amount) or withdrawal (-ve amount) applied to a Portfolio.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CashFlow {
+    pub time: DateTime<Utc>,
+    pub amount: f64,
+    /// Number of [`EquityCurve`] marks recorded (including any since evicted) before the
+    /// [`CashFlow`] was applied. The [`CashFlow`] is attributed to the bar ending at the next
+    /// mark.
+    pub marks_before: usize,
+}
+
+impl EquityCurve {
+    /// Constructs a new [`EquityCurve`] using the starting equity as the realised total.
+    pub fn new(starting_equity: f64) -> Self {
+        Self {
+            realised_total: starting_equity,
+            unrealised: HashMap::new(),
+            marks: VecDeque::new(),
+            max_marks: None,
+            marks_evicted: 0,
+            cash_flows: Vec::new(),
+            time_weighted_return: TimeWeightedReturn::init(starting_equity),
+            peak_marked: starting_equity,
+            daily_profit_loss: DailyProfitLoss::default(),
+        }
+    }
+
+    /// Retain at most the provided number of the most recent marks. `None` retains every mark.
+    pub fn set_max_marks(&mut self, max_marks: Option<usize>) {
+        self.max_marks = max_marks;
+        self.evict_marks();
+    }
+
+    /// Number of marks recorded, including any since evicted.
+    fn marks_recorded(&self) -> usize {
+        self.marks_evicted + self.marks.len()
+    }
+
+    /// Current mark-to-market equity.
+    pub fn current(&self) -> f64 {
+        self.realised_total + self.unrealised.values().sum::<f64>()
+    }
+
+    /// Peak mark-to-market equity, including the current equity if it has not been marked yet.
+    pub fn peak(&self) -> f64 {
+        self.peak_marked.max(self.current())
+    }
+
+    /// [`EquitySnapshot`] of the current & peak mark-to-market equity, and the
+    /// [`DailyProfitLoss`].
+    pub fn snapshot(&self) -> EquitySnapshot {
+        EquitySnapshot {
+            current: self.current(),
+            peak: self.peak(),
+            daily_profit_loss: self.daily_profit_loss,
+        }
+    }
+
+    /// Upsert the latest unrealised P&L of an open [`Position`](super::position::Position).
+    pub fn update_unrealised(&mut self, position_id: &PositionId, unrealised_profit_loss: f64) {
+        self.unrealised
+            .insert(position_id.clone(), unrealised_profit_loss);
+    }
+
+    /// Removes an exited [`Position`](super::position::Position) & sets the new realised total.
+    pub fn update_realised(&mut self, exited_position_id: &PositionId, realised_total: f64) {
+        self.unrealised.remove(exited_position_id);
+        self.realised_total = realised_total;
+    }
+
+    /// Records P&L realised by a fill at the provided time in the [`DailyProfitLoss`].
+    pub fn record_realised_profit_loss(&mut self, time: DateTime<Utc>, profit_loss: f64) {
+        self.daily_profit_loss.update(time, profit_loss);
+    }
+
+    /// Applies an external cash deposit (+ve amount) or withdrawal (-ve amount) to the realised
+    /// total, and records the [`CashFlow`] so it can be excluded from returns.
+    pub fn apply_cash_flow(&mut self, amount: f64, time: DateTime<Utc>) {
+        self.time_weighted_return
+            .apply_cash_flow(self.current(), amount);
+        self.realised_total += amount;
+        self.peak_marked += amount;
+        self.cash_flows.push(CashFlow {
+            time,
+            amount,
+            marks_before: self.marks_recorded(),
+        });
+    }
+
+    /// Records the current mark-to-market equity at the provided time. If the latest mark has the
+    /// same time (eg/ a market update of another market on the same bar) & no [`CashFlow`] has
+    /// been applied since, it is updated rather than recording another mark.
+    pub fn mark(&mut self, time: DateTime<Utc>) {
+        let total = self.current();
+        self.peak_marked = self.peak_marked.max(total);
+        self.time_weighted_return.update(total);
+
+        let cash_flow_since_mark = self
+            .cash_flows
+            .last()
+            .is_some_and(|cash_flow| cash_flow.marks_before == self.marks_recorded());
+
+        match self.marks.back_mut() {
+            Some(latest) if latest.time == time && !cash_flow_since_mark => latest.total = total,
+            _ => {
+                self.marks.push_back(EquityPoint { time, total });
+                self.evict_marks();
+            }
+        }
+    }
+
+    /// Evict the oldest marks exceeding `max_marks`, along with the [`CashFlow`]s only relevant
+    /// to the returns of evicted bars.
+    fn evict_marks(&mut self) {
+        let Some(max_marks) = self.max_marks else {
+            return;
+        };
+
+        while self.marks.len() > max_marks {
+            self.marks.pop_front();
+            self.marks_evicted += 1;
+        }
+
+        let marks_evicted = self.marks_evicted;
+        self.cash_flows
+            .retain(|cash_flow| cash_flow.marks_before > marks_evicted);
+    }
+
+    /// Discrete per-bar returns, calculated as the fractional change in equity between
+    /// consecutive marks. Each return is timestamped with the later mark's time.
+    ///
+    /// Any [`CashFlow`]s applied between two marks are added to the earlier mark's equity (ie/
+    /// treated as available from the start of the bar), so deposits & withdrawals are not counted
+    /// as performance. Compounding these returns approximates the [`TimeWeightedReturn`], which
+    /// instead values the Portfolio at the exact moment of each [`CashFlow`].
+    ///
+    /// The first mark has no prior equity to compare against, so it yields no return. A return
+    /// from a bar starting with zero equity is undefined & reported as 0.0.
+    pub fn bar_returns(&self) -> Vec<(DateTime<Utc>, f64)> {
+        self.marks
+            .iter()
+            .zip(self.marks.iter().skip(1))
+            .enumerate()
+            .map(|(index, (prev, next))| {
+                let net_cash_flow = self.net_cash_flow_before_mark(self.marks_evicted + index + 1);
+                let start = prev.total + net_cash_flow;
+                let bar_return = match start == 0.0 {
+                    true => 0.0,
+                    false => (next.total - start) / start,
+                };
+                (next.time, bar_return)
+            })
+            .collect()
+    }
+
+    /// Net [`CashFlow`] applied after the previous mark & before the mark at the provided index
+    /// (including any marks since evicted).
+    fn net_cash_flow_before_mark(&self, mark_index: usize) -> f64 {
+        self.cash_flows
+            .iter()
+            .filter(|cash_flow| cash_flow.marks_before == mark_index)
+            .map(|cash_flow| cash_flow.amount)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::ops::Add;
+
+    #[test]
+    fn equity_curve_bar_returns_with_no_marks() {
+        let curve = EquityCurve::new(100.0);
+        assert!(curve.bar_returns().is_empty());
+    }
+
+    #[test]
+    fn equity_curve_bar_returns_with_first_mark_only() {
+        let mut curve = EquityCurve::new(100.0);
+        curve.mark(Utc::now());
+        assert!(curve.bar_returns().is_empty());
+    }
+
+    #[test]
+    fn equity_curve_bar_returns() {
+        let base_time = Utc::now();
+        let position_id = "position".to_owned();
+        let mut curve = EquityCurve::new(100.0);
+
+        // Bar 0: 100.0 equity
+        curve.mark(base_time);
+
+        // Bar 1: open Position +10.0 unrealised -> 110.0 equity
+        curve.update_unrealised(&position_id, 10.0);
+        curve.mark(base_time.add(Duration::minutes(1)));
+
+        // Bar 2: open Position -1.0 unrealised -> 99.0 equity
+        curve.update_unrealised(&position_id, -1.0);
+        curve.mark(base_time.add(Duration::minutes(2)));
+
+        // Bar 3: exited Position realised 21.0 -> 121.0 equity
+        curve.update_realised(&position_id, 121.0);
+        curve.mark(base_time.add(Duration::minutes(3)));
+
+        let expected = vec![
+            (base_time.add(Duration::minutes(1)), 0.1),
+            (base_time.add(Duration::minutes(2)), -0.1),
+            (base_time.add(Duration::minutes(3)), 22.0 / 99.0),
+        ];
+
+        let actual = curve.bar_returns();
+        assert_eq!(actual.len(), expected.len());
+        for ((actual_time, actual), (expected_time, expected)) in actual.into_iter().zip(expected) {
+            assert_eq!(actual_time, expected_time);
+            assert!((actual - expected).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn equity_curve_bar_returns_exclude_cash_flows() {
+        let base_time = Utc::now();
+        let position_id = "position".to_owned();
+        let mut curve = EquityCurve::new(100.0);
+
+        // Bar 0: 100.0 equity
+        curve.mark(base_time);
+
+        // Bar 1: deposit 100.0 & open Position +20.0 unrealised -> 220.0 equity
+        curve.apply_cash_flow(100.0, base_time.add(Duration::seconds(30)));
+        curve.update_unrealised(&position_id, 20.0);
+        curve.mark(base_time.add(Duration::minutes(1)));
+
+        // Bar 2: withdraw 110.0 -> 110.0 equity
+        curve.apply_cash_flow(-110.0, base_time.add(Duration::seconds(90)));
+        curve.mark(base_time.add(Duration::minutes(2)));
+
+        assert_eq!(curve.current(), 110.0);
+        assert_eq!(curve.cash_flows.len(), 2);
+
+        // Bar 1: 200.0 -> 220.0, Bar 2: 110.0 -> 110.0
+        let actual = curve.bar_returns();
+        assert!((actual[0].1 - 0.1).abs() < 1e-10);
+        assert!((actual[1].1 - 0.0).abs() < 1e-10);
+
+        // Deposit valued at 100.0 (+0%), withdrawal valued at 220.0 (+10%)
+        let twr = curve.time_weighted_return.time_weighted_return();
+        assert!((twr - 0.1).abs() < 1e-10);
+    }
+
+    #[test]
+    fn equity_curve_marks_once_per_bar_and_retains_max_marks() {
+        let base_time = Utc::now();
+        let position_id = "position".to_owned();
+        let mut curve = EquityCurve::new(100.0);
+        curve.set_max_marks(Some(2));
+
+        // Market updates of two markets on the same bar update a single mark
+        curve.mark(base_time);
+        curve.update_unrealised(&position_id, 10.0);
+        curve.mark(base_time);
+        assert_eq!(curve.marks.len(), 1);
+        assert_eq!(curve.marks[0].total, 110.0);
+
+        // Deposit 100.0 during bar 1, then the oldest mark is evicted by bar 2
+        curve.apply_cash_flow(100.0, base_time.add(Duration::seconds(30)));
+        curve.mark(base_time.add(Duration::minutes(1)));
+        curve.update_unrealised(&position_id, 31.0);
+        curve.mark(base_time.add(Duration::minutes(2)));
+        assert_eq!(curve.marks.len(), 2);
+        assert!(curve.cash_flows.is_empty());
+
+        // Bar 2: 210.0 -> 231.0
+        let actual = curve.bar_returns();
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].0, base_time.add(Duration::minutes(2)));
+        assert!((actual[0].1 - 0.1).abs() < 1e-10);
+    }
+
+    #[test]
+    fn equity_curve_bar_returns_after_zero_equity_mark() {
+        let mut curve = EquityCurve::new(0.0);
+        curve.mark(Utc::now());
+        curve.realised_total = 10.0;
+        curve.mark(Utc::now());
+
+        assert_eq!(curve.bar_returns()[0].1, 0.0);
+    }
+
+    #[test]
+    fn equity_curve_peak_tracks_highest_equity_excluding_cash_flows() {
+        let position_id = "position".to_owned();
+        let mut curve = EquityCurve::new(100.0);
+        assert_eq!(curve.peak(), 100.0);
+
+        // Marked at 130.0, then falls to 110.0
+        curve.update_unrealised(&position_id, 30.0);
+        curve.mark(Utc::now());
+        curve.update_unrealised(&position_id, 10.0);
+        curve.mark(Utc::now());
+        assert_eq!(
+            curve.snapshot(),
+            EquitySnapshot {
+                current: 110.0,
+                peak: 130.0,
+                daily_profit_loss: DailyProfitLoss::default(),
+            }
+        );
+
+        // Withdrawal of 50.0 shifts the peak rather than counting as drawdown
+        curve.apply_cash_flow(-50.0, Utc::now());
+        assert_eq!(curve.peak(), 80.0);
+        assert_eq!(curve.current(), 60.0);
+
+        // Unmarked current equity above the peak is included
+        curve.update_unrealised(&position_id, 100.0);
+        assert_eq!(curve.peak(), 150.0);
+    }
+}
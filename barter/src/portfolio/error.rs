@@ -1,5 +1,7 @@
 use crate::portfolio::repository::error::RepositoryError;
+use barter_integration::model::instrument::symbol::Symbol;
 use thiserror::Error;
+use uuid::Uuid;
 
 /// All errors generated in the barter::portfolio module.
 #[derive(Error, Debug)]
@@ -16,9 +18,46 @@ pub enum PortfolioError {
     #[error("Cannot exit Position with an entry decision FillEvent.")]
     CannotExitPositionWithEntryFill,
 
+    #[error("Cannot add to Position with an exit or opposite side FillEvent.")]
+    CannotAddToPositionWithFill,
+
     #[error("Cannot generate PositionExit from Position that has not been exited")]
     PositionExit,
 
+    #[error("Cannot withdraw {withdrawal} with only {available} available cash")]
+    InsufficientAvailableCash { available: f64, withdrawal: f64 },
+
+    #[error(
+        "Cannot limit to {0} Positions per market since Positions are netted, at most 1 may be open"
+    )]
+    UnsupportedMaxPositionsPerMarket(usize),
+
+    #[error("Cannot trade with {0} leverage, leverage must be finite & at least 1.0")]
+    InvalidLeverage(f64),
+
+    #[error("No FX rate available to convert {from} into {to}")]
+    MissingFxRate { from: Symbol, to: Symbol },
+
+    #[error(
+        "Cannot restore a snapshot of Engine {snapshot} into the Portfolio of Engine {portfolio}"
+    )]
+    SnapshotEngineMismatch { portfolio: Uuid, snapshot: Uuid },
+
+    #[error(
+        "Cannot restore a snapshot that does not extend the exited Positions already persisted"
+    )]
+    SnapshotExitedPositionsConflict,
+
     #[error("Failed to interact with repository")]
     RepositoryInteraction(#[from] RepositoryError),
 }
+
+impl PortfolioError {
+    /// Determines if the [`PortfolioError`] was caused by the repository being unavailable.
+    pub fn is_repository_unavailable(&self) -> bool {
+        matches!(
+            self,
+            PortfolioError::RepositoryInteraction(RepositoryError::Unavailable)
+        )
+    }
+}
@@ -0,0 +1,155 @@
+use crate::portfolio::{error::PortfolioError, ContractType};
+use barter_integration::model::instrument::{symbol::Symbol, Instrument};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+};
+
+/// Provides the exchange rates used to convert amounts between currencies.
+pub trait FxRateProvider {
+    /// Returns the rate converting one unit of the `from` currency into the `to` currency, if it
+    /// is known.
+    fn rate(&self, from: &Symbol, to: &Symbol) -> Option<f64>;
+}
+
+/// [`FxRateProvider`] backed by a fixed table of exchange rates.
+///
+/// A currency converts into itself at 1.0, and a rate provided for one direction is inverted to
+/// convert in the other direction.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct StaticFxRates {
+    rates: HashMap<(Symbol, Symbol), f64>,
+}
+
+impl FxRateProvider for StaticFxRates {
+    fn rate(&self, from: &Symbol, to: &Symbol) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+
+        self.rates
+            .get(&(from.clone(), to.clone()))
+            .copied()
+            .or_else(|| {
+                self.rates
+                    .get(&(to.clone(), from.clone()))
+                    .map(|rate| 1.0 / rate)
+            })
+    }
+}
+
+impl StaticFxRates {
+    /// Constructs a new [`StaticFxRates`] with no exchange rates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the rate converting one unit of the `from` currency into the `to` currency.
+    pub fn with_rate<S>(mut self, from: S, to: S, rate: f64) -> Self
+    where
+        S: Into<Symbol>,
+    {
+        self.set_rate(from, to, rate);
+        self
+    }
+
+    /// Upsert the rate converting one unit of the `from` currency into the `to` currency (eg/ as
+    /// new market data arrives).
+    pub fn set_rate<S>(&mut self, from: S, to: S, rate: f64)
+    where
+        S: Into<Symbol>,
+    {
+        let (from, to) = (from.into(), to.into());
+        self.rates.remove(&(to.clone(), from.clone()));
+        self.rates.insert((from, to), rate);
+    }
+}
+
+/// Base currency a multi-currency Portfolio reports it's equity in, and the [`FxRateProvider`]
+/// used to convert each settlement currency into it.
+pub struct FxConversion {
+    pub base_currency: Symbol,
+    pub rates: Box<dyn FxRateProvider + Send>,
+}
+
+impl Debug for FxConversion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FxConversion")
+            .field("base_currency", &self.base_currency)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FxConversion {
+    /// Constructs a new [`FxConversion`] into the provided base currency.
+    pub fn new<S, Rates>(base_currency: S, rates: Rates) -> Self
+    where
+        S: Into<Symbol>,
+        Rates: FxRateProvider + Send + 'static,
+    {
+        Self {
+            base_currency: base_currency.into(),
+            rates: Box::new(rates),
+        }
+    }
+
+    /// Returns the rate converting one unit of the provided currency into the base currency.
+    ///
+    /// Returns a [`PortfolioError::MissingFxRate`] if the [`FxRateProvider`] has no such rate.
+    pub fn rate_to_base(&self, currency: &Symbol) -> Result<f64, PortfolioError> {
+        self.rates
+            .rate(currency, &self.base_currency)
+            .ok_or_else(|| PortfolioError::MissingFxRate {
+                from: currency.clone(),
+                to: self.base_currency.clone(),
+            })
+    }
+}
+
+/// Determines the currency the notional values, fees & P&L of an [`Instrument`] are settled in.
+/// [`ContractType::Linear`] contracts settle in the quote currency, and [`ContractType::Inverse`]
+/// contracts in the base currency.
+pub fn settlement_currency(instrument: &Instrument, contract_type: ContractType) -> &Symbol {
+    match contract_type {
+        ContractType::Linear => &instrument.quote,
+        ContractType::Inverse => &instrument.base,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::instrument::kind::InstrumentKind;
+
+    #[test]
+    fn static_fx_rates_convert_identity_direct_and_inverse_rates() {
+        let rates = StaticFxRates::new().with_rate("eur", "usd", 1.25);
+
+        let (usd, eur, btc) = (Symbol::new("usd"), Symbol::new("eur"), Symbol::new("btc"));
+        assert_eq!(rates.rate(&btc, &btc), Some(1.0));
+        assert_eq!(rates.rate(&eur, &usd), Some(1.25));
+        assert_eq!(rates.rate(&usd, &eur), Some(0.8));
+        assert_eq!(rates.rate(&btc, &usd), None);
+
+        let fx = FxConversion::new("usd", rates);
+        assert_eq!(fx.rate_to_base(&eur).unwrap(), 1.25);
+        assert!(matches!(
+            fx.rate_to_base(&btc),
+            Err(PortfolioError::MissingFxRate { .. })
+        ));
+    }
+
+    #[test]
+    fn settlement_currency_of_linear_and_inverse_contracts() {
+        let instrument = Instrument::from(("btc", "usd", InstrumentKind::Perpetual));
+
+        assert_eq!(
+            settlement_currency(&instrument, ContractType::Linear),
+            &Symbol::new("usd")
+        );
+        assert_eq!(
+            settlement_currency(&instrument, ContractType::Inverse),
+            &Symbol::new("btc")
+        );
+    }
+}
@@ -2,11 +2,15 @@ use crate::{
     data::MarketMeta,
     event::Event,
     execution::FillEvent,
-    portfolio::{error::PortfolioError, position::PositionUpdate},
+    portfolio::{
+        error::PortfolioError,
+        position::{PositionCounts, PositionUpdate},
+    },
+    statistic::{metric::returns::TimeWeightedReturn, summary::exposure::TimeInMarketSummary},
     strategy::{Decision, Signal, SignalForceExit},
 };
 use barter_data::event::{DataKind, MarketEvent};
-use barter_integration::model::{instrument::Instrument, Exchange};
+use barter_integration::model::{instrument::Instrument, Exchange, Side};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -14,9 +18,16 @@ use uuid::Uuid;
 /// Logic for [`OrderEvent`] quantity allocation.
 pub mod allocator;
 
+/// In-memory mark-to-market equity curve of a Portfolio, used to derive per-bar returns.
+pub mod equity;
+
 /// Barter portfolio module specific errors.
 pub mod error;
 
+/// Conversion of multi-currency Portfolio balances into a base currency via an injectable
+/// [`FxRateProvider`](fx::FxRateProvider).
+pub mod fx;
+
 /// Core Portfolio logic containing an implementation of [`MarketUpdater`],
 /// [`OrderGenerator`] and [`FillUpdater`]. Utilises the risk and allocator logic to optimise
 /// [`OrderEvent`] generation.
@@ -26,21 +37,27 @@ pub mod portfolio;
 /// well as the logic for entering, updating and exiting them.
 pub mod position;
 
+/// Portfolio level rebalancing of [`Position`](position::Position)s to target weights on a
+/// schedule.
+pub mod rebalance;
+
 /// Repositories for persisting Portfolio state.
 pub mod repository;
 
 /// Logic for evaluating the risk associated with a proposed [`OrderEvent`].
 pub mod risk;
 
+/// Serialisable export of the entire Portfolio state, used to restore it after a crash.
+pub mod snapshot;
+
 /// Updates the Portfolio from an input [`MarketEvent`].
 pub trait MarketUpdater {
-    /// Determines if the Portfolio has an open Position relating to the input [`MarketEvent`]. If
-    /// so it updates it using the market data, and returns a [`PositionUpdate`] detailing the
-    /// changes.
+    /// Updates every open Position the Portfolio has relating to the input [`MarketEvent`] using
+    /// the market data, and returns a [`PositionUpdate`] detailing the changes of each.
     fn update_from_market(
         &mut self,
         market: &MarketEvent<Instrument, DataKind>,
-    ) -> Result<Option<PositionUpdate>, PortfolioError>;
+    ) -> Result<Vec<PositionUpdate>, PortfolioError>;
 }
 
 /// May generate an [`OrderEvent`] from an input advisory [`Signal`].
@@ -54,6 +71,25 @@ pub trait OrderGenerator {
         &mut self,
         signal: SignalForceExit,
     ) -> Result<Option<OrderEvent>, PortfolioError>;
+
+    /// Updates the Portfolio with a generated [`OrderEvent`] that expired unfilled, and so will
+    /// never generate a [`FillEvent`].
+    ///
+    /// Defaults to doing nothing.
+    fn update_from_expired_order(&mut self, _order: &OrderEvent) -> Result<(), PortfolioError> {
+        Ok(())
+    }
+
+    /// Generates any entry [`OrderEvent`]s, deferred from [`Signal`]s of previous bars, that are
+    /// due on the bar of the input [`MarketEvent`].
+    ///
+    /// Defaults to generating none.
+    fn generate_delayed_orders(
+        &mut self,
+        _market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Updates the Portfolio from an input [`FillEvent`].
@@ -64,6 +100,43 @@ pub trait FillUpdater {
     fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError>;
 }
 
+/// Reports the time-in-market (exposure duration) of the Portfolio.
+///
+/// The default implementation reports an empty [`TimeInMarketSummary`], so a Portfolio that
+/// does not track exposure only needs an empty `impl` block.
+pub trait ExposureReporter {
+    /// Returns the [`TimeInMarketSummary`] per [`Market`](barter_integration::model::Market) &
+    /// in total, measured as the fraction of [`MarketEvent`] bars during which a
+    /// [`Position`](position::Position) was open.
+    fn time_in_market(&self) -> TimeInMarketSummary {
+        TimeInMarketSummary::default()
+    }
+}
+
+/// Reports the number of [`Position`](position::Position)s the Portfolio has opened & closed.
+///
+/// The default implementation reports zero [`PositionCounts`], so a Portfolio that does not
+/// count Positions only needs an empty `impl` block.
+pub trait PositionCountReporter {
+    /// Returns the [`PositionCounts`] of the Portfolio, maintained as counters so it is cheap to
+    /// call frequently (eg/ for monitoring & risk checks).
+    fn position_counts(&self) -> PositionCounts {
+        PositionCounts::default()
+    }
+}
+
+/// Reports the returns of the Portfolio.
+///
+/// The default implementation reports a zero [`TimeWeightedReturn`], so a Portfolio that does
+/// not track its equity curve only needs an empty `impl` block.
+pub trait ReturnReporter {
+    /// Returns the [`TimeWeightedReturn`] of the Portfolio equity, which excludes the effect of
+    /// external cash flows (deposits & withdrawals).
+    fn time_weighted_return(&self) -> TimeWeightedReturn {
+        TimeWeightedReturn::default()
+    }
+}
+
 /// Orders are generated by the portfolio and details work to be done by an Execution handler to
 /// open a trade.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
@@ -79,6 +152,19 @@ pub struct OrderEvent {
     pub quantity: f64,
     /// MARKET, LIMIT etc
     pub order_type: OrderType,
+    /// Limit price of an [`OrderType::Limit`] order.
+    pub limit_price: Option<f64>,
+    /// Units of the underlying per contract, such that notional = quantity * price *
+    /// contract_multiplier. 1.0 for spot.
+    #[serde(default = "default_contract_multiplier")]
+    pub contract_multiplier: f64,
+    /// Linear or Inverse, determining how the notional & PnL of the contracts are calculated.
+    #[serde(default)]
+    pub contract_type: ContractType,
+    /// Optional sub-strategy tag propagated from the source [`Signal`]. See
+    /// [`PositionKey`](position::PositionKey).
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
 impl OrderEvent {
@@ -91,6 +177,78 @@ impl OrderEvent {
     }
 }
 
+/// Default contract multiplier, where one contract is one unit of the underlying (eg/ spot).
+pub fn default_contract_multiplier() -> f64 {
+    1.0
+}
+
+/// Determines how the notional value & PnL of a contract are calculated from its price.
+///
+/// Notional & PnL are denominated in the settlement currency of the contract, which is the quote
+/// asset for [`ContractType::Linear`] & the base asset for [`ContractType::Inverse`]. A Portfolio
+/// is assumed to hold its balance in the settlement currency of the contracts it trades.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum ContractType {
+    /// Quote-margined contract (eg/ spot or USDT perpetual), where notional = abs(quantity) *
+    /// price * contract_multiplier.
+    #[default]
+    Linear,
+    /// Coin-margined contract (eg/ BTC-USD inverse perpetual) with a face value of
+    /// contract_multiplier units of the quote asset, where notional = abs(quantity) *
+    /// contract_multiplier / price.
+    Inverse,
+}
+
+impl ContractType {
+    /// Notional value of the provided quantity of contracts at the provided price. The notional of
+    /// an [`ContractType::Inverse`] contract at a zero price is undefined, and is 0.0.
+    pub fn notional(&self, quantity: f64, price: f64, contract_multiplier: f64) -> f64 {
+        match self {
+            ContractType::Linear => quantity.abs() * price * contract_multiplier,
+            ContractType::Inverse if price == 0.0 => 0.0,
+            ContractType::Inverse => quantity.abs() * contract_multiplier / price,
+        }
+    }
+
+    /// Average price per unit of the underlying implied by the notional value of the provided
+    /// quantity of contracts. The price of an [`ContractType::Inverse`] contract with a zero
+    /// notional is undefined, and is 0.0.
+    pub fn price(&self, quantity: f64, notional: f64, contract_multiplier: f64) -> f64 {
+        match self {
+            ContractType::Linear => (notional / (quantity * contract_multiplier)).abs(),
+            ContractType::Inverse if notional == 0.0 => 0.0,
+            ContractType::Inverse => (quantity * contract_multiplier / notional).abs(),
+        }
+    }
+
+    /// Number of contracts with the provided notional value at the provided price.
+    pub fn quantity(&self, notional: f64, price: f64, contract_multiplier: f64) -> f64 {
+        match self {
+            ContractType::Linear => notional / (price * contract_multiplier),
+            ContractType::Inverse => notional * price / contract_multiplier,
+        }
+    }
+
+    /// Gross PnL (excluding fees) of a [`Side`] position from the notional value it was entered
+    /// at to the notional value it is exited (or marked) at.
+    ///
+    /// The notional of an [`ContractType::Inverse`] contract falls as the price rises, so a long
+    /// position profits from a fall in notional: PnL = abs(quantity) * contract_multiplier *
+    /// (1 / enter_price - 1 / exit_price).
+    pub fn profit_loss(&self, side: Side, enter_notional: f64, exit_notional: f64) -> f64 {
+        match (self, side) {
+            (ContractType::Linear, Side::Buy) | (ContractType::Inverse, Side::Sell) => {
+                exit_notional - enter_notional
+            }
+            (ContractType::Linear, Side::Sell) | (ContractType::Inverse, Side::Buy) => {
+                enter_notional - exit_notional
+            }
+        }
+    }
+}
+
 /// Type of order the portfolio wants the execution::handler to place.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub enum OrderType {
@@ -115,6 +273,10 @@ pub struct OrderEventBuilder {
     pub decision: Option<Decision>,
     pub quantity: Option<f64>,
     pub order_type: Option<OrderType>,
+    pub limit_price: Option<f64>,
+    pub contract_multiplier: Option<f64>,
+    pub contract_type: Option<ContractType>,
+    pub tag: Option<String>,
 }
 
 impl OrderEventBuilder {
@@ -171,6 +333,34 @@ impl OrderEventBuilder {
         }
     }
 
+    pub fn limit_price(self, value: f64) -> Self {
+        Self {
+            limit_price: Some(value),
+            ..self
+        }
+    }
+
+    pub fn contract_multiplier(self, value: f64) -> Self {
+        Self {
+            contract_multiplier: Some(value),
+            ..self
+        }
+    }
+
+    pub fn contract_type(self, value: ContractType) -> Self {
+        Self {
+            contract_type: Some(value),
+            ..self
+        }
+    }
+
+    pub fn tag(self, value: String) -> Self {
+        Self {
+            tag: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<OrderEvent, PortfolioError> {
         Ok(OrderEvent {
             time: self.time.ok_or(PortfolioError::BuilderIncomplete("time"))?,
@@ -192,6 +382,12 @@ impl OrderEventBuilder {
             order_type: self
                 .order_type
                 .ok_or(PortfolioError::BuilderIncomplete("order_type"))?,
+            limit_price: self.limit_price,
+            contract_multiplier: self
+                .contract_multiplier
+                .unwrap_or_else(default_contract_multiplier),
+            contract_type: self.contract_type.unwrap_or_default(),
+            tag: self.tag,
         })
     }
 }
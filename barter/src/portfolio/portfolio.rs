@@ -1,27 +1,46 @@
 use super::{
-    allocator::OrderAllocator,
+    allocator::{AllocationContext, OrderAllocator},
+    default_contract_multiplier,
+    equity::EquityCurve,
     error::PortfolioError,
+    fx::{settlement_currency, FxConversion},
     position::{
-        determine_position_id, Position, PositionEnterer, PositionExiter, PositionId,
-        PositionUpdate, PositionUpdater,
+        MarkPrice, MarketPositionKey, Position, PositionAdder, PositionCounts, PositionEnterer,
+        PositionExiter, PositionId, PositionKey, PositionUpdate, PositionUpdater,
     },
+    rebalance::Rebalancer,
     repository::{error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler},
     risk::OrderEvaluator,
-    Balance, FillUpdater, MarketUpdater, OrderEvent, OrderGenerator, OrderType,
+    snapshot::PortfolioSnapshot,
+    Balance, ContractType, ExposureReporter, FillUpdater, MarketUpdater, OrderEvent,
+    OrderGenerator, OrderType, PositionCountReporter, ReturnReporter,
 };
 use crate::{
     data::MarketMeta,
     event::Event,
     execution::FillEvent,
-    statistic::summary::{Initialiser, PositionSummariser},
+    statistic::{
+        metric::returns::TimeWeightedReturn,
+        summary::{
+            exposure::TimeInMarketSummary, win_loss::WinLossSummary, Initialiser,
+            PositionSummariser,
+        },
+        StatisticExclusion, StatisticUpdate,
+    },
     strategy::{Decision, Signal, SignalForceExit, SignalStrength},
 };
 use barter_data::event::{DataKind, MarketEvent};
-use barter_integration::model::{instrument::Instrument, Market, MarketId, Side};
-use chrono::Utc;
-use serde::Serialize;
-use std::{collections::HashMap, marker::PhantomData};
-use tracing::info;
+use barter_integration::model::{
+    instrument::{symbol::Symbol, Instrument},
+    Exchange, Market, MarketId, Side,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::Entry, BTreeSet, HashMap, VecDeque},
+    marker::PhantomData,
+};
+use tracing::{info, warn};
 use uuid::Uuid;
 
 /// Lego components for constructing & initialising a [`MetaPortfolio`] via the init() constructor
@@ -48,6 +67,36 @@ where
     pub risk: RiskManager,
     /// Cash balance a [`MetaPortfolio`] starts with.
     pub starting_cash: f64,
+    /// Leverage applied to every [`Position`], such that entering a [`Position`] of notional N
+    /// consumes N / leverage of available cash as margin. 1.0 is fully-funded.
+    pub leverage: f64,
+    /// Optional base currency & [`FxRateProvider`](super::fx::FxRateProvider) used to track a
+    /// [`Balance`] per settlement currency & report equity in the base currency. If not present,
+    /// every market is assumed to settle in the same currency as the starting cash.
+    pub fx_conversion: Option<FxConversion>,
+    /// Contract multiplier of each [`Market`] traded by a [`MetaPortfolio`], such that notional =
+    /// quantity * price * contract_multiplier. [`Market`]s not present default to 1.0 (eg/ spot).
+    pub contract_multipliers: HashMap<MarketId, f64>,
+    /// [`ContractType`] of each [`Market`] traded by a [`MetaPortfolio`]. [`Market`]s not present
+    /// default to [`ContractType::Linear`].
+    pub contract_types: HashMap<MarketId, ContractType>,
+    /// Maximum number of [`Position`]s per market, counting both the open [`Position`] & any
+    /// entry [`OrderEvent`]s yet to be filled. Positions are netted (one [`PositionId`] per
+    /// market), so this must be 0 (no entries) or 1.
+    pub max_positions_per_market: usize,
+    /// Maximum number of same side entries added to an open [`Position`] (ie/ pyramiding),
+    /// counting both the adds filled & any add [`OrderEvent`]s yet to be filled. Zero disables
+    /// adding to open [`Position`]s.
+    pub max_adds: usize,
+    /// Number of bars an entry [`OrderEvent`] is deferred by after the bar of the [`Signal`] that
+    /// advised it, such that a [`Signal`] on bar t generates an entry on bar t+k. Zero generates
+    /// entries on the [`Signal`] bar. See [`OrderGenerator::generate_delayed_orders`].
+    pub entry_delay_bars: usize,
+    /// Source of the price open [`Position`]s are marked to market at.
+    pub mark_price: MarkPrice,
+    /// Time windows during which per-market statistics are not accumulated. See
+    /// [`MetaPortfolio::is_statistics_excluded`].
+    pub statistic_exclusions: Vec<StatisticExclusion>,
     /// Configuration used to initialise the Statistics for every Market's performance tracked by a
     /// [`MetaPortfolio`].
     pub statistic_config: Statistic::Config,
@@ -57,12 +106,13 @@ where
 /// Portfolio with state persisted in a repository. [`MarketUpdater`], [`OrderGenerator`],
 /// [`FillUpdater`] and [`PositionHandler`].
 #[derive(Debug)]
-pub struct MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+pub struct MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key = MarketPositionKey>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
     Allocator: OrderAllocator,
     RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
 {
     /// Identifier for the [`Engine`](crate::engine::Engine) this Portfolio is associated with (1-to-1 relationship).
     engine_id: Uuid,
@@ -73,83 +123,160 @@ where
     allocation_manager: Allocator,
     /// Risk manager implements [`OrderEvaluator`].
     risk_manager: RiskManager,
+    /// [`Market`]s traded by the Portfolio, used to export it's state in a [`PortfolioSnapshot`].
+    markets: Vec<Market>,
+    /// Leverage applied to every [`Position`]. See [`MetaPortfolio::used_margin`].
+    leverage: f64,
+    /// Margin consumed by the open [`Position`]s, updated on every fill update.
+    used_margin: f64,
+    /// Margin consumed by each open [`Position`] in the base currency, converted at the rate of
+    /// it's entry & add fills, such that exactly this amount is released when it exits.
+    position_margins: HashMap<PositionId, f64>,
+    /// Optional base currency & [`FxRateProvider`](super::fx::FxRateProvider) used to convert
+    /// the settlement currency of every market. If not present, every market is assumed to
+    /// settle in the same currency.
+    fx_conversion: Option<FxConversion>,
+    /// [`Balance`] of each settlement currency, tracked if an [`FxConversion`] is configured.
+    currency_balances: HashMap<Symbol, Balance>,
+    /// Contract multiplier of each [`Market`], defaulting to 1.0 if not present.
+    contract_multipliers: HashMap<MarketId, f64>,
+    /// [`ContractType`] of each [`Market`], defaulting to [`ContractType::Linear`] if not present.
+    contract_types: HashMap<MarketId, ContractType>,
+    /// Maximum number of open [`Position`]s & pending entry [`OrderEvent`]s per market.
+    max_positions_per_market: usize,
+    /// Maximum number of same side entries added to an open [`Position`], including pending adds.
+    max_adds: usize,
+    /// Entry [`OrderEvent`]s generated per [`PositionId`] that are yet to be filled or expire, in
+    /// the order they were generated. See [`MetaPortfolio::available_cash`].
+    pending_entries: HashMap<PositionId, VecDeque<PendingEntry>>,
+    /// Number of bars entry [`OrderEvent`]s are deferred by after the [`Signal`] bar.
+    entry_delay_bars: usize,
+    /// Entry [`Signal`] per [`PositionId`] deferred until its entry delay has elapsed.
+    delayed_entries: HashMap<PositionId, DelayedEntry>,
+    /// Source of the price open [`Position`]s are marked to market at.
+    mark_price: MarkPrice,
+    /// Time windows during which per-market statistics are not accumulated.
+    statistic_exclusions: Vec<StatisticExclusion>,
+    /// In-memory mark-to-market [`EquityCurve`] updated on every market & fill update.
+    equity: EquityCurve,
+    /// Time-in-market of the Portfolio per market & in total, updated on every market update.
+    time_in_market: TimeInMarketSummary,
+    /// Counts of the [`Position`]s opened & closed, updated on every fill update.
+    position_counts: PositionCounts,
+    /// [`WinLossSummary`] of the [`Position`]s closed, updated on every fill update & used to
+    /// size orders by the [`OrderAllocator`]. Held in memory only, so it resets on a process
+    /// restart, unless restored via [`MetaPortfolio::restore`].
+    win_loss: WinLossSummary,
+    /// If true, entry [`OrderEvent`]s are not generated for any market. See
+    /// [`MetaPortfolio::set_close_only`].
+    close_only: bool,
+    /// Sub-strategy tags of the open [`Position`]s of each market, used to find every open
+    /// [`Position`] of a market when they are keyed by a tag aware [`PositionKey`].
+    position_tags: HashMap<MarketId, BTreeSet<String>>,
     _statistic_marker: PhantomData<Statistic>,
+    _position_key_marker: PhantomData<Key>,
+}
+
+/// Currency the values of a market settle in, & the rate converting it into the base currency.
+#[derive(Clone, Debug)]
+struct Settlement {
+    /// Settlement currency, if an [`FxConversion`] is configured.
+    currency: Option<Symbol>,
+    rate: f64,
+}
+
+impl Settlement {
+    /// Converts an amount of the settlement currency into the base currency.
+    fn to_base(&self, amount: f64) -> f64 {
+        amount * self.rate
+    }
+}
+
+/// Relative tolerance within which the quantity filled or expired is deemed to complete a
+/// [`PendingEntry`].
+const PENDING_QUANTITY_TOLERANCE: f64 = 1e-9;
+
+/// Entry [`OrderEvent`] generated by a [`MetaPortfolio`] that is yet to be completely filled or
+/// expire, & the cash reserved for it's remaining quantity.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct PendingEntry {
+    /// Absolute quantity yet to be filled or expire.
+    remaining_quantity: f64,
+    /// Cash reserved for the remaining quantity.
+    reserved_cash: f64,
+    /// If true, a tranche of the [`OrderEvent`] has already been filled.
+    partially_filled: bool,
+}
+
+/// Entry [`Signal`] deferred until the configured number of bars has elapsed.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct DelayedEntry {
+    pub signal: Signal,
+    pub bars_remaining: usize,
 }
 
-impl<Repository, Allocator, RiskManager, Statistic> MarketUpdater
-    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+impl<Repository, Allocator, RiskManager, Statistic, Key> MarketUpdater
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
     Allocator: OrderAllocator,
     RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
 {
     fn update_from_market(
         &mut self,
         market: &MarketEvent<Instrument, DataKind>,
-    ) -> Result<Option<PositionUpdate>, PortfolioError> {
-        // Determine the position_id associated to the input MarketEvent
-        let position_id =
-            determine_position_id(self.engine_id, &market.exchange, &market.instrument);
+    ) -> Result<Vec<PositionUpdate>, PortfolioError> {
+        // Update every open Position the Portfolio has for that Symbol-Exchange combination
+        let mut position_updates = Vec::new();
+        let mut market_in_market = false;
+        for position_id in self.market_position_ids(&market.exchange, &market.instrument) {
+            let mut position = match self.repository.get_open_position(&position_id)? {
+                Some(position) => position,
+                None => continue,
+            };
+            market_in_market = true;
 
-        // Update Position if Portfolio has an open Position for that Symbol-Exchange combination
-        if let Some(mut position) = self.repository.get_open_position(&position_id)? {
             // Derive PositionUpdate event that communicates the open Position's change in state
-            if let Some(position_update) = position.update(market) {
-                // Save updated open Position in the repository
+            if let Some(update) = position.update_with_mark_price(market, self.mark_price) {
+                // Save updated open Position in the repository, marking equity in the base currency
+                let settlement = self.settlement(&position.instrument, position.contract_type)?;
+                self.equity.update_unrealised(
+                    &position_id,
+                    settlement.to_base(position.unrealised_profit_loss),
+                );
                 self.repository.set_open_position(position)?;
-                return Ok(Some(position_update));
+                position_updates.push(update);
             }
         }
 
-        Ok(None)
+        // Mark-to-market the Portfolio equity for this bar
+        self.equity.mark(market.exchange_time);
+
+        // Update time-in-market for this bar, the Portfolio is in the market if any Position is open
+        self.time_in_market.update(
+            MarketId::new(&market.exchange, &market.instrument),
+            market.exchange_time,
+            market_in_market,
+            market_in_market || !self.equity.unrealised.is_empty(),
+        );
+
+        Ok(position_updates)
     }
 }
 
-impl<Repository, Allocator, RiskManager, Statistic> OrderGenerator
-    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+impl<Repository, Allocator, RiskManager, Statistic, Key> OrderGenerator
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
     Allocator: OrderAllocator,
     RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
 {
     fn generate_order(&mut self, signal: &Signal) -> Result<Option<OrderEvent>, PortfolioError> {
-        // Determine the position_id & associated Option<Position> related to input SignalEvent
-        let position_id =
-            determine_position_id(self.engine_id, &signal.exchange, &signal.instrument);
-        let position = self.repository.get_open_position(&position_id)?;
-
-        // If signal is advising to open a new Position rather than close one, check we have cash
-        if position.is_none() && self.no_cash_to_enter_new_position()? {
-            return Ok(None);
-        }
-
-        // Parse signals from Strategy to determine net signal decision & associated strength
-        let position = position.as_ref();
-        let (signal_decision, signal_strength) =
-            match parse_signal_decisions(&position, &signal.signals) {
-                None => return Ok(None),
-                Some(net_signal) => net_signal,
-            };
-
-        // Construct mutable OrderEvent that can be modified by Allocation & Risk management
-        let mut order = OrderEvent {
-            time: Utc::now(),
-            exchange: signal.exchange.clone(),
-            instrument: signal.instrument.clone(),
-            market_meta: signal.market_meta,
-            decision: *signal_decision,
-            quantity: 0.0,
-            order_type: OrderType::default(),
-        };
-
-        // Manage OrderEvent size allocation
-        self.allocation_manager
-            .allocate_order(&mut order, position, *signal_strength);
-
-        // Manage global risk when evaluating OrderEvent - keep the same, refine or cancel
-        Ok(self.risk_manager.evaluate_order(order))
+        self.generate_order_with_entry_delay(signal, self.entry_delay_bars)
     }
 
     fn generate_exit_order(
@@ -158,7 +285,7 @@ where
     ) -> Result<Option<OrderEvent>, PortfolioError> {
         // Determine PositionId associated with the SignalForceExit
         let position_id =
-            determine_position_id(self.engine_id, &signal.exchange, &signal.instrument);
+            self.position_id(&signal.exchange, &signal.instrument, signal.tag.as_deref());
 
         // Retrieve Option<Position> associated with the PositionId
         let position = match self.repository.get_open_position(&position_id)? {
@@ -180,71 +307,289 @@ where
             market_meta: MarketMeta {
                 close: position.current_symbol_price,
                 time: position.meta.update_time,
+                volatility: None,
+                volume: None,
+                spread: None,
             },
             decision: position.determine_exit_decision(),
             quantity: 0.0 - position.quantity,
             order_type: OrderType::Market,
+            limit_price: None,
+            contract_multiplier: position.contract_multiplier,
+            contract_type: position.contract_type,
+            tag: position.tag,
         }))
     }
+
+    fn update_from_expired_order(&mut self, order: &OrderEvent) -> Result<(), PortfolioError> {
+        // Expired entry OrderEvents will never fill, so stop counting them towards position limits
+        if order.decision.is_entry() {
+            let position_id =
+                self.position_id(&order.exchange, &order.instrument, order.tag.as_deref());
+            self.release_pending_entry(&position_id, order.quantity.abs(), false);
+        }
+
+        Ok(())
+    }
+
+    fn generate_delayed_orders(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        // Only count bars of the market that yield a price the delayed entries can be sized at
+        let close = match MarkPrice::Close.price(&market.kind, Side::Buy) {
+            Some(close) => close,
+            None => return Ok(Vec::new()),
+        };
+
+        // Count down the delayed entries of the market, removing those whose delay has elapsed
+        let mut signals = Vec::new();
+        self.delayed_entries.retain(|_, delayed_entry| {
+            if delayed_entry.signal.exchange != market.exchange
+                || delayed_entry.signal.instrument != market.instrument
+            {
+                return true;
+            }
+
+            delayed_entry.bars_remaining -= 1;
+            if delayed_entry.bars_remaining > 0 {
+                return true;
+            }
+
+            signals.push(delayed_entry.signal.clone());
+            false
+        });
+        signals.sort_by(|a, b| a.tag.cmp(&b.tag));
+
+        // Entry delay has elapsed, so generate the entries at the current bar's MarketMeta, keeping
+        // the Signal's volatility estimate
+        let (volume, spread) = match &market.kind {
+            DataKind::Trade(trade) => (Some(trade.amount), None),
+            DataKind::Candle(candle) => (Some(candle.volume), None),
+            DataKind::OrderBookL1(book_l1) => {
+                (None, Some(book_l1.best_ask.price - book_l1.best_bid.price))
+            }
+            _ => (None, None),
+        };
+        let mut orders = Vec::with_capacity(signals.len());
+        for mut signal in signals {
+            signal.market_meta = MarketMeta {
+                close,
+                time: market.exchange_time,
+                volatility: signal.market_meta.volatility,
+                volume,
+                spread,
+            };
+            orders.extend(self.generate_order_with_entry_delay(&signal, 0)?);
+        }
+
+        Ok(orders)
+    }
 }
 
-impl<Repository, Allocator, RiskManager, Statistic> FillUpdater
-    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+impl<Repository, Allocator, RiskManager, Statistic, Key> FillUpdater
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
     Allocator: OrderAllocator,
     RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser + Serialize,
+    Key: PositionKey,
 {
     fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError> {
         // Allocate Vector<Event> to contain any update_from_fill generated events
-        let mut generated_events: Vec<Event> = Vec::with_capacity(2);
+        let mut generated_events: Vec<Event> = Vec::with_capacity(3);
 
         // Get the Portfolio Balance from Repository & update timestamp
         let mut balance = self.repository.get_balance(self.engine_id)?;
         balance.time = fill.time;
 
         // Determine the position_id that is related to the input FillEvent
-        let position_id = determine_position_id(self.engine_id, &fill.exchange, &fill.instrument);
+        let position_id = self.position_id(&fill.exchange, &fill.instrument, fill.tag.as_deref());
+
+        // Determine the currency the FillEvent settles in, & the rate converting it into the base
+        let settlement = self.settlement(&fill.instrument, fill.contract_type)?;
 
         // Determine FillEvent context based on existence or absence of an open Position
-        match self.repository.remove_position(&position_id)? {
+        // '--> open Position is only removed once the FillEvent has exited it successfully
+        match self.repository.get_open_position(&position_id)? {
+            // ADD SCENARIO - entry FillEvent for Symbol-Exchange combination with open Position
+            Some(mut position) if fill.decision.is_entry() => {
+                // Add to Position (in place mutation), & add the PositionUpdate event to Vec<Event>
+                let position_update = position.add(fill)?;
+
+                // Tranches of a partially filled entry OrderEvent are a single add
+                if self.release_pending_entry(&position_id, fill.quantity.abs(), true) {
+                    position.adds -= 1;
+                }
+                generated_events.push(Event::PositionUpdate(position_update));
+
+                // Update Portfolio Balance.available on Position add, consuming the added margin
+                let margin = fill.fill_value_gross / self.leverage;
+                let base_margin = settlement.to_base(margin);
+                self.consume_margin(&position_id, base_margin);
+                self.update_balance(
+                    &mut balance,
+                    &settlement,
+                    0.0,
+                    -fill.fees.calculate_total_fees(),
+                    (-margin, -base_margin),
+                );
+
+                // Persist updated open Position in Repository
+                self.equity.update_unrealised(
+                    &position_id,
+                    settlement.to_base(position.unrealised_profit_loss),
+                );
+                self.repository.set_open_position(position)?;
+            }
+
+            // PARTIAL EXIT SCENARIO - exit FillEvent for less than the open Position quantity
+            Some(mut position) if position.is_partial_exit(fill) => {
+                let (enter_value_gross, enter_fees_total, realised_profit_loss) = (
+                    position.enter_value_gross,
+                    position.enter_fees_total,
+                    position.realised_profit_loss,
+                );
+
+                // Partially exit Position (in place mutation), & add the PositionUpdate event
+                let exited_fraction = fill.quantity.abs() / position.quantity.abs();
+                let position_update = position.partial_exit(fill)?;
+                generated_events.push(Event::PositionUpdate(position_update));
+
+                // Update Portfolio balance on partial exit, releasing the exited margin
+                // '--> available balance adds exited enter fees since included in realised PnL
+                let exited_profit_loss = position.realised_profit_loss - realised_profit_loss;
+                let margin = (enter_value_gross - position.enter_value_gross) / self.leverage;
+                let base_margin = self.release_margin(&position_id, exited_fraction);
+                self.update_balance(
+                    &mut balance,
+                    &settlement,
+                    exited_profit_loss,
+                    exited_profit_loss + (enter_fees_total - position.enter_fees_total),
+                    (margin, base_margin),
+                );
+                self.equity.update_realised(&position_id, balance.total);
+                self.equity
+                    .record_realised_profit_loss(fill.time, settlement.to_base(exited_profit_loss));
+
+                // Persist remaining open Position in Repository
+                self.equity.update_unrealised(
+                    &position_id,
+                    settlement.to_base(position.unrealised_profit_loss),
+                );
+                self.repository.set_open_position(position)?;
+            }
+
             // EXIT SCENARIO - FillEvent for Symbol-Exchange combination with open Position
             Some(mut position) => {
                 // Exit Position (in place mutation), & add the PositionExit event to Vec<Event>
-                let position_exit = position.exit(balance, fill)?;
-                generated_events.push(Event::PositionExit(position_exit));
+                let realised_profit_loss = position.realised_profit_loss;
+                let mut position_exit = position.exit(balance, fill)?;
+                self.repository.remove_position(&position_id)?;
 
-                // Update Portfolio balance on Position exit
+                // Update Portfolio balance on Position exit, releasing the Position margin
                 // '--> available balance adds enter_total_fees since included in result PnL calc
-                balance.available += position.enter_value_gross
-                    + position.realised_profit_loss
-                    + position.enter_fees_total;
-                balance.total += position.realised_profit_loss;
-
-                // Update statistics for exited Position market
-                let market_id = MarketId::new(&fill.exchange, &fill.instrument);
-
-                let mut stats = self.repository.get_statistics(&market_id)?;
-                stats.update(&position);
+                // '--> PnL realised by partial exits is already included in the balance
+                let exit_profit_loss = position.realised_profit_loss - realised_profit_loss;
+                let margin = position.enter_value_gross / self.leverage;
+                let base_margin = self.release_margin(&position_id, 1.0);
+                self.update_balance(
+                    &mut balance,
+                    &settlement,
+                    exit_profit_loss,
+                    exit_profit_loss + position.enter_fees_total,
+                    (margin, base_margin),
+                );
+                self.equity.update_realised(&position_id, balance.total);
+                self.equity
+                    .record_realised_profit_loss(fill.time, settlement.to_base(exit_profit_loss));
+
+                // Exit Balance total is reported in the base currency
+                position_exit.exit_balance.total = balance.total;
+                if let Some(exit_balance) = &mut position.meta.exit_balance {
+                    exit_balance.total = balance.total;
+                }
+                generated_events.push(Event::PositionExit(position_exit));
 
-                // Persist exited Position & Updated Market statistics in Repository
-                self.repository.set_statistics(market_id, stats)?;
+                // Update statistics for exited Position market, unless excluded
+                if !self.is_statistics_excluded(fill.market_meta.time) {
+                    let market_id = MarketId::new(&fill.exchange, &fill.instrument);
+
+                    let mut stats = self.repository.get_statistics(&market_id)?;
+                    stats.update(&position);
+
+                    // Stream snapshot of the updated Market statistics
+                    match serde_json::to_value(stats) {
+                        Ok(statistics) => {
+                            generated_events.push(Event::StatisticUpdate(StatisticUpdate {
+                                time: fill.time,
+                                market_id: market_id.clone(),
+                                statistics,
+                            }))
+                        }
+                        Err(error) => warn!(
+                            ?error,
+                            ?market_id,
+                            "failed to serialise statistics for StatisticUpdate event"
+                        ),
+                    }
+
+                    // Persist Updated Market statistics in Repository
+                    self.repository.set_statistics(market_id, stats)?;
+                }
+
+                // Stop tracking the sub-strategy tag of the exited Position
+                if let Some(tag) = &position.tag {
+                    let market_id = MarketId::new(&fill.exchange, &fill.instrument);
+                    if let Some(tags) = self.position_tags.get_mut(&market_id) {
+                        tags.remove(tag);
+                        if tags.is_empty() {
+                            self.position_tags.remove(&market_id);
+                        }
+                    }
+                }
+
+                // Persist exited Position in Repository
+                self.win_loss.update(&position);
                 self.repository
                     .set_exited_position(self.engine_id, position)?;
+                self.position_counts.record_closed();
             }
 
             // ENTRY SCENARIO - FillEvent for Symbol-Exchange with no Position
             None => {
-                // Enter new Position, & add the PositionNew event to Vec<Event>
-                let position = Position::enter(self.engine_id, fill)?;
+                // Enter new Position keyed by the PositionKey, & add the PositionNew event to Vec<Event>
+                let mut position = Position::enter(self.engine_id, fill)?;
+                position.position_id = position_id.clone();
+                self.release_pending_entry(&position_id, fill.quantity.abs(), true);
+                if let Some(tag) = &position.tag {
+                    self.position_tags
+                        .entry(MarketId::new(&fill.exchange, &fill.instrument))
+                        .or_default()
+                        .insert(tag.clone());
+                }
                 generated_events.push(Event::PositionNew(position.clone()));
 
-                // Update Portfolio Balance.available on Position entry
-                balance.available += -position.enter_value_gross - position.enter_fees_total;
+                // Update Portfolio Balance.available on Position entry, consuming the Position margin
+                let margin = position.enter_value_gross / self.leverage;
+                let base_margin = settlement.to_base(margin);
+                self.consume_margin(&position_id, base_margin);
+                self.update_balance(
+                    &mut balance,
+                    &settlement,
+                    0.0,
+                    -position.enter_fees_total,
+                    (-margin, -base_margin),
+                );
 
                 // Add to current Positions in Repository
+                self.equity.update_unrealised(
+                    &position_id,
+                    settlement.to_base(position.unrealised_profit_loss),
+                );
                 self.repository.set_open_position(position)?;
+                self.position_counts.record_opened();
             }
         };
 
@@ -258,13 +603,56 @@ where
     }
 }
 
-impl<Repository, Allocator, RiskManager, Statistic> PositionHandler
-    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+impl<Repository, Allocator, RiskManager, Statistic, Key> ExposureReporter
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
+{
+    fn time_in_market(&self) -> TimeInMarketSummary {
+        self.time_in_market.clone()
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic, Key> PositionCountReporter
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
     Allocator: OrderAllocator,
     RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
+{
+    fn position_counts(&self) -> PositionCounts {
+        self.position_counts
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic, Key> ReturnReporter
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
+{
+    fn time_weighted_return(&self) -> TimeWeightedReturn {
+        self.equity.time_weighted_return
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic, Key> PositionHandler
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
 {
     fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
         self.repository.set_open_position(position)
@@ -282,7 +670,13 @@ where
         _: Uuid,
         markets: Markets,
     ) -> Result<Vec<Position>, RepositoryError> {
-        self.repository.get_open_positions(self.engine_id, markets)
+        // Fetch every open Position of each market, since there may be one per sub-strategy tag
+        markets
+            .flat_map(|market| self.market_position_ids(&market.exchange, &market.instrument))
+            .collect::<Vec<_>>()
+            .iter()
+            .filter_map(|position_id| self.repository.get_open_position(position_id).transpose())
+            .collect()
     }
 
     fn remove_position(
@@ -302,13 +696,14 @@ where
     }
 }
 
-impl<Repository, Allocator, RiskManager, Statistic> StatisticHandler<Statistic>
-    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+impl<Repository, Allocator, RiskManager, Statistic, Key> StatisticHandler<Statistic>
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
     Allocator: OrderAllocator,
     RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
 {
     fn set_statistics(
         &mut self,
@@ -342,7 +737,31 @@ where
             repository: lego.repository,
             allocation_manager: lego.allocator,
             risk_manager: lego.risk,
+            markets: lego.markets.clone(),
+            leverage: validate_leverage(lego.leverage)?,
+            used_margin: 0.0,
+            position_margins: HashMap::new(),
+            fx_conversion: lego.fx_conversion,
+            currency_balances: HashMap::new(),
+            contract_multipliers: lego.contract_multipliers,
+            contract_types: lego.contract_types,
+            max_positions_per_market: validate_max_positions_per_market(
+                lego.max_positions_per_market,
+            )?,
+            max_adds: lego.max_adds,
+            pending_entries: HashMap::new(),
+            entry_delay_bars: lego.entry_delay_bars,
+            delayed_entries: HashMap::new(),
+            mark_price: lego.mark_price,
+            statistic_exclusions: lego.statistic_exclusions,
+            equity: EquityCurve::default(),
+            time_in_market: TimeInMarketSummary::default(),
+            position_counts: PositionCounts::default(),
+            win_loss: WinLossSummary::default(),
+            close_only: false,
+            position_tags: HashMap::new(),
             _statistic_marker: PhantomData,
+            _position_key_marker: PhantomData,
         };
 
         // Persist initial state in the repository
@@ -351,8 +770,28 @@ where
         Ok(portfolio)
     }
 
+    /// Returns a [`MetaPortfolioBuilder`] instance. Use
+    /// [`MetaPortfolioBuilder::position_key`] to key [`Position`]s by something other than
+    /// their market.
+    pub fn builder() -> MetaPortfolioBuilder<Repository, Allocator, RiskManager, Statistic> {
+        MetaPortfolioBuilder::new()
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic, Key>
+    MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
+{
     /// Persist initial [`MetaPortfolio`] state in the repository. This includes initialised
     /// Statistics every market provided, as well as starting `AvailableCash` & `TotalEquity`.
+    ///
+    /// The [`PositionCounts`] are seeded from the open & exited [`Position`]s the repository
+    /// already holds (eg/ persisted by a previous session).
     pub fn bootstrap_repository<Markets, Id>(
         &mut self,
         starting_cash: f64,
@@ -363,6 +802,22 @@ where
         Markets: IntoIterator<Item = Id>,
         Id: Into<MarketId>,
     {
+        // Initialise the equity curve from the starting cash, with no margin in use
+        let max_marks = self.equity.max_marks;
+        self.equity = EquityCurve::new(starting_cash);
+        self.equity.set_max_marks(max_marks);
+        self.used_margin = 0.0;
+        self.position_margins.clear();
+
+        // Starting cash is held in the base currency
+        self.currency_balances.clear();
+        if let Some(fx_conversion) = &self.fx_conversion {
+            self.currency_balances.insert(
+                fx_conversion.base_currency.clone(),
+                Balance::new(Utc::now(), starting_cash, starting_cash),
+            );
+        }
+
         // Persist initial Balance (total & available)
         self.repository.set_balance(
             self.engine_id,
@@ -378,559 +833,2698 @@ where
             self.repository
                 .set_statistics(market.into(), Statistic::init(statistic_config))
                 .map_err(PortfolioError::RepositoryInteraction)
-        })
-    }
+        })?;
+
+        // Seed the PositionCounts from the Positions already held in the repository
+        let portfolio_markets = self.markets.clone();
+        let open = self
+            .get_open_positions(self.engine_id, portfolio_markets.iter())?
+            .len() as u64;
+        let closed = self.repository.get_exited_positions(self.engine_id)?.len() as u64;
+        self.position_counts = PositionCounts {
+            opened: open + closed,
+            closed,
+        };
 
-    /// Returns a [`MetaPortfolioBuilder`] instance.
-    pub fn builder() -> MetaPortfolioBuilder<Repository, Allocator, RiskManager, Statistic> {
-        MetaPortfolioBuilder::new()
+        Ok(())
     }
 
-    /// Determines if the Portfolio has any cash to enter a new [`Position`].
-    fn no_cash_to_enter_new_position(&mut self) -> Result<bool, PortfolioError> {
-        self.repository
-            .get_balance(self.engine_id)
-            .map(|balance| balance.available == 0.0)
-            .map_err(PortfolioError::RepositoryInteraction)
+    /// Exports the entire state of the Portfolio to a [`PortfolioSnapshot`], including the
+    /// repository state of every [`Market`] it trades & it's in-memory session state.
+    ///
+    /// Unlike the per-key repository storage, the [`PortfolioSnapshot`] is a single value that
+    /// can be persisted as one blob & restored via [`Self::restore`], independent of the
+    /// repository backend. Pending & delayed entry [`OrderEvent`]s are not included, since the
+    /// in-flight orders of the execution handler are not snapshotted alongside them.
+    pub fn snapshot(&mut self) -> Result<PortfolioSnapshot<Statistic>, PortfolioError> {
+        let markets = self.markets.clone();
+        let statistics = markets
+            .iter()
+            .map(|market| {
+                let market_id = MarketId::new(&market.exchange, &market.instrument);
+                self.repository
+                    .get_statistics(&market_id)
+                    .map(|statistic| (market_id, statistic))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(PortfolioSnapshot {
+            engine_id: self.engine_id,
+            time: Utc::now(),
+            balance: self.repository.get_balance(self.engine_id)?,
+            open_positions: self.get_open_positions(self.engine_id, markets.iter())?,
+            exited_positions: self.repository.get_exited_positions(self.engine_id)?,
+            statistics,
+            used_margin: self.used_margin,
+            position_margins: self.position_margins.clone(),
+            currency_balances: self.currency_balances.clone(),
+            equity: self.equity.clone(),
+            time_in_market: self.time_in_market.clone(),
+            position_counts: self.position_counts,
+            win_loss: self.win_loss,
+            close_only: self.close_only,
+            position_tags: self.position_tags.clone(),
+        })
     }
-}
 
-#[derive(Debug, Default)]
-pub struct MetaPortfolioBuilder<Repository, Allocator, RiskManager, Statistic>
-where
-    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
-    Allocator: OrderAllocator,
-    RiskManager: OrderEvaluator,
-    Statistic: Initialiser + PositionSummariser,
-{
-    engine_id: Option<Uuid>,
-    markets: Option<Vec<Market>>,
-    starting_cash: Option<f64>,
-    repository: Option<Repository>,
-    allocation_manager: Option<Allocator>,
-    risk_manager: Option<RiskManager>,
-    statistic_config: Option<Statistic::Config>,
-    _statistic_marker: Option<PhantomData<Statistic>>,
-}
+    /// Restores the entire state of the Portfolio from a [`PortfolioSnapshot`] (eg/ on startup
+    /// after a crash), replacing the open [`Position`]s, [`Balance`], statistics & in-memory
+    /// session state.
+    ///
+    /// Exited [`Position`]s are append only, so those already persisted in the repository must
+    /// be the first of the snapshot's exited [`Position`]s (eg/ none, if restoring into an empty
+    /// repository), and only the remainder are persisted. The repository is left untouched if
+    /// the [`PortfolioSnapshot`] is of another Engine, or conflicts with the exited
+    /// [`Position`]s already persisted.
+    ///
+    /// Restoring is not atomic: if the repository errors part way through, the Portfolio may be
+    /// left partially restored. Any pending entry reservations & delayed entry [`Signal`]s are
+    /// cleared, since the in-flight [`OrderEvent`]s they track did not survive the restart.
+    pub fn restore(
+        &mut self,
+        snapshot: PortfolioSnapshot<Statistic>,
+    ) -> Result<(), PortfolioError> {
+        if snapshot.engine_id != self.engine_id {
+            return Err(PortfolioError::SnapshotEngineMismatch {
+                portfolio: self.engine_id,
+                snapshot: snapshot.engine_id,
+            });
+        }
 
-impl<Repository, Allocator, RiskManager, Statistic>
-    MetaPortfolioBuilder<Repository, Allocator, RiskManager, Statistic>
-where
-    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
-    Allocator: OrderAllocator,
-    RiskManager: OrderEvaluator,
-    Statistic: Initialiser + PositionSummariser,
-{
-    pub fn new() -> Self {
-        Self {
-            engine_id: None,
-            markets: None,
-            starting_cash: None,
-            repository: None,
-            allocation_manager: None,
-            risk_manager: None,
-            statistic_config: None,
-            _statistic_marker: None,
+        let persisted_exits = self.repository.get_exited_positions(self.engine_id)?;
+        if !snapshot.exited_positions.starts_with(&persisted_exits) {
+            return Err(PortfolioError::SnapshotExitedPositionsConflict);
         }
-    }
 
-    pub fn engine_id(self, value: Uuid) -> Self {
-        Self {
-            engine_id: Some(value),
-            ..self
+        // Replace the open Positions, which must be found before restoring the Position tags
+        let markets = self.markets.clone();
+        for position in self.get_open_positions(self.engine_id, markets.iter())? {
+            self.repository.remove_position(&position.position_id)?;
+        }
+        for position in snapshot.open_positions {
+            self.repository.set_open_position(position)?;
         }
-    }
 
-    pub fn markets(self, value: Vec<Market>) -> Self {
-        Self {
-            markets: Some(value),
-            ..self
+        // Append the exited Positions not already persisted
+        for position in snapshot
+            .exited_positions
+            .into_iter()
+            .skip(persisted_exits.len())
+        {
+            self.repository
+                .set_exited_position(self.engine_id, position)?;
         }
-    }
 
-    pub fn starting_cash(self, value: f64) -> Self {
-        Self {
-            starting_cash: Some(value),
-            ..self
+        self.repository
+            .set_balance(self.engine_id, snapshot.balance)?;
+        for (market_id, statistic) in snapshot.statistics {
+            self.repository.set_statistics(market_id, statistic)?;
         }
+
+        // Restore in-memory session state
+        self.used_margin = snapshot.used_margin;
+        self.position_margins = snapshot.position_margins;
+        self.currency_balances = snapshot.currency_balances;
+        self.equity = snapshot.equity;
+        self.time_in_market = snapshot.time_in_market;
+        self.position_counts = snapshot.position_counts;
+        self.win_loss = snapshot.win_loss;
+        self.close_only = snapshot.close_only;
+        self.position_tags = snapshot.position_tags;
+        self.pending_entries.clear();
+        self.delayed_entries.clear();
+
+        Ok(())
     }
 
-    pub fn repository(self, value: Repository) -> Self {
-        Self {
-            repository: Some(value),
-            ..self
-        }
+    /// Per-bar returns of the Portfolio equity, marked to market on every [`MarketEvent`]. Useful
+    /// for exporting to external risk tools. See [`EquityCurve::bar_returns`].
+    pub fn bar_returns(&self) -> Vec<(DateTime<Utc>, f64)> {
+        self.equity.bar_returns()
     }
 
-    pub fn allocation_manager(self, value: Allocator) -> Self {
-        Self {
-            allocation_manager: Some(value),
-            ..self
+    /// Applies an external cash deposit (+ve amount) or withdrawal (-ve amount) to the Portfolio
+    /// [`Balance`], returning the updated [`Balance`].
+    ///
+    /// The [`CashFlow`](super::equity::CashFlow) adjusts both the total & available cash, and is
+    /// recorded in the [`EquityCurve`] so it is not counted as trading P&L:
+    ///  - The [`EquityCurve`] steps by the amount immediately, and the next mark includes it.
+    ///  - [`Self::bar_returns`] exclude the amount from the return of the bar it was applied in,
+    ///    so compounding them yields the time-weighted return, independent of cash flow timing.
+    ///  - Per-market [`Position`] statistics are unaffected, but drawdown metrics derived from
+    ///    [`Position`] exit [`Balance`]s will observe the step in total equity.
+    ///
+    /// A withdrawal greater than the [`Self::available_cash`] (ie/ cash not allocated to open
+    /// [`Position`]s, net of reserved cash) is rejected.
+    pub fn apply_cash_flow(
+        &mut self,
+        amount: f64,
+        timestamp: DateTime<Utc>,
+    ) -> Result<Balance, PortfolioError> {
+        // Cash reserved for in-flight entry OrderEvents cannot be withdrawn
+        let available = self.available_cash()?;
+        if available + amount < 0.0 {
+            return Err(PortfolioError::InsufficientAvailableCash {
+                available,
+                withdrawal: -amount,
+            });
         }
-    }
 
-    pub fn risk_manager(self, value: RiskManager) -> Self {
-        Self {
-            risk_manager: Some(value),
-            ..self
+        let mut balance = self.repository.get_balance(self.engine_id)?;
+
+        balance.time = timestamp;
+        balance.total += amount;
+        balance.available += amount;
+        self.repository.set_balance(self.engine_id, balance)?;
+
+        // Cash flows are in the base currency
+        if let Some(fx_conversion) = &self.fx_conversion {
+            let currency_balance = self
+                .currency_balances
+                .entry(fx_conversion.base_currency.clone())
+                .or_default();
+            currency_balance.time = timestamp;
+            currency_balance.total += amount;
+            currency_balance.available += amount;
         }
+
+        self.equity.apply_cash_flow(amount, timestamp);
+
+        Ok(balance)
     }
 
-    pub fn statistic_config(self, value: Statistic::Config) -> Self {
-        Self {
-            statistic_config: Some(value),
-            ..self
-        }
+    /// Base currency the Portfolio [`Balance`] & equity are reported in, if an [`FxConversion`]
+    /// is configured.
+    pub fn base_currency(&self) -> Option<&Symbol> {
+        self.fx_conversion
+            .as_ref()
+            .map(|fx_conversion| &fx_conversion.base_currency)
     }
 
-    pub fn build_and_init(
-        self,
-    ) -> Result<MetaPortfolio<Repository, Allocator, RiskManager, Statistic>, PortfolioError> {
-        // Construct Portfolio
-        let mut portfolio = MetaPortfolio {
-            engine_id: self
-                .engine_id
-                .ok_or(PortfolioError::BuilderIncomplete("engine_id"))?,
-            repository: self
-                .repository
-                .ok_or(PortfolioError::BuilderIncomplete("repository"))?,
-            allocation_manager: self
-                .allocation_manager
-                .ok_or(PortfolioError::BuilderIncomplete("allocation_manager"))?,
-            risk_manager: self
-                .risk_manager
-                .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
-            _statistic_marker: PhantomData,
-        };
+    /// [`Balance`] of each settlement currency, denominated in that currency. Only tracked if an
+    /// [`FxConversion`] is configured, whereas the Portfolio [`Balance`] is the sum of these
+    /// converted into the base currency at the rates of each fill.
+    ///
+    /// Note: like the [`EquityCurve`], these are tracked for the current trading session only.
+    pub fn currency_balances(&self) -> &HashMap<Symbol, Balance> {
+        &self.currency_balances
+    }
 
-        // Persist initial state in the Repository
-        portfolio.bootstrap_repository(
-            self.starting_cash
-                .ok_or(PortfolioError::BuilderIncomplete("starting_cash"))?,
-            &self
-                .markets
-                .ok_or(PortfolioError::BuilderIncomplete("markets"))?,
-            self.statistic_config
-                .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?,
-        )?;
+    /// Leverage applied to every [`Position`], such that entering a [`Position`] of notional N
+    /// consumes N / leverage of available cash as margin.
+    pub fn leverage(&self) -> f64 {
+        self.leverage
+    }
 
-        Ok(portfolio)
+    /// Margin consumed by the open [`Position`]s (ie/ the sum of their entry notional / leverage).
+    pub fn used_margin(&self) -> f64 {
+        self.used_margin
     }
-}
 
-/// Parses an incoming [`Signal`]'s signals map. Determines what the net signal [`Decision`]
-/// will be, and it's associated [`SignalStrength`].
-pub fn parse_signal_decisions<'a>(
-    position: &'a Option<&Position>,
-    signals: &'a HashMap<Decision, SignalStrength>,
-) -> Option<(&'a Decision, &'a SignalStrength)> {
-    // Determine the presence of signals in the provided signals HashMap
-    let signal_close_long = signals.get_key_value(&Decision::CloseLong);
-    let signal_long = signals.get_key_value(&Decision::Long);
-    let signal_close_short = signals.get_key_value(&Decision::CloseShort);
-    let signal_short = signals.get_key_value(&Decision::Short);
+    /// Free margin available to enter new [`Position`]s, which is the [`Self::available_cash`].
+    pub fn free_margin(&mut self) -> Result<f64, PortfolioError> {
+        self.available_cash()
+    }
 
-    // If an existing Position exists, check for net close signals
-    if let Some(position) = position {
-        return match position.side {
-            Side::Buy if signal_close_long.is_some() => signal_close_long,
-            Side::Sell if signal_close_short.is_some() => signal_close_short,
-            _ => None,
+    /// Current cash of the Portfolio [`Balance`] after deducting the used margin & entry fees of
+    /// open [`Position`]s. Includes the cash reserved for in-flight entry [`OrderEvent`]s.
+    pub fn current_cash(&mut self) -> Result<f64, PortfolioError> {
+        self.repository
+            .get_balance(self.engine_id)
+            .map(|balance| balance.available)
+            .map_err(PortfolioError::RepositoryInteraction)
+    }
+
+    /// Cash available to enter new [`Position`]s, which is the [`Self::current_cash`] after
+    /// deducting the [`Self::reserved_cash`] of in-flight entry [`OrderEvent`]s.
+    pub fn available_cash(&mut self) -> Result<f64, PortfolioError> {
+        Ok(self.current_cash()? - self.reserved_cash())
+    }
+
+    /// Cash reserved for the entry [`OrderEvent`]s generated that are yet to be filled or
+    /// expire, so concurrent [`Signal`]s cannot commit the same cash before the first
+    /// [`FillEvent`] lands.
+    ///
+    /// Each entry [`OrderEvent`] reserves it's estimated margin (ie/ notional at the
+    /// [`MarketMeta`] close / leverage) when it is generated. The reservation is released in
+    /// proportion to the quantity of each [`FillEvent`] applied (which then consumes the actual
+    /// margin & fees from the current cash), or of the [`OrderEvent`] reported expired, such
+    /// that a partially filled [`OrderEvent`] only reserves cash for it's remaining quantity.
+    /// Reservations of a [`PositionId`] are released in the order they were made.
+    pub fn reserved_cash(&self) -> f64 {
+        self.pending_entries
+            .values()
+            .flatten()
+            .map(|pending_entry| pending_entry.reserved_cash)
+            .sum()
+    }
+
+    /// Generates the [`OrderEvent`]s required to rebalance the Portfolio's open [`Position`]s to
+    /// the [`Rebalancer`] target weights of the current mark-to-market equity, if a scheduled
+    /// rebalance is due. See [`Rebalancer::generate_orders`].
+    pub fn generate_rebalance_orders(
+        &mut self,
+        rebalancer: &mut Rebalancer,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        let open_positions = self
+            .repository
+            .get_open_positions(self.engine_id, rebalancer.markets())?;
+
+        Ok(
+            rebalancer.generate_orders(self.equity.current(), &open_positions, |market| {
+                (
+                    self.contract_multiplier(&market.exchange, &market.instrument),
+                    self.contract_type(&market.exchange, &market.instrument),
+                )
+            }),
+        )
+    }
+
+    /// Contract multiplier of the provided [`Exchange`] & [`Instrument`] [`Market`], defaulting
+    /// to 1.0 if it has not been configured.
+    pub fn contract_multiplier(&self, exchange: &Exchange, instrument: &Instrument) -> f64 {
+        self.contract_multipliers
+            .get(&MarketId::new(exchange, instrument))
+            .copied()
+            .unwrap_or_else(default_contract_multiplier)
+    }
+
+    /// [`ContractType`] of the provided [`Exchange`] & [`Instrument`] [`Market`], defaulting to
+    /// [`ContractType::Linear`] if it has not been configured.
+    pub fn contract_type(&self, exchange: &Exchange, instrument: &Instrument) -> ContractType {
+        self.contract_types
+            .get(&MarketId::new(exchange, instrument))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Determines if per-market statistics are not accumulated at the provided market time, due
+    /// to it falling within a configured [`StatisticExclusion`] window.
+    ///
+    /// A [`Position`] exited within a [`StatisticExclusion`] window trades normally, but is not
+    /// used to update it's market's statistics, and generates no
+    /// [`Event::StatisticUpdate`]. The Portfolio [`Balance`] & [`EquityCurve`] are unaffected,
+    /// so the equity curve remains continuous across excluded bars:
+    ///  - [`Self::bar_returns`] & the [`TimeWeightedReturn`] still include excluded bars.
+    ///  - The realised P&L of excluded [`Position`]s is carried in the total equity, so
+    ///    drawdown statistics derived from the exit [`Balance`] of the next included [`Position`]
+    ///    observe it as a step in equity rather than being unaware of it.
+    pub fn is_statistics_excluded(&self, time: DateTime<Utc>) -> bool {
+        self.statistic_exclusions
+            .iter()
+            .any(|exclusion| exclusion.contains(time))
+    }
+
+    /// Enable or disable close only mode. Whilst enabled, no entry [`OrderEvent`]s are generated
+    /// for any market, but exit [`OrderEvent`]s & market updates are processed as normal. Useful
+    /// for winding down a Portfolio or as an emergency risk control.
+    ///
+    /// Since the Portfolio is shared by every [`Trader`](crate::engine::trader::Trader), this
+    /// applies globally, unlike pausing an individual [`Trader`](crate::engine::trader::Trader).
+    pub fn set_close_only(&mut self, close_only: bool) {
+        info!(close_only, "portfolio close only mode updated");
+        self.close_only = close_only;
+    }
+
+    /// Determines if the Portfolio is in close only mode. See [`Self::set_close_only`].
+    pub fn is_close_only(&self) -> bool {
+        self.close_only
+    }
+
+    /// [`PositionId`] of the provided market & optional sub-strategy tag, as determined by the
+    /// [`PositionKey`].
+    fn position_id(
+        &self,
+        exchange: &Exchange,
+        instrument: &Instrument,
+        tag: Option<&str>,
+    ) -> PositionId {
+        Key::position_id(self.engine_id, exchange, instrument, tag)
+    }
+
+    /// [`PositionId`]s of every [`Position`] the Portfolio may have open in the provided market,
+    /// ie/ the untagged [`PositionId`] followed by one per tracked sub-strategy tag.
+    fn market_position_ids(&self, exchange: &Exchange, instrument: &Instrument) -> Vec<PositionId> {
+        let mut position_ids = vec![self.position_id(exchange, instrument, None)];
+
+        let market_id = MarketId::new(exchange, instrument);
+        for tag in self.position_tags.get(&market_id).into_iter().flatten() {
+            let position_id = self.position_id(exchange, instrument, Some(tag));
+            if !position_ids.contains(&position_id) {
+                position_ids.push(position_id);
+            }
+        }
+
+        position_ids
+    }
+
+    /// May generate an [`OrderEvent`] from the input advisory [`Signal`], deferring any entry by
+    /// the provided number of bars.
+    fn generate_order_with_entry_delay(
+        &mut self,
+        signal: &Signal,
+        entry_delay_bars: usize,
+    ) -> Result<Option<OrderEvent>, PortfolioError> {
+        // Determine the position_id & associated Option<Position> related to input SignalEvent
+        let position_id =
+            self.position_id(&signal.exchange, &signal.instrument, signal.tag.as_deref());
+        let position = self.repository.get_open_position(&position_id)?;
+
+        // If signal is advising to open a new Position rather than close one, check we have cash
+        if position.is_none() && self.no_cash_to_enter_new_position()? {
+            return Ok(None);
+        }
+
+        // Parse signals from Strategy to determine net signal decision & associated strength
+        let position = position.as_ref();
+        let net_signal = match self.max_adds {
+            0 => parse_signal_decisions(&position, &signal.signals),
+            _ => parse_signal_decisions(&position, &signal.signals)
+                .or_else(|| parse_signal_add(&position, &signal.signals)),
+        };
+        let (signal_decision, signal_strength) = match net_signal {
+            None => return Ok(None),
+            Some(net_signal) => net_signal,
+        };
+
+        // Reject all entries whilst the Portfolio is close only, but continue to process exits
+        if self.close_only && signal_decision.is_entry() {
+            info!(
+                position_id = &*position_id,
+                outcome = "no OrderEvent generated",
+                "portfolio is close only"
+            );
+            return Ok(None);
+        }
+
+        // Enforce the position limit, counting the open Position & any pending entry OrderEvents
+        let pending_entries = self
+            .pending_entries
+            .get(&position_id)
+            .map_or(0, VecDeque::len);
+        if signal_decision.is_entry()
+            && position.is_none()
+            && pending_entries >= self.max_positions_per_market
+        {
+            info!(
+                position_id = &*position_id,
+                pending_entries,
+                outcome = "no OrderEvent generated",
+                "max positions per market reached"
+            );
+            return Ok(None);
+        }
+
+        // Enforce the add limit, counting the adds filled & any pending add OrderEvents
+        if let Some(position) = position.filter(|_| signal_decision.is_entry()) {
+            if position.adds + pending_entries >= self.max_adds {
+                info!(
+                    position_id = &*position_id,
+                    adds = position.adds,
+                    pending_entries,
+                    outcome = "no OrderEvent generated",
+                    "max adds to Position reached"
+                );
+                return Ok(None);
+            }
+        }
+
+        // Guard against a degenerate MarketMeta (eg/ first bar with no prior close) sizing an order
+        if !signal.market_meta.close.is_finite() || signal.market_meta.close <= 0.0 {
+            warn!(
+                position_id = &*position_id,
+                close = signal.market_meta.close,
+                outcome = "no OrderEvent generated",
+                "cannot size OrderEvent from Signal with degenerate MarketMeta close"
+            );
+            return Ok(None);
+        }
+
+        // Defer the entry until the entry delay has elapsed, tracking one delayed entry per market
+        if entry_delay_bars > 0 && signal_decision.is_entry() {
+            match self.delayed_entries.entry(position_id) {
+                Entry::Occupied(delayed_entry) => info!(
+                    position_id = delayed_entry.key(),
+                    outcome = "no OrderEvent generated",
+                    "entry already delayed for market"
+                ),
+                Entry::Vacant(delayed_entry) => {
+                    delayed_entry.insert(DelayedEntry {
+                        signal: signal.clone(),
+                        bars_remaining: entry_delay_bars,
+                    });
+                }
+            }
+            return Ok(None);
+        }
+
+        // Construct mutable OrderEvent that can be modified by Allocation & Risk management
+        let mut order = OrderEvent {
+            time: Utc::now(),
+            exchange: signal.exchange.clone(),
+            instrument: signal.instrument.clone(),
+            market_meta: signal.market_meta,
+            decision: *signal_decision,
+            quantity: 0.0,
+            order_type: OrderType::default(),
+            limit_price: None,
+            contract_multiplier: self.contract_multiplier(&signal.exchange, &signal.instrument),
+            contract_type: self.contract_type(&signal.exchange, &signal.instrument),
+            tag: signal.tag.clone(),
         };
+
+        // Manage OrderEvent size allocation
+        let equity = self.equity.snapshot();
+        self.allocation_manager.allocate_order(
+            &mut order,
+            AllocationContext {
+                position,
+                signal_strength: *signal_strength,
+                equity: &equity,
+                trades: &self.win_loss,
+            },
+        );
+
+        // Never generate a zero or NaN quantity OrderEvent
+        if !order.quantity.is_finite() || order.quantity == 0.0 {
+            return Ok(None);
+        }
+
+        // Manage global risk when evaluating OrderEvent - keep the same, refine or cancel
+        let order = match self.risk_manager.evaluate_order(order, &equity) {
+            Some(order) if order.decision.is_entry() => {
+                // Reject entries requiring more margin than the cash available, in the base currency
+                let settlement = self.settlement(&order.instrument, order.contract_type)?;
+                let required_margin = settlement.to_base(order.contract_type.notional(
+                    order.quantity,
+                    order.market_meta.close,
+                    order.contract_multiplier,
+                )) / self.leverage;
+                let available_cash = self.available_cash()?;
+                if required_margin > available_cash {
+                    info!(
+                        position_id = &*position_id,
+                        required_margin,
+                        available_cash,
+                        outcome = "no OrderEvent generated",
+                        "insufficient available cash to enter Position"
+                    );
+                    return Ok(None);
+                }
+
+                // Reserve the required margin until the entry OrderEvent is filled or expires
+                self.pending_entries
+                    .entry(position_id)
+                    .or_default()
+                    .push_back(PendingEntry {
+                        remaining_quantity: order.quantity.abs(),
+                        reserved_cash: required_margin,
+                        partially_filled: false,
+                    });
+                Some(order)
+            }
+            order => order,
+        };
+
+        Ok(order)
     }
 
-    // Else check for net open signals
-    match (signal_long, signal_short) {
-        (Some(signal_long), None) => Some(signal_long),
-        (None, Some(signal_short)) => Some(signal_short),
-        _ => None,
+    /// Determines the [`Settlement`] of the provided [`Instrument`] & [`ContractType`].
+    fn settlement(
+        &self,
+        instrument: &Instrument,
+        contract_type: ContractType,
+    ) -> Result<Settlement, PortfolioError> {
+        match &self.fx_conversion {
+            None => Ok(Settlement {
+                currency: None,
+                rate: 1.0,
+            }),
+            Some(fx_conversion) => {
+                let currency = settlement_currency(instrument, contract_type);
+                Ok(Settlement {
+                    currency: Some(currency.clone()),
+                    rate: fx_conversion.rate_to_base(currency)?,
+                })
+            }
+        }
+    }
+
+    /// Applies the change in total & available cash, denominated in the [`Settlement`]
+    /// currency, to the Portfolio [`Balance`] in the base currency & to the [`Balance`] of the
+    /// settlement currency.
+    ///
+    /// The change in available cash due to margin is provided separately as (settlement, base)
+    /// amounts, since margin is released at the rate it was consumed at rather than the
+    /// current rate.
+    fn update_balance(
+        &mut self,
+        balance: &mut Balance,
+        settlement: &Settlement,
+        total: f64,
+        available: f64,
+        (margin, base_margin): (f64, f64),
+    ) {
+        balance.total += settlement.to_base(total);
+        balance.available += settlement.to_base(available) + base_margin;
+
+        if let Some(currency) = &settlement.currency {
+            let currency_balance = self.currency_balances.entry(currency.clone()).or_default();
+            currency_balance.time = balance.time;
+            currency_balance.total += total;
+            currency_balance.available += available + margin;
+        }
+    }
+
+    /// Consumes margin in the base currency for the open [`Position`] of the provided
+    /// [`PositionId`].
+    fn consume_margin(&mut self, position_id: &PositionId, base_margin: f64) {
+        self.used_margin += base_margin;
+        *self
+            .position_margins
+            .entry(position_id.clone())
+            .or_default() += base_margin;
+    }
+
+    /// Releases the exited fraction of the margin consumed by the open [`Position`] of the
+    /// provided [`PositionId`], returning the released base currency amount. A fraction of 1.0
+    /// releases all of it.
+    fn release_margin(&mut self, position_id: &PositionId, fraction: f64) -> f64 {
+        let released = match self.position_margins.get_mut(position_id) {
+            Some(margin) if fraction < 1.0 => {
+                let released = *margin * fraction;
+                *margin -= released;
+                released
+            }
+            _ => self
+                .position_margins
+                .remove(position_id)
+                .unwrap_or_default(),
+        };
+
+        self.used_margin -= released;
+        released
+    }
+
+    /// Releases the cash reserved by the earliest pending entry [`OrderEvent`] of the provided
+    /// [`PositionId`] in proportion to the absolute quantity filled or expired, & stops tracking
+    /// it once no quantity remains.
+    ///
+    /// Returns true if the quantity is filled & an earlier tranche of the same [`OrderEvent`]
+    /// has already been filled.
+    fn release_pending_entry(
+        &mut self,
+        position_id: &PositionId,
+        quantity: f64,
+        filled: bool,
+    ) -> bool {
+        let Some(pending_entries) = self.pending_entries.get_mut(position_id) else {
+            return false;
+        };
+        let Some(pending_entry) = pending_entries.front_mut() else {
+            return false;
+        };
+        let continues_order = filled && pending_entry.partially_filled;
+
+        // Allow for floating point error in the summed quantity of the tranches
+        if quantity < pending_entry.remaining_quantity * (1.0 - PENDING_QUANTITY_TOLERANCE) {
+            pending_entry.reserved_cash *= 1.0 - quantity / pending_entry.remaining_quantity;
+            pending_entry.remaining_quantity -= quantity;
+            pending_entry.partially_filled |= filled;
+        } else {
+            pending_entries.pop_front();
+            if pending_entries.is_empty() {
+                self.pending_entries.remove(position_id);
+            }
+        }
+
+        continues_order
+    }
+
+    /// Determines if the Portfolio has any cash to enter a new [`Position`].
+    fn no_cash_to_enter_new_position(&mut self) -> Result<bool, PortfolioError> {
+        self.repository
+            .get_balance(self.engine_id)
+            .map(|balance| balance.available == 0.0)
+            .map_err(PortfolioError::RepositoryInteraction)
     }
 }
 
-#[cfg(test)]
-pub mod tests {
-    use super::*;
+#[derive(Debug, Default)]
+pub struct MetaPortfolioBuilder<
+    Repository,
+    Allocator,
+    RiskManager,
+    Statistic,
+    Key = MarketPositionKey,
+> where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
+{
+    engine_id: Option<Uuid>,
+    markets: Option<Vec<Market>>,
+    starting_cash: Option<f64>,
+    leverage: Option<f64>,
+    fx_conversion: Option<FxConversion>,
+    repository: Option<Repository>,
+    allocation_manager: Option<Allocator>,
+    risk_manager: Option<RiskManager>,
+    contract_multipliers: Option<HashMap<MarketId, f64>>,
+    contract_types: Option<HashMap<MarketId, ContractType>>,
+    max_positions_per_market: Option<usize>,
+    max_adds: Option<usize>,
+    entry_delay_bars: Option<usize>,
+    mark_price: Option<MarkPrice>,
+    statistic_exclusions: Option<Vec<StatisticExclusion>>,
+    max_equity_marks: Option<usize>,
+    statistic_config: Option<Statistic::Config>,
+    _statistic_marker: Option<PhantomData<Statistic>>,
+    _position_key_marker: Option<PhantomData<Key>>,
+}
 
-    use crate::{
-        execution::Fees,
-        portfolio::{
-            allocator::DefaultAllocator, position::PositionBuilder,
-            repository::error::RepositoryError, risk::DefaultRisk,
-        },
-        statistic::summary::pnl::PnLReturnSummary,
-        strategy::SignalForceExit,
-        test_util::{fill_event, market_event_trade, position, signal},
-    };
-    use barter_integration::model::{
-        instrument::{kind::InstrumentKind, Instrument},
-        Exchange, Side,
-    };
+impl<Repository, Allocator, RiskManager, Statistic, Key>
+    MetaPortfolioBuilder<Repository, Allocator, RiskManager, Statistic, Key>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+    Key: PositionKey,
+{
+    pub fn new() -> Self {
+        Self {
+            engine_id: None,
+            markets: None,
+            starting_cash: None,
+            leverage: None,
+            fx_conversion: None,
+            repository: None,
+            allocation_manager: None,
+            risk_manager: None,
+            contract_multipliers: None,
+            contract_types: None,
+            max_positions_per_market: None,
+            max_adds: None,
+            entry_delay_bars: None,
+            mark_price: None,
+            statistic_exclusions: None,
+            max_equity_marks: None,
+            statistic_config: None,
+            _statistic_marker: None,
+            _position_key_marker: None,
+        }
+    }
 
-    #[derive(Default)]
-    struct MockRepository<Statistic> {
-        set_open_position: Option<fn(position: Position) -> Result<(), RepositoryError>>,
-        get_open_position:
-            Option<fn(position_id: &String) -> Result<Option<Position>, RepositoryError>>,
-        get_open_positions: Option<
-            fn(engine_id: Uuid, markets: Vec<&Market>) -> Result<Vec<Position>, RepositoryError>,
-        >,
-        remove_position:
-            Option<fn(engine_id: &String) -> Result<Option<Position>, RepositoryError>>,
-        set_exited_position:
-            Option<fn(engine_id: Uuid, position: Position) -> Result<(), RepositoryError>>,
-        get_exited_positions: Option<fn(engine_id: Uuid) -> Result<Vec<Position>, RepositoryError>>,
-        set_balance: Option<fn(engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError>>,
-        get_balance: Option<fn(engine_id: Uuid) -> Result<Balance, RepositoryError>>,
-        set_statistics:
-            Option<fn(market_id: MarketId, statistic: Statistic) -> Result<(), RepositoryError>>,
-        get_statistics: Option<fn(market_id: &MarketId) -> Result<Statistic, RepositoryError>>,
-        position: Option<PositionBuilder>,
-        balance: Option<Balance>,
+    pub fn engine_id(self, value: Uuid) -> Self {
+        Self {
+            engine_id: Some(value),
+            ..self
+        }
     }
 
-    impl<Statistic> PositionHandler for MockRepository<Statistic> {
-        fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
-            self.position = Some(
-                Position::builder()
-                    .side(position.side.clone())
-                    .current_symbol_price(position.current_symbol_price)
-                    .current_value_gross(position.current_value_gross)
-                    .enter_fees_total(position.enter_fees_total)
-                    .enter_value_gross(position.enter_value_gross)
-                    .enter_avg_price_gross(position.enter_avg_price_gross)
-                    .exit_fees_total(position.exit_fees_total)
-                    .exit_value_gross(position.exit_value_gross)
-                    .exit_avg_price_gross(position.exit_avg_price_gross)
-                    .unrealised_profit_loss(position.unrealised_profit_loss)
-                    .realised_profit_loss(position.realised_profit_loss),
-            );
-            self.set_open_position.unwrap()(position)
+    pub fn markets(self, value: Vec<Market>) -> Self {
+        Self {
+            markets: Some(value),
+            ..self
+        }
+    }
+
+    pub fn starting_cash(self, value: f64) -> Self {
+        Self {
+            starting_cash: Some(value),
+            ..self
+        }
+    }
+
+    /// Leverage applied to every [`Position`], such that entering a [`Position`] of notional N
+    /// consumes N / leverage of available cash as margin. Defaults to 1.0 (fully-funded) if not
+    /// provided.
+    pub fn leverage(self, value: f64) -> Self {
+        Self {
+            leverage: Some(value),
+            ..self
+        }
+    }
+
+    /// Base currency & [`FxRateProvider`](super::fx::FxRateProvider) used to track a
+    /// [`Balance`] per settlement currency & report equity in the base currency. The starting
+    /// cash is in the base currency. Defaults to every market settling in the same currency if
+    /// not provided.
+    pub fn fx_conversion(self, value: FxConversion) -> Self {
+        Self {
+            fx_conversion: Some(value),
+            ..self
         }
+    }
+
+    pub fn repository(self, value: Repository) -> Self {
+        Self {
+            repository: Some(value),
+            ..self
+        }
+    }
+
+    pub fn allocation_manager(self, value: Allocator) -> Self {
+        Self {
+            allocation_manager: Some(value),
+            ..self
+        }
+    }
+
+    pub fn risk_manager(self, value: RiskManager) -> Self {
+        Self {
+            risk_manager: Some(value),
+            ..self
+        }
+    }
+
+    pub fn contract_multipliers(self, value: HashMap<MarketId, f64>) -> Self {
+        Self {
+            contract_multipliers: Some(value),
+            ..self
+        }
+    }
+
+    /// [`ContractType`] of each [`Market`]. [`Market`]s not present default to
+    /// [`ContractType::Linear`].
+    pub fn contract_types(self, value: HashMap<MarketId, ContractType>) -> Self {
+        Self {
+            contract_types: Some(value),
+            ..self
+        }
+    }
+
+    /// Maximum number of open [`Position`]s & pending entry [`OrderEvent`]s per market. Defaults
+    /// to 1 if not provided.
+    pub fn max_positions_per_market(self, value: usize) -> Self {
+        Self {
+            max_positions_per_market: Some(value),
+            ..self
+        }
+    }
+
+    /// Maximum number of same side entries added to an open [`Position`] (ie/ pyramiding),
+    /// including pending adds. Defaults to 0 (no adds) if not provided.
+    pub fn max_adds(self, value: usize) -> Self {
+        Self {
+            max_adds: Some(value),
+            ..self
+        }
+    }
+
+    /// Number of bars entry [`OrderEvent`]s are deferred by after the [`Signal`] bar. Defaults
+    /// to 0 (no delay) if not provided.
+    pub fn entry_delay_bars(self, value: usize) -> Self {
+        Self {
+            entry_delay_bars: Some(value),
+            ..self
+        }
+    }
+
+    /// Source of the price open [`Position`]s are marked to market at. Defaults to
+    /// [`MarkPrice::Close`] if not provided.
+    pub fn mark_price(self, value: MarkPrice) -> Self {
+        Self {
+            mark_price: Some(value),
+            ..self
+        }
+    }
+
+    /// Time windows during which per-market statistics are not accumulated. Defaults to none if
+    /// not provided.
+    pub fn statistic_exclusions(self, value: Vec<StatisticExclusion>) -> Self {
+        Self {
+            statistic_exclusions: Some(value),
+            ..self
+        }
+    }
+
+    /// Maximum number of bars of the mark-to-market [`EquityCurve`] retained, which bounds the
+    /// memory used by a long running (eg/ live) Portfolio. Defaults to retaining every bar if
+    /// not provided.
+    pub fn max_equity_marks(self, value: usize) -> Self {
+        Self {
+            max_equity_marks: Some(value),
+            ..self
+        }
+    }
+
+    pub fn statistic_config(self, value: Statistic::Config) -> Self {
+        Self {
+            statistic_config: Some(value),
+            ..self
+        }
+    }
+
+    /// [`PositionKey`] used to identify [`Position`]s. Defaults to [`MarketPositionKey`] (ie/ one
+    /// [`Position`] per market) if not provided.
+    ///
+    /// Note that the [`Rebalancer`] only manages untagged [`Position`]s, and a
+    /// [`SignalForceExit`] (eg/ when a [`Trader`](crate::engine::trader::Trader) flattens it's
+    /// market) only exits the [`Position`] of it's tag.
+    pub fn position_key<NewKey>(
+        self,
+    ) -> MetaPortfolioBuilder<Repository, Allocator, RiskManager, Statistic, NewKey>
+    where
+        NewKey: PositionKey,
+    {
+        MetaPortfolioBuilder {
+            engine_id: self.engine_id,
+            markets: self.markets,
+            starting_cash: self.starting_cash,
+            leverage: self.leverage,
+            fx_conversion: self.fx_conversion,
+            repository: self.repository,
+            allocation_manager: self.allocation_manager,
+            risk_manager: self.risk_manager,
+            contract_multipliers: self.contract_multipliers,
+            contract_types: self.contract_types,
+            max_positions_per_market: self.max_positions_per_market,
+            max_adds: self.max_adds,
+            entry_delay_bars: self.entry_delay_bars,
+            mark_price: self.mark_price,
+            statistic_exclusions: self.statistic_exclusions,
+            max_equity_marks: self.max_equity_marks,
+            statistic_config: self.statistic_config,
+            _statistic_marker: self._statistic_marker,
+            _position_key_marker: None,
+        }
+    }
+
+    pub fn build_and_init(
+        self,
+    ) -> Result<MetaPortfolio<Repository, Allocator, RiskManager, Statistic, Key>, PortfolioError>
+    {
+        // Construct Portfolio
+        let mut portfolio = MetaPortfolio {
+            engine_id: self
+                .engine_id
+                .ok_or(PortfolioError::BuilderIncomplete("engine_id"))?,
+            repository: self
+                .repository
+                .ok_or(PortfolioError::BuilderIncomplete("repository"))?,
+            allocation_manager: self
+                .allocation_manager
+                .ok_or(PortfolioError::BuilderIncomplete("allocation_manager"))?,
+            risk_manager: self
+                .risk_manager
+                .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            markets: self
+                .markets
+                .ok_or(PortfolioError::BuilderIncomplete("markets"))?,
+            leverage: validate_leverage(self.leverage.unwrap_or(1.0))?,
+            used_margin: 0.0,
+            position_margins: HashMap::new(),
+            fx_conversion: self.fx_conversion,
+            currency_balances: HashMap::new(),
+            contract_multipliers: self.contract_multipliers.unwrap_or_default(),
+            contract_types: self.contract_types.unwrap_or_default(),
+            max_positions_per_market: validate_max_positions_per_market(
+                self.max_positions_per_market.unwrap_or(1),
+            )?,
+            max_adds: self.max_adds.unwrap_or_default(),
+            pending_entries: HashMap::new(),
+            entry_delay_bars: self.entry_delay_bars.unwrap_or_default(),
+            delayed_entries: HashMap::new(),
+            mark_price: self.mark_price.unwrap_or_default(),
+            statistic_exclusions: self.statistic_exclusions.unwrap_or_default(),
+            equity: EquityCurve::default(),
+            time_in_market: TimeInMarketSummary::default(),
+            position_counts: PositionCounts::default(),
+            win_loss: WinLossSummary::default(),
+            close_only: false,
+            position_tags: HashMap::new(),
+            _statistic_marker: PhantomData,
+            _position_key_marker: PhantomData,
+        };
+
+        // Persist initial state in the Repository
+        let markets = portfolio.markets.clone();
+        portfolio.bootstrap_repository(
+            self.starting_cash
+                .ok_or(PortfolioError::BuilderIncomplete("starting_cash"))?,
+            &markets,
+            self.statistic_config
+                .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?,
+        )?;
+        portfolio.equity.set_max_marks(self.max_equity_marks);
+
+        Ok(portfolio)
+    }
+}
+
+/// Validates the maximum number of [`Position`]s per market. Positions are netted, so at most 1
+/// may be open per market.
+fn validate_max_positions_per_market(value: usize) -> Result<usize, PortfolioError> {
+    match value {
+        0 | 1 => Ok(value),
+        _ => Err(PortfolioError::UnsupportedMaxPositionsPerMarket(value)),
+    }
+}
+
+/// Validates the leverage applied to every [`Position`] is finite & at least 1.0 (fully-funded).
+fn validate_leverage(value: f64) -> Result<f64, PortfolioError> {
+    match value.is_finite() && value >= 1.0 {
+        true => Ok(value),
+        false => Err(PortfolioError::InvalidLeverage(value)),
+    }
+}
+
+/// Parses an incoming [`Signal`]'s signals map for an entry [`Decision`] on the same side as the
+/// open [`Position`] (ie/ adding to it), and it's associated [`SignalStrength`]. Entries are only
+/// advised if the signals map contains no exit of the [`Position`] & no opposite side entry.
+pub fn parse_signal_add<'a>(
+    position: &'a Option<&Position>,
+    signals: &'a HashMap<Decision, SignalStrength>,
+) -> Option<(&'a Decision, &'a SignalStrength)> {
+    let (entry, exit, opposite_entry) = match position.as_ref()?.side {
+        Side::Buy => (Decision::Long, Decision::CloseLong, Decision::Short),
+        Side::Sell => (Decision::Short, Decision::CloseShort, Decision::Long),
+    };
+
+    if signals.contains_key(&exit) || signals.contains_key(&opposite_entry) {
+        return None;
+    }
+
+    signals.get_key_value(&entry)
+}
+
+/// Parses an incoming [`Signal`]'s signals map. Determines what the net signal [`Decision`]
+/// will be, and it's associated [`SignalStrength`].
+pub fn parse_signal_decisions<'a>(
+    position: &'a Option<&Position>,
+    signals: &'a HashMap<Decision, SignalStrength>,
+) -> Option<(&'a Decision, &'a SignalStrength)> {
+    // Determine the presence of signals in the provided signals HashMap
+    let signal_close_long = signals.get_key_value(&Decision::CloseLong);
+    let signal_long = signals.get_key_value(&Decision::Long);
+    let signal_close_short = signals.get_key_value(&Decision::CloseShort);
+    let signal_short = signals.get_key_value(&Decision::Short);
+
+    // If an existing Position exists, check for net close signals
+    if let Some(position) = position {
+        return match position.side {
+            Side::Buy if signal_close_long.is_some() => signal_close_long,
+            Side::Sell if signal_close_short.is_some() => signal_close_short,
+            _ => None,
+        };
+    }
+
+    // Else check for net open signals
+    match (signal_long, signal_short) {
+        (Some(signal_long), None) => Some(signal_long),
+        (None, Some(signal_short)) => Some(signal_short),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use crate::{
+        execution::{
+            simulated::{Config, SimulatedExecution},
+            ExecutionClient, Fees,
+        },
+        portfolio::{
+            allocator::DefaultAllocator,
+            fx::StaticFxRates,
+            position::{determine_position_id, PositionBuilder, TaggedPositionKey},
+            repository::{error::RepositoryError, in_memory::InMemoryRepository},
+            risk::{DailyLossLimitRisk, DefaultRisk},
+        },
+        statistic::summary::pnl::PnLReturnSummary,
+        strategy::SignalForceExit,
+        test_util::{closed_position, fill_event, market_event_trade, position, signal},
+    };
+    use barter_integration::model::{
+        instrument::{kind::InstrumentKind, Instrument},
+        Exchange, Side,
+    };
+
+    #[derive(Default)]
+    struct MockRepository<Statistic> {
+        set_open_position: Option<fn(position: Position) -> Result<(), RepositoryError>>,
+        get_open_position:
+            Option<fn(position_id: &String) -> Result<Option<Position>, RepositoryError>>,
+        get_open_positions: Option<
+            fn(engine_id: Uuid, markets: Vec<&Market>) -> Result<Vec<Position>, RepositoryError>,
+        >,
+        remove_position:
+            Option<fn(engine_id: &String) -> Result<Option<Position>, RepositoryError>>,
+        set_exited_position:
+            Option<fn(engine_id: Uuid, position: Position) -> Result<(), RepositoryError>>,
+        get_exited_positions: Option<fn(engine_id: Uuid) -> Result<Vec<Position>, RepositoryError>>,
+        set_balance: Option<fn(engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError>>,
+        get_balance: Option<fn(engine_id: Uuid) -> Result<Balance, RepositoryError>>,
+        set_statistics:
+            Option<fn(market_id: MarketId, statistic: Statistic) -> Result<(), RepositoryError>>,
+        get_statistics: Option<fn(market_id: &MarketId) -> Result<Statistic, RepositoryError>>,
+        position: Option<PositionBuilder>,
+        balance: Option<Balance>,
+    }
+
+    impl<Statistic> PositionHandler for MockRepository<Statistic> {
+        fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
+            self.position = Some(
+                Position::builder()
+                    .side(position.side.clone())
+                    .current_symbol_price(position.current_symbol_price)
+                    .current_value_gross(position.current_value_gross)
+                    .enter_fees_total(position.enter_fees_total)
+                    .enter_value_gross(position.enter_value_gross)
+                    .enter_avg_price_gross(position.enter_avg_price_gross)
+                    .exit_fees_total(position.exit_fees_total)
+                    .exit_value_gross(position.exit_value_gross)
+                    .exit_avg_price_gross(position.exit_avg_price_gross)
+                    .unrealised_profit_loss(position.unrealised_profit_loss)
+                    .realised_profit_loss(position.realised_profit_loss)
+                    .max_favorable(position.max_favorable)
+                    .max_adverse(position.max_adverse),
+            );
+            self.set_open_position.unwrap()(position)
+        }
+
+        fn get_open_position(
+            &mut self,
+            position_id: &String,
+        ) -> Result<Option<Position>, RepositoryError> {
+            self.get_open_position.unwrap()(position_id)
+        }
+
+        fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+            &mut self,
+            engine_id: Uuid,
+            markets: Markets,
+        ) -> Result<Vec<Position>, RepositoryError> {
+            self.get_open_positions.unwrap()(engine_id, markets.into_iter().collect())
+        }
+
+        fn remove_position(
+            &mut self,
+            position_id: &String,
+        ) -> Result<Option<Position>, RepositoryError> {
+            self.remove_position.unwrap()(position_id)
+        }
+
+        fn set_exited_position(
+            &mut self,
+            portfolio_id: Uuid,
+            position: Position,
+        ) -> Result<(), RepositoryError> {
+            self.set_exited_position.unwrap()(portfolio_id, position)
+        }
+
+        fn get_exited_positions(
+            &mut self,
+            portfolio_id: Uuid,
+        ) -> Result<Vec<Position>, RepositoryError> {
+            self.get_exited_positions.unwrap()(portfolio_id)
+        }
+    }
+
+    impl<Statistic> BalanceHandler for MockRepository<Statistic> {
+        fn set_balance(
+            &mut self,
+            engine_id: Uuid,
+            balance: Balance,
+        ) -> Result<(), RepositoryError> {
+            self.balance = Some(balance);
+            self.set_balance.unwrap()(engine_id, balance)
+        }
+
+        fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+            self.get_balance.unwrap()(engine_id)
+        }
+    }
+
+    impl<Statistic> StatisticHandler<Statistic> for MockRepository<Statistic> {
+        fn set_statistics(
+            &mut self,
+            market_id: MarketId,
+            statistic: Statistic,
+        ) -> Result<(), RepositoryError> {
+            self.set_statistics.unwrap()(market_id, statistic)
+        }
+
+        fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+            self.get_statistics.unwrap()(market_id)
+        }
+    }
+
+    fn new_mocked_portfolio<Repository, Statistic>(
+        mock_repository: Repository,
+    ) -> Result<MetaPortfolio<Repository, DefaultAllocator, DefaultRisk, Statistic>, PortfolioError>
+    where
+        Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+        Statistic: PositionSummariser + Initialiser,
+    {
+        let builder = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .starting_cash(1000.0)
+            .repository(mock_repository)
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {});
+
+        build_uninitialised_portfolio(builder)
+    }
+
+    fn build_uninitialised_portfolio<Repository, Statistic, Key>(
+        builder: MetaPortfolioBuilder<Repository, DefaultAllocator, DefaultRisk, Statistic, Key>,
+    ) -> Result<
+        MetaPortfolio<Repository, DefaultAllocator, DefaultRisk, Statistic, Key>,
+        PortfolioError,
+    >
+    where
+        Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+        Statistic: PositionSummariser + Initialiser,
+        Key: PositionKey,
+    {
+        Ok(MetaPortfolio {
+            engine_id: builder
+                .engine_id
+                .ok_or(PortfolioError::BuilderIncomplete("engine_id"))?,
+            repository: builder
+                .repository
+                .ok_or(PortfolioError::BuilderIncomplete("repository"))?,
+            allocation_manager: builder
+                .allocation_manager
+                .ok_or(PortfolioError::BuilderIncomplete("allocation_manager"))?,
+            risk_manager: builder
+                .risk_manager
+                .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            markets: builder.markets.unwrap_or_default(),
+            leverage: builder.leverage.unwrap_or(1.0),
+            used_margin: 0.0,
+            position_margins: HashMap::new(),
+            fx_conversion: builder.fx_conversion,
+            currency_balances: HashMap::new(),
+            contract_multipliers: builder.contract_multipliers.unwrap_or_default(),
+            contract_types: builder.contract_types.unwrap_or_default(),
+            max_positions_per_market: builder.max_positions_per_market.unwrap_or(1),
+            max_adds: builder.max_adds.unwrap_or_default(),
+            pending_entries: HashMap::new(),
+            entry_delay_bars: builder.entry_delay_bars.unwrap_or_default(),
+            delayed_entries: HashMap::new(),
+            mark_price: builder.mark_price.unwrap_or_default(),
+            statistic_exclusions: builder.statistic_exclusions.unwrap_or_default(),
+            equity: EquityCurve::default(),
+            time_in_market: TimeInMarketSummary::default(),
+            position_counts: PositionCounts::default(),
+            win_loss: WinLossSummary::default(),
+            close_only: false,
+            position_tags: HashMap::new(),
+            _statistic_marker: Default::default(),
+            _position_key_marker: PhantomData,
+        })
+    }
+
+    fn new_signal_force_exit() -> SignalForceExit {
+        SignalForceExit {
+            time: Utc::now(),
+            exchange: Exchange::from("binance"),
+            instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            tag: None,
+        }
+    }
+
+    #[test]
+    fn update_from_market_with_long_position_increasing_in_value() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| {
+            Ok(Some({
+                let mut input_position = position();
+                input_position.side = Side::Buy;
+                input_position.quantity = 1.0;
+                input_position.enter_fees_total = 3.0;
+                input_position.current_symbol_price = 100.0;
+                input_position.current_value_gross = 100.0;
+                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                input_position
+            }))
+        });
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input MarketEvent
+        let mut input_market = market_event_trade(Side::Buy);
+
+        match input_market.kind {
+            // candle.close +100.0 on input_position.current_symbol_price
+            DataKind::Candle(ref mut candle) => candle.close = 200.0,
+            DataKind::Trade(ref mut trade) => trade.price = 200.0,
+            _ => todo!(),
+        };
+
+        let result_pos_update = portfolio
+            .update_from_market(&input_market)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let updated_position = portfolio.repository.position.unwrap();
+
+        assert_eq!(updated_position.current_symbol_price.unwrap(), 200.0);
+        assert_eq!(updated_position.current_value_gross.unwrap(), 200.0);
+
+        // Unreal PnL Long = current_value_gross - enter_value_gross - enter_fees_total*2
+        assert_eq!(
+            updated_position.unrealised_profit_loss.unwrap(),
+            200.0 - 100.0 - 6.0
+        );
+        assert_eq!(
+            result_pos_update.unrealised_profit_loss,
+            200.0 - 100.0 - 6.0
+        );
+    }
+
+    #[test]
+    fn update_from_market_with_long_position_decreasing_in_value() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| {
+            Ok(Some({
+                let mut input_position = position();
+                input_position.side = Side::Buy;
+                input_position.quantity = 1.0;
+                input_position.enter_fees_total = 3.0;
+                input_position.current_symbol_price = 100.0;
+                input_position.current_value_gross = 100.0;
+                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                input_position
+            }))
+        });
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input MarketEvent
+        let mut input_market = market_event_trade(Side::Buy);
+        match input_market.kind {
+            // -50.0 on input_position.current_symbol_price
+            DataKind::Candle(ref mut candle) => candle.close = 50.0,
+            DataKind::Trade(ref mut trade) => trade.price = 50.0,
+            _ => todo!(),
+        };
+
+        let result_pos_update = portfolio
+            .update_from_market(&input_market)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let updated_position = portfolio.repository.position.unwrap();
+
+        assert_eq!(updated_position.current_symbol_price.unwrap(), 50.0);
+        assert_eq!(updated_position.current_value_gross.unwrap(), 50.0);
+        // Unreal PnL Long = current_value_gross - enter_value_gross - enter_fees_total*2
+        assert_eq!(
+            updated_position.unrealised_profit_loss.unwrap(),
+            50.0 - 100.0 - 6.0
+        );
+        assert_eq!(result_pos_update.unrealised_profit_loss, 50.0 - 100.0 - 6.0);
+    }
+
+    #[test]
+    fn update_from_market_with_short_position_increasing_in_value() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| {
+            Ok(Some({
+                let mut input_position = position();
+                input_position.side = Side::Sell;
+                input_position.quantity = -1.0;
+                input_position.enter_fees_total = 3.0;
+                input_position.current_symbol_price = 100.0;
+                input_position.current_value_gross = 100.0;
+                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                input_position
+            }))
+        });
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input MarketEvent
+        let mut input_market = market_event_trade(Side::Buy);
+
+        match input_market.kind {
+            // -50.0 on input_position.current_symbol_price
+            DataKind::Candle(ref mut candle) => candle.close = 50.0,
+            DataKind::Trade(ref mut trade) => trade.price = 50.0,
+            _ => todo!(),
+        };
+
+        let result_pos_update = portfolio
+            .update_from_market(&input_market)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let updated_position = portfolio.repository.position.unwrap();
+
+        assert_eq!(updated_position.current_symbol_price.unwrap(), 50.0);
+        assert_eq!(updated_position.current_value_gross.unwrap(), 50.0);
+        // Unreal PnL Short = enter_value_gross - current_value_gross - enter_fees_total*2
+        assert_eq!(
+            updated_position.unrealised_profit_loss.unwrap(),
+            100.0 - 50.0 - 6.0
+        );
+        assert_eq!(result_pos_update.unrealised_profit_loss, 100.0 - 50.0 - 6.0);
+    }
+
+    #[test]
+    fn update_from_market_with_short_position_decreasing_in_value() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| {
+            Ok(Some({
+                let mut input_position = position();
+                input_position.side = Side::Sell;
+                input_position.quantity = -1.0;
+                input_position.enter_fees_total = 3.0;
+                input_position.current_symbol_price = 100.0;
+                input_position.current_value_gross = 100.0;
+                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                input_position
+            }))
+        });
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input MarketEvent
+        let mut input_market = market_event_trade(Side::Buy);
+
+        match input_market.kind {
+            // +100.0 on input_position.current_symbol_price
+            DataKind::Candle(ref mut candle) => candle.close = 200.0,
+            DataKind::Trade(ref mut trade) => trade.price = 200.0,
+            _ => todo!(),
+        };
+
+        let result_pos_update = portfolio
+            .update_from_market(&input_market)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let updated_position = portfolio.repository.position.unwrap();
+
+        assert_eq!(updated_position.current_symbol_price.unwrap(), 200.0);
+        assert_eq!(updated_position.current_value_gross.unwrap(), 200.0);
+        // Unreal PnL Short = enter_value_gross - current_value_gross - enter_fees_total*2
+        assert_eq!(
+            updated_position.unrealised_profit_loss.unwrap(),
+            100.0 - 200.0 - 6.0
+        );
+        assert_eq!(
+            result_pos_update.unrealised_profit_loss,
+            100.0 - 200.0 - 6.0
+        );
+    }
+
+    #[test]
+    fn bar_returns_marked_to_market_from_update_from_market() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| {
+            Ok(Some({
+                let mut input_position = position();
+                input_position.side = Side::Buy;
+                input_position.quantity = 1.0;
+                input_position.enter_fees_total = 3.0;
+                input_position.current_symbol_price = 100.0;
+                input_position.current_value_gross = 100.0;
+                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                input_position
+            }))
+        });
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Initial equity mark of 1000.0 with no unrealised PnL
+        let mut input_market = market_event_trade(Side::Buy);
+        portfolio.equity = EquityCurve::new(1000.0);
+        portfolio
+            .equity
+            .mark(input_market.exchange_time - chrono::Duration::minutes(1));
+        assert!(portfolio.bar_returns().is_empty());
+
+        match input_market.kind {
+            // trade.price +100.0 on input_position.current_symbol_price
+            DataKind::Trade(ref mut trade) => trade.price = 200.0,
+            _ => todo!(),
+        };
+
+        portfolio.update_from_market(&input_market).unwrap();
+
+        // Unreal PnL Long = 200.0 - 100.0 - 6.0 = 94.0, so equity moves 1000.0 -> 1094.0
+        let bar_returns = portfolio.bar_returns();
+        assert_eq!(bar_returns.len(), 1);
+        assert_eq!(bar_returns[0].0, input_market.exchange_time);
+        assert!((bar_returns[0].1 - 0.094).abs() < 1e-10);
+    }
+
+    #[test]
+    fn apply_cash_flow_deposit_adjusts_balance_and_equity() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| Ok(Balance::new(Utc::now(), 1000.0, 400.0))),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+        portfolio.equity = EquityCurve::new(1000.0);
+
+        let timestamp = Utc::now();
+        let balance = portfolio.apply_cash_flow(500.0, timestamp).unwrap();
+
+        assert_eq!(balance, Balance::new(timestamp, 1500.0, 900.0));
+        assert_eq!(portfolio.repository.balance, Some(balance));
+        assert_eq!(portfolio.equity.current(), 1500.0);
+        assert_eq!(portfolio.equity.cash_flows[0].amount, 500.0);
+    }
+
+    #[test]
+    fn apply_cash_flow_withdrawal_greater_than_available_cash_is_rejected() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| Ok(Balance::new(Utc::now(), 1000.0, 400.0))),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        let actual = portfolio.apply_cash_flow(-500.0, Utc::now());
+
+        assert!(matches!(
+            actual,
+            Err(PortfolioError::InsufficientAvailableCash { .. })
+        ));
+        assert!(portfolio.equity.cash_flows.is_empty());
+    }
+
+    #[test]
+    fn apply_cash_flow_withdrawal_of_reserved_cash_is_rejected() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| Ok(Balance::new(Utc::now(), 1000.0, 400.0))),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // In-flight entry OrderEvent reserves 200.0 of the 400.0 current cash
+        portfolio.pending_entries.insert(
+            "pending_position".to_owned(),
+            VecDeque::from([PendingEntry {
+                remaining_quantity: 2.0,
+                reserved_cash: 200.0,
+                partially_filled: false,
+            }]),
+        );
+
+        let actual = portfolio.apply_cash_flow(-300.0, Utc::now());
+
+        assert!(matches!(
+            actual,
+            Err(PortfolioError::InsufficientAvailableCash { available, withdrawal })
+                if available == 200.0 && withdrawal == 300.0
+        ));
+        assert!(portfolio.equity.cash_flows.is_empty());
+    }
+
+    #[test]
+    fn time_in_market_updated_from_update_from_market() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| Ok(None));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input MarketEvent with no open Position
+        let input_market = market_event_trade(Side::Buy);
+        portfolio.update_from_market(&input_market).unwrap();
+
+        // Simulate an open Position in another market during the next bar
+        portfolio
+            .equity
+            .update_unrealised(&"other_position".to_owned(), 0.0);
+        let mut input_market = input_market;
+        input_market.exchange_time += chrono::Duration::minutes(1);
+        portfolio.update_from_market(&input_market).unwrap();
+
+        let time_in_market = portfolio.time_in_market();
+        let market_id = MarketId::new(&input_market.exchange, &input_market.instrument);
+        assert_eq!(time_in_market.market(&market_id).bars, 2);
+        assert_eq!(time_in_market.market(&market_id).fraction(), 0.0);
+        assert_eq!(time_in_market.total.fraction(), 0.5);
+    }
+
+    #[test]
+    fn generate_no_order_with_no_position_and_no_cash() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| Ok(None));
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 100.0,
+                available: 0.0,
+            })
+        });
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input SignalEvent
+        let input_signal = signal();
+
+        let actual = portfolio.generate_order(&input_signal).unwrap();
+
+        assert!(actual.is_none())
+    }
+
+    #[test]
+    fn generate_no_second_entry_order_for_same_market_while_first_is_pending_or_open() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 1000.0,
+                    available: 1000.0,
+                })
+            }),
+            set_open_position: Some(|_| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input SignalEvent
+        let mut input_signal = signal();
+        input_signal.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+
+        // First entry OrderEvent is generated, but the second is ignored whilst it is pending
+        let order = portfolio.generate_order(&input_signal).unwrap().unwrap();
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
+
+        // Fill first entry OrderEvent, which opens a Position
+        let mut input_fill = fill_event();
+        input_fill.decision = order.decision;
+        input_fill.quantity = order.quantity;
+        portfolio.update_from_fill(&input_fill).unwrap();
+        assert!(portfolio.pending_entries.is_empty());
+
+        // Second entry OrderEvent is ignored whilst the first Position is open
+        portfolio.repository.get_open_position = Some(|_| Ok(Some(position())));
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
+    }
+
+    #[test]
+    fn generate_entry_order_again_once_pending_entry_order_expires() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 1000.0,
+                    available: 1000.0,
+                })
+            }),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input SignalEvent
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+
+        // Entry OrderEvent is pending, so a second entry is ignored
+        let order = portfolio.generate_order(&input_signal).unwrap().unwrap();
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
+
+        // Entry OrderEvent expires unfilled, freeing the position limit
+        portfolio.update_from_expired_order(&order).unwrap();
+        assert!(portfolio.pending_entries.is_empty());
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_some());
+    }
+
+    #[test]
+    fn replaced_working_entry_order_releases_pending_entry() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 1000.0,
+                    available: 1000.0,
+                })
+            }),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+        let mut execution = SimulatedExecution::new(Config::default());
+
+        // Input SignalEvent
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+
+        // Entry OrderEvent is submitted as a non-marketable limit order, so it is held working
+        let mut order = portfolio.generate_order(&input_signal).unwrap().unwrap();
+        order.order_type = OrderType::Limit;
+        order.limit_price = Some(order.market_meta.close * 0.9);
+        assert!(execution.submit_order(&order).unwrap().is_none());
+        assert!(!portfolio.pending_entries.is_empty());
+
+        // A new order for the same market replaces the working entry order
+        let mut replacement = order.clone();
+        replacement.limit_price = Some(order.market_meta.close * 0.8);
+        assert!(execution.submit_order(&replacement).unwrap().is_none());
+
+        // Replaced entry OrderEvent is reported as expired, which releases it's pending entry
+        for expired in execution.take_expired_orders() {
+            portfolio.update_from_expired_order(&expired).unwrap();
+        }
+        assert!(portfolio.pending_entries.is_empty());
+        assert_eq!(
+            portfolio.available_cash().unwrap(),
+            portfolio.current_cash().unwrap()
+        );
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_some());
+    }
+
+    #[test]
+    fn generate_no_entry_order_whilst_close_only_but_generate_exit_order() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 1000.0,
+                    available: 1000.0,
+                })
+            }),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+        portfolio.set_close_only(true);
+        assert!(portfolio.is_close_only());
+
+        // Input SignalEvent
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+
+        // Entry OrderEvent is rejected whilst close only
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
+        assert!(portfolio.pending_entries.is_empty());
+
+        // Exit OrderEvent for an open Position is still generated
+        portfolio.repository.get_open_position = Some(|_| {
+            Ok(Some({
+                let mut position = position();
+                position.side = Side::Buy;
+                position
+            }))
+        });
+        let mut exit_signal = signal();
+        exit_signal
+            .signals
+            .insert(Decision::CloseLong, SignalStrength(1.0));
+        let order = portfolio.generate_order(&exit_signal).unwrap().unwrap();
+        assert_eq!(order.decision, Decision::CloseLong);
+
+        // Entry OrderEvents are generated again once close only is disabled
+        portfolio.set_close_only(false);
+        portfolio.repository.get_open_position = Some(|_| Ok(None));
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_some());
+    }
+
+    #[test]
+    fn generate_entry_order_on_bar_after_configured_entry_delay() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 1000.0,
+                    available: 1000.0,
+                })
+            }),
+            ..Default::default()
+        };
+        let builder = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .starting_cash(1000.0)
+            .repository(mock_repository)
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .entry_delay_bars(2);
+        let mut portfolio = build_uninitialised_portfolio(builder).unwrap();
+
+        // Bar t: entry Signal is deferred
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
 
-        fn get_open_position(
-            &mut self,
-            position_id: &String,
-        ) -> Result<Option<Position>, RepositoryError> {
-            self.get_open_position.unwrap()(position_id)
-        }
+        // Further entry Signals are ignored whilst an entry is delayed
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
 
-        fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
-            &mut self,
-            engine_id: Uuid,
-            markets: Markets,
-        ) -> Result<Vec<Position>, RepositoryError> {
-            self.get_open_positions.unwrap()(engine_id, markets.into_iter().collect())
-        }
+        let bar = |price: f64| {
+            let mut market = market_event_trade(Side::Buy);
+            market.exchange = input_signal.exchange.clone();
+            market.instrument = input_signal.instrument.clone();
+            if let DataKind::Trade(trade) = &mut market.kind {
+                trade.price = price;
+            }
+            market
+        };
 
-        fn remove_position(
-            &mut self,
-            position_id: &String,
-        ) -> Result<Option<Position>, RepositoryError> {
-            self.remove_position.unwrap()(position_id)
-        }
+        // Bar t+1: entry delay has not elapsed
+        assert!(portfolio
+            .generate_delayed_orders(&bar(100.0))
+            .unwrap()
+            .is_empty());
 
-        fn set_exited_position(
-            &mut self,
-            portfolio_id: Uuid,
-            position: Position,
-        ) -> Result<(), RepositoryError> {
-            self.set_exited_position.unwrap()(portfolio_id, position)
+        // Bars of other markets do not count towards the entry delay
+        let mut other_market = bar(100.0);
+        other_market.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+        assert!(portfolio
+            .generate_delayed_orders(&other_market)
+            .unwrap()
+            .is_empty());
+
+        // Bar t+2: entry OrderEvent generated at the current bar's close & volume
+        let release_bar = bar(200.0);
+        let orders = portfolio.generate_delayed_orders(&release_bar).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].decision, Decision::Long);
+        assert_eq!(orders[0].market_meta.close, 200.0);
+        match &release_bar.kind {
+            DataKind::Trade(trade) => assert_eq!(orders[0].market_meta.volume, Some(trade.amount)),
+            _ => unreachable!(),
         }
+        assert_eq!(orders[0].market_meta.spread, None);
+        assert_eq!(orders[0].quantity, 0.5);
 
-        fn get_exited_positions(
-            &mut self,
-            portfolio_id: Uuid,
-        ) -> Result<Vec<Position>, RepositoryError> {
-            self.get_exited_positions.unwrap()(portfolio_id)
-        }
+        // Delayed entry is only generated once
+        assert!(portfolio
+            .generate_delayed_orders(&bar(200.0))
+            .unwrap()
+            .is_empty());
     }
 
-    impl<Statistic> BalanceHandler for MockRepository<Statistic> {
-        fn set_balance(
-            &mut self,
-            engine_id: Uuid,
-            balance: Balance,
-        ) -> Result<(), RepositoryError> {
-            self.balance = Some(balance);
-            self.set_balance.unwrap()(engine_id, balance)
-        }
+    #[test]
+    fn position_counts_updated_from_fills() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+        assert_eq!(portfolio.position_counts(), PositionCounts::default());
+
+        let mut entry_fill = fill_event();
+        portfolio
+            .repository
+            .set_statistics(
+                MarketId::new(&entry_fill.exchange, &entry_fill.instrument),
+                PnLReturnSummary::init(()),
+            )
+            .unwrap();
 
-        fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
-            self.get_balance.unwrap()(engine_id)
-        }
+        // Entry FillEvent opens a Position
+        entry_fill.decision = Decision::Long;
+        portfolio.update_from_fill(&entry_fill).unwrap();
+
+        let counts = portfolio.position_counts();
+        assert_eq!((counts.open(), counts.closed, counts.opened), (1, 0, 1));
+
+        // Exit FillEvent closes the Position
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        portfolio.update_from_fill(&exit_fill).unwrap();
+
+        let counts = portfolio.position_counts();
+        assert_eq!((counts.open(), counts.closed, counts.opened), (0, 1, 1));
+
+        // Re-entry opens a second Position
+        portfolio.update_from_fill(&entry_fill).unwrap();
+
+        let counts = portfolio.position_counts();
+        assert_eq!((counts.open(), counts.closed, counts.opened), (1, 1, 2));
     }
 
-    impl<Statistic> StatisticHandler<Statistic> for MockRepository<Statistic> {
-        fn set_statistics(
-            &mut self,
-            market_id: MarketId,
-            statistic: Statistic,
-        ) -> Result<(), RepositoryError> {
-            self.set_statistics.unwrap()(market_id, statistic)
+    #[test]
+    fn position_counts_seeded_from_repository_on_init() {
+        let engine_id = Uuid::new_v4();
+        let open = position();
+        let market = Market::new(open.exchange.clone(), open.instrument.clone());
+
+        // Repository already holds an open & two exited Positions from a previous session
+        let mut repository = InMemoryRepository::<PnLReturnSummary>::new();
+        repository
+            .set_open_position(Position {
+                position_id: determine_position_id(engine_id, &open.exchange, &open.instrument),
+                ..open
+            })
+            .unwrap();
+        for _ in 0..2 {
+            repository
+                .set_exited_position(engine_id, closed_position(10.0))
+                .unwrap();
         }
 
-        fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
-            self.get_statistics.unwrap()(market_id)
-        }
+        let portfolio = MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market])
+            .starting_cash(1000.0)
+            .repository(repository)
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        let counts = portfolio.position_counts();
+        assert_eq!((counts.open(), counts.closed, counts.opened), (1, 2, 3));
     }
 
-    fn new_mocked_portfolio<Repository, Statistic>(
-        mock_repository: Repository,
-    ) -> Result<MetaPortfolio<Repository, DefaultAllocator, DefaultRisk, Statistic>, PortfolioError>
-    where
-        Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
-        Statistic: PositionSummariser + Initialiser,
-    {
-        let builder = MetaPortfolio::builder()
+    #[test]
+    fn generate_add_order_only_with_max_adds_remaining() {
+        let portfolio = |max_adds| {
+            let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+            mock_repository.get_open_position = Some(|_| {
+                Ok(Some({
+                    let mut position = position();
+                    position.adds = 1;
+                    position
+                }))
+            });
+            mock_repository.get_balance = Some(|_| Ok(Balance::new(Utc::now(), 1000.0, 900.0)));
+
+            build_uninitialised_portfolio(
+                MetaPortfolio::builder()
+                    .engine_id(Uuid::new_v4())
+                    .starting_cash(1000.0)
+                    .max_adds(max_adds)
+                    .repository(mock_repository)
+                    .allocation_manager(DefaultAllocator {
+                        default_order_value: 100.0,
+                    })
+                    .risk_manager(DefaultRisk {}),
+            )
+            .unwrap()
+        };
+
+        let mut add_signal = signal();
+        add_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+
+        // Position already has 1 add, so max_adds of 1 generates no add OrderEvent
+        assert!(portfolio(1).generate_order(&add_signal).unwrap().is_none());
+
+        // Add OrderEvent generated, but no second add whilst the first is pending
+        let mut portfolio = portfolio(3);
+        let order = portfolio.generate_order(&add_signal).unwrap().unwrap();
+        assert_eq!((order.decision, order.quantity), (Decision::Long, 1.0));
+        assert!(portfolio.generate_order(&add_signal).unwrap().is_some());
+        assert!(portfolio.generate_order(&add_signal).unwrap().is_none());
+
+        // Close signal takes priority over adding
+        add_signal
+            .signals
+            .insert(Decision::CloseLong, SignalStrength(1.0));
+        let order = portfolio.generate_order(&add_signal).unwrap().unwrap();
+        assert_eq!(order.decision, Decision::CloseLong);
+    }
+
+    #[test]
+    fn add_and_partial_exit_fills_update_position_and_balance() {
+        let mut portfolio = MetaPortfolio::builder()
             .engine_id(Uuid::new_v4())
+            .markets(vec![])
             .starting_cash(1000.0)
-            .repository(mock_repository)
+            .max_adds(1)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
             .allocation_manager(DefaultAllocator {
                 default_order_value: 100.0,
             })
-            .risk_manager(DefaultRisk {});
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        build_uninitialised_portfolio(builder)
+        let mut entry_fill = fill_event();
+        let market_id = MarketId::new(&entry_fill.exchange, &entry_fill.instrument);
+        let position_id = determine_position_id(
+            portfolio.engine_id,
+            &entry_fill.exchange,
+            &entry_fill.instrument,
+        );
+        portfolio
+            .repository
+            .set_statistics(market_id, PnLReturnSummary::init(()))
+            .unwrap();
+
+        // Enter 1.0 @ 100.0, then add 1.0 @ 200.0
+        entry_fill.decision = Decision::Long;
+        portfolio.update_from_fill(&entry_fill).unwrap();
+        let mut add_fill = entry_fill.clone();
+        add_fill.fill_value_gross = 200.0;
+        let events = portfolio.update_from_fill(&add_fill).unwrap();
+        assert!(matches!(events[0], Event::PositionUpdate(_)));
+
+        let position = portfolio
+            .repository
+            .get_open_position(&position_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!((position.quantity, position.adds), (2.0, 1));
+        assert_eq!(position.enter_avg_price_gross, 150.0);
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert_eq!((balance.total, balance.available), (1000.0, 700.0));
+
+        // Partially exit 1.0 @ 170.0, realising 20.0 profit & leaving 1.0 open
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 170.0;
+        let events = portfolio.update_from_fill(&exit_fill).unwrap();
+        assert!(matches!(events[0], Event::PositionUpdate(_)));
+
+        let position = portfolio
+            .repository
+            .get_open_position(&position_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (position.quantity, position.enter_value_gross),
+            (1.0, 150.0)
+        );
+        assert_eq!(position.realised_profit_loss, 20.0);
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert_eq!((balance.total, balance.available), (1020.0, 870.0));
+        assert_eq!(portfolio.position_counts().open(), 1);
+
+        // Exit the remaining 1.0 @ 150.0
+        exit_fill.fill_value_gross = 150.0;
+        portfolio.update_from_fill(&exit_fill).unwrap();
+
+        assert!(portfolio
+            .repository
+            .get_open_position(&position_id)
+            .unwrap()
+            .is_none());
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert_eq!((balance.total, balance.available), (1020.0, 1020.0));
+        assert_eq!(portfolio.position_counts().open(), 0);
+
+        // Exited Position includes the P&L realised by the partial exit
+        let exited = portfolio
+            .repository
+            .get_exited_positions(portfolio.engine_id)
+            .unwrap();
+        assert_eq!(exited.len(), 1);
+        assert_eq!(exited[0].realised_profit_loss, 20.0);
+        assert_eq!(exited[0].unrealised_profit_loss, 0.0);
     }
 
-    fn build_uninitialised_portfolio<Repository, Statistic>(
-        builder: MetaPortfolioBuilder<Repository, DefaultAllocator, DefaultRisk, Statistic>,
-    ) -> Result<MetaPortfolio<Repository, DefaultAllocator, DefaultRisk, Statistic>, PortfolioError>
-    where
-        Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
-        Statistic: PositionSummariser + Initialiser,
-    {
-        Ok(MetaPortfolio {
-            engine_id: builder
-                .engine_id
-                .ok_or(PortfolioError::BuilderIncomplete("engine_id"))?,
-            repository: builder
-                .repository
-                .ok_or(PortfolioError::BuilderIncomplete("repository"))?,
-            allocation_manager: builder
-                .allocation_manager
-                .ok_or(PortfolioError::BuilderIncomplete("allocation_manager"))?,
-            risk_manager: builder
-                .risk_manager
-                .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
-            _statistic_marker: Default::default(),
-        })
+    #[test]
+    fn tagged_position_key_tracks_a_position_per_sub_strategy_tag_in_the_same_market() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .position_key::<TaggedPositionKey>()
+            .build_and_init()
+            .unwrap();
+
+        let tagged_fill = |tag: &str, decision: Decision, quantity: f64| {
+            let mut fill = fill_event();
+            fill.tag = Some(tag.to_owned());
+            fill.decision = decision;
+            fill.quantity = quantity;
+            fill
+        };
+        let market = Market {
+            exchange: fill_event().exchange,
+            instrument: fill_event().instrument,
+        };
+        portfolio
+            .repository
+            .set_statistics(
+                MarketId::new(&market.exchange, &market.instrument),
+                PnLReturnSummary::init(()),
+            )
+            .unwrap();
+
+        // Entry FillEvents of different sub-strategy tags open separate Positions in the market
+        portfolio
+            .update_from_fill(&tagged_fill("trend", Decision::Long, 1.0))
+            .unwrap();
+        portfolio
+            .update_from_fill(&tagged_fill("mean_reversion", Decision::Short, -1.0))
+            .unwrap();
+
+        let open_positions = portfolio
+            .get_open_positions(Uuid::new_v4(), std::iter::once(&market))
+            .unwrap();
+        assert_eq!(open_positions.len(), 2);
+        assert_ne!(open_positions[0].position_id, open_positions[1].position_id);
+
+        // Every open Position of the market is updated from a MarketEvent
+        let mut input_market = market_event_trade(Side::Buy);
+        input_market.exchange = market.exchange.clone();
+        input_market.instrument = market.instrument.clone();
+        assert_eq!(
+            portfolio.update_from_market(&input_market).unwrap().len(),
+            2
+        );
+
+        // Forced exit only targets the Position of the SignalForceExit sub-strategy tag
+        let mut signal_force_exit = SignalForceExit::from(market.clone());
+        signal_force_exit.tag = Some("mean_reversion".to_owned());
+        let exit_order = portfolio
+            .generate_exit_order(signal_force_exit)
+            .unwrap()
+            .unwrap();
+        assert_eq!(exit_order.decision, Decision::CloseShort);
+        assert_eq!(exit_order.tag.as_deref(), Some("mean_reversion"));
+
+        // Exit FillEvent only closes the Position of it's sub-strategy tag
+        portfolio
+            .update_from_fill(&tagged_fill("trend", Decision::CloseLong, -1.0))
+            .unwrap();
+
+        let open_positions = portfolio
+            .get_open_positions(Uuid::new_v4(), std::iter::once(&market))
+            .unwrap();
+        assert_eq!(open_positions.len(), 1);
+        assert_eq!(open_positions[0].tag.as_deref(), Some("mean_reversion"));
+        assert_eq!(portfolio.position_counts().open(), 1);
     }
 
-    fn new_signal_force_exit() -> SignalForceExit {
-        SignalForceExit {
-            time: Utc::now(),
-            exchange: Exchange::from("binance"),
-            instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+    #[test]
+    fn build_portfolio_with_unsupported_max_positions_per_market() {
+        let result = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(1000.0)
+            .repository(MockRepository::<PnLReturnSummary>::default())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .max_positions_per_market(2)
+            .statistic_config(())
+            .build_and_init();
+
+        assert!(matches!(
+            result,
+            Err(PortfolioError::UnsupportedMaxPositionsPerMarket(2))
+        ));
+    }
+
+    #[test]
+    fn build_portfolio_with_invalid_leverage() {
+        for leverage in [0.5, f64::NAN] {
+            let result = MetaPortfolio::builder()
+                .engine_id(Uuid::new_v4())
+                .markets(vec![])
+                .starting_cash(1000.0)
+                .leverage(leverage)
+                .repository(MockRepository::<PnLReturnSummary>::default())
+                .allocation_manager(DefaultAllocator {
+                    default_order_value: 100.0,
+                })
+                .risk_manager(DefaultRisk {})
+                .statistic_config(())
+                .build_and_init();
+
+            assert!(matches!(result, Err(PortfolioError::InvalidLeverage(_))));
         }
     }
 
     #[test]
-    fn update_from_market_with_long_position_increasing_in_value() {
-        // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut input_position = position();
-                input_position.side = Side::Buy;
-                input_position.quantity = 1.0;
-                input_position.enter_fees_total = 3.0;
-                input_position.current_symbol_price = 100.0;
-                input_position.current_value_gross = 100.0;
-                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
-                input_position
-            }))
-        });
-        mock_repository.set_open_position = Some(|_| Ok(()));
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+    fn leveraged_fills_consume_and_release_margin() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(1000.0)
+            .leverage(4.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        // Input MarketEvent
-        let mut input_market = market_event_trade(Side::Buy);
+        let mut entry_fill = fill_event();
+        portfolio
+            .repository
+            .set_statistics(
+                MarketId::new(&entry_fill.exchange, &entry_fill.instrument),
+                PnLReturnSummary::init(()),
+            )
+            .unwrap();
 
-        match input_market.kind {
-            // candle.close +100.0 on input_position.current_symbol_price
-            DataKind::Candle(ref mut candle) => candle.close = 200.0,
-            DataKind::Trade(ref mut trade) => trade.price = 200.0,
-            _ => todo!(),
+        // Entry of notional 100.0 at 4x leverage consumes 25.0 margin
+        entry_fill.decision = Decision::Long;
+        portfolio.update_from_fill(&entry_fill).unwrap();
+        assert_eq!(portfolio.leverage(), 4.0);
+        assert_eq!(portfolio.used_margin(), 25.0);
+        assert_eq!(portfolio.free_margin().unwrap(), 975.0);
+
+        // Exit of notional 120.0 releases the margin & realises the 20.0 profit
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 120.0;
+        portfolio.update_from_fill(&exit_fill).unwrap();
+
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert_eq!(portfolio.used_margin(), 0.0);
+        assert_eq!((balance.total, balance.available), (1020.0, 1020.0));
+    }
+
+    #[test]
+    fn fills_settling_in_a_foreign_currency_convert_into_the_base_currency() {
+        let fx_portfolio = |rates| {
+            MetaPortfolio::builder()
+                .engine_id(Uuid::new_v4())
+                .markets(vec![])
+                .starting_cash(1000.0)
+                .fx_conversion(FxConversion::new("usd", rates))
+                .repository(InMemoryRepository::<PnLReturnSummary>::new())
+                .allocation_manager(DefaultAllocator {
+                    default_order_value: 100.0,
+                })
+                .risk_manager(DefaultRisk {})
+                .statistic_config(())
+                .build_and_init()
+                .unwrap()
         };
+        let (usd, usdt) = (Symbol::new("usd"), Symbol::new("usdt"));
+
+        let mut portfolio = fx_portfolio(StaticFxRates::new().with_rate("usdt", "usd", 0.5));
+        assert_eq!(portfolio.base_currency(), Some(&usd));
+        assert_eq!(portfolio.currency_balances()[&usd].total, 1000.0);
+
+        let mut entry_fill = fill_event();
+        portfolio
+            .repository
+            .set_statistics(
+                MarketId::new(&entry_fill.exchange, &entry_fill.instrument),
+                PnLReturnSummary::init(()),
+            )
+            .unwrap();
 
-        let result_pos_update = portfolio
-            .update_from_market(&input_market)
-            .unwrap()
+        // Entry of notional 100.0 usdt consumes 50.0 usd of the base Balance
+        entry_fill.decision = Decision::Long;
+        portfolio.update_from_fill(&entry_fill).unwrap();
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
             .unwrap();
-        let updated_position = portfolio.repository.position.unwrap();
+        assert_eq!((balance.total, balance.available), (1000.0, 950.0));
+        assert_eq!(portfolio.used_margin(), 50.0);
+        assert_eq!(portfolio.currency_balances()[&usdt].available, -100.0);
+
+        // Exit of notional 120.0 usdt realises 20.0 usdt profit, worth 10.0 usd
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 120.0;
+        portfolio.update_from_fill(&exit_fill).unwrap();
+
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert_eq!((balance.total, balance.available), (1010.0, 1010.0));
+        assert_eq!(portfolio.used_margin(), 0.0);
+
+        let usdt_balance = portfolio.currency_balances()[&usdt];
+        assert_eq!((usdt_balance.total, usdt_balance.available), (20.0, 20.0));
+        let usd_balance = portfolio.currency_balances()[&usd];
+        assert_eq!((usd_balance.total, usd_balance.available), (1000.0, 1000.0));
+
+        // Fills settling in a currency without a rate into the base currency are rejected
+        let mut portfolio = fx_portfolio(StaticFxRates::new());
+        assert!(matches!(
+            portfolio.update_from_fill(&entry_fill),
+            Err(PortfolioError::MissingFxRate { .. })
+        ));
+    }
 
-        assert_eq!(updated_position.current_symbol_price.unwrap(), 200.0);
-        assert_eq!(updated_position.current_value_gross.unwrap(), 200.0);
+    #[test]
+    fn exits_release_the_margin_consumed_at_the_entry_fx_rate() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(1000.0)
+            .fx_conversion(FxConversion::new(
+                "usd",
+                StaticFxRates::new().with_rate("usdt", "usd", 0.5),
+            ))
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        // Unreal PnL Long = current_value_gross - enter_value_gross - enter_fees_total*2
+        let mut entry_fill = fill_event();
+        portfolio
+            .repository
+            .set_statistics(
+                MarketId::new(&entry_fill.exchange, &entry_fill.instrument),
+                PnLReturnSummary::init(()),
+            )
+            .unwrap();
+
+        // Entry of notional 100.0 usdt consumes 50.0 usd of margin at the entry rate
+        entry_fill.decision = Decision::Long;
+        portfolio.update_from_fill(&entry_fill).unwrap();
+        assert_eq!(portfolio.used_margin(), 50.0);
+
+        // Rate moves before the Position exits
+        portfolio.fx_conversion = Some(FxConversion::new(
+            "usd",
+            StaticFxRates::new().with_rate("usdt", "usd", 0.8),
+        ));
+
+        // Partial exit of half the Position releases half the margin consumed at entry
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -0.5;
+        exit_fill.fill_value_gross = 60.0;
+        portfolio.update_from_fill(&exit_fill).unwrap();
+        assert_eq!(portfolio.used_margin(), 25.0);
+
+        // Exit of the remainder releases the rest, & the 20.0 usdt profit is worth 16.0 usd
+        portfolio.update_from_fill(&exit_fill).unwrap();
+        assert_eq!(portfolio.used_margin(), 0.0);
+
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert_eq!((balance.total, balance.available), (1016.0, 1016.0));
+    }
+
+    #[test]
+    fn restore_portfolio_from_snapshot_into_a_new_repository() {
+        let engine_id = Uuid::new_v4();
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let portfolio = || {
+            MetaPortfolio::builder()
+                .engine_id(engine_id)
+                .markets(vec![market.clone()])
+                .starting_cash(1000.0)
+                .repository(InMemoryRepository::<PnLReturnSummary>::new())
+                .allocation_manager(DefaultAllocator {
+                    default_order_value: 100.0,
+                })
+                .risk_manager(DefaultRisk {})
+                .statistic_config(())
+                .build_and_init()
+                .unwrap()
+        };
+
+        // Exit one Position & leave another open
+        let mut original = portfolio();
+        original
+            .repository
+            .set_statistics(
+                MarketId::new(&market.exchange, &market.instrument),
+                PnLReturnSummary::init(()),
+            )
+            .unwrap();
+        for (decision, quantity, fill_value_gross) in [
+            (Decision::Long, 1.0, 100.0),
+            (Decision::CloseLong, -1.0, 120.0),
+            (Decision::Short, -1.0, 100.0),
+        ] {
+            let mut fill = fill_event();
+            fill.decision = decision;
+            fill.quantity = quantity;
+            fill.fill_value_gross = fill_value_gross;
+            original.update_from_fill(&fill).unwrap();
+        }
+        original.set_close_only(true);
+
+        // Snapshot survives a serialisation round trip
+        let snapshot = original.snapshot().unwrap();
+        assert_eq!(snapshot.open_positions.len(), 1);
+        assert_eq!(snapshot.exited_positions.len(), 1);
+        assert_eq!(snapshot.balance.total, 1020.0);
+        let snapshot: PortfolioSnapshot<PnLReturnSummary> =
+            serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        let mut restored = portfolio();
+        restored.restore(snapshot.clone()).unwrap();
+
+        let mut restored_snapshot = restored.snapshot().unwrap();
+        restored_snapshot.time = snapshot.time;
+        assert_eq!(restored_snapshot, snapshot);
+        assert!(restored.is_close_only());
+        assert_eq!(restored.position_counts(), original.position_counts());
+
+        // Restoring clears the cash reserved by entry OrderEvents pending before the restore
+        let mut signal = signal();
+        signal.instrument = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        signal.signals.insert(Decision::Long, SignalStrength(1.0));
+        restored.set_close_only(false);
+        restored.generate_order(&signal).unwrap();
+        assert!(restored.reserved_cash() > 0.0);
+
+        // Restoring again does not duplicate the exited Positions
+        restored.restore(snapshot.clone()).unwrap();
+        assert_eq!(restored.reserved_cash(), 0.0);
         assert_eq!(
-            updated_position.unrealised_profit_loss.unwrap(),
-            200.0 - 100.0 - 6.0
+            restored.available_cash().unwrap(),
+            restored.current_cash().unwrap()
         );
-        assert_eq!(
-            result_pos_update.unrealised_profit_loss,
-            200.0 - 100.0 - 6.0
+        assert_eq!(
+            restored.get_exited_positions(engine_id).unwrap(),
+            snapshot.exited_positions
         );
     }
 
     #[test]
-    fn update_from_market_with_long_position_decreasing_in_value() {
-        // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut input_position = position();
-                input_position.side = Side::Buy;
-                input_position.quantity = 1.0;
-                input_position.enter_fees_total = 3.0;
-                input_position.current_symbol_price = 100.0;
-                input_position.current_value_gross = 100.0;
-                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
-                input_position
-            }))
-        });
-        mock_repository.set_open_position = Some(|_| Ok(()));
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+    fn restore_portfolio_from_incompatible_snapshot_returns_error() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        // Input MarketEvent
-        let mut input_market = market_event_trade(Side::Buy);
-        match input_market.kind {
-            // -50.0 on input_position.current_symbol_price
-            DataKind::Candle(ref mut candle) => candle.close = 50.0,
-            DataKind::Trade(ref mut trade) => trade.price = 50.0,
-            _ => todo!(),
-        };
+        let mut snapshot = portfolio.snapshot().unwrap();
+        snapshot.engine_id = Uuid::new_v4();
+        assert!(matches!(
+            portfolio.restore(snapshot.clone()),
+            Err(PortfolioError::SnapshotEngineMismatch { .. })
+        ));
+
+        // Exited Positions already persisted must be the first of the snapshot's
+        snapshot.engine_id = portfolio.engine_id;
+        portfolio
+            .repository
+            .set_exited_position(portfolio.engine_id, position())
+            .unwrap();
+        assert!(matches!(
+            portfolio.restore(snapshot),
+            Err(PortfolioError::SnapshotExitedPositionsConflict)
+        ));
+    }
 
-        let result_pos_update = portfolio
-            .update_from_market(&input_market)
-            .unwrap()
+    #[test]
+    fn generate_no_entry_order_after_breaching_daily_loss_limit() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DailyLossLimitRisk {
+                max_daily_loss: 10.0,
+            })
+            .statistic_config(())
+            .build_and_init()
             .unwrap();
-        let updated_position = portfolio.repository.position.unwrap();
 
-        assert_eq!(updated_position.current_symbol_price.unwrap(), 50.0);
-        assert_eq!(updated_position.current_value_gross.unwrap(), 50.0);
-        // Unreal PnL Long = current_value_gross - enter_value_gross - enter_fees_total*2
+        let mut entry_fill = fill_event();
+        portfolio
+            .repository
+            .set_statistics(
+                MarketId::new(&entry_fill.exchange, &entry_fill.instrument),
+                PnLReturnSummary::init(()),
+            )
+            .unwrap();
+
+        // Round trip realising a 20.0 loss today
+        entry_fill.time = Utc::now();
+        entry_fill.decision = Decision::Long;
+        portfolio.update_from_fill(&entry_fill).unwrap();
+        let mut exit_fill = entry_fill.clone();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 80.0;
+        portfolio.update_from_fill(&exit_fill).unwrap();
         assert_eq!(
-            updated_position.unrealised_profit_loss.unwrap(),
-            50.0 - 100.0 - 6.0
+            portfolio
+                .equity
+                .daily_profit_loss
+                .realised_on(exit_fill.time),
+            -20.0
         );
-        assert_eq!(result_pos_update.unrealised_profit_loss, 50.0 - 100.0 - 6.0);
+
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
     }
 
     #[test]
-    fn update_from_market_with_short_position_increasing_in_value() {
-        // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut input_position = position();
-                input_position.side = Side::Sell;
-                input_position.quantity = -1.0;
-                input_position.enter_fees_total = 3.0;
-                input_position.current_symbol_price = 100.0;
-                input_position.current_value_gross = 100.0;
-                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
-                input_position
-            }))
-        });
-        mock_repository.set_open_position = Some(|_| Ok(()));
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
-
-        // Input MarketEvent
-        let mut input_market = market_event_trade(Side::Buy);
+    fn in_flight_entry_orders_reserve_cash_until_filled_or_expired() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(150.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        match input_market.kind {
-            // -50.0 on input_position.current_symbol_price
-            DataKind::Candle(ref mut candle) => candle.close = 50.0,
-            DataKind::Trade(ref mut trade) => trade.price = 50.0,
-            _ => todo!(),
+        let signal = |base| {
+            let mut input_signal = signal();
+            input_signal.instrument = Instrument::from((base, "usdt", InstrumentKind::Spot));
+            input_signal
+                .signals
+                .insert(Decision::Long, SignalStrength(1.0));
+            input_signal
         };
 
-        let result_pos_update = portfolio
-            .update_from_market(&input_market)
-            .unwrap()
+        // Entry of notional 100.0 reserves the cash before it is filled
+        let eth_order = portfolio.generate_order(&signal("eth")).unwrap().unwrap();
+        assert_eq!(portfolio.current_cash().unwrap(), 150.0);
+        assert_eq!(portfolio.reserved_cash(), 100.0);
+        assert_eq!(portfolio.available_cash().unwrap(), 50.0);
+
+        // Concurrent entry cannot commit the reserved cash
+        assert!(portfolio.generate_order(&signal("btc")).unwrap().is_none());
+
+        // Expired entry releases it's reservation
+        portfolio.update_from_expired_order(&eth_order).unwrap();
+        assert_eq!(portfolio.reserved_cash(), 0.0);
+        portfolio.generate_order(&signal("btc")).unwrap().unwrap();
+        assert_eq!(portfolio.available_cash().unwrap(), 50.0);
+
+        // Fill settles the reservation into the consumed cash
+        let mut fill = fill_event();
+        fill.instrument = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        fill.decision = Decision::Long;
+        portfolio.update_from_fill(&fill).unwrap();
+        assert_eq!(portfolio.reserved_cash(), 0.0);
+        assert_eq!(portfolio.current_cash().unwrap(), 50.0);
+        assert_eq!(portfolio.available_cash().unwrap(), 50.0);
+    }
+
+    #[test]
+    fn partially_filled_entry_orders_release_reserved_cash_per_tranche() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![])
+            .starting_cash(1000.0)
+            .max_adds(1)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
             .unwrap();
-        let updated_position = portfolio.repository.position.unwrap();
 
-        assert_eq!(updated_position.current_symbol_price.unwrap(), 50.0);
-        assert_eq!(updated_position.current_value_gross.unwrap(), 50.0);
-        // Unreal PnL Short = enter_value_gross - current_value_gross - enter_fees_total*2
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+        let tranche = |quantity: f64| {
+            let mut fill = fill_event();
+            fill.instrument = input_signal.instrument.clone();
+            fill.decision = Decision::Long;
+            fill.quantity = quantity;
+            fill.fill_value_gross = quantity * 100.0;
+            fill
+        };
+        let position_id =
+            portfolio.position_id(&input_signal.exchange, &input_signal.instrument, None);
+
+        // Entry OrderEvent of quantity 1.0 reserves 100.0, released in proportion to each tranche
+        let order = portfolio.generate_order(&input_signal).unwrap().unwrap();
+        assert_eq!(order.quantity, 1.0);
+        portfolio.update_from_fill(&tranche(0.4)).unwrap();
+        assert!((portfolio.reserved_cash() - 60.0).abs() < 1e-10);
+
+        // Final tranche of the entry OrderEvent is not counted as an add
+        portfolio.update_from_fill(&tranche(0.6)).unwrap();
+        assert_eq!(portfolio.reserved_cash(), 0.0);
+        let position = portfolio.get_open_position(&position_id).unwrap().unwrap();
+        assert_eq!((position.quantity, position.adds), (1.0, 0));
+
+        // Add OrderEvent is counted once, & the expired remainder releases the rest of it's cash
+        let order = portfolio.generate_order(&input_signal).unwrap().unwrap();
+        portfolio.update_from_fill(&tranche(0.5)).unwrap();
+        portfolio.update_from_fill(&tranche(0.25)).unwrap();
+        let position = portfolio.get_open_position(&position_id).unwrap().unwrap();
+        assert_eq!(position.adds, 1);
+
+        let mut remainder = order;
+        remainder.quantity = 0.25;
+        portfolio.update_from_expired_order(&remainder).unwrap();
+        assert!(portfolio.pending_entries.is_empty());
         assert_eq!(
-            updated_position.unrealised_profit_loss.unwrap(),
-            100.0 - 50.0 - 6.0
+            portfolio.available_cash().unwrap(),
+            portfolio.current_cash().unwrap()
         );
-        assert_eq!(result_pos_update.unrealised_profit_loss, 100.0 - 50.0 - 6.0);
     }
 
     #[test]
-    fn update_from_market_with_short_position_decreasing_in_value() {
-        // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut input_position = position();
-                input_position.side = Side::Sell;
-                input_position.quantity = -1.0;
-                input_position.enter_fees_total = 3.0;
-                input_position.current_symbol_price = 100.0;
-                input_position.current_value_gross = 100.0;
-                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
-                input_position
-            }))
-        });
-        mock_repository.set_open_position = Some(|_| Ok(()));
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+    fn generate_no_entry_order_exceeding_free_margin() {
+        let portfolio = |leverage| {
+            let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+            mock_repository.get_open_position = Some(|_| Ok(None));
+            mock_repository.get_balance = Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 50.0,
+                    available: 50.0,
+                })
+            });
+
+            build_uninitialised_portfolio(
+                MetaPortfolio::builder()
+                    .engine_id(Uuid::new_v4())
+                    .starting_cash(50.0)
+                    .leverage(leverage)
+                    .repository(mock_repository)
+                    .allocation_manager(DefaultAllocator {
+                        default_order_value: 100.0,
+                    })
+                    .risk_manager(DefaultRisk {}),
+            )
+            .unwrap()
+        };
 
-        // Input MarketEvent
-        let mut input_market = market_event_trade(Side::Buy);
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
 
-        match input_market.kind {
-            // +100.0 on input_position.current_symbol_price
-            DataKind::Candle(ref mut candle) => candle.close = 200.0,
-            DataKind::Trade(ref mut trade) => trade.price = 200.0,
-            _ => todo!(),
-        };
+        // Fully-funded entry of notional 100.0 requires more than the 50.0 free margin
+        assert!(portfolio(1.0)
+            .generate_order(&input_signal)
+            .unwrap()
+            .is_none());
 
-        let result_pos_update = portfolio
-            .update_from_market(&input_market)
+        // At 2x leverage the same entry only requires 50.0 margin
+        let actual = portfolio(2.0)
+            .generate_order(&input_signal)
             .unwrap()
             .unwrap();
-        let updated_position = portfolio.repository.position.unwrap();
-
-        assert_eq!(updated_position.current_symbol_price.unwrap(), 200.0);
-        assert_eq!(updated_position.current_value_gross.unwrap(), 200.0);
-        // Unreal PnL Short = enter_value_gross - current_value_gross - enter_fees_total*2
-        assert_eq!(
-            updated_position.unrealised_profit_loss.unwrap(),
-            100.0 - 200.0 - 6.0
-        );
-        assert_eq!(
-            result_pos_update.unrealised_profit_loss,
-            100.0 - 200.0 - 6.0
-        );
+        assert_eq!(actual.decision, Decision::Long);
+        assert_eq!(actual.quantity, 1.0);
     }
 
     #[test]
-    fn generate_no_order_with_no_position_and_no_cash() {
+    fn generate_no_order_with_degenerate_market_meta_close() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| Ok(None));
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 100.0,
-                available: 0.0,
-            })
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 100.0,
+                })
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
-        // Input SignalEvent
-        let input_signal = signal();
-
-        let actual = portfolio.generate_order(&input_signal).unwrap();
+        for close in [0.0, f64::NAN, f64::INFINITY] {
+            let mut input_signal = signal();
+            input_signal.market_meta.close = close;
+            input_signal
+                .signals
+                .insert(Decision::Long, SignalStrength(1.0));
 
-        assert!(actual.is_none())
+            assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
+        }
     }
 
     #[test]
@@ -980,6 +3574,41 @@ pub mod tests {
         assert_eq!(actual.decision, Decision::Long)
     }
 
+    #[test]
+    fn generate_order_long_sized_in_contracts_with_configured_contract_multiplier() {
+        // Build Portfolio with a contract multiplier for the signal Market
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 100.0,
+                })
+            }),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        let mut input_signal = signal();
+        portfolio.contract_multipliers.insert(
+            MarketId::new(&input_signal.exchange, &input_signal.instrument),
+            5.0,
+        );
+
+        // Input SignalEvent
+        input_signal.market_meta.close = 10.0;
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+
+        let actual = portfolio.generate_order(&input_signal).unwrap().unwrap();
+
+        // default_order_value 100.0 / (close 10.0 * contract_multiplier 5.0)
+        assert_eq!(actual.contract_multiplier, 5.0);
+        assert_eq!(actual.quantity, 2.0);
+    }
+
     #[test]
     fn generate_order_short_with_no_position_and_input_net_short_signal() {
         // Build Portfolio
@@ -1152,7 +3781,7 @@ pub mod tests {
                 available: 200.0,
             })
         });
-        mock_repository.remove_position = Some(|_| Ok(None));
+        mock_repository.get_open_position = Some(|_| Ok(None));
         mock_repository.set_open_position = Some(|_| Ok(()));
         mock_repository.set_balance = Some(|_, _| Ok(()));
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
@@ -1191,7 +3820,7 @@ pub mod tests {
                 available: 200.0,
             })
         });
-        mock_repository.remove_position = Some(|_| Ok(None));
+        mock_repository.get_open_position = Some(|_| Ok(None));
         mock_repository.set_open_position = Some(|_| Ok(()));
         mock_repository.set_balance = Some(|_, _| Ok(()));
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
@@ -1219,6 +3848,36 @@ pub mod tests {
         assert_eq!(updated_cash, 200.0 - 100.0 - 3.0); // cash += enter_value_gross - enter_fees
     }
 
+    #[test]
+    fn update_from_fill_with_invalid_add_fill_keeps_open_position() {
+        // Build Portfolio without remove_position, which would panic if the Position was removed
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| Ok(Balance::new(Utc::now(), 200.0, 97.0))),
+            get_open_position: Some(|_| {
+                let mut input_position = position();
+                input_position.side = Side::Buy;
+                input_position.quantity = 1.0;
+                Ok(Some(input_position))
+            }),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input entry FillEvent on the opposite Side of the open Position
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::Short;
+        input_fill.quantity = -1.0;
+
+        let result = portfolio.update_from_fill(&input_fill);
+
+        assert!(matches!(
+            result,
+            Err(PortfolioError::CannotAddToPositionWithFill)
+        ));
+        assert!(portfolio.repository.position.is_none());
+        assert!(portfolio.repository.balance.is_none());
+    }
+
     #[test]
     fn update_from_fill_exiting_long_position_in_profit() {
         // Build Portfolio
@@ -1230,7 +3889,7 @@ pub mod tests {
                 available: 97.0,
             })
         });
-        mock_repository.remove_position = Some(|_| {
+        mock_repository.get_open_position = Some(|_| {
             Ok({
                 Some({
                     let mut input_position = position();
@@ -1242,6 +3901,7 @@ pub mod tests {
                 })
             })
         });
+        mock_repository.remove_position = Some(|_| Ok(None));
         mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
         mock_repository.set_statistics = Some(|_, _| Ok(()));
         mock_repository.set_exited_position = Some(|_, _| Ok(()));
@@ -1259,6 +3919,10 @@ pub mod tests {
             network: 1.0,
         };
 
+        // Open Position consumed it's enter_value_gross as margin on entry
+        let position_id = portfolio.position_id(&input_fill.exchange, &input_fill.instrument, None);
+        portfolio.consume_margin(&position_id, 100.0);
+
         let result = portfolio.update_from_fill(&input_fill);
         let updated_repository = portfolio.repository;
         let updated_cash = updated_repository.balance.unwrap().available;
@@ -1272,6 +3936,83 @@ pub mod tests {
         assert_eq!(updated_value, 200.0 + (200.0 - 100.0 - 6.0));
     }
 
+    #[test]
+    fn update_from_fill_exiting_position_generates_statistic_update_event() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| Ok(Balance::new(Utc::now(), 200.0, 97.0))),
+            get_open_position: Some(|_| Ok(Some(position()))),
+            remove_position: Some(|_| Ok(None)),
+            get_statistics: Some(|_| Ok(PnLReturnSummary::default())),
+            set_statistics: Some(|_, _| Ok(())),
+            set_exited_position: Some(|_, _| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input FillEvent
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.0;
+        input_fill.fill_value_gross = 200.0;
+
+        let events = portfolio.update_from_fill(&input_fill).unwrap();
+
+        let update = events
+            .iter()
+            .find_map(|event| match event {
+                Event::StatisticUpdate(update) => Some(update),
+                _ => None,
+            })
+            .expect("expected Event::StatisticUpdate");
+
+        assert_eq!(
+            update.market_id,
+            MarketId::new(&input_fill.exchange, &input_fill.instrument)
+        );
+        assert_eq!(update.time, input_fill.time);
+        assert_eq!(update.statistics["total"]["count"], 1);
+    }
+
+    #[test]
+    fn update_from_fill_exiting_position_within_statistic_exclusion_skips_statistics() {
+        // Build Portfolio without statistics handling, which would panic if used
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| Ok(Balance::new(Utc::now(), 200.0, 97.0))),
+            get_open_position: Some(|_| Ok(Some(position()))),
+            remove_position: Some(|_| Ok(None)),
+            set_exited_position: Some(|_, _| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input FillEvent
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.0;
+        input_fill.fill_value_gross = 200.0;
+
+        portfolio.statistic_exclusions = vec![StatisticExclusion {
+            start: input_fill.market_meta.time - chrono::Duration::minutes(1),
+            end: input_fill.market_meta.time + chrono::Duration::minutes(1),
+        }];
+
+        let events = portfolio.update_from_fill(&input_fill).unwrap();
+
+        // Position still exits & updates the Balance
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Event::PositionExit(_))));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Event::Balance(_))));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, Event::StatisticUpdate(_))));
+    }
+
     #[test]
     fn update_from_fill_exiting_long_position_in_loss() {
         // Build Portfolio
@@ -1283,7 +4024,7 @@ pub mod tests {
                 available: 97.0,
             })
         });
-        mock_repository.remove_position = Some(|_| {
+        mock_repository.get_open_position = Some(|_| {
             Ok({
                 Some({
                     let mut input_position = position();
@@ -1295,6 +4036,7 @@ pub mod tests {
                 })
             })
         });
+        mock_repository.remove_position = Some(|_| Ok(None));
         mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
         mock_repository.set_statistics = Some(|_, _| Ok(()));
         mock_repository.set_exited_position = Some(|_, _| Ok(()));
@@ -1312,6 +4054,10 @@ pub mod tests {
             network: 1.0,
         };
 
+        // Open Position consumed it's enter_value_gross as margin on entry
+        let position_id = portfolio.position_id(&input_fill.exchange, &input_fill.instrument, None);
+        portfolio.consume_margin(&position_id, 100.0);
+
         let result = portfolio.update_from_fill(&input_fill);
         let updated_repository = portfolio.repository;
         let updated_cash = updated_repository.balance.unwrap().available;
@@ -1336,7 +4082,7 @@ pub mod tests {
                 available: 97.0,
             })
         });
-        mock_repository.remove_position = Some(|_| {
+        mock_repository.get_open_position = Some(|_| {
             Ok({
                 Some({
                     let mut input_position = position();
@@ -1348,6 +4094,7 @@ pub mod tests {
                 })
             })
         });
+        mock_repository.remove_position = Some(|_| Ok(None));
         mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
         mock_repository.set_statistics = Some(|_, _| Ok(()));
         mock_repository.set_exited_position = Some(|_, _| Ok(()));
@@ -1365,6 +4112,10 @@ pub mod tests {
             network: 1.0,
         };
 
+        // Open Position consumed it's enter_value_gross as margin on entry
+        let position_id = portfolio.position_id(&input_fill.exchange, &input_fill.instrument, None);
+        portfolio.consume_margin(&position_id, 100.0);
+
         let result = portfolio.update_from_fill(&input_fill);
         let updated_repository = portfolio.repository;
         let updated_cash = updated_repository.balance.unwrap().available;
@@ -1389,7 +4140,7 @@ pub mod tests {
                 available: 97.0,
             })
         });
-        mock_repository.remove_position = Some(|_| {
+        mock_repository.get_open_position = Some(|_| {
             Ok({
                 Some({
                     let mut input_position = position();
@@ -1401,6 +4152,7 @@ pub mod tests {
                 })
             })
         });
+        mock_repository.remove_position = Some(|_| Ok(None));
         mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
         mock_repository.set_statistics = Some(|_, _| Ok(()));
         mock_repository.set_exited_position = Some(|_, _| Ok(()));
@@ -1418,6 +4170,10 @@ pub mod tests {
             network: 1.0,
         };
 
+        // Open Position consumed it's enter_value_gross as margin on entry
+        let position_id = portfolio.position_id(&input_fill.exchange, &input_fill.instrument, None);
+        portfolio.consume_margin(&position_id, 100.0);
+
         let result = portfolio.update_from_fill(&input_fill);
         let updated_repository = portfolio.repository;
         let updated_cash = updated_repository.balance.unwrap().available;
@@ -1,6 +1,6 @@
 use crate::{
     execution::{FeeAmount, Fees, FillEvent},
-    portfolio::{error::PortfolioError, Balance},
+    portfolio::{default_contract_multiplier, error::PortfolioError, Balance, ContractType},
     strategy::Decision,
 };
 use barter_data::event::{DataKind, MarketEvent};
@@ -20,14 +20,90 @@ pub trait PositionEnterer {
 pub trait PositionUpdater {
     /// Updates an open [`Position`] using the latest input [`MarketEvent`], returning a
     /// [`PositionUpdate`] that communicates the open [`Position`]'s change in state.
-    fn update(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<PositionUpdate>;
+    ///
+    /// Marks the [`Position`] using [`MarkPrice::Close`].
+    fn update(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<PositionUpdate> {
+        self.update_with_mark_price(market, MarkPrice::Close)
+    }
+
+    /// Updates an open [`Position`] using the latest input [`MarketEvent`], marking it at the
+    /// price determined by the provided [`MarkPrice`] source.
+    fn update_with_mark_price(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+        mark_price: MarkPrice,
+    ) -> Option<PositionUpdate>;
+}
+
+/// Adds to an open [`Position`] (ie/ pyramiding).
+pub trait PositionAdder {
+    /// Adds the input same side entry [`FillEvent`] to an open [`Position`], recomputing it's
+    /// quantity, weighted-average entry price & entry fees. Returns a [`PositionUpdate`] that
+    /// communicates the open [`Position`]'s change in state.
+    fn add(&mut self, fill: &FillEvent) -> Result<PositionUpdate, PortfolioError>;
 }
 
 /// Exits an open [`Position`].
 pub trait PositionExiter {
     /// Exits an open [`Position`], given the input Portfolio equity & the [`FillEvent`] returned
-    /// from an Execution handler.
+    /// from an Execution handler. Any P&L already realised by partial exits is included in the
+    /// realised P&L of the exited [`Position`].
     fn exit(&mut self, balance: Balance, fill: &FillEvent) -> Result<PositionExit, PortfolioError>;
+
+    /// Partially exits an open [`Position`] with an exit [`FillEvent`] for less than it's quantity.
+    /// The exited proportion of the quantity, entry value & entry fees is closed, and it's P&L is
+    /// moved into the realised P&L of the [`Position`], which remains open. Returns a
+    /// [`PositionUpdate`] that communicates the open [`Position`]'s change in state.
+    fn partial_exit(&mut self, fill: &FillEvent) -> Result<PositionUpdate, PortfolioError>;
+}
+
+/// Flattens a [`Position`] into a fixed schema [`PositionRecord`].
+pub trait PositionRecorder {
+    /// Returns a [`PositionRecord`] of scalar fields suitable for columnar insertion (eg/ into a
+    /// time-series database).
+    fn to_record(&self) -> PositionRecord;
+}
+
+/// Source of the price an open [`Position`] is marked to market at.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum MarkPrice {
+    /// Trade price, candle close, or order book volume weighted mid price.
+    #[default]
+    Close,
+    /// Order book mid price of the best bid & ask. Trades & candles are marked at their close.
+    Mid,
+    /// Order book best bid for long [`Position`]s & best ask for short [`Position`]s, ie/ the
+    /// price each could be exited at. Trades & candles are marked at their close.
+    BidAskConservative,
+}
+
+impl MarkPrice {
+    /// Determines the price to mark a [`Position`] of the provided [`Side`] at, if the
+    /// [`DataKind`] yields one.
+    pub fn price(&self, kind: &DataKind, side: Side) -> Option<f64> {
+        match (self, kind) {
+            (_, DataKind::Trade(trade)) => Some(trade.price),
+            (_, DataKind::Candle(candle)) => Some(candle.close),
+            (_, DataKind::Liquidation(_)) => None,
+            (MarkPrice::Close, DataKind::OrderBookL1(book_l1)) => {
+                Some(book_l1.volume_weighed_mid_price())
+            }
+            (MarkPrice::Close, DataKind::OrderBook(book)) => book.volume_weighed_mid_price(),
+            (MarkPrice::Mid, DataKind::OrderBookL1(book_l1)) => Some(book_l1.mid_price()),
+            (MarkPrice::Mid, DataKind::OrderBook(book)) => book.mid_price(),
+            (MarkPrice::BidAskConservative, DataKind::OrderBookL1(book_l1)) => match side {
+                Side::Buy => Some(book_l1.best_bid.price),
+                Side::Sell => Some(book_l1.best_ask.price),
+            },
+            (MarkPrice::BidAskConservative, DataKind::OrderBook(book)) => match side {
+                Side::Buy => book.best_bid(),
+                Side::Sell => book.best_ask(),
+            }
+            .map(|level| level.price),
+        }
+    }
 }
 
 /// Communicates a String represents a unique [`Position`] identifier.
@@ -42,12 +118,67 @@ pub fn determine_position_id(
     format!("{}_{}_{}_position", engine_id, exchange, instrument)
 }
 
+/// Determines the [`PositionId`] a [`Position`] is keyed by in a Portfolio & it's repository,
+/// given an engine_id, [`Exchange`], [`Instrument`] & optional sub-strategy tag (see
+/// [`Signal::tag`](crate::strategy::Signal)).
+///
+/// Each distinct [`PositionId`] is a separate [`Position`], so a [`PositionKey`] that includes
+/// the tag enables multiple concurrent [`Position`]s per market.
+pub trait PositionKey {
+    fn position_id(
+        engine_id: Uuid,
+        exchange: &Exchange,
+        instrument: &Instrument,
+        tag: Option<&str>,
+    ) -> PositionId;
+}
+
+/// Default [`PositionKey`] that keys [`Position`]s by [`Exchange`] & [`Instrument`] only, so
+/// [`Position`]s are netted to one per market regardless of their tag.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Deserialize, Serialize)]
+pub struct MarketPositionKey;
+
+impl PositionKey for MarketPositionKey {
+    fn position_id(
+        engine_id: Uuid,
+        exchange: &Exchange,
+        instrument: &Instrument,
+        _: Option<&str>,
+    ) -> PositionId {
+        determine_position_id(engine_id, exchange, instrument)
+    }
+}
+
+/// [`PositionKey`] that keys [`Position`]s by [`Exchange`], [`Instrument`] & tag, enabling one
+/// [`Position`] per sub-strategy tag per market. Untagged [`Position`]s are keyed as per the
+/// [`MarketPositionKey`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Deserialize, Serialize)]
+pub struct TaggedPositionKey;
+
+impl PositionKey for TaggedPositionKey {
+    fn position_id(
+        engine_id: Uuid,
+        exchange: &Exchange,
+        instrument: &Instrument,
+        tag: Option<&str>,
+    ) -> PositionId {
+        match tag {
+            None => determine_position_id(engine_id, exchange, instrument),
+            Some(tag) => format!("{}_{}_{}_{}_position", engine_id, exchange, instrument, tag),
+        }
+    }
+}
+
 /// Data encapsulating the state of an ongoing or closed [`Position`].
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Position {
     /// Unique identifier for a [`Position`] generated from an engine_id, [`Exchange`] & [`Instrument`].
     pub position_id: PositionId,
 
+    /// Optional sub-strategy tag propagated from the entry [`FillEvent`]. See [`PositionKey`].
+    #[serde(default)]
+    pub tag: Option<String>,
+
     /// Metadata detailing trace UUIDs, timestamps & equity associated with entering, updating & exiting.
     pub meta: PositionMeta,
 
@@ -67,6 +198,16 @@ pub struct Position {
     /// +ve or -ve quantity of symbol contracts opened.
     pub quantity: f64,
 
+    /// Units of the underlying per contract, such that notional = abs(Quantity) * price *
+    /// contract_multiplier. 1.0 for spot.
+    #[serde(default = "default_contract_multiplier")]
+    pub contract_multiplier: f64,
+
+    /// Linear or Inverse, determining how the notional values & P&L of the [`Position`] are
+    /// calculated. See [`ContractType`].
+    #[serde(default)]
+    pub contract_type: ContractType,
+
     /// All fees types incurred from entering a [`Position`], and their associated [`FeeAmount`].
     pub enter_fees: Fees,
 
@@ -76,7 +217,8 @@ pub struct Position {
     /// Enter average price excluding the entry_fees_total.
     pub enter_avg_price_gross: f64,
 
-    /// abs(Quantity) * enter_avg_price_gross.
+    /// Notional value on entry, ie/ abs(Quantity) * enter_avg_price_gross * contract_multiplier
+    /// for [`ContractType::Linear`] contracts.
     pub enter_value_gross: f64,
 
     /// All fees types incurred from exiting a [`Position`], and their associated [`FeeAmount`].
@@ -88,20 +230,35 @@ pub struct Position {
     /// Exit average price excluding the exit_fees_total.
     pub exit_avg_price_gross: f64,
 
-    /// abs(Quantity) * exit_avg_price_gross.
+    /// Notional value on exit, ie/ abs(Quantity) * exit_avg_price_gross * contract_multiplier
+    /// for [`ContractType::Linear`] contracts.
     pub exit_value_gross: f64,
 
     /// Symbol current close price.
     pub current_symbol_price: f64,
 
-    /// abs(Quantity) * current_symbol_price.
+    /// Current notional value, ie/ abs(Quantity) * current_symbol_price * contract_multiplier
+    /// for [`ContractType::Linear`] contracts.
     pub current_value_gross: f64,
 
-    /// Unrealised P&L whilst the [`Position`] is open.
+    /// Unrealised P&L of the quantity still open. Zero once the [`Position`] has closed.
     pub unrealised_profit_loss: f64,
 
-    /// Realised P&L after the [`Position`] has closed.
+    /// Realised P&L booked by partial exits whilst the [`Position`] is open, and in total after
+    /// the [`Position`] has closed.
     pub realised_profit_loss: f64,
+
+    /// Maximum Favorable Excursion (MFE) - the best unrealised P&L observed whilst the
+    /// [`Position`] was open, starting from the unrealised P&L on entry.
+    pub max_favorable: f64,
+
+    /// Maximum Adverse Excursion (MAE) - the worst unrealised P&L observed whilst the
+    /// [`Position`] was open, starting from the unrealised P&L on entry.
+    pub max_adverse: f64,
+
+    /// Number of entry [`FillEvent`]s added to the [`Position`] after it was entered.
+    #[serde(default)]
+    pub adds: usize,
 }
 
 impl PositionEnterer for Position {
@@ -124,11 +281,14 @@ impl PositionEnterer for Position {
 
         Ok(Position {
             position_id: determine_position_id(engine_id, &fill.exchange, &fill.instrument),
+            tag: fill.tag.clone(),
             exchange: fill.exchange.clone(),
             instrument: fill.instrument.clone(),
             meta: metadata,
             side: Position::parse_entry_side(fill)?,
             quantity: fill.quantity,
+            contract_multiplier: fill.contract_multiplier,
+            contract_type: fill.contract_type,
             enter_fees: fill.fees,
             enter_fees_total,
             enter_avg_price_gross,
@@ -141,31 +301,78 @@ impl PositionEnterer for Position {
             current_value_gross: fill.fill_value_gross,
             unrealised_profit_loss,
             realised_profit_loss: 0.0,
+            max_favorable: unrealised_profit_loss,
+            max_adverse: unrealised_profit_loss,
+            adds: 0,
         })
     }
 }
 
-impl PositionUpdater for Position {
-    fn update(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<PositionUpdate> {
-        // Determine close from MarketEvent
-        let close = match &market.kind {
-            DataKind::Trade(trade) => trade.price,
-            DataKind::Candle(candle) => candle.close,
-            DataKind::OrderBookL1(book_l1) => book_l1.volume_weighed_mid_price(),
-            DataKind::OrderBook(book) => book.volume_weighed_mid_price()?,
-            DataKind::Liquidation(_) => return None,
+impl PositionAdder for Position {
+    fn add(&mut self, fill: &FillEvent) -> Result<PositionUpdate, PortfolioError> {
+        if fill.decision.is_exit() || Position::parse_entry_side(fill)? != self.side {
+            return Err(PortfolioError::CannotAddToPositionWithFill);
+        }
+
+        // Enter fees
+        self.enter_fees = Fees {
+            exchange: self.enter_fees.exchange + fill.fees.exchange,
+            slippage: self.enter_fees.slippage + fill.fees.slippage,
+            network: self.enter_fees.network + fill.fees.network,
         };
+        self.enter_fees_total = self.enter_fees.calculate_total_fees();
+
+        // Quantity, enter value & weighted-average enter price
+        self.quantity += fill.quantity;
+        self.enter_value_gross += fill.fill_value_gross;
+        self.enter_avg_price_gross = self.contract_type.price(
+            self.quantity,
+            self.enter_value_gross,
+            self.contract_multiplier,
+        );
+        self.adds += 1;
+
+        // Mark the Position at the add price
+        self.meta.update_time = fill.time;
+        self.current_symbol_price = Position::calculate_avg_price_gross(fill);
+        self.current_value_gross = self.contract_type.notional(
+            self.quantity,
+            self.current_symbol_price,
+            self.contract_multiplier,
+        );
+        self.unrealised_profit_loss = self.calculate_unrealised_profit_loss();
+        self.max_favorable = self.max_favorable.max(self.unrealised_profit_loss);
+        self.max_adverse = self.max_adverse.min(self.unrealised_profit_loss);
+
+        Ok(PositionUpdate::from(self))
+    }
+}
+
+impl PositionUpdater for Position {
+    fn update_with_mark_price(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+        mark_price: MarkPrice,
+    ) -> Option<PositionUpdate> {
+        // Determine mark price from MarketEvent
+        let close = mark_price.price(&market.kind, self.side)?;
 
         self.meta.update_time = market.exchange_time;
 
         self.current_symbol_price = close;
 
         // Market value gross
-        self.current_value_gross = close * self.quantity.abs();
+        self.current_value_gross =
+            self.contract_type
+                .notional(self.quantity, close, self.contract_multiplier);
 
         // Unreal profit & loss
         self.unrealised_profit_loss = self.calculate_unrealised_profit_loss();
 
+        // Maximum favorable & adverse excursion
+        self.max_favorable = self.max_favorable.max(self.unrealised_profit_loss);
+        self.max_adverse = self.max_adverse.min(self.unrealised_profit_loss);
+
         // Return a PositionUpdate event that communicates the change in state
         Some(PositionUpdate::from(self))
     }
@@ -189,17 +396,73 @@ impl PositionExiter for Position {
         self.exit_value_gross = fill.fill_value_gross;
         self.exit_avg_price_gross = Position::calculate_avg_price_gross(fill);
 
-        // Result profit & loss
-        self.realised_profit_loss = self.calculate_realised_profit_loss();
-        self.unrealised_profit_loss = self.realised_profit_loss;
+        // Result profit & loss, moving the remaining unrealised P&L into the realised P&L
+        let exit_profit_loss = self.calculate_realised_profit_loss();
+        self.realised_profit_loss += exit_profit_loss;
+        self.unrealised_profit_loss = 0.0;
 
         // Metadata
-        balance.total += self.realised_profit_loss;
+        balance.total += exit_profit_loss;
         self.meta.update_time = fill.time;
         self.meta.exit_balance = Some(balance);
 
         PositionExit::try_from(self)
     }
+
+    fn partial_exit(&mut self, fill: &FillEvent) -> Result<PositionUpdate, PortfolioError> {
+        if fill.decision.is_entry() {
+            return Err(PortfolioError::CannotExitPositionWithEntryFill);
+        }
+
+        // Close the exited proportion of the Position, moving it's P&L into the realised P&L
+        let exited_fraction = fill.quantity.abs() / self.quantity.abs();
+        let exited_profit_loss = self.contract_type.profit_loss(
+            self.side,
+            self.enter_value_gross * exited_fraction,
+            fill.fill_value_gross,
+        ) - self.enter_fees_total * exited_fraction
+            - fill.fees.calculate_total_fees();
+
+        self.scale(1.0 - exited_fraction);
+        self.realised_profit_loss += exited_profit_loss;
+        self.meta.update_time = fill.time;
+
+        Ok(PositionUpdate::from(self))
+    }
+}
+
+impl PositionRecorder for Position {
+    fn to_record(&self) -> PositionRecord {
+        PositionRecord {
+            position_id: self.position_id.clone(),
+            exchange: self.exchange.to_string(),
+            base: self.instrument.base.to_string(),
+            quote: self.instrument.quote.to_string(),
+            instrument_kind: self.instrument.kind.to_string(),
+            side: self.side.to_string(),
+            quantity: self.quantity,
+            contract_multiplier: self.contract_multiplier,
+            enter_time: self.meta.enter_time.timestamp_millis(),
+            update_time: self.meta.update_time.timestamp_millis(),
+            exit_time: self
+                .meta
+                .exit_balance
+                .map(|balance| balance.time.timestamp_millis()),
+            exit_balance_total: self.meta.exit_balance.map(|balance| balance.total),
+            enter_fees_total: self.enter_fees_total,
+            enter_avg_price_gross: self.enter_avg_price_gross,
+            enter_value_gross: self.enter_value_gross,
+            exit_fees_total: self.exit_fees_total,
+            exit_avg_price_gross: self.exit_avg_price_gross,
+            exit_value_gross: self.exit_value_gross,
+            current_symbol_price: self.current_symbol_price,
+            current_value_gross: self.current_value_gross,
+            unrealised_profit_loss: self.unrealised_profit_loss,
+            realised_profit_loss: self.realised_profit_loss,
+            max_favorable: self.max_favorable,
+            max_adverse: self.max_adverse,
+        }
+    }
 }
 
 impl Position {
@@ -209,9 +472,13 @@ impl Position {
     }
 
     /// Calculates the [`Position::enter_avg_price_gross`] or [`Position::exit_avg_price_gross`] of
-    /// a [`FillEvent`].
+    /// a [`FillEvent`], per unit of the underlying.
     pub fn calculate_avg_price_gross(fill: &FillEvent) -> f64 {
-        (fill.fill_value_gross / fill.quantity).abs()
+        fill.contract_type.price(
+            fill.quantity,
+            fill.fill_value_gross,
+            fill.contract_multiplier,
+        )
     }
 
     /// Determine the [`Position`] entry [`Side`] by analysing the input [`FillEvent`].
@@ -226,6 +493,32 @@ impl Position {
         }
     }
 
+    /// Determines if the input exit [`FillEvent`] is for less than the open [`Position`]
+    /// quantity, and so only closes part of it. See [`PositionExiter::partial_exit`].
+    pub fn is_partial_exit(&self, fill: &FillEvent) -> bool {
+        fill.decision.is_exit() && fill.quantity.abs() < self.quantity.abs()
+    }
+
+    /// Total P&L of the [`Position`], ie/ the realised P&L plus the unrealised P&L of the
+    /// quantity still open.
+    pub fn total_profit_loss(&self) -> f64 {
+        self.realised_profit_loss + self.unrealised_profit_loss
+    }
+
+    /// Scales the quantity, entry value, entry fees & current value of an open [`Position`].
+    fn scale(&mut self, factor: f64) {
+        self.quantity *= factor;
+        self.enter_fees = Fees {
+            exchange: self.enter_fees.exchange * factor,
+            slippage: self.enter_fees.slippage * factor,
+            network: self.enter_fees.network * factor,
+        };
+        self.enter_fees_total *= factor;
+        self.enter_value_gross *= factor;
+        self.current_value_gross *= factor;
+        self.unrealised_profit_loss = self.calculate_unrealised_profit_loss();
+    }
+
     /// Determines the [`Decision`] required to exit this [`Side`] (Buy or Sell) [`Position`].
     pub fn determine_exit_decision(&self) -> Decision {
         match self.side {
@@ -238,24 +531,24 @@ impl Position {
     pub fn calculate_unrealised_profit_loss(&self) -> f64 {
         let approx_total_fees = self.enter_fees_total * 2.0;
 
-        match self.side {
-            Side::Buy => self.current_value_gross - self.enter_value_gross - approx_total_fees,
-            Side::Sell => self.enter_value_gross - self.current_value_gross - approx_total_fees,
-        }
+        self.contract_type
+            .profit_loss(self.side, self.enter_value_gross, self.current_value_gross)
+            - approx_total_fees
     }
 
-    /// Calculate the exact [`Position::realised_profit_loss`] of a [`Position`].
+    /// Calculate the exact realised P&L of exiting the quantity of a [`Position`] still open,
+    /// excluding any P&L already realised by partial exits.
     pub fn calculate_realised_profit_loss(&self) -> f64 {
         let total_fees = self.enter_fees_total + self.exit_fees_total;
 
-        match self.side {
-            Side::Buy => self.exit_value_gross - self.enter_value_gross - total_fees,
-            Side::Sell => self.enter_value_gross - self.exit_value_gross - total_fees,
-        }
+        self.contract_type
+            .profit_loss(self.side, self.enter_value_gross, self.exit_value_gross)
+            - total_fees
     }
 
     /// Calculate the PnL return of a closed [`Position`] - assumed [`Position::realised_profit_loss`] is
-    /// appropriately calculated.
+    /// appropriately calculated. After partial exits, the return is relative to the entry value of
+    /// the quantity held until the final exit.
     pub fn calculate_profit_loss_return(&self) -> f64 {
         self.realised_profit_loss / self.enter_value_gross
     }
@@ -267,9 +560,12 @@ pub struct PositionBuilder {
     pub position_id: Option<PositionId>,
     pub exchange: Option<Exchange>,
     pub instrument: Option<Instrument>,
+    pub tag: Option<String>,
     pub meta: Option<PositionMeta>,
     pub side: Option<Side>,
     pub quantity: Option<f64>,
+    pub contract_multiplier: Option<f64>,
+    pub contract_type: Option<ContractType>,
     pub enter_fees: Option<Fees>,
     pub enter_fees_total: Option<FeeAmount>,
     pub enter_avg_price_gross: Option<f64>,
@@ -282,6 +578,9 @@ pub struct PositionBuilder {
     pub current_value_gross: Option<f64>,
     pub unrealised_profit_loss: Option<f64>,
     pub realised_profit_loss: Option<f64>,
+    pub max_favorable: Option<f64>,
+    pub max_adverse: Option<f64>,
+    pub adds: Option<usize>,
 }
 
 impl PositionBuilder {
@@ -331,6 +630,27 @@ impl PositionBuilder {
         }
     }
 
+    pub fn contract_multiplier(self, value: f64) -> Self {
+        Self {
+            contract_multiplier: Some(value),
+            ..self
+        }
+    }
+
+    pub fn contract_type(self, value: ContractType) -> Self {
+        Self {
+            contract_type: Some(value),
+            ..self
+        }
+    }
+
+    pub fn tag(self, value: String) -> Self {
+        Self {
+            tag: Some(value),
+            ..self
+        }
+    }
+
     pub fn enter_fees(self, value: Fees) -> Self {
         Self {
             enter_fees: Some(value),
@@ -415,11 +735,33 @@ impl PositionBuilder {
         }
     }
 
+    pub fn max_favorable(self, value: f64) -> Self {
+        Self {
+            max_favorable: Some(value),
+            ..self
+        }
+    }
+
+    pub fn max_adverse(self, value: f64) -> Self {
+        Self {
+            max_adverse: Some(value),
+            ..self
+        }
+    }
+
+    pub fn adds(self, value: usize) -> Self {
+        Self {
+            adds: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<Position, PortfolioError> {
         Ok(Position {
             position_id: self
                 .position_id
                 .ok_or(PortfolioError::BuilderIncomplete("position_id"))?,
+            tag: self.tag,
             exchange: self
                 .exchange
                 .ok_or(PortfolioError::BuilderIncomplete("exchange"))?,
@@ -431,6 +773,10 @@ impl PositionBuilder {
             quantity: self
                 .quantity
                 .ok_or(PortfolioError::BuilderIncomplete("quantity"))?,
+            contract_multiplier: self
+                .contract_multiplier
+                .unwrap_or_else(default_contract_multiplier),
+            contract_type: self.contract_type.unwrap_or_default(),
             enter_fees: self
                 .enter_fees
                 .ok_or(PortfolioError::BuilderIncomplete("enter_fees"))?,
@@ -467,10 +813,45 @@ impl PositionBuilder {
             realised_profit_loss: self
                 .realised_profit_loss
                 .ok_or(PortfolioError::BuilderIncomplete("realised_profit_loss"))?,
+            max_favorable: self
+                .max_favorable
+                .ok_or(PortfolioError::BuilderIncomplete("max_favorable"))?,
+            max_adverse: self
+                .max_adverse
+                .ok_or(PortfolioError::BuilderIncomplete("max_adverse"))?,
+            adds: self.adds.unwrap_or_default(),
         })
     }
 }
 
+/// Running counts of the [`Position`]s a Portfolio has opened & closed.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub struct PositionCounts {
+    /// Total number of [`Position`]s ever opened.
+    pub opened: u64,
+    /// Number of [`Position`]s that have been closed.
+    pub closed: u64,
+}
+
+impl PositionCounts {
+    /// Number of [`Position`]s that are currently open.
+    pub fn open(&self) -> u64 {
+        self.opened.saturating_sub(self.closed)
+    }
+
+    /// Record a newly opened [`Position`].
+    pub fn record_opened(&mut self) {
+        self.opened += 1;
+    }
+
+    /// Record a closed [`Position`].
+    pub fn record_closed(&mut self) {
+        self.closed += 1;
+    }
+}
+
 /// Metadata detailing the trace UUIDs & timestamps associated with entering, updating & exiting
 /// a [`Position`].
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
@@ -508,6 +889,9 @@ pub struct PositionUpdate {
     pub current_value_gross: f64,
     /// Unrealised P&L whilst the [`Position`] is open.
     pub unrealised_profit_loss: f64,
+    /// Realised P&L booked by partial exits whilst the [`Position`] is open.
+    #[serde(default)]
+    pub realised_profit_loss: f64,
 }
 
 impl From<&mut Position> for PositionUpdate {
@@ -518,6 +902,7 @@ impl From<&mut Position> for PositionUpdate {
             current_symbol_price: updated_position.current_symbol_price,
             current_value_gross: updated_position.current_value_gross,
             unrealised_profit_loss: updated_position.unrealised_profit_loss,
+            realised_profit_loss: updated_position.realised_profit_loss,
         }
     }
 }
@@ -570,10 +955,86 @@ impl TryFrom<&mut Position> for PositionExit {
     }
 }
 
+/// Flat, fixed schema representation of a [`Position`] for programmatic time-series ingestion.
+///
+/// Notes:
+/// - All timestamps are epoch milliseconds.
+/// - All enums are their lowercase `String` representation.
+/// - `exit_time` & `exit_balance_total` are `None` whilst the [`Position`] is open.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct PositionRecord {
+    pub position_id: String,
+    pub exchange: String,
+    pub base: String,
+    pub quote: String,
+    pub instrument_kind: String,
+    pub side: String,
+    pub quantity: f64,
+    pub contract_multiplier: f64,
+    pub enter_time: i64,
+    pub update_time: i64,
+    pub exit_time: Option<i64>,
+    pub exit_balance_total: Option<f64>,
+    pub enter_fees_total: f64,
+    pub enter_avg_price_gross: f64,
+    pub enter_value_gross: f64,
+    pub exit_fees_total: f64,
+    pub exit_avg_price_gross: f64,
+    pub exit_value_gross: f64,
+    pub current_symbol_price: f64,
+    pub current_value_gross: f64,
+    pub unrealised_profit_loss: f64,
+    pub realised_profit_loss: f64,
+    pub max_favorable: f64,
+    pub max_adverse: f64,
+}
+
+/// Scalar type of a [`PositionRecord`] field.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum RecordFieldKind {
+    String,
+    Float,
+    Integer,
+    NullableFloat,
+    NullableInteger,
+}
+
+impl PositionRecord {
+    /// Ordered field names & [`RecordFieldKind`]s of a [`PositionRecord`], matching the order
+    /// the fields are declared (and serialised) in.
+    pub const SCHEMA: [(&'static str, RecordFieldKind); 24] = [
+        ("position_id", RecordFieldKind::String),
+        ("exchange", RecordFieldKind::String),
+        ("base", RecordFieldKind::String),
+        ("quote", RecordFieldKind::String),
+        ("instrument_kind", RecordFieldKind::String),
+        ("side", RecordFieldKind::String),
+        ("quantity", RecordFieldKind::Float),
+        ("contract_multiplier", RecordFieldKind::Float),
+        ("enter_time", RecordFieldKind::Integer),
+        ("update_time", RecordFieldKind::Integer),
+        ("exit_time", RecordFieldKind::NullableInteger),
+        ("exit_balance_total", RecordFieldKind::NullableFloat),
+        ("enter_fees_total", RecordFieldKind::Float),
+        ("enter_avg_price_gross", RecordFieldKind::Float),
+        ("enter_value_gross", RecordFieldKind::Float),
+        ("exit_fees_total", RecordFieldKind::Float),
+        ("exit_avg_price_gross", RecordFieldKind::Float),
+        ("exit_value_gross", RecordFieldKind::Float),
+        ("current_symbol_price", RecordFieldKind::Float),
+        ("current_value_gross", RecordFieldKind::Float),
+        ("unrealised_profit_loss", RecordFieldKind::Float),
+        ("realised_profit_loss", RecordFieldKind::Float),
+        ("max_favorable", RecordFieldKind::Float),
+        ("max_adverse", RecordFieldKind::Float),
+    ];
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_util::{fill_event, market_event_trade, position};
+    use barter_data::subscription::book::{Level, OrderBookL1};
     use barter_integration::model::Side;
 
     #[test]
@@ -737,6 +1198,208 @@ mod tests {
         }
     }
 
+    fn inverse_fill(decision: Decision, quantity: f64, price: f64) -> FillEvent {
+        let mut fill = fill_event();
+        fill.decision = decision;
+        fill.quantity = quantity;
+        fill.contract_multiplier = 1.0;
+        fill.contract_type = ContractType::Inverse;
+        fill.fill_value_gross = ContractType::Inverse.notional(quantity, price, 1.0);
+        fill.fees = Fees::default();
+        fill
+    }
+
+    fn inverse_market(price: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_trade(Side::Buy);
+        match market.kind {
+            DataKind::Trade(ref mut trade) => trade.price = price,
+            _ => todo!(),
+        };
+        market
+    }
+
+    #[test]
+    fn inverse_contract_notional_price_and_quantity_are_consistent() {
+        // 10,000 contracts with a face value of 1 USD at 10,000 USD/BTC is 1 BTC notional
+        let notional = ContractType::Inverse.notional(-10_000.0, 10_000.0, 1.0);
+        assert_eq!(notional, 1.0);
+        assert_eq!(
+            ContractType::Inverse.price(-10_000.0, notional, 1.0),
+            10_000.0
+        );
+        assert_eq!(ContractType::Inverse.quantity(1.0, 10_000.0, 1.0), 10_000.0);
+
+        // 1,000 contracts with a face value of 10 USD at 20,000 USD/BTC is 0.5 BTC notional
+        assert_eq!(ContractType::Inverse.notional(1_000.0, 20_000.0, 10.0), 0.5);
+
+        // Notional at a zero price, & price of a zero notional, are undefined
+        assert_eq!(ContractType::Inverse.notional(1_000.0, 0.0, 10.0), 0.0);
+        assert_eq!(ContractType::Inverse.price(1_000.0, 0.0, 10.0), 0.0);
+    }
+
+    #[test]
+    fn inverse_long_position_pnl_is_denominated_in_base_asset() {
+        // Long 10,000 USD face value of BTC-USD inverse contracts at 10,000 USD/BTC
+        let mut position = Position::enter(
+            Uuid::new_v4(),
+            &inverse_fill(Decision::Long, 10_000.0, 10_000.0),
+        )
+        .unwrap();
+        assert_eq!(position.contract_type, ContractType::Inverse);
+        assert_eq!(position.enter_avg_price_gross, 10_000.0);
+        assert_eq!(position.enter_value_gross, 1.0);
+
+        // Price rises to 11,000 USD/BTC: PnL = 10,000 * (1/10,000 - 1/11,000) = 1/11 BTC
+        position.update(&inverse_market(11_000.0));
+        assert!((position.current_value_gross - 10.0 / 11.0).abs() < 1e-12);
+        assert!((position.unrealised_profit_loss - 1.0 / 11.0).abs() < 1e-12);
+
+        // Exit at 8,000 USD/BTC: PnL = 10,000 * (1/10,000 - 1/8,000) = -0.25 BTC
+        let exit = inverse_fill(Decision::CloseLong, -10_000.0, 8_000.0);
+        position
+            .exit(Balance::new(Utc::now(), 1.0, 0.0), &exit)
+            .unwrap();
+        assert_eq!(position.exit_avg_price_gross, 8_000.0);
+        assert!((position.realised_profit_loss - -0.25).abs() < 1e-12);
+        assert!((position.calculate_profit_loss_return() - -0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn inverse_short_position_pnl_is_denominated_in_base_asset() {
+        // Short 10,000 USD face value of BTC-USD inverse contracts at 10,000 USD/BTC
+        let mut position = Position::enter(
+            Uuid::new_v4(),
+            &inverse_fill(Decision::Short, -10_000.0, 10_000.0),
+        )
+        .unwrap();
+
+        // Price rises to 11,000 USD/BTC: PnL = -10,000 * (1/10,000 - 1/11,000) = -1/11 BTC
+        position.update(&inverse_market(11_000.0));
+        assert!((position.unrealised_profit_loss - -1.0 / 11.0).abs() < 1e-12);
+
+        // Exit at 8,000 USD/BTC: PnL = -10,000 * (1/10,000 - 1/8,000) = +0.25 BTC
+        let exit = inverse_fill(Decision::CloseShort, 10_000.0, 8_000.0);
+        position
+            .exit(Balance::new(Utc::now(), 1.0, 0.0), &exit)
+            .unwrap();
+        assert!((position.realised_profit_loss - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn add_to_long_position_recomputes_weighted_average_entry() {
+        // Long 1.0 @ 100.0 with 1.0 of fees
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::Long;
+        input_fill.fees = Fees {
+            exchange: 1.0,
+            slippage: 0.0,
+            network: 0.0,
+        };
+        let mut position = Position::enter(Uuid::new_v4(), &input_fill).unwrap();
+
+        // Add 3.0 @ 120.0 with 2.0 of fees
+        let mut add_fill = input_fill.clone();
+        add_fill.quantity = 3.0;
+        add_fill.fill_value_gross = 360.0;
+        add_fill.fees.exchange = 2.0;
+        let update = position.add(&add_fill).unwrap();
+
+        assert_eq!(position.adds, 1);
+        assert_eq!(position.quantity, 4.0);
+        assert_eq!(position.enter_value_gross, 460.0);
+        assert_eq!(position.enter_avg_price_gross, 115.0);
+        assert_eq!(position.enter_fees_total, 3.0);
+        assert_eq!(position.current_symbol_price, 120.0);
+        assert_eq!(position.current_value_gross, 480.0);
+        assert_eq!(position.unrealised_profit_loss, 20.0 - 6.0);
+        assert_eq!(
+            update.unrealised_profit_loss,
+            position.unrealised_profit_loss
+        );
+    }
+
+    #[test]
+    fn add_to_position_with_exit_or_opposite_side_fill_returns_err() {
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::Long;
+        let mut position = Position::enter(Uuid::new_v4(), &input_fill).unwrap();
+
+        let mut short_fill = input_fill.clone();
+        short_fill.decision = Decision::Short;
+        short_fill.quantity = -1.0;
+        assert!(matches!(
+            position.add(&short_fill),
+            Err(PortfolioError::CannotAddToPositionWithFill)
+        ));
+
+        let mut exit_fill = input_fill;
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        assert!(position.add(&exit_fill).is_err());
+        assert_eq!((position.quantity, position.adds), (1.0, 0));
+    }
+
+    #[test]
+    fn partial_exit_moves_closed_profit_loss_into_realised_profit_loss() {
+        // Long 4.0 @ 100.0 with 4.0 of fees
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::Long;
+        input_fill.quantity = 4.0;
+        input_fill.fill_value_gross = 400.0;
+        input_fill.fees = Fees {
+            exchange: 4.0,
+            slippage: 0.0,
+            network: 0.0,
+        };
+        let mut position = Position::enter(Uuid::new_v4(), &input_fill).unwrap();
+
+        // Mark @ 110.0, unrealised P&L of 40.0 - 2 * 4.0 fees
+        let mut input_market = market_event_trade(Side::Buy);
+        if let DataKind::Trade(ref mut trade) = input_market.kind {
+            trade.price = 110.0;
+        }
+        position.update(&input_market);
+        assert_eq!(position.unrealised_profit_loss, 32.0);
+        assert_eq!(position.realised_profit_loss, 0.0);
+
+        // Exit 1.0 of the 4.0 @ 110.0 with 1.0 of fees
+        let mut exit_fill = input_fill.clone();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 110.0;
+        exit_fill.fees.exchange = 1.0;
+        assert!(position.is_partial_exit(&exit_fill));
+        let update = position.partial_exit(&exit_fill).unwrap();
+
+        // Closed 10.0 - 1.0 entry fees - 1.0 exit fees is realised, the remaining 3.0 is open
+        assert_eq!(position.realised_profit_loss, 8.0);
+        assert_eq!(update.realised_profit_loss, 8.0);
+        assert_eq!(
+            (
+                position.quantity,
+                position.enter_value_gross,
+                position.enter_fees_total
+            ),
+            (3.0, 300.0, 3.0)
+        );
+        assert_eq!(position.enter_avg_price_gross, 100.0);
+        assert_eq!(position.unrealised_profit_loss, 30.0 - 6.0);
+        assert_eq!(position.total_profit_loss(), 8.0 + 24.0);
+
+        // Exit the remaining 3.0 @ 120.0 with 3.0 of fees, realising 60.0 - 3.0 - 3.0 more
+        exit_fill.quantity = -3.0;
+        exit_fill.fill_value_gross = 360.0;
+        exit_fill.fees.exchange = 3.0;
+        assert!(!position.is_partial_exit(&exit_fill));
+        let exit = position.exit(Balance::default(), &exit_fill).unwrap();
+
+        assert_eq!(position.realised_profit_loss, 8.0 + 54.0);
+        assert_eq!(position.unrealised_profit_loss, 0.0);
+        assert_eq!(position.total_profit_loss(), 62.0);
+        assert_eq!(exit.realised_profit_loss, 62.0);
+        assert_eq!(exit.exit_balance.total, Balance::default().total + 54.0);
+    }
+
     #[test]
     fn update_long_position_so_unreal_pnl_increases() {
         // Initial Position
@@ -850,6 +1513,122 @@ mod tests {
         assert_eq!(position.unrealised_profit_loss, (50.0 - 100.0 - 6.0));
     }
 
+    #[test]
+    fn update_position_with_mark_price_sources() {
+        let mut input_market = market_event_trade(Side::Buy);
+        input_market.kind = DataKind::OrderBookL1(OrderBookL1 {
+            last_update_time: Utc::now(),
+            best_bid: Level::new(99.0, 1.0),
+            best_ask: Level::new(103.0, 3.0),
+        });
+
+        struct TestCase {
+            side: Side,
+            mark_price: MarkPrice,
+            expected: f64,
+        }
+
+        let cases = [
+            TestCase {
+                // TC0: volume weighted mid price
+                side: Side::Buy,
+                mark_price: MarkPrice::Close,
+                expected: 100.0,
+            },
+            TestCase {
+                // TC1: mid price
+                side: Side::Buy,
+                mark_price: MarkPrice::Mid,
+                expected: 101.0,
+            },
+            TestCase {
+                // TC2: long marked at the best bid
+                side: Side::Buy,
+                mark_price: MarkPrice::BidAskConservative,
+                expected: 99.0,
+            },
+            TestCase {
+                // TC3: short marked at the best ask
+                side: Side::Sell,
+                mark_price: MarkPrice::BidAskConservative,
+                expected: 103.0,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let mut position = position();
+            position.side = test.side;
+            position.quantity = match test.side {
+                Side::Buy => 1.0,
+                Side::Sell => -1.0,
+            };
+
+            position.update_with_mark_price(&input_market, test.mark_price);
+            assert_eq!(
+                position.current_symbol_price, test.expected,
+                "TC{index} failed"
+            );
+        }
+    }
+
+    #[test]
+    fn enter_update_and_exit_position_with_contract_multiplier() {
+        // Long 2.0 contracts @ 100.0 with a contract multiplier of 50.0 & no fees
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::Long;
+        input_fill.quantity = 2.0;
+        input_fill.fill_value_gross = 2.0 * 100.0 * 50.0;
+        input_fill.fees = Fees::default();
+        input_fill.contract_multiplier = 50.0;
+        let mut position = Position::enter(Uuid::new_v4(), &input_fill).unwrap();
+        assert_eq!(position.contract_multiplier, 50.0);
+        assert_eq!(position.enter_avg_price_gross, 100.0);
+
+        // Price +1.0 is worth 2.0 * 50.0 = 100.0 of unrealised P&L
+        let mut input_market = market_event_trade(Side::Buy);
+        if let DataKind::Trade(ref mut trade) = input_market.kind {
+            trade.price = 101.0;
+        }
+        position.update(&input_market);
+        assert_eq!(position.current_value_gross, 2.0 * 101.0 * 50.0);
+        assert_eq!(position.unrealised_profit_loss, 100.0);
+
+        // Exit @ 102.0
+        let mut exit_fill = input_fill.clone();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -2.0;
+        exit_fill.fill_value_gross = 2.0 * 102.0 * 50.0;
+        position.exit(Balance::default(), &exit_fill).unwrap();
+        assert_eq!(position.exit_avg_price_gross, 102.0);
+        assert_eq!(position.realised_profit_loss, 200.0);
+    }
+
+    #[test]
+    fn update_position_tracks_max_favorable_and_adverse_excursion() {
+        // Initial Long Position entered @ 100.0 with no fees
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::Long;
+        input_fill.quantity = 1.0;
+        input_fill.fill_value_gross = 100.0;
+        input_fill.fees = Fees::default();
+        let mut position = Position::enter(Uuid::new_v4(), &input_fill).unwrap();
+        assert_eq!(position.max_favorable, 0.0);
+        assert_eq!(position.max_adverse, 0.0);
+
+        // Price path: 120.0 -> 90.0 -> 110.0
+        for price in [120.0, 90.0, 110.0] {
+            let mut input_market = market_event_trade(Side::Buy);
+            if let DataKind::Trade(ref mut trade) = input_market.kind {
+                trade.price = price;
+            }
+            position.update(&input_market);
+        }
+
+        assert_eq!(position.unrealised_profit_loss, 10.0);
+        assert_eq!(position.max_favorable, 20.0);
+        assert_eq!(position.max_adverse, -10.0);
+    }
+
     #[test]
     fn update_short_position_so_unreal_pnl_increases() {
         // Initial Position
@@ -1026,7 +1805,7 @@ mod tests {
 
         // exit_value_gross - enter_value_gross - total_fees
         assert_eq!(position.realised_profit_loss, (200.0 - 100.0 - 6.0));
-        assert_eq!(position.unrealised_profit_loss, (200.0 - 100.0 - 6.0));
+        assert_eq!(position.unrealised_profit_loss, 0.0);
 
         // Assert EquityPoint on Exit is correct
         assert_eq!(
@@ -1097,7 +1876,7 @@ mod tests {
 
         // exit_value_gross - enter_value_gross - total_fees
         assert_eq!(position.realised_profit_loss, (50.0 - 100.0 - 6.0));
-        assert_eq!(position.unrealised_profit_loss, (50.0 - 100.0 - 6.0));
+        assert_eq!(position.unrealised_profit_loss, 0.0);
 
         // Assert EquityPoint on Exit is correct
         assert_eq!(
@@ -1168,7 +1947,7 @@ mod tests {
 
         // enter_value_gross - current_value_gross - approx_total_fees
         assert_eq!(position.realised_profit_loss, (100.0 - 50.0 - 6.0));
-        assert_eq!(position.unrealised_profit_loss, (100.0 - 50.0 - 6.0));
+        assert_eq!(position.unrealised_profit_loss, 0.0);
 
         // Assert EquityPoint on Exit is correct
         assert_eq!(
@@ -1239,7 +2018,7 @@ mod tests {
 
         // enter_value_gross - current_value_gross - approx_total_fees
         assert_eq!(position.realised_profit_loss, (100.0 - 200.0 - 6.0));
-        assert_eq!(position.unrealised_profit_loss, (100.0 - 200.0 - 6.0));
+        assert_eq!(position.unrealised_profit_loss, 0.0);
 
         // Assert EquityPoint on Exit is correct
         assert_eq!(
@@ -1640,4 +2419,122 @@ mod tests {
 
         assert!(PositionExit::try_from(&mut exited_position).is_err());
     }
+
+    #[test]
+    fn position_to_record_with_exited_position() {
+        let exit_time = Utc::now();
+        let mut exited_position = position();
+        exited_position.side = Side::Sell;
+        exited_position.realised_profit_loss = 15.0;
+        exited_position.max_favorable = 25.0;
+        exited_position.max_adverse = -5.0;
+        exited_position.meta.exit_balance = Some(Balance {
+            time: exit_time,
+            total: 1015.0,
+            available: 1015.0,
+        });
+
+        let record = exited_position.to_record();
+
+        assert_eq!(record.exchange, "binance");
+        assert_eq!(record.base, "eth");
+        assert_eq!(record.quote, "usdt");
+        assert_eq!(record.instrument_kind, "spot");
+        assert_eq!(record.side, "sell");
+        assert_eq!(
+            record.enter_time,
+            exited_position.meta.enter_time.timestamp_millis()
+        );
+        assert_eq!(record.exit_time, Some(exit_time.timestamp_millis()));
+        assert_eq!(record.exit_balance_total, Some(1015.0));
+        assert_eq!(record.realised_profit_loss, 15.0);
+        assert_eq!(record.max_favorable, 25.0);
+        assert_eq!(record.max_adverse, -5.0);
+    }
+
+    #[test]
+    fn position_to_record_with_open_position() {
+        let mut open_position = position();
+        open_position.meta.exit_balance = None;
+
+        let record = open_position.to_record();
+
+        assert_eq!(record.exit_time, None);
+        assert_eq!(record.exit_balance_total, None);
+    }
+
+    /// Field names of a serialised map, in the order they were serialised.
+    struct OrderedFields(Vec<String>);
+
+    impl<'de> Deserialize<'de> for OrderedFields {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = OrderedFields;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    formatter.write_str("a map")
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: serde::de::MapAccess<'de>,
+                {
+                    let mut fields = Vec::new();
+                    while let Some(field) = map.next_key::<String>()? {
+                        map.next_value::<serde::de::IgnoredAny>()?;
+                        fields.push(field);
+                    }
+                    Ok(OrderedFields(fields))
+                }
+            }
+
+            deserializer.deserialize_map(Visitor)
+        }
+    }
+
+    #[test]
+    fn position_record_schema_matches_serialised_fields_in_order() {
+        let expected = vec![
+            "position_id",
+            "exchange",
+            "base",
+            "quote",
+            "instrument_kind",
+            "side",
+            "quantity",
+            "contract_multiplier",
+            "enter_time",
+            "update_time",
+            "exit_time",
+            "exit_balance_total",
+            "enter_fees_total",
+            "enter_avg_price_gross",
+            "enter_value_gross",
+            "exit_fees_total",
+            "exit_avg_price_gross",
+            "exit_value_gross",
+            "current_symbol_price",
+            "current_value_gross",
+            "unrealised_profit_loss",
+            "realised_profit_loss",
+            "max_favorable",
+            "max_adverse",
+        ];
+
+        // Serialised column order, eg/ the header of a CSV export
+        let serialised = serde_json::to_string(&position().to_record()).unwrap();
+        let OrderedFields(fields) = serde_json::from_str(&serialised).unwrap();
+        assert_eq!(fields, expected);
+
+        let schema_fields = PositionRecord::SCHEMA
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+        assert_eq!(schema_fields, expected);
+    }
 }
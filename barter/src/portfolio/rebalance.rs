@@ -0,0 +1,352 @@
+use crate::{
+    data::MarketMeta,
+    portfolio::{position::Position, ContractType, OrderEvent, OrderType},
+    strategy::Decision,
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::{instrument::Instrument, Market, Side};
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Schedule on which a [`Rebalancer`] rebalances the Portfolio to its target weights.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum RebalanceSchedule {
+    /// Rebalance on the first [`MarketEvent`] of each UTC day.
+    Daily,
+    /// Rebalance on the first [`MarketEvent`] of each ISO week.
+    Weekly,
+}
+
+impl RebalanceSchedule {
+    /// Identifier of the schedule period the provided time falls within.
+    fn period(&self, time: DateTime<Utc>) -> (i32, u32) {
+        match self {
+            RebalanceSchedule::Daily => (time.year(), time.ordinal()),
+            RebalanceSchedule::Weekly => {
+                let week = time.iso_week();
+                (week.year(), week.week())
+            }
+        }
+    }
+}
+
+/// Target weight of Portfolio equity allocated to a [`Market`]. A negative weight targets a short
+/// [`Position`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct TargetWeight {
+    pub market: Market,
+    pub weight: f64,
+}
+
+/// Configuration for constructing a [`Rebalancer`] via the new() constructor method.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub schedule: RebalanceSchedule,
+    pub target_weights: Vec<TargetWeight>,
+    /// Minimum difference between the current & target notional of a [`Market`], as a fraction of
+    /// Portfolio equity, required to rebalance it (eg/ 0.01 for 1%). Avoids churning
+    /// [`Position`]s over negligible drift.
+    pub drift_threshold: f64,
+}
+
+/// Portfolio level rebalancer that periodically generates the [`OrderEvent`]s required to move
+/// the current [`Position`] notional of each [`Market`] to its target weight of Portfolio equity.
+/// Complements per-market strategy [`Signal`](crate::strategy::Signal)s.
+///
+/// A [`Position`] is entered & exited in full, so resizing an existing [`Position`] is performed
+/// as an exit [`OrderEvent`] followed by a new entry [`OrderEvent`] at the target notional.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rebalancer {
+    schedule: RebalanceSchedule,
+    target_weights: HashMap<Market, f64>,
+    drift_threshold: f64,
+    latest_market_meta: HashMap<Market, MarketMeta>,
+    latest_period: Option<(i32, u32)>,
+    rebalance_due: bool,
+}
+
+impl Rebalancer {
+    /// Constructs a new [`Rebalancer`] using the provided configuration.
+    pub fn new(config: Config) -> Self {
+        Self {
+            schedule: config.schedule,
+            target_weights: config
+                .target_weights
+                .into_iter()
+                .map(|target| (target.market, target.weight))
+                .collect(),
+            drift_threshold: config.drift_threshold,
+            latest_market_meta: HashMap::new(),
+            latest_period: None,
+            rebalance_due: false,
+        }
+    }
+
+    /// [`Market`]s with a target weight.
+    pub fn markets(&self) -> impl Iterator<Item = &Market> {
+        self.target_weights.keys()
+    }
+
+    /// Updates the latest price of the [`MarketEvent`]'s [`Market`], returning true if a scheduled
+    /// rebalance is now due (ie/ the [`MarketEvent`] starts a new schedule period).
+    pub fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) -> bool {
+        let close = match &market.kind {
+            DataKind::Trade(trade) => trade.price,
+            DataKind::Candle(candle) => candle.close,
+            _ => return self.rebalance_due,
+        };
+
+        self.latest_market_meta.insert(
+            Market::new(market.exchange.clone(), market.instrument.clone()),
+            MarketMeta {
+                close,
+                time: market.exchange_time,
+                volatility: None,
+                volume: None,
+                spread: None,
+            },
+        );
+
+        let period = self.schedule.period(market.exchange_time);
+        if self.latest_period != Some(period) {
+            self.latest_period = Some(period);
+            self.rebalance_due = true;
+        }
+
+        self.rebalance_due
+    }
+
+    /// Generates the [`OrderEvent`]s required to rebalance the open [`Position`]s to the target
+    /// weights of the provided Portfolio equity, if a rebalance is due.
+    ///
+    /// Entry quantities are sized in contracts using the provided contract multiplier &
+    /// [`ContractType`] of each [`Market`], whereas open [`Position`]s are valued & exited using
+    /// their own.
+    ///
+    /// [`Market`]s without a latest price are skipped until one is received via
+    /// [`Rebalancer::update_from_market`].
+    pub fn generate_orders<Contract>(
+        &mut self,
+        equity: f64,
+        open_positions: &[Position],
+        contract: Contract,
+    ) -> Vec<OrderEvent>
+    where
+        Contract: Fn(&Market) -> (f64, ContractType),
+    {
+        if !self.rebalance_due {
+            return Vec::new();
+        }
+        self.rebalance_due = false;
+
+        let mut orders = Vec::new();
+        for (market, weight) in &self.target_weights {
+            let market_meta = match self.latest_market_meta.get(market) {
+                Some(market_meta) => *market_meta,
+                None => continue,
+            };
+
+            let position = open_positions.iter().find(|position| {
+                position.tag.is_none()
+                    && position.exchange == market.exchange
+                    && position.instrument == market.instrument
+            });
+
+            // Signed notional of the current Position & the target
+            let current_notional = position.map_or(0.0, |position| {
+                let notional = position.contract_type.notional(
+                    position.quantity,
+                    market_meta.close,
+                    position.contract_multiplier,
+                );
+                match position.side {
+                    Side::Buy => notional,
+                    Side::Sell => -notional,
+                }
+            });
+            let target_notional = weight * equity;
+
+            if (target_notional - current_notional).abs() < self.drift_threshold * equity.abs() {
+                continue;
+            }
+
+            let order = |decision, quantity, contract_multiplier, contract_type| OrderEvent {
+                time: Utc::now(),
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                market_meta,
+                decision,
+                quantity,
+                order_type: OrderType::Market,
+                limit_price: None,
+                contract_multiplier,
+                contract_type,
+                tag: None,
+            };
+
+            // Exit the current Position in full
+            if let Some(position) = position {
+                orders.push(order(
+                    position.determine_exit_decision(),
+                    0.0 - position.quantity,
+                    position.contract_multiplier,
+                    position.contract_type,
+                ));
+            }
+
+            // Enter a new Position at the target notional
+            let (contract_multiplier, contract_type) = contract(market);
+            if target_notional != 0.0 && market_meta.close * contract_multiplier != 0.0 {
+                let decision = match target_notional.is_sign_positive() {
+                    true => Decision::Long,
+                    false => Decision::Short,
+                };
+                orders.push(order(
+                    decision,
+                    contract_type.quantity(target_notional, market_meta.close, contract_multiplier),
+                    contract_multiplier,
+                    contract_type,
+                ));
+            }
+        }
+
+        orders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{market_event_trade, position};
+    use barter_integration::model::instrument::kind::InstrumentKind;
+    use chrono::Duration;
+    use std::ops::Add;
+
+    fn market(base: &str) -> Market {
+        Market::new("binance", (base, "usdt", InstrumentKind::Spot))
+    }
+
+    fn trade(
+        market: &Market,
+        time: DateTime<Utc>,
+        price: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_trade(Side::Buy);
+        event.exchange = market.exchange.clone();
+        event.instrument = market.instrument.clone();
+        event.exchange_time = time;
+        if let DataKind::Trade(trade) = &mut event.kind {
+            trade.price = price;
+        }
+        event
+    }
+
+    fn rebalancer() -> Rebalancer {
+        Rebalancer::new(Config {
+            schedule: RebalanceSchedule::Daily,
+            target_weights: vec![
+                TargetWeight {
+                    market: market("btc"),
+                    weight: 0.5,
+                },
+                TargetWeight {
+                    market: market("eth"),
+                    weight: -0.25,
+                },
+            ],
+            drift_threshold: 0.01,
+        })
+    }
+
+    #[test]
+    fn rebalancer_is_due_once_per_schedule_period() {
+        let mut rebalancer = rebalancer();
+        let time = DateTime::parse_from_rfc3339("2023-01-02T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert!(rebalancer.update_from_market(&trade(&market("btc"), time, 100.0)));
+        rebalancer.generate_orders(1000.0, &[], |_| (1.0, ContractType::Linear));
+
+        let later_same_day = time.add(Duration::hours(1));
+        assert!(!rebalancer.update_from_market(&trade(&market("btc"), later_same_day, 100.0)));
+
+        let next_day = time.add(Duration::days(1));
+        assert!(rebalancer.update_from_market(&trade(&market("btc"), next_day, 100.0)));
+    }
+
+    #[test]
+    fn rebalancer_generates_entry_orders_to_target_weights() {
+        let mut rebalancer = rebalancer();
+        let time = Utc::now();
+
+        rebalancer.update_from_market(&trade(&market("btc"), time, 100.0));
+        rebalancer.update_from_market(&trade(&market("eth"), time, 10.0));
+
+        let mut orders = rebalancer.generate_orders(1000.0, &[], |_| (1.0, ContractType::Linear));
+        orders.sort_by(|a, b| a.instrument.base.cmp(&b.instrument.base));
+
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].decision, Decision::Long);
+        assert_eq!(orders[0].quantity, 5.0);
+        assert_eq!(orders[1].decision, Decision::Short);
+        assert_eq!(orders[1].quantity, -25.0);
+
+        // Not due again until the next schedule period
+        assert!(rebalancer
+            .generate_orders(1000.0, &[], |_| (1.0, ContractType::Linear))
+            .is_empty());
+    }
+
+    #[test]
+    fn rebalancer_resizes_drifted_position_via_exit_and_entry() {
+        let mut rebalancer = rebalancer();
+        rebalancer.update_from_market(&trade(&market("btc"), Utc::now(), 100.0));
+
+        // Long 8.0 btc @ 100.0 = 800.0 notional vs 500.0 target
+        let mut btc_position = position();
+        btc_position.exchange = market("btc").exchange;
+        btc_position.instrument = market("btc").instrument;
+        btc_position.side = Side::Buy;
+        btc_position.quantity = 8.0;
+
+        let orders =
+            rebalancer.generate_orders(1000.0, &[btc_position], |_| (1.0, ContractType::Linear));
+
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].decision, Decision::CloseLong);
+        assert_eq!(orders[0].quantity, -8.0);
+        assert_eq!(orders[1].decision, Decision::Long);
+        assert_eq!(orders[1].quantity, 5.0);
+    }
+
+    #[test]
+    fn rebalancer_ignores_drift_below_threshold() {
+        let mut rebalancer = rebalancer();
+        rebalancer.update_from_market(&trade(&market("btc"), Utc::now(), 100.0));
+
+        // Long 5.05 btc @ 100.0 = 505.0 notional vs 500.0 target, within 1% of equity
+        let mut btc_position = position();
+        btc_position.exchange = market("btc").exchange;
+        btc_position.instrument = market("btc").instrument;
+        btc_position.quantity = 5.05;
+
+        assert!(rebalancer
+            .generate_orders(1000.0, &[btc_position], |_| (1.0, ContractType::Linear))
+            .is_empty());
+    }
+
+    #[test]
+    fn rebalancer_sizes_entry_orders_in_contracts_with_contract_multiplier() {
+        let mut rebalancer = rebalancer();
+        rebalancer.update_from_market(&trade(&market("btc"), Utc::now(), 100.0));
+
+        // 500.0 target notional / (100.0 * 2.5) = 2.0 contracts
+        let orders = rebalancer.generate_orders(1000.0, &[], |_| (2.5, ContractType::Linear));
+
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].quantity, 2.0);
+        assert_eq!(orders[0].contract_multiplier, 2.5);
+    }
+}
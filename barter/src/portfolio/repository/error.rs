@@ -17,4 +17,7 @@ pub enum RepositoryError {
 
     #[error("Failed to retrieve expected data due to it not being present")]
     ExpectedDataNotPresentError,
+
+    #[error("Repository is unavailable (eg/ connection dropped or timed out)")]
+    Unavailable,
 }
@@ -15,6 +15,15 @@ pub mod in_memory;
 /// Redis repository for state keeping.
 pub mod redis;
 
+/// Embedded Sled repository for state keeping without an external service. Requires the `sled`
+/// feature.
+#[cfg(feature = "sled")]
+pub mod sled;
+
+/// Repository wrapper that follows a configurable failure policy whilst the underlying repository
+/// is unavailable.
+pub mod resilient;
+
 /// Handles the reading & writing of a [`Position`] to/from the persistence layer.
 pub trait PositionHandler {
     /// Upsert the open [`Position`] using it's [`PositionId`].
@@ -11,12 +11,13 @@ use crate::{
     statistic::summary::PositionSummariser,
 };
 use barter_integration::model::{Market, MarketId};
-use redis::{Commands, Connection, ErrorKind};
+use redis::{Client, Commands, Connection, ErrorKind, RedisError};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     fmt::{Debug, Formatter},
     marker::PhantomData,
 };
+use tracing::{info, warn};
 use uuid::Uuid;
 
 /// Configuration for constructing a [`RedisRepository`] via the new() constructor method.
@@ -28,11 +29,19 @@ pub struct Config {
 /// Redis persisted repository that implements [`PositionHandler`], [`BalanceHandler`],
 /// & [`PositionSummariser`]. Used by a Portfolio implementation to persist the Portfolio state,
 /// including total equity, available cash & Positions.
+///
+/// Transient connection failures are returned as [`RepositoryError::Unavailable`]. If the
+/// [`RedisRepository`] is built with a Redis [`Client`], the connection is re-established via
+/// the [`Client`] on the next access after such a failure. Wrap a reconnecting
+/// [`RedisRepository`] in a [`ResilientRepository`](super::resilient::ResilientRepository) to
+/// survive a Redis outage.
 pub struct RedisRepository<Statistic>
 where
     Statistic: PositionSummariser + Serialize + DeserializeOwned,
 {
     conn: Connection,
+    client: Option<Client>,
+    reconnect: bool,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -43,9 +52,9 @@ where
     fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
         let position_string = serde_json::to_string(&position)?;
 
-        self.conn
+        self.connection()?
             .set(position.position_id, position_string)
-            .map_err(|_| RepositoryError::WriteError)
+            .map_err(|error| self.map_error(error, RepositoryError::WriteError))
     }
 
     fn get_open_position(
@@ -53,9 +62,9 @@ where
         position_id: &PositionId,
     ) -> Result<Option<Position>, RepositoryError> {
         let position_value: String = self
-            .conn
+            .connection()?
             .get(position_id)
-            .map_err(|_| RepositoryError::ReadError)?;
+            .map_err(|error| self.map_error(error, RepositoryError::ReadError))?;
 
         Ok(Some(serde_json::from_str::<Position>(&position_value)?))
     }
@@ -83,9 +92,9 @@ where
     ) -> Result<Option<Position>, RepositoryError> {
         let position = self.get_open_position(position_id)?;
 
-        self.conn
-            .del(position_id)
-            .map_err(|_| RepositoryError::DeleteError)?;
+        self.connection()?
+            .del::<_, ()>(position_id)
+            .map_err(|error| self.map_error(error, RepositoryError::DeleteError))?;
 
         Ok(position)
     }
@@ -95,20 +104,20 @@ where
         engine_id: Uuid,
         position: Position,
     ) -> Result<(), RepositoryError> {
-        self.conn
+        self.connection()?
             .lpush(
                 determine_exited_positions_id(engine_id),
                 serde_json::to_string(&position)?,
             )
-            .map_err(|_| RepositoryError::WriteError)
+            .map_err(|error| self.map_error(error, RepositoryError::WriteError))
     }
 
     fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
-        self.conn
+        self.connection()?
             .get(determine_exited_positions_id(engine_id))
             .or_else(|err| match err.kind() {
                 ErrorKind::TypeError => Ok(Vec::<String>::new()),
-                _ => Err(RepositoryError::ReadError),
+                _ => Err(self.map_error(err, RepositoryError::ReadError)),
             })?
             .iter()
             .map(|position| serde_json::from_str::<Position>(position))
@@ -124,16 +133,16 @@ where
     fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
         let balance_string = serde_json::to_string(&balance)?;
 
-        self.conn
+        self.connection()?
             .set(Balance::balance_id(engine_id), balance_string)
-            .map_err(|_| RepositoryError::WriteError)
+            .map_err(|error| self.map_error(error, RepositoryError::WriteError))
     }
 
     fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
         let balance_value: String = self
-            .conn
+            .connection()?
             .get(Balance::balance_id(engine_id))
-            .map_err(|_| RepositoryError::ReadError)?;
+            .map_err(|error| self.map_error(error, RepositoryError::ReadError))?;
 
         Ok(serde_json::from_str::<Balance>(&balance_value)?)
     }
@@ -148,16 +157,16 @@ where
         market_id: MarketId,
         statistic: Statistic,
     ) -> Result<(), RepositoryError> {
-        self.conn
+        self.connection()?
             .set(market_id.0, serde_json::to_string(&statistic)?)
-            .map_err(|_| RepositoryError::WriteError)
+            .map_err(|error| self.map_error(error, RepositoryError::WriteError))
     }
 
     fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
         let statistics: String = self
-            .conn
+            .connection()?
             .get(&market_id.0)
-            .map_err(|_| RepositoryError::ReadError)?;
+            .map_err(|error| self.map_error(error, RepositoryError::ReadError))?;
 
         serde_json::from_str(&statistics).map_err(RepositoryError::JsonSerDeError)
     }
@@ -177,9 +186,14 @@ where
     Statistic: PositionSummariser + Serialize + DeserializeOwned,
 {
     /// Constructs a new [`RedisRepository`] component using the provided Redis connection struct.
+    ///
+    /// Without a Redis [`Client`] the connection cannot be re-established after a transient
+    /// failure, so use the [`RedisRepositoryBuilder`] to provide one.
     pub fn new(connection: Connection) -> Self {
         Self {
             conn: connection,
+            client: None,
+            reconnect: false,
             _statistic_marker: PhantomData,
         }
     }
@@ -191,11 +205,55 @@ where
 
     /// Establish & return a Redis connection.
     pub fn setup_redis_connection(cfg: Config) -> Connection {
-        redis::Client::open(cfg.uri)
-            .expect("Failed to create Redis client")
+        Self::setup_redis_client(cfg)
             .get_connection()
             .expect("Failed to connect to Redis")
     }
+
+    /// Construct & return a Redis [`Client`], used to re-establish the connection of a
+    /// [`RedisRepository`] after a transient failure.
+    pub fn setup_redis_client(cfg: Config) -> Client {
+        Client::open(cfg.uri).expect("Failed to create Redis client")
+    }
+
+    /// Returns the Redis [`Connection`], first re-establishing it via the Redis [`Client`] if
+    /// a previous command failed due to a transient connection failure.
+    fn connection(&mut self) -> Result<&mut Connection, RepositoryError> {
+        if let (true, Some(client)) = (self.reconnect, &self.client) {
+            self.conn = client.get_connection().map_err(|error| {
+                warn!(?error, "failed to re-establish Redis connection");
+                RepositoryError::Unavailable
+            })?;
+            self.reconnect = false;
+            info!("re-established Redis connection");
+        }
+
+        Ok(&mut self.conn)
+    }
+
+    /// Maps a [`RedisError`] via [`map_redis_error`], flagging the connection to be
+    /// re-established if it is caused by a transient connection failure.
+    fn map_error(&mut self, error: RedisError, otherwise: RepositoryError) -> RepositoryError {
+        let error = map_redis_error(error, otherwise);
+        if matches!(error, RepositoryError::Unavailable) {
+            self.reconnect = true;
+        }
+        error
+    }
+}
+
+/// Maps a [`RedisError`] to [`RepositoryError::Unavailable`] if it is caused by a transient
+/// connection failure, otherwise to the provided [`RepositoryError`].
+fn map_redis_error(error: RedisError, otherwise: RepositoryError) -> RepositoryError {
+    if error.is_io_error()
+        || error.is_connection_dropped()
+        || error.is_connection_refusal()
+        || error.is_timeout()
+    {
+        RepositoryError::Unavailable
+    } else {
+        otherwise
+    }
 }
 
 /// Builder to construct [`RedisRepository`] instances.
@@ -205,6 +263,7 @@ where
     Statistic: PositionSummariser + Serialize + DeserializeOwned,
 {
     conn: Option<Connection>,
+    client: Option<Client>,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -215,6 +274,7 @@ where
     pub fn new() -> Self {
         Self {
             conn: None,
+            client: None,
             _statistic_marker: PhantomData,
         }
     }
@@ -226,9 +286,19 @@ where
         }
     }
 
+    /// Optional Redis [`Client`] used to re-establish the connection after a transient failure.
+    pub fn client(self, value: Client) -> Self {
+        Self {
+            client: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<RedisRepository<Statistic>, PortfolioError> {
         Ok(RedisRepository {
             conn: self.conn.ok_or(PortfolioError::BuilderIncomplete("conn"))?,
+            client: self.client,
+            reconnect: false,
             _statistic_marker: PhantomData,
         })
     }
@@ -241,6 +311,7 @@ where
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RedisRepositoryBuilder")
             .field("conn", &"Option<redis::Connection>")
+            .field("client", &self.client)
             .field("_statistic_market", &self._statistic_marker)
             .finish()
     }
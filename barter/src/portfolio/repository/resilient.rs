@@ -0,0 +1,613 @@
+use crate::{
+    portfolio::{
+        position::{Position, PositionId},
+        repository::{
+            error::RepositoryError, in_memory::InMemoryRepository, BalanceHandler, PositionHandler,
+            StatisticHandler,
+        },
+        Balance,
+    },
+    statistic::summary::PositionSummariser,
+};
+use barter_integration::model::{Market, MarketId};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Policy a [`ResilientRepository`] follows when the underlying repository is unavailable (ie/
+/// returns [`RepositoryError::Unavailable`]).
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum FailurePolicy {
+    /// Fail every write with [`RepositoryError::Unavailable`] so the
+    /// [`Trader`](crate::engine::trader::Trader) halts trading & flattens its open
+    /// [`Position`]. Equivalent to a [`FailurePolicy::Buffer`] with no buffer capacity.
+    #[default]
+    Halt,
+    /// Buffer up to `max_buffered_writes` writes in memory, and retry them in order before every
+    /// subsequent repository access. Once the buffer is full, further writes fail with
+    /// [`RepositoryError::Unavailable`] as per [`FailurePolicy::Halt`].
+    ///
+    /// Retries only succeed if the underlying repository recovers by itself, so a
+    /// [`RedisRepository`](super::redis::RedisRepository) must be built with a Redis `Client`
+    /// to re-establish it's connection.
+    Buffer { max_buffered_writes: usize },
+}
+
+impl FailurePolicy {
+    /// Maximum number of writes that may be buffered whilst the repository is unavailable.
+    pub fn max_buffered_writes(&self) -> usize {
+        match self {
+            Self::Halt => 0,
+            Self::Buffer {
+                max_buffered_writes,
+            } => *max_buffered_writes,
+        }
+    }
+}
+
+/// Write to the underlying repository that is buffered until the repository is available.
+#[derive(Clone, Debug)]
+pub enum BufferedWrite<Statistic> {
+    SetOpenPosition(Position),
+    RemovePosition(PositionId),
+    SetExitedPosition(Uuid, Position),
+    SetBalance(Uuid, Balance),
+    SetStatistics(MarketId, Statistic),
+}
+
+/// Repository wrapper that keeps the Portfolio state available while the underlying repository
+/// (eg/ a [`RedisRepository`](super::redis::RedisRepository)) suffers a transient outage,
+/// according to the configured [`FailurePolicy`].
+///
+/// Every successful read & write is mirrored in memory. Whilst the underlying repository is
+/// unavailable, reads are served from the in-memory mirror and writes are buffered up to the
+/// [`FailurePolicy`] limit. Buffered writes are retried in order before every subsequent access,
+/// and reads go back to the underlying repository once every buffered write has succeeded.
+///
+/// Buffering limits:
+///  - The mirror only contains state read or written via this [`ResilientRepository`], so an
+///    outage read of state that has not been accessed since construction finds nothing. In
+///    particular, only exited [`Position`]s written since construction are available.
+///  - Buffered writes are held in memory only, and are lost if the process exits before the
+///    underlying repository recovers.
+///  - Non-transient errors (eg/ [`RepositoryError::JsonSerDeError`]) are never buffered.
+///
+/// Writes that could not be buffered because the buffer is full, & buffered writes that fail
+/// with a non-transient error when retried, are kept as failed writes rather than dropped. They
+/// can be inspected & re-applied via [`ResilientRepository::take_failed_writes`].
+#[derive(Debug)]
+pub struct ResilientRepository<Repository, Statistic>
+where
+    Statistic: PositionSummariser,
+{
+    repository: Repository,
+    policy: FailurePolicy,
+    mirror: InMemoryRepository<Statistic>,
+    buffered_writes: VecDeque<BufferedWrite<Statistic>>,
+    failed_writes: Vec<BufferedWrite<Statistic>>,
+}
+
+impl<Repository, Statistic> PositionHandler for ResilientRepository<Repository, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Statistic: PositionSummariser,
+{
+    fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
+        self.write(BufferedWrite::SetOpenPosition(position.clone()))?;
+        self.mirror.set_open_position(position)
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        if self.retry_buffered_writes()? {
+            match self.repository.get_open_position(position_id) {
+                Ok(Some(position)) => {
+                    self.mirror.set_open_position(position.clone())?;
+                    return Ok(Some(position));
+                }
+                Ok(None) => {
+                    self.mirror.remove_position(position_id)?;
+                    return Ok(None);
+                }
+                Err(RepositoryError::Unavailable) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        self.mirror.get_open_position(position_id)
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        let markets = markets.collect::<Vec<_>>();
+
+        if self.retry_buffered_writes()? {
+            match self
+                .repository
+                .get_open_positions(engine_id, markets.iter().copied())
+            {
+                Ok(positions) => {
+                    for position in &positions {
+                        self.mirror.set_open_position(position.clone())?;
+                    }
+                    return Ok(positions);
+                }
+                Err(RepositoryError::Unavailable) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        self.mirror
+            .get_open_positions(engine_id, markets.into_iter())
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        // Determine the Position being removed before the write, so it is not lost if buffered
+        let position = self.get_open_position(position_id)?;
+        self.write(BufferedWrite::RemovePosition(position_id.clone()))?;
+        self.mirror.remove_position(position_id)?;
+        Ok(position)
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.write(BufferedWrite::SetExitedPosition(
+            engine_id,
+            position.clone(),
+        ))?;
+        self.mirror.set_exited_position(engine_id, position)
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        if self.retry_buffered_writes()? {
+            match self.repository.get_exited_positions(engine_id) {
+                Err(RepositoryError::Unavailable) => {}
+                result => return result,
+            }
+        }
+
+        self.mirror.get_exited_positions(engine_id)
+    }
+}
+
+impl<Repository, Statistic> BalanceHandler for ResilientRepository<Repository, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Statistic: PositionSummariser,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        self.write(BufferedWrite::SetBalance(engine_id, balance))?;
+        self.mirror.set_balance(engine_id, balance)
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        if self.retry_buffered_writes()? {
+            match self.repository.get_balance(engine_id) {
+                Ok(balance) => {
+                    self.mirror.set_balance(engine_id, balance)?;
+                    return Ok(balance);
+                }
+                Err(RepositoryError::Unavailable) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        self.mirror.get_balance(engine_id)
+    }
+}
+
+impl<Repository, Statistic> StatisticHandler<Statistic>
+    for ResilientRepository<Repository, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Statistic: PositionSummariser,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        self.write(BufferedWrite::SetStatistics(market_id.clone(), statistic))?;
+        self.mirror.set_statistics(market_id, statistic)
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        if self.retry_buffered_writes()? {
+            match self.repository.get_statistics(market_id) {
+                Ok(statistic) => {
+                    self.mirror.set_statistics(market_id.clone(), statistic)?;
+                    return Ok(statistic);
+                }
+                Err(RepositoryError::Unavailable) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        self.mirror.get_statistics(market_id)
+    }
+}
+
+impl<Repository, Statistic> ResilientRepository<Repository, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Statistic: PositionSummariser,
+{
+    /// Constructs a new [`ResilientRepository`] that wraps the provided repository, and follows
+    /// the [`FailurePolicy`] whilst it is unavailable.
+    pub fn new(repository: Repository, policy: FailurePolicy) -> Self {
+        Self {
+            repository,
+            policy,
+            mirror: InMemoryRepository::new(),
+            buffered_writes: VecDeque::new(),
+            failed_writes: Vec::new(),
+        }
+    }
+
+    /// Number of writes buffered whilst the underlying repository is unavailable.
+    pub fn buffered_writes(&self) -> usize {
+        self.buffered_writes.len()
+    }
+
+    /// Writes that could not be applied to the underlying repository, in the order they failed.
+    pub fn failed_writes(&self) -> &[BufferedWrite<Statistic>] {
+        &self.failed_writes
+    }
+
+    /// Removes & returns the failed writes, eg/ to re-apply them once the cause is resolved.
+    pub fn take_failed_writes(&mut self) -> Vec<BufferedWrite<Statistic>> {
+        std::mem::take(&mut self.failed_writes)
+    }
+
+    /// Write to the underlying repository, buffering the write if the repository is unavailable
+    /// and the [`FailurePolicy`] permits it.
+    fn write(&mut self, write: BufferedWrite<Statistic>) -> Result<(), RepositoryError> {
+        if self.retry_buffered_writes()? {
+            match apply_write(&mut self.repository, &write) {
+                Err(RepositoryError::Unavailable) => {}
+                result => return result,
+            }
+        }
+
+        let max_buffered_writes = self.policy.max_buffered_writes();
+        if self.buffered_writes.len() >= max_buffered_writes {
+            error!(
+                policy = ?self.policy,
+                buffered_writes = self.buffered_writes.len(),
+                "repository unavailable & no capacity to buffer write, kept as failed write"
+            );
+            self.failed_writes.push(write);
+            return Err(RepositoryError::Unavailable);
+        }
+
+        self.buffered_writes.push_back(write);
+        warn!(
+            buffered_writes = self.buffered_writes.len(),
+            max_buffered_writes, "repository unavailable, buffered write for retry"
+        );
+
+        Ok(())
+    }
+
+    /// Retry any buffered writes in order, returning true if every buffered write has now been
+    /// written to the underlying repository.
+    fn retry_buffered_writes(&mut self) -> Result<bool, RepositoryError> {
+        if self.buffered_writes.is_empty() {
+            return Ok(true);
+        }
+
+        let buffered_writes = self.buffered_writes.len();
+        while let Some(write) = self.buffered_writes.front() {
+            match apply_write(&mut self.repository, write) {
+                Ok(()) => {}
+                Err(RepositoryError::Unavailable) => return Ok(false),
+                Err(error) => {
+                    // Non-transient errors will never succeed, so keep the write as failed
+                    error!(?error, "buffered write failed, kept as failed write");
+                    self.failed_writes.extend(self.buffered_writes.pop_front());
+                    return Err(error);
+                }
+            }
+            self.buffered_writes.pop_front();
+        }
+
+        info!(
+            buffered_writes,
+            "repository available, retried all buffered writes"
+        );
+
+        Ok(true)
+    }
+}
+
+/// Apply the [`BufferedWrite`] to the provided repository.
+fn apply_write<Repository, Statistic>(
+    repository: &mut Repository,
+    write: &BufferedWrite<Statistic>,
+) -> Result<(), RepositoryError>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Statistic: PositionSummariser,
+{
+    match write {
+        BufferedWrite::SetOpenPosition(position) => repository.set_open_position(position.clone()),
+        BufferedWrite::RemovePosition(position_id) => {
+            repository.remove_position(position_id).map(|_| ())
+        }
+        BufferedWrite::SetExitedPosition(engine_id, position) => {
+            repository.set_exited_position(*engine_id, position.clone())
+        }
+        BufferedWrite::SetBalance(engine_id, balance) => {
+            repository.set_balance(*engine_id, *balance)
+        }
+        BufferedWrite::SetStatistics(market_id, statistic) => {
+            repository.set_statistics(market_id.clone(), *statistic)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{statistic::summary::pnl::PnLReturnSummary, test_util::position};
+    use chrono::Utc;
+
+    /// Repository that returns [`RepositoryError::Unavailable`] while it is down.
+    /// Repository that returns [`RepositoryError::WriteError`] for every write while it is
+    /// broken.
+    #[derive(Debug, Default)]
+    struct FlakyRepository {
+        down: bool,
+        broken: bool,
+        repository: InMemoryRepository<PnLReturnSummary>,
+    }
+
+    impl FlakyRepository {
+        fn check(&self) -> Result<(), RepositoryError> {
+            match self.down {
+                true => Err(RepositoryError::Unavailable),
+                false => Ok(()),
+            }
+        }
+
+        fn check_write(&self) -> Result<(), RepositoryError> {
+            self.check()?;
+            match self.broken {
+                true => Err(RepositoryError::WriteError),
+                false => Ok(()),
+            }
+        }
+    }
+
+    impl PositionHandler for FlakyRepository {
+        fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
+            self.check()?;
+            self.repository.set_open_position(position)
+        }
+
+        fn get_open_position(
+            &mut self,
+            position_id: &PositionId,
+        ) -> Result<Option<Position>, RepositoryError> {
+            self.check()?;
+            self.repository.get_open_position(position_id)
+        }
+
+        fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+            &mut self,
+            engine_id: Uuid,
+            markets: Markets,
+        ) -> Result<Vec<Position>, RepositoryError> {
+            self.check()?;
+            self.repository.get_open_positions(engine_id, markets)
+        }
+
+        fn remove_position(
+            &mut self,
+            position_id: &PositionId,
+        ) -> Result<Option<Position>, RepositoryError> {
+            self.check()?;
+            self.repository.remove_position(position_id)
+        }
+
+        fn set_exited_position(
+            &mut self,
+            engine_id: Uuid,
+            position: Position,
+        ) -> Result<(), RepositoryError> {
+            self.check()?;
+            self.repository.set_exited_position(engine_id, position)
+        }
+
+        fn get_exited_positions(
+            &mut self,
+            engine_id: Uuid,
+        ) -> Result<Vec<Position>, RepositoryError> {
+            self.check()?;
+            self.repository.get_exited_positions(engine_id)
+        }
+    }
+
+    impl BalanceHandler for FlakyRepository {
+        fn set_balance(
+            &mut self,
+            engine_id: Uuid,
+            balance: Balance,
+        ) -> Result<(), RepositoryError> {
+            self.check_write()?;
+            self.repository.set_balance(engine_id, balance)
+        }
+
+        fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+            self.check()?;
+            self.repository.get_balance(engine_id)
+        }
+    }
+
+    impl StatisticHandler<PnLReturnSummary> for FlakyRepository {
+        fn set_statistics(
+            &mut self,
+            market_id: MarketId,
+            statistic: PnLReturnSummary,
+        ) -> Result<(), RepositoryError> {
+            self.check()?;
+            self.repository.set_statistics(market_id, statistic)
+        }
+
+        fn get_statistics(
+            &mut self,
+            market_id: &MarketId,
+        ) -> Result<PnLReturnSummary, RepositoryError> {
+            self.check()?;
+            self.repository.get_statistics(market_id)
+        }
+    }
+
+    fn balance(total: f64) -> Balance {
+        Balance {
+            time: Utc::now(),
+            total,
+            available: total,
+        }
+    }
+
+    #[test]
+    fn buffer_policy_serves_outage_from_mirror_and_retries_writes_in_order() {
+        let engine_id = Uuid::new_v4();
+        let mut repository = ResilientRepository::new(
+            FlakyRepository::default(),
+            FailurePolicy::Buffer {
+                max_buffered_writes: 3,
+            },
+        );
+        repository.set_balance(engine_id, balance(1000.0)).unwrap();
+
+        // Outage: writes are buffered & reads are served from the mirror
+        repository.repository.down = true;
+        let position = position();
+        repository.set_open_position(position.clone()).unwrap();
+        repository.set_balance(engine_id, balance(900.0)).unwrap();
+        assert_eq!(repository.buffered_writes(), 2);
+        assert_eq!(repository.get_balance(engine_id).unwrap().total, 900.0);
+        assert_eq!(
+            repository.get_open_position(&position.position_id).unwrap(),
+            Some(position.clone())
+        );
+
+        // Underlying repository still holds the pre-outage state
+        assert_eq!(
+            repository
+                .repository
+                .repository
+                .get_balance(engine_id)
+                .unwrap()
+                .total,
+            1000.0
+        );
+
+        // Recovery: buffered writes are retried before the next access
+        repository.repository.down = false;
+        assert_eq!(repository.get_balance(engine_id).unwrap().total, 900.0);
+        assert_eq!(repository.buffered_writes(), 0);
+        assert_eq!(
+            repository
+                .repository
+                .repository
+                .get_open_position(&position.position_id)
+                .unwrap(),
+            Some(position)
+        );
+    }
+
+    #[test]
+    fn buffer_policy_fails_writes_once_buffer_is_full() {
+        let engine_id = Uuid::new_v4();
+        let mut repository = ResilientRepository::new(
+            FlakyRepository::default(),
+            FailurePolicy::Buffer {
+                max_buffered_writes: 1,
+            },
+        );
+
+        repository.repository.down = true;
+        repository.set_balance(engine_id, balance(900.0)).unwrap();
+        assert!(matches!(
+            repository.set_balance(engine_id, balance(800.0)),
+            Err(RepositoryError::Unavailable)
+        ));
+
+        // Write that could not be buffered is not applied to the mirror, but is kept as failed
+        assert_eq!(repository.get_balance(engine_id).unwrap().total, 900.0);
+        assert!(matches!(
+            repository.failed_writes(),
+            [BufferedWrite::SetBalance(_, balance)] if balance.total == 800.0
+        ));
+    }
+
+    #[test]
+    fn buffer_policy_keeps_buffered_write_that_fails_permanently_on_retry() {
+        let engine_id = Uuid::new_v4();
+        let mut repository = ResilientRepository::new(
+            FlakyRepository::default(),
+            FailurePolicy::Buffer {
+                max_buffered_writes: 2,
+            },
+        );
+
+        repository.repository.down = true;
+        repository.set_balance(engine_id, balance(900.0)).unwrap();
+
+        // Recovery: buffered write fails with a non-transient error when retried
+        repository.repository.down = false;
+        repository.repository.broken = true;
+        assert!(matches!(
+            repository.get_balance(engine_id),
+            Err(RepositoryError::WriteError)
+        ));
+        assert_eq!(repository.buffered_writes(), 0);
+
+        // Failed write is kept, & can be re-applied once the cause is resolved
+        repository.repository.broken = false;
+        let failed_writes = repository.take_failed_writes();
+        assert_eq!(failed_writes.len(), 1);
+        assert!(repository.failed_writes().is_empty());
+        for write in &failed_writes {
+            apply_write(&mut repository.repository, write).unwrap();
+        }
+        assert_eq!(repository.get_balance(engine_id).unwrap().total, 900.0);
+    }
+
+    #[test]
+    fn halt_policy_fails_writes_but_serves_reads_from_mirror() {
+        let engine_id = Uuid::new_v4();
+        let mut repository =
+            ResilientRepository::new(FlakyRepository::default(), FailurePolicy::Halt);
+        let position = position();
+        repository.set_open_position(position.clone()).unwrap();
+
+        repository.repository.down = true;
+        assert!(matches!(
+            repository.set_balance(engine_id, balance(900.0)),
+            Err(RepositoryError::Unavailable)
+        ));
+        assert_eq!(repository.buffered_writes(), 0);
+        assert_eq!(
+            repository.get_open_position(&position.position_id).unwrap(),
+            Some(position)
+        );
+    }
+}
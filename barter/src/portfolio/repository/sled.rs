@@ -0,0 +1,414 @@
+use crate::{
+    portfolio::{
+        error::PortfolioError,
+        position::{determine_position_id, Position, PositionId},
+        repository::{
+            determine_exited_positions_id, error::RepositoryError, BalanceHandler, PositionHandler,
+            StatisticHandler,
+        },
+        Balance,
+    },
+    statistic::summary::PositionSummariser,
+};
+use barter_integration::model::{Market, MarketId};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sled::{Db, Tree};
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+use uuid::Uuid;
+
+/// Configuration for constructing a [`SledRepository`] via the new() constructor method.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    /// Path of the directory containing the embedded Sled database.
+    pub path: String,
+}
+
+/// Embedded Sled persisted repository that implements [`PositionHandler`], [`BalanceHandler`],
+/// & [`PositionSummariser`]. Used by a Portfolio implementation to persist the Portfolio state,
+/// including total equity, available cash & Positions, across runs without an external service
+/// (eg/ for single-binary deployments).
+///
+/// State is stored as JSON values keyed the same way as the
+/// [`RedisRepository`](super::redis::RedisRepository). Exited [`Position`]s are each stored under
+/// their own key in a per engine Sled [`Tree`], keyed by a monotonically increasing id so they are
+/// iterated in the order they are exited. Appending an exited [`Position`] therefore never rewrites
+/// the previously exited [`Position`]s.
+pub struct SledRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    db: Db,
+    _statistic_marker: PhantomData<Statistic>,
+}
+
+impl<Statistic> PositionHandler for SledRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
+        let position_string = serde_json::to_vec(&position)?;
+        self.insert(&position.position_id, position_string)
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.get(position_id)
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        markets
+            .filter_map(|market| {
+                self.get_open_position(&determine_position_id(
+                    engine_id,
+                    &market.exchange,
+                    &market.instrument,
+                ))
+                .transpose()
+            })
+            .collect()
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &String,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.db
+            .remove(position_id)
+            .map_err(|error| map_sled_error(error, RepositoryError::DeleteError))?
+            .map(|position| serde_json::from_slice::<Position>(&position))
+            .transpose()
+            .map_err(RepositoryError::JsonSerDeError)
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        let position_string = serde_json::to_vec(&position)?;
+
+        // Big-endian ids sort lexicographically in the order they are generated
+        let id = self
+            .db
+            .generate_id()
+            .map_err(|error| map_sled_error(error, RepositoryError::WriteError))?;
+
+        self.exited_positions(engine_id, RepositoryError::WriteError)?
+            .insert(id.to_be_bytes(), position_string)
+            .map(|_| ())
+            .map_err(|error| map_sled_error(error, RepositoryError::WriteError))
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        self.exited_positions(engine_id, RepositoryError::ReadError)?
+            .iter()
+            .values()
+            .map(|position| {
+                let position =
+                    position.map_err(|error| map_sled_error(error, RepositoryError::ReadError))?;
+                serde_json::from_slice::<Position>(&position)
+                    .map_err(RepositoryError::JsonSerDeError)
+            })
+            .collect()
+    }
+}
+
+impl<Statistic> BalanceHandler for SledRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        let balance_string = serde_json::to_vec(&balance)?;
+        self.insert(&Balance::balance_id(engine_id), balance_string)
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        self.get(&Balance::balance_id(engine_id))?
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)
+    }
+}
+
+impl<Statistic> StatisticHandler<Statistic> for SledRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        self.insert(&market_id.0, serde_json::to_vec(&statistic)?)
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        self.get(&market_id.0)?
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)
+    }
+}
+
+impl<Statistic: PositionSummariser> Debug for SledRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SledRepository").finish()
+    }
+}
+
+impl<Statistic: PositionSummariser> SledRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    /// Constructs a new [`SledRepository`] component using the provided Sled database.
+    pub fn new(db: Db) -> Self {
+        Self {
+            db,
+            _statistic_marker: PhantomData,
+        }
+    }
+
+    /// Returns a [`SledRepositoryBuilder`] instance.
+    pub fn builder() -> SledRepositoryBuilder<Statistic> {
+        SledRepositoryBuilder::new()
+    }
+
+    /// Open & return the Sled database at the configured path, creating it if it does not exist.
+    pub fn setup_sled_db(cfg: Config) -> Result<Db, RepositoryError> {
+        sled::open(cfg.path).map_err(|error| map_sled_error(error, RepositoryError::ReadError))
+    }
+
+    /// Open the Sled [`Tree`] of the provided engine's exited [`Position`]s, mapping any failure to
+    /// the provided [`RepositoryError`].
+    fn exited_positions(
+        &self,
+        engine_id: Uuid,
+        otherwise: RepositoryError,
+    ) -> Result<Tree, RepositoryError> {
+        self.db
+            .open_tree(determine_exited_positions_id(engine_id))
+            .map_err(|error| map_sled_error(error, otherwise))
+    }
+
+    /// Upsert the JSON value at the provided key.
+    fn insert(&self, key: &str, value: Vec<u8>) -> Result<(), RepositoryError> {
+        self.db
+            .insert(key, value)
+            .map(|_| ())
+            .map_err(|error| map_sled_error(error, RepositoryError::WriteError))
+    }
+
+    /// Get & deserialise the JSON value at the provided key, if present.
+    fn get<T>(&self, key: &str) -> Result<Option<T>, RepositoryError>
+    where
+        T: DeserializeOwned,
+    {
+        self.db
+            .get(key)
+            .map_err(|error| map_sled_error(error, RepositoryError::ReadError))?
+            .map(|value| serde_json::from_slice::<T>(&value))
+            .transpose()
+            .map_err(RepositoryError::JsonSerDeError)
+    }
+}
+
+/// Maps a [`sled::Error`] to [`RepositoryError::Unavailable`] if it is caused by an IO failure,
+/// otherwise to the provided [`RepositoryError`].
+fn map_sled_error(error: sled::Error, otherwise: RepositoryError) -> RepositoryError {
+    match error {
+        sled::Error::Io(_) => RepositoryError::Unavailable,
+        _ => otherwise,
+    }
+}
+
+/// Builder to construct [`SledRepository`] instances.
+#[derive(Default)]
+pub struct SledRepositoryBuilder<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    db: Option<Db>,
+    _statistic_marker: PhantomData<Statistic>,
+}
+
+impl<Statistic: PositionSummariser> SledRepositoryBuilder<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    pub fn new() -> Self {
+        Self {
+            db: None,
+            _statistic_marker: PhantomData,
+        }
+    }
+
+    pub fn db(self, value: Db) -> Self {
+        Self {
+            db: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Result<SledRepository<Statistic>, PortfolioError> {
+        Ok(SledRepository {
+            db: self.db.ok_or(PortfolioError::BuilderIncomplete("db"))?,
+            _statistic_marker: PhantomData,
+        })
+    }
+}
+
+impl<Statistic> Debug for SledRepositoryBuilder<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SledRepositoryBuilder")
+            .field("db", &"Option<sled::Db>")
+            .field("_statistic_marker", &self._statistic_marker)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{statistic::summary::pnl::PnLReturnSummary, test_util::position};
+    use barter_integration::model::instrument::{kind::InstrumentKind, Instrument};
+
+    fn repository(db: Db) -> SledRepository<PnLReturnSummary> {
+        SledRepository::builder().db(db).build().unwrap()
+    }
+
+    fn temporary_db() -> Db {
+        sled::Config::new().temporary(true).open().unwrap()
+    }
+
+    /// Open the Sled database at the provided path, retrying while the file lock of a previously
+    /// dropped [`Db`] is still held by it's background flush thread.
+    fn reopen_db(path: &std::path::Path) -> Db {
+        for _ in 0..100 {
+            match sled::open(path) {
+                Ok(db) => return db,
+                Err(sled::Error::Io(_)) => std::thread::sleep(std::time::Duration::from_millis(10)),
+                Err(error) => panic!("failed to reopen Sled database: {error}"),
+            }
+        }
+        panic!("timed out reopening Sled database")
+    }
+
+    #[test]
+    fn sled_repository_round_trips_positions_balances_and_statistics() {
+        let mut repository = repository(temporary_db());
+        let engine_id = Uuid::new_v4();
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+
+        // Open Positions are keyed by PositionId
+        let mut open = position();
+        open.position_id = determine_position_id(engine_id, &market.exchange, &market.instrument);
+        repository.set_open_position(open.clone()).unwrap();
+        assert_eq!(
+            repository.get_open_position(&open.position_id).unwrap(),
+            Some(open.clone())
+        );
+        assert_eq!(
+            repository
+                .get_open_positions(engine_id, [market.clone()].iter())
+                .unwrap(),
+            vec![open.clone()]
+        );
+        assert_eq!(
+            repository.remove_position(&open.position_id).unwrap(),
+            Some(open.clone())
+        );
+        assert_eq!(
+            repository.get_open_position(&open.position_id).unwrap(),
+            None
+        );
+
+        // Exited Positions are appended in the order they are exited
+        assert!(repository
+            .get_exited_positions(engine_id)
+            .unwrap()
+            .is_empty());
+        let mut exited = position();
+        exited.instrument = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        repository
+            .set_exited_position(engine_id, open.clone())
+            .unwrap();
+        repository
+            .set_exited_position(engine_id, exited.clone())
+            .unwrap();
+        assert_eq!(
+            repository.get_exited_positions(engine_id).unwrap(),
+            vec![open, exited]
+        );
+
+        // Balance & statistics are absent until set
+        assert!(matches!(
+            repository.get_balance(engine_id),
+            Err(RepositoryError::ExpectedDataNotPresentError)
+        ));
+        let balance = Balance::default();
+        repository.set_balance(engine_id, balance).unwrap();
+        assert_eq!(repository.get_balance(engine_id).unwrap(), balance);
+
+        let market_id = MarketId::from(&market);
+        let statistic = PnLReturnSummary::new();
+        repository
+            .set_statistics(market_id.clone(), statistic)
+            .unwrap();
+        assert_eq!(repository.get_statistics(&market_id).unwrap(), statistic);
+    }
+
+    #[test]
+    fn sled_repository_persists_state_across_reopening() {
+        let path = std::env::temp_dir().join(format!("barter_sled_{}", Uuid::new_v4()));
+        let config = Config {
+            path: path.to_string_lossy().into_owned(),
+        };
+        let engine_id = Uuid::new_v4();
+        let balance = Balance::default();
+        let exited = position();
+
+        {
+            let mut repository = repository(
+                SledRepository::<PnLReturnSummary>::setup_sled_db(config.clone()).unwrap(),
+            );
+            repository.set_balance(engine_id, balance).unwrap();
+            repository
+                .set_exited_position(engine_id, exited.clone())
+                .unwrap();
+        }
+
+        let mut repository = repository(reopen_db(&path));
+        assert_eq!(repository.get_balance(engine_id).unwrap(), balance);
+        assert_eq!(
+            repository.get_exited_positions(engine_id).unwrap(),
+            vec![exited]
+        );
+
+        drop(repository);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn setup_sled_db_fails_with_unavailable_if_path_is_not_a_directory() {
+        let path = std::env::temp_dir().join(format!("barter_sled_{}", Uuid::new_v4()));
+        std::fs::write(&path, b"not a sled database").unwrap();
+
+        let result = SledRepository::<PnLReturnSummary>::setup_sled_db(Config {
+            path: path.to_string_lossy().into_owned(),
+        });
+        assert!(matches!(result, Err(RepositoryError::Unavailable)));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,6 +1,9 @@
+use barter_integration::model::Market;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{info, warn};
 
-use crate::portfolio::{OrderEvent, OrderType};
+use crate::portfolio::{equity::DailyProfitLoss, OrderEvent, OrderType};
 
 /// Evaluates the risk associated with an [`OrderEvent`] to determine if it should be actioned. It
 /// can also amend the order (eg/ [`OrderType`]) to better fit the risk strategy required for
@@ -8,9 +11,30 @@ use crate::portfolio::{OrderEvent, OrderType};
 pub trait OrderEvaluator {
     const DEFAULT_ORDER_TYPE: OrderType;
 
-    /// May return an amended [`OrderEvent`] if the associated risk is appropriate. Returns `None`
-    /// if the risk is too high.
-    fn evaluate_order(&self, order: OrderEvent) -> Option<OrderEvent>;
+    /// May return an amended [`OrderEvent`] if the associated risk is appropriate, given the
+    /// Portfolio's [`EquitySnapshot`]. Returns `None` if the risk is too high.
+    fn evaluate_order(&self, order: OrderEvent, equity: &EquitySnapshot) -> Option<OrderEvent>;
+}
+
+/// Current & peak mark-to-market equity of a Portfolio at the time an [`OrderEvent`] is
+/// evaluated, as well as the P&L realised today. See [`EquityCurve`](super::equity::EquityCurve).
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct EquitySnapshot {
+    pub current: f64,
+    pub peak: f64,
+    #[serde(default)]
+    pub daily_profit_loss: DailyProfitLoss,
+}
+
+impl EquitySnapshot {
+    /// Fractional drawdown of the current equity from the peak equity (eg/ 0.1 for 10% below
+    /// the peak). Returns 0.0 if the peak equity is not positive.
+    pub fn drawdown(&self) -> f64 {
+        match self.peak > 0.0 {
+            true => ((self.peak - self.current) / self.peak).max(0.0),
+            false => 0.0,
+        }
+    }
 }
 
 /// Default risk manager that implements [`OrderEvaluator`].
@@ -20,7 +44,7 @@ pub struct DefaultRisk {}
 impl OrderEvaluator for DefaultRisk {
     const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
 
-    fn evaluate_order(&self, mut order: OrderEvent) -> Option<OrderEvent> {
+    fn evaluate_order(&self, mut order: OrderEvent, _: &EquitySnapshot) -> Option<OrderEvent> {
         if self.risk_too_high(&order) {
             return None;
         }
@@ -34,3 +58,432 @@ impl DefaultRisk {
         false
     }
 }
+
+/// Max-drawdown circuit breaker risk manager that implements [`OrderEvaluator`].
+///
+/// Rejects every entry [`OrderEvent`] whilst the current equity is more than `max_drawdown_pct`
+/// below the peak equity, and accepts them again once the equity recovers within the limit. Exit
+/// [`OrderEvent`]s are always accepted so open [`Position`](super::position::Position)s can still
+/// be closed.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct DrawdownRisk {
+    /// Maximum fractional drawdown from the peak equity before entries are rejected (eg/ 0.1 for
+    /// 10%).
+    pub max_drawdown_pct: f64,
+}
+
+impl OrderEvaluator for DrawdownRisk {
+    const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
+
+    fn evaluate_order(&self, mut order: OrderEvent, equity: &EquitySnapshot) -> Option<OrderEvent> {
+        if order.decision.is_entry() && equity.drawdown() > self.max_drawdown_pct {
+            warn!(
+                exchange = %order.exchange,
+                instrument = %order.instrument,
+                decision = ?order.decision,
+                current_equity = equity.current,
+                peak_equity = equity.peak,
+                max_drawdown_pct = self.max_drawdown_pct,
+                outcome = "OrderEvent rejected",
+                "max drawdown breached"
+            );
+            return None;
+        }
+        order.order_type = DrawdownRisk::DEFAULT_ORDER_TYPE;
+        Some(order)
+    }
+}
+
+/// Daily loss limit risk manager that implements [`OrderEvaluator`].
+///
+/// Rejects every entry [`OrderEvent`] once the P&L realised since the last UTC midnight is a loss
+/// greater than `max_daily_loss`, halting new entries for the rest of the UTC day. The realised
+/// P&L is accumulated from fill timestamps, and is compared on the UTC day of the
+/// [`OrderEvent`] time, so entries resume on the next day. Exit [`OrderEvent`]s are always
+/// accepted so open [`Position`](super::position::Position)s can still be closed.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct DailyLossLimitRisk {
+    /// Maximum realised loss per UTC day before entries are rejected, in the currency of the
+    /// Portfolio [`Balance`](super::Balance) (eg/ 500.0).
+    pub max_daily_loss: f64,
+}
+
+impl OrderEvaluator for DailyLossLimitRisk {
+    const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
+
+    fn evaluate_order(&self, mut order: OrderEvent, equity: &EquitySnapshot) -> Option<OrderEvent> {
+        let realised_today = equity.daily_profit_loss.realised_on(order.time);
+        if order.decision.is_entry() && -realised_today > self.max_daily_loss {
+            warn!(
+                exchange = %order.exchange,
+                instrument = %order.instrument,
+                decision = ?order.decision,
+                realised_today,
+                max_daily_loss = self.max_daily_loss,
+                outcome = "OrderEvent rejected",
+                "daily loss limit breached"
+            );
+            return None;
+        }
+        order.order_type = DailyLossLimitRisk::DEFAULT_ORDER_TYPE;
+        Some(order)
+    }
+}
+
+/// Position size limits of a [`PositionLimitRisk`] risk manager. A limit of `None` is unbounded.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct Limits {
+    /// Maximum notional value of an entry [`OrderEvent`], as determined by it's
+    /// [`ContractType`](super::ContractType) at the [`MarketMeta`](crate::data::MarketMeta) close.
+    #[serde(default)]
+    pub max_notional: Option<f64>,
+    /// Maximum absolute quantity of an entry [`OrderEvent`].
+    #[serde(default)]
+    pub max_quantity: Option<f64>,
+    /// Behaviour when an entry [`OrderEvent`] exceeds a limit.
+    #[serde(default)]
+    pub oversize: OversizePolicy,
+}
+
+/// Behaviour of a [`PositionLimitRisk`] risk manager when an entry [`OrderEvent`] exceeds it's
+/// [`Limits`].
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum OversizePolicy {
+    /// Reduce the [`OrderEvent`] quantity to the largest quantity within the [`Limits`].
+    #[default]
+    Shrink,
+    /// Reject the [`OrderEvent`].
+    Reject,
+}
+
+/// Position size risk manager that implements [`OrderEvaluator`], capping the quantity & notional
+/// value of entry [`OrderEvent`]s produced by the allocator.
+///
+/// Each [`Market`] uses it's override [`Limits`] if present, else the default [`Limits`]. Entry
+/// [`OrderEvent`]s exceeding the [`Limits`] are shrunk or rejected according to the
+/// [`OversizePolicy`], and are rejected if shrinking leaves no quantity. Exit [`OrderEvent`]s are
+/// always accepted unchanged so open [`Position`](super::position::Position)s are fully closed.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct PositionLimitRisk {
+    pub default: Limits,
+    pub overrides: HashMap<Market, Limits>,
+}
+
+impl OrderEvaluator for PositionLimitRisk {
+    const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
+
+    fn evaluate_order(&self, mut order: OrderEvent, _: &EquitySnapshot) -> Option<OrderEvent> {
+        if order.decision.is_entry() {
+            let limits = self.limits(&order);
+            let max_quantity = PositionLimitRisk::max_quantity(limits, &order);
+
+            if order.quantity.abs() > max_quantity {
+                if limits.oversize == OversizePolicy::Reject || max_quantity <= 0.0 {
+                    warn!(
+                        exchange = %order.exchange,
+                        instrument = %order.instrument,
+                        quantity = order.quantity,
+                        max_quantity,
+                        outcome = "OrderEvent rejected",
+                        "OrderEvent exceeds position limits"
+                    );
+                    return None;
+                }
+
+                info!(
+                    exchange = %order.exchange,
+                    instrument = %order.instrument,
+                    quantity = order.quantity,
+                    max_quantity,
+                    outcome = "OrderEvent quantity reduced",
+                    "OrderEvent exceeds position limits"
+                );
+                order.quantity = max_quantity.copysign(order.quantity);
+            }
+        }
+
+        order.order_type = PositionLimitRisk::DEFAULT_ORDER_TYPE;
+        Some(order)
+    }
+}
+
+impl PositionLimitRisk {
+    /// Constructs a new [`PositionLimitRisk`] applying the provided default [`Limits`] to every
+    /// [`Market`].
+    pub fn new(default: Limits) -> Self {
+        Self {
+            default,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Overrides the [`Limits`] of the provided [`Market`]. Replaces any existing override for
+    /// that [`Market`].
+    pub fn with_override<M>(mut self, market: M, limits: Limits) -> Self
+    where
+        M: Into<Market>,
+    {
+        self.overrides.insert(market.into(), limits);
+        self
+    }
+
+    /// [`Limits`] of the [`Market`] of the provided [`OrderEvent`].
+    fn limits(&self, order: &OrderEvent) -> Limits {
+        self.overrides
+            .get(&Market::new(
+                order.exchange.clone(),
+                order.instrument.clone(),
+            ))
+            .copied()
+            .unwrap_or(self.default)
+    }
+
+    /// Largest absolute quantity of the provided [`OrderEvent`] within the [`Limits`]. The
+    /// notional limit is ignored if the [`OrderEvent`] has a degenerate close price.
+    fn max_quantity(limits: Limits, order: &OrderEvent) -> f64 {
+        let close = order.market_meta.close;
+        let notional_quantity = limits
+            .max_notional
+            .filter(|_| close.is_finite() && close > 0.0)
+            .map(|max_notional| {
+                order
+                    .contract_type
+                    .quantity(max_notional, close, order.contract_multiplier)
+            });
+
+        [limits.max_quantity, notional_quantity]
+            .into_iter()
+            .flatten()
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::MarketMeta, portfolio::ContractType, strategy::Decision, test_util::order_event,
+    };
+    use barter_integration::model::instrument::kind::InstrumentKind;
+    use chrono::{DateTime, Duration, Utc};
+
+    fn order(decision: Decision) -> OrderEvent {
+        OrderEvent {
+            decision,
+            ..order_event()
+        }
+    }
+
+    #[test]
+    fn drawdown_risk_blocks_entries_after_breaching_max_drawdown() {
+        let risk = DrawdownRisk {
+            max_drawdown_pct: 0.1,
+        };
+        let equity = |current| EquitySnapshot {
+            current,
+            peak: 1000.0,
+            ..Default::default()
+        };
+
+        // Within the limit, entries & exits are accepted
+        assert!(risk
+            .evaluate_order(order(Decision::Long), &equity(1000.0))
+            .is_some());
+        assert!(risk
+            .evaluate_order(order(Decision::Short), &equity(900.0))
+            .is_some());
+
+        // Breached the limit, entries are blocked but exits are still accepted
+        assert!(risk
+            .evaluate_order(order(Decision::Long), &equity(899.0))
+            .is_none());
+        assert!(risk
+            .evaluate_order(order(Decision::Short), &equity(500.0))
+            .is_none());
+        assert!(risk
+            .evaluate_order(order(Decision::CloseLong), &equity(899.0))
+            .is_some());
+
+        // Recovered within the limit, entries are accepted again
+        assert!(risk
+            .evaluate_order(order(Decision::Long), &equity(950.0))
+            .is_some());
+    }
+
+    #[test]
+    fn daily_loss_limit_risk_halts_entries_until_the_next_utc_day() {
+        let risk = DailyLossLimitRisk {
+            max_daily_loss: 100.0,
+        };
+        let day_one = DateTime::parse_from_rfc3339("2024-01-01T22:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let day_two = day_one + Duration::hours(3);
+        let order = |decision, time| OrderEvent {
+            time,
+            ..order(decision)
+        };
+
+        // Losses realised by fills on day one accumulate until the limit is breached
+        let mut equity = EquitySnapshot::default();
+        equity.daily_profit_loss.update(day_one, -60.0);
+        assert!(risk
+            .evaluate_order(order(Decision::Long, day_one), &equity)
+            .is_some());
+        equity
+            .daily_profit_loss
+            .update(day_one + Duration::minutes(30), -50.0);
+        assert_eq!(equity.daily_profit_loss.realised_on(day_one), -110.0);
+
+        // Breached the limit, entries are blocked for the rest of the day but exits are accepted
+        assert!(risk
+            .evaluate_order(order(Decision::Short, day_one), &equity)
+            .is_none());
+        assert!(risk
+            .evaluate_order(order(Decision::CloseLong, day_one), &equity)
+            .is_some());
+
+        // Entries resume on the next UTC day, before any fill has reset the realised P&L
+        assert_eq!(equity.daily_profit_loss.realised_on(day_two), 0.0);
+        assert!(risk
+            .evaluate_order(order(Decision::Long, day_two), &equity)
+            .is_some());
+
+        // First fill of the next UTC day resets the realised P&L, ignoring late fills of day one
+        equity.daily_profit_loss.update(day_two, -20.0);
+        equity.daily_profit_loss.update(day_one, -500.0);
+        assert_eq!(equity.daily_profit_loss.realised_on(day_two), -20.0);
+        assert!(risk
+            .evaluate_order(order(Decision::Long, day_two), &equity)
+            .is_some());
+    }
+
+    #[test]
+    fn equity_snapshot_drawdown() {
+        let drawdown = |current, peak| {
+            EquitySnapshot {
+                current,
+                peak,
+                ..Default::default()
+            }
+            .drawdown()
+        };
+
+        assert_eq!(drawdown(1000.0, 1000.0), 0.0);
+        assert_eq!(drawdown(750.0, 1000.0), 0.25);
+        assert_eq!(drawdown(1100.0, 1000.0), 0.0);
+        assert_eq!(drawdown(-10.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn position_limit_risk_shrinks_or_rejects_oversized_entries() {
+        let limits = Limits {
+            max_notional: Some(1000.0),
+            max_quantity: Some(5.0),
+            oversize: OversizePolicy::Shrink,
+        };
+        let risk = PositionLimitRisk::new(limits);
+        let sized_order = |decision, quantity| OrderEvent {
+            decision,
+            quantity,
+            market_meta: MarketMeta {
+                close: 400.0,
+                ..Default::default()
+            },
+            ..order(decision)
+        };
+        let evaluate = |risk: &PositionLimitRisk, order| {
+            risk.evaluate_order(order, &EquitySnapshot::default())
+                .map(|order| order.quantity)
+        };
+
+        // Within limits, the quantity is unchanged
+        assert_eq!(evaluate(&risk, sized_order(Decision::Long, 2.0)), Some(2.0));
+
+        // Notional of 4.0 * 400.0 exceeds 1000.0, so shrunk to 2.5, preserving the sign
+        assert_eq!(evaluate(&risk, sized_order(Decision::Long, 4.0)), Some(2.5));
+        assert_eq!(
+            evaluate(&risk, sized_order(Decision::Short, -4.0)),
+            Some(-2.5)
+        );
+
+        // Exits are never limited
+        assert_eq!(
+            evaluate(&risk, sized_order(Decision::CloseLong, -10.0)),
+            Some(-10.0)
+        );
+
+        // Reject policy rejects rather than shrinks
+        let reject = PositionLimitRisk::new(Limits {
+            oversize: OversizePolicy::Reject,
+            ..limits
+        });
+        assert_eq!(evaluate(&reject, sized_order(Decision::Long, 4.0)), None);
+        assert_eq!(
+            evaluate(&reject, sized_order(Decision::Long, 2.0)),
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn position_limit_risk_uses_per_market_overrides() {
+        let default_order = order_event();
+        let market = Market::new(
+            default_order.exchange.clone(),
+            default_order.instrument.clone(),
+        );
+        let risk = PositionLimitRisk::new(Limits {
+            max_quantity: Some(10.0),
+            ..Default::default()
+        })
+        .with_override(
+            market,
+            Limits {
+                max_quantity: Some(0.5),
+                ..Default::default()
+            },
+        )
+        .with_override(
+            ("binance", "btc", "usdt", InstrumentKind::Spot),
+            Limits {
+                max_quantity: Some(0.0),
+                ..Default::default()
+            },
+        );
+
+        let quantity = |order: OrderEvent| {
+            risk.evaluate_order(
+                OrderEvent {
+                    quantity: 2.0,
+                    contract_type: ContractType::Linear,
+                    ..order
+                },
+                &EquitySnapshot::default(),
+            )
+            .map(|order| order.quantity)
+        };
+
+        // Overridden market is shrunk to it's own limit
+        assert_eq!(quantity(order_event()), Some(0.5));
+
+        // Market with a zero limit has no quantity left after shrinking, so is rejected
+        assert_eq!(
+            quantity(OrderEvent {
+                instrument: ("btc", "usdt", InstrumentKind::Spot).into(),
+                ..order_event()
+            }),
+            None
+        );
+
+        // Market without an override uses the default limits
+        assert_eq!(
+            quantity(OrderEvent {
+                instrument: ("sol", "usdt", InstrumentKind::Spot).into(),
+                ..order_event()
+            }),
+            Some(2.0)
+        );
+    }
+}
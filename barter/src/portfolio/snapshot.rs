@@ -0,0 +1,46 @@
+use crate::{
+    portfolio::{
+        equity::EquityCurve,
+        position::{Position, PositionCounts, PositionId},
+        Balance,
+    },
+    statistic::summary::{exposure::TimeInMarketSummary, win_loss::WinLossSummary},
+};
+use barter_integration::model::{instrument::symbol::Symbol, MarketId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+/// Serialisable export of the entire state of a
+/// [`MetaPortfolio`](super::portfolio::MetaPortfolio), independent of the repository it is
+/// persisted in. Used to recover a Portfolio after a crash (eg/ by persisting the snapshot as a
+/// single JSON blob & restoring it on startup).
+///
+/// Includes both the repository state (open & exited [`Position`]s, [`Balance`] & per-market
+/// statistics) and the in-memory session state (eg/ the [`EquityCurve`]). Configuration (eg/
+/// leverage) is not included, since it is provided when the Portfolio is constructed. Neither
+/// are pending entry [`OrderEvent`](super::OrderEvent)s, since the execution side is not
+/// snapshotted.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct PortfolioSnapshot<Statistic> {
+    /// Identifier for the [`Engine`](crate::engine::Engine) the Portfolio is associated with.
+    pub engine_id: Uuid,
+    /// Time the snapshot was taken.
+    pub time: DateTime<Utc>,
+    pub balance: Balance,
+    pub open_positions: Vec<Position>,
+    /// Exited [`Position`]s in the order they were exited.
+    pub exited_positions: Vec<Position>,
+    pub statistics: HashMap<MarketId, Statistic>,
+    pub used_margin: f64,
+    /// Margin consumed by each open [`Position`] in the base currency.
+    pub position_margins: HashMap<PositionId, f64>,
+    pub currency_balances: HashMap<Symbol, Balance>,
+    pub equity: EquityCurve,
+    pub time_in_market: TimeInMarketSummary,
+    pub position_counts: PositionCounts,
+    pub win_loss: WinLossSummary,
+    pub close_only: bool,
+    pub position_tags: HashMap<MarketId, BTreeSet<String>>,
+}
@@ -8,4 +8,14 @@ pub enum StatisticError {
 
     #[error("Failed to build struct due to insufficient metrics provided")]
     BuilderNoMetricsProvided,
+
+    #[error("Invalid {metric} window of {window} bars, expected at least {minimum}")]
+    InvalidWindow {
+        metric: &'static str,
+        window: usize,
+        minimum: usize,
+    },
+
+    #[error("Undefined log return of simple return {0}, expected a simple return above -1")]
+    UndefinedLogReturn(f64),
 }
@@ -0,0 +1,176 @@
+use crate::statistic::error::StatisticError;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Configuration for constructing a [`RollingBeta`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Number of bars in the rolling OLS window. Must be at least 2.
+    pub window: usize,
+}
+
+/// Rolling OLS hedge ratio β estimator for pairs trading, where β = cov(y, x) / var(x) over the
+/// most recent `window` bars of the dependent (y) & independent (x) leg prices.
+///
+/// ## Warm Up
+/// No β is produced until `window` bars have been observed, since a partially filled window
+/// yields an unreliable estimate. β is also withheld if the independent leg has (near) zero
+/// variance over the window, in which case the regression is undefined.
+///
+/// ## Numerical Stability
+/// Rather than maintaining running sums that are incremented & decremented as bars enter and
+/// leave the window (which suffers catastrophic cancellation for large price levels over long
+/// runs), β is recalculated each bar using a two-pass algorithm over the window: means first,
+/// then mean-centred co-moments. This is O(window) per bar, but is robust to price level and
+/// does not accumulate floating point error.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct RollingBeta {
+    pub window: usize,
+    pub observations: VecDeque<(f64, f64)>,
+    pub beta: Option<f64>,
+}
+
+impl RollingBeta {
+    /// Standard deviation of the independent leg, relative to its mean, below which any
+    /// variation is indistinguishable from floating point rounding & β is considered undefined.
+    const MIN_RELATIVE_STD_DEV: f64 = 1e3 * f64::EPSILON;
+
+    /// Constructs a new [`RollingBeta`] using the provided configuration.
+    ///
+    /// Returns a [`StatisticError::InvalidWindow`] if the window is less than 2 bars.
+    pub fn new(config: Config) -> Result<Self, StatisticError> {
+        if config.window < 2 {
+            return Err(StatisticError::InvalidWindow {
+                metric: "RollingBeta",
+                window: config.window,
+                minimum: 2,
+            });
+        }
+
+        Ok(Self {
+            window: config.window,
+            observations: VecDeque::with_capacity(config.window),
+            beta: None,
+        })
+    }
+
+    /// Update the rolling window with the next bar's dependent (y) & independent (x) prices, and
+    /// return the latest β, if warmed up.
+    pub fn update(&mut self, dependent: f64, independent: f64) -> Option<f64> {
+        if self.observations.len() == self.window {
+            self.observations.pop_front();
+        }
+        self.observations.push_back((dependent, independent));
+
+        self.beta = match self.observations.len() < self.window {
+            true => None,
+            false => self.calculate(),
+        };
+
+        self.beta
+    }
+
+    /// Latest β, if warmed up.
+    pub fn beta(&self) -> Option<f64> {
+        self.beta
+    }
+
+    /// Determines if the estimator has observed a full window of bars.
+    pub fn is_warm(&self) -> bool {
+        self.observations.len() == self.window
+    }
+
+    /// Two-pass calculation of β = cov(y, x) / var(x) over the current window.
+    fn calculate(&self) -> Option<f64> {
+        let count = self.observations.len() as f64;
+
+        let (sum_y, sum_x) = self
+            .observations
+            .iter()
+            .fold((0.0, 0.0), |(sum_y, sum_x), (y, x)| (sum_y + y, sum_x + x));
+        let (mean_y, mean_x) = (sum_y / count, sum_x / count);
+
+        let (co_moment, moment_x) =
+            self.observations
+                .iter()
+                .fold((0.0, 0.0), |(co_moment, moment_x), (y, x)| {
+                    let (delta_y, delta_x) = (y - mean_y, x - mean_x);
+                    (co_moment + delta_y * delta_x, moment_x + delta_x * delta_x)
+                });
+
+        // Bessel's correction cancels out in the ratio, so use population moments
+        let std_dev_x = (moment_x / count).sqrt();
+        match std_dev_x <= Self::MIN_RELATIVE_STD_DEV * mean_x.abs().max(1.0) {
+            true => None,
+            false => Some(co_moment / moment_x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolling_beta_warms_up_before_producing_beta() {
+        let mut beta = RollingBeta::new(Config { window: 3 }).unwrap();
+
+        assert_eq!(beta.update(2.0, 1.0), None);
+        assert_eq!(beta.update(4.0, 2.0), None);
+        assert!(!beta.is_warm());
+
+        let actual = beta.update(6.0, 3.0).unwrap();
+        assert!(beta.is_warm());
+        assert!((actual - 2.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn rolling_beta_only_uses_most_recent_window() {
+        let mut beta = RollingBeta::new(Config { window: 3 }).unwrap();
+
+        // y = 2x
+        beta.update(2.0, 1.0);
+        beta.update(4.0, 2.0);
+        beta.update(6.0, 3.0);
+
+        // y = 0.5x + c, after fully rolling out the y = 2x bars
+        beta.update(10.0, 4.0);
+        beta.update(10.5, 5.0);
+        let actual = beta.update(11.0, 6.0).unwrap();
+
+        assert!((actual - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn rolling_beta_is_stable_for_large_price_levels() {
+        let mut beta = RollingBeta::new(Config { window: 50 }).unwrap();
+
+        let mut actual = None;
+        for i in 0..10_000 {
+            let x = 1e8 + (i % 7) as f64;
+            actual = beta.update(1.5 * x + 3.0, x);
+        }
+
+        assert!((actual.unwrap() - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rolling_beta_with_constant_independent_leg_is_undefined() {
+        let mut beta = RollingBeta::new(Config { window: 2 }).unwrap();
+
+        beta.update(1.0, 100.0);
+        assert_eq!(beta.update(2.0, 100.0), None);
+    }
+
+    #[test]
+    fn rolling_beta_with_window_below_two_returns_error() {
+        assert!(matches!(
+            RollingBeta::new(Config { window: 1 }),
+            Err(StatisticError::InvalidWindow {
+                window: 1,
+                minimum: 2,
+                ..
+            })
+        ));
+    }
+}
@@ -192,6 +192,44 @@ impl AvgDrawdown {
     }
 }
 
+/// [`UlcerIndex`] is the root-mean-square of the percentage drawdowns of the Portfolio, or
+/// investment, from its running equity peak. Unlike [`MaxDrawdown`], it captures both the depth &
+/// the duration of drawdowns, since every [`EquityPoint`] spent below the peak contributes.
+///
+/// See documentation: <https://www.investopedia.com/terms/u/ulcerindex.asp>
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct UlcerIndex {
+    pub equity_peak: f64,
+    pub count: u64,
+    pub sum_squared_drawdown_pct: f64,
+    pub ulcer_index: f64,
+}
+
+impl UlcerIndex {
+    /// Initialises a new [`UlcerIndex`] using the starting equity as the first peak.
+    pub fn init(starting_equity: f64) -> Self {
+        Self {
+            equity_peak: starting_equity,
+            ..Self::default()
+        }
+    }
+
+    /// Updates the [`UlcerIndex`] using the latest input [`EquityPoint`] of the Portfolio.
+    pub fn update(&mut self, current: EquityPoint) {
+        self.equity_peak = self.equity_peak.max(current.total);
+
+        // Percentage drawdown from the running equity peak (zero at a new peak)
+        let drawdown_pct = match self.equity_peak == 0.0 {
+            true => 0.0,
+            false => 100.0 * (current.total - self.equity_peak) / self.equity_peak,
+        };
+
+        self.count += 1;
+        self.sum_squared_drawdown_pct += drawdown_pct * drawdown_pct;
+        self.ulcer_index = (self.sum_squared_drawdown_pct / self.count as f64).sqrt();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -576,4 +614,24 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn ulcer_index_update() {
+        let base_time = Utc::now();
+        let mut ulcer_index = UlcerIndex::init(100.0);
+
+        // Percentage drawdowns from running peak: 0, -10, -20, 0 (new peak 120), -25
+        let equity = [100.0, 90.0, 80.0, 120.0, 90.0];
+        for (index, total) in equity.into_iter().enumerate() {
+            ulcer_index.update(EquityPoint {
+                time: base_time.add(Duration::days(index as i64)),
+                total,
+            });
+        }
+
+        // sqrt((0 + 100 + 400 + 0 + 625) / 5) = sqrt(225) = 15
+        assert_eq!(ulcer_index.count, 5);
+        assert_eq!(ulcer_index.equity_peak, 120.0);
+        assert!((ulcer_index.ulcer_index - 15.0).abs() < 1e-10);
+    }
 }
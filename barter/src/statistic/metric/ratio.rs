@@ -1,39 +1,49 @@
-use crate::statistic::summary::pnl::PnLReturnSummary;
+use crate::{
+    portfolio::position::Position,
+    statistic::summary::{drawdown::DrawdownSummary, pnl::PnLReturnSummary},
+};
 use serde::{Deserialize, Serialize};
 
+/// Risk adjusted return ratio, calculated per trade & scaled to daily or annual figures using the
+/// trades per day. A ratio is `None` if it is undefined (eg/ a zero denominator), rather than
+/// NaN or infinite.
 pub trait Ratio {
     fn init(risk_free_return: f64) -> Self;
-    fn ratio(&self) -> f64;
-    fn trades_per_day(&self) -> f64;
-    fn daily(&self) -> f64 {
-        calculate_daily(self.ratio(), self.trades_per_day())
+    fn ratio(&self) -> Option<f64>;
+    fn trades_per_day(&self) -> Option<f64>;
+    fn daily(&self) -> Option<f64> {
+        Some(calculate_daily(self.ratio()?, self.trades_per_day()?))
     }
-    fn annual(&self, trading_days: u32) -> f64 {
-        calculate_annual(self.ratio(), self.trades_per_day(), trading_days)
+    fn annual(&self, trading_days: u32) -> Option<f64> {
+        Some(calculate_annual(
+            self.ratio()?,
+            self.trades_per_day()?,
+            trading_days,
+        ))
     }
 }
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SharpeRatio {
     pub risk_free_return: f64,
-    pub trades_per_day: f64,
-    pub sharpe_ratio_per_trade: f64,
+    pub trades_per_day: Option<f64>,
+    pub sharpe_ratio_per_trade: Option<f64>,
 }
 
 impl Ratio for SharpeRatio {
     fn init(risk_free_return: f64) -> Self {
         Self {
             risk_free_return,
-            sharpe_ratio_per_trade: 0.0,
-            trades_per_day: 0.0,
+            sharpe_ratio_per_trade: None,
+            trades_per_day: None,
         }
     }
 
-    fn ratio(&self) -> f64 {
+    fn ratio(&self) -> Option<f64> {
         self.sharpe_ratio_per_trade
     }
 
-    fn trades_per_day(&self) -> f64 {
+    fn trades_per_day(&self) -> Option<f64> {
         self.trades_per_day
     }
 }
@@ -44,12 +54,13 @@ impl SharpeRatio {
         self.trades_per_day = pnl_returns.trades_per_day;
 
         // Calculate Sharpe Ratio Per Trade
+        // Undefined if there is no variance in returns
         self.sharpe_ratio_per_trade = match pnl_returns.total.dispersion.std_dev == 0.0 {
-            true => 0.0,
-            false => {
+            true => None,
+            false => Some(
                 (pnl_returns.total.mean - self.risk_free_return)
-                    / pnl_returns.total.dispersion.std_dev
-            }
+                    / pnl_returns.total.dispersion.std_dev,
+            ),
         };
     }
 }
@@ -57,24 +68,24 @@ impl SharpeRatio {
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SortinoRatio {
     pub risk_free_return: f64,
-    pub trades_per_day: f64,
-    pub sortino_ratio_per_trade: f64,
+    pub trades_per_day: Option<f64>,
+    pub sortino_ratio_per_trade: Option<f64>,
 }
 
 impl Ratio for SortinoRatio {
     fn init(risk_free_return: f64) -> Self {
         Self {
             risk_free_return,
-            trades_per_day: 0.0,
-            sortino_ratio_per_trade: 0.0,
+            trades_per_day: None,
+            sortino_ratio_per_trade: None,
         }
     }
 
-    fn ratio(&self) -> f64 {
+    fn ratio(&self) -> Option<f64> {
         self.sortino_ratio_per_trade
     }
 
-    fn trades_per_day(&self) -> f64 {
+    fn trades_per_day(&self) -> Option<f64> {
         self.trades_per_day
     }
 }
@@ -85,12 +96,13 @@ impl SortinoRatio {
         self.trades_per_day = pnl_returns.trades_per_day;
 
         // Calculate Sortino Ratio Per Trade
+        // Undefined if there is no variance in losing returns
         self.sortino_ratio_per_trade = match pnl_returns.losses.dispersion.std_dev == 0.0 {
-            true => 0.0,
-            false => {
+            true => None,
+            false => Some(
                 (pnl_returns.total.mean - self.risk_free_return)
-                    / pnl_returns.losses.dispersion.std_dev
-            }
+                    / pnl_returns.losses.dispersion.std_dev,
+            ),
         };
     }
 }
@@ -98,24 +110,24 @@ impl SortinoRatio {
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct CalmarRatio {
     pub risk_free_return: f64,
-    pub trades_per_day: f64,
-    pub calmar_ratio_per_trade: f64,
+    pub trades_per_day: Option<f64>,
+    pub calmar_ratio_per_trade: Option<f64>,
 }
 
 impl Ratio for CalmarRatio {
     fn init(risk_free_return: f64) -> Self {
         Self {
             risk_free_return,
-            trades_per_day: 0.0,
-            calmar_ratio_per_trade: 0.0,
+            trades_per_day: None,
+            calmar_ratio_per_trade: None,
         }
     }
 
-    fn ratio(&self) -> f64 {
+    fn ratio(&self) -> Option<f64> {
         self.calmar_ratio_per_trade
     }
 
-    fn trades_per_day(&self) -> f64 {
+    fn trades_per_day(&self) -> Option<f64> {
         self.trades_per_day
     }
 }
@@ -126,9 +138,97 @@ impl CalmarRatio {
         self.trades_per_day = pnl_returns.trades_per_day;
 
         // Calculate Calmar Ratio Per Trade
+        // Undefined until a drawdown has occurred
         self.calmar_ratio_per_trade = match max_drawdown == 0.0 {
+            true => None,
+            false => Some((pnl_returns.total.mean - self.risk_free_return) / max_drawdown.abs()),
+        };
+    }
+}
+
+/// Recovery Factor is the cumulative net profit divided by the max drawdown, both in currency
+/// terms. The max drawdown includes any ongoing drawdown, and the recovery factor is undefined
+/// (`None`) until a drawdown has occurred.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct RecoveryFactor {
+    pub net_profit: f64,
+    pub max_drawdown: f64,
+    pub recovery_factor: Option<f64>,
+}
+
+impl RecoveryFactor {
+    pub fn init() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, position: &Position, drawdown: &DrawdownSummary) {
+        // Only update RecoveryFactor with closed Positions
+        if position.meta.exit_balance.is_none() {
+            return;
+        }
+
+        // Update cumulative net profit
+        self.net_profit += position.realised_profit_loss;
+
+        // Determine the largest of the max & ongoing drawdown, in currency terms
+        let max_drawdown = match drawdown.current_drawdown.drawdown.abs()
+            > drawdown.max_drawdown.drawdown.drawdown.abs()
+        {
+            true => drawdown.current_drawdown,
+            false => drawdown.max_drawdown.drawdown,
+        };
+        self.max_drawdown = match max_drawdown.drawdown == 0.0 {
             true => 0.0,
-            false => (pnl_returns.total.mean - self.risk_free_return) / max_drawdown.abs(),
+            false => max_drawdown.equity_range.calculate(),
+        };
+
+        // Calculate Recovery Factor
+        self.recovery_factor = match self.max_drawdown == 0.0 {
+            true => None,
+            false => Some(self.net_profit / self.max_drawdown),
+        };
+    }
+}
+
+/// Omega Ratio is the probability weighted gains over the probability weighted losses of the
+/// [`Position`] return distribution, relative to a threshold return. It is calculated
+/// incrementally as the sum of returns in excess of the threshold divided by the sum of return
+/// shortfalls below the threshold.
+///
+/// Unlike the [`Ratio`]s, the Omega Ratio uses every moment of the return distribution and is not
+/// scaled by the trades per day. It is 0.0 if every return is below the threshold, and undefined
+/// (`None`) until a return falls below the threshold.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct OmegaRatio {
+    pub threshold_return: f64,
+    /// Sum of returns in excess of the threshold return.
+    pub gains: f64,
+    /// Sum of return shortfalls below the threshold return.
+    pub losses: f64,
+    pub omega_ratio: Option<f64>,
+}
+
+impl OmegaRatio {
+    pub fn init(threshold_return: f64) -> Self {
+        Self {
+            threshold_return,
+            ..Self::default()
+        }
+    }
+
+    pub fn update(&mut self, position: &Position) {
+        // Accumulate the excess or shortfall of the Position PnL Return relative to the threshold
+        let excess_return = position.calculate_profit_loss_return() - self.threshold_return;
+        match excess_return.is_sign_negative() {
+            true => self.losses -= excess_return,
+            false => self.gains += excess_return,
+        }
+
+        // Calculate Omega Ratio
+        // Undefined until a return has fallen below the threshold
+        self.omega_ratio = match self.losses == 0.0 {
+            true => None,
+            false => Some(self.gains / self.losses),
         };
     }
 }
@@ -144,7 +244,21 @@ pub fn calculate_annual(ratio_per_trade: f64, trades_per_day: f64, trading_days:
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::statistic::summary::pnl::PnLReturnSummary;
+    use crate::{
+        portfolio::Balance,
+        statistic::summary::{pnl::PnLReturnSummary, PositionSummariser},
+        test_util::position,
+    };
+    use chrono::Utc;
+
+    fn assert_ratio_eq(actual: Option<f64>, expected: Option<f64>, index: usize) {
+        match (actual, expected) {
+            (Some(actual), Some(expected)) => {
+                assert!((actual - expected).abs() < 1e-8, "Test case: {:?}", index)
+            }
+            (actual, expected) => assert_eq!(actual, expected, "Test case: {:?}", index),
+        }
+    }
 
     fn sharpe_ratio_input(count: u64, mean: f64, std_dev: f64) -> PnLReturnSummary {
         let mut pnl_returns = PnLReturnSummary::new();
@@ -175,7 +289,7 @@ mod tests {
 
         struct TestCase {
             input_return: PnLReturnSummary,
-            expected_sharpe: f64,
+            expected_sharpe: Option<f64>,
         }
 
         // Returns  = [0.1, 0.2, 0.3, 0.4, -0.4]
@@ -185,34 +299,33 @@ mod tests {
             TestCase {
                 // Test case 0: 1st trade, 10% profit
                 input_return: sharpe_ratio_input(1, 0.1, 0.0),
-                expected_sharpe: 0.0,
+                expected_sharpe: None,
             },
             TestCase {
                 // Test case 1: 2nd trade, 20% profit
                 input_return: sharpe_ratio_input(2, 0.15, 0.05),
-                expected_sharpe: 3.0,
+                expected_sharpe: Some(3.0),
             },
             TestCase {
                 // Test case 2: 3rd trade, 30% profit
                 input_return: sharpe_ratio_input(3, 0.2, (1.0_f64 / 150.0_f64).sqrt()),
-                expected_sharpe: 6.0_f64.sqrt(),
+                expected_sharpe: Some(6.0_f64.sqrt()),
             },
             TestCase {
                 // Test case 3: 4th trade, 40% profit
                 input_return: sharpe_ratio_input(4, 0.25, (0.0125_f64).sqrt()),
-                expected_sharpe: 5.0_f64.sqrt(),
+                expected_sharpe: Some(5.0_f64.sqrt()),
             },
             TestCase {
                 // Test case 4: 5th trade, -40% profit
                 input_return: sharpe_ratio_input(5, 0.12, (0.388_f64 / 5.0_f64).sqrt()),
-                expected_sharpe: ((3.0 * 194_f64.sqrt()) / 97.0),
+                expected_sharpe: Some((3.0 * 194_f64.sqrt()) / 97.0),
             },
         ];
 
         for (index, test) in test_cases.into_iter().enumerate() {
             sharpe.update(&test.input_return);
-            let sharpe_diff = sharpe.sharpe_ratio_per_trade - test.expected_sharpe;
-            assert!(sharpe_diff < 1e-10, "Test case: {:?}", index);
+            assert_ratio_eq(sharpe.sharpe_ratio_per_trade, test.expected_sharpe, index);
         }
     }
 
@@ -222,7 +335,7 @@ mod tests {
 
         struct TestCase {
             input_return: PnLReturnSummary,
-            expected_sortino: f64,
+            expected_sortino: Option<f64>,
         }
 
         // Returns       = [0.1, 0.2, 0.3, 0.4, -0.4, -0.6, -0.7]
@@ -233,44 +346,47 @@ mod tests {
             TestCase {
                 // Test case 0: 1st trade, 10% profit
                 input_return: sortino_update_input(1, 0.1, 0.0),
-                expected_sortino: 0.0,
+                expected_sortino: None,
             },
             TestCase {
                 // Test case 1: 2nd trade, 20% profit
                 input_return: sortino_update_input(2, 0.15, 0.0),
-                expected_sortino: 0.0,
+                expected_sortino: None,
             },
             TestCase {
                 // Test case 2: 3rd trade, 30% profit
                 input_return: sortino_update_input(3, 0.2, 0.0),
-                expected_sortino: 0.0,
+                expected_sortino: None,
             },
             TestCase {
                 // Test case 3: 4th trade, 40% profit
                 input_return: sortino_update_input(4, 0.25, 0.0),
-                expected_sortino: 0.0,
+                expected_sortino: None,
             },
             TestCase {
                 // Test case 4: 5th trade, -40% profit
                 input_return: sortino_update_input(5, 0.12, 0.0),
-                expected_sortino: 0.0,
+                expected_sortino: None,
             },
             TestCase {
                 // Test case 5: 6th trade, -60% profit
                 input_return: sortino_update_input(6, 0.0, 0.1),
-                expected_sortino: 0.0,
+                expected_sortino: Some(0.0),
             },
             TestCase {
                 // Test case 5: 6th trade, -70% profit
                 input_return: sortino_update_input(7, -0.1, 0.12472191),
-                expected_sortino: -0.8017837443,
+                expected_sortino: Some(-0.8017837443),
             },
         ];
 
         for (index, test) in test_cases.into_iter().enumerate() {
             sortino.update(&test.input_return);
-            let sortino_diff = sortino.sortino_ratio_per_trade - test.expected_sortino;
-            assert!(sortino_diff < 1e-10, "Test case: {:?}", index);
+            assert_ratio_eq(
+                sortino.sortino_ratio_per_trade,
+                test.expected_sortino,
+                index,
+            );
         }
     }
 
@@ -281,7 +397,7 @@ mod tests {
         struct TestCase {
             input_return: PnLReturnSummary,
             input_max_dd: f64,
-            expected_calmar: f64,
+            expected_calmar: Option<f64>,
         }
 
         // Returns       = [0.5, -0.7, 0.8, 1.4, -0.8]
@@ -293,38 +409,37 @@ mod tests {
                 // Test case 0
                 input_return: calmar_ratio_returns_input(1, 0.5),
                 input_max_dd: 0.0,
-                expected_calmar: 0.0,
+                expected_calmar: None,
             },
             TestCase {
                 // Test case 1
-                input_return: calmar_ratio_returns_input(2, -0.5),
+                input_return: calmar_ratio_returns_input(2, -0.1),
                 input_max_dd: -0.70,
-                expected_calmar: (-0.1 / 0.7),
+                expected_calmar: Some(-0.1 / 0.7),
             },
             TestCase {
                 // Test case 2
                 input_return: calmar_ratio_returns_input(3, 0.2),
                 input_max_dd: -0.7,
-                expected_calmar: (0.2 / 0.7),
+                expected_calmar: Some(0.2 / 0.7),
             },
             TestCase {
                 // Test case 3
                 input_return: calmar_ratio_returns_input(4, 0.5),
                 input_max_dd: -0.7,
-                expected_calmar: (0.5 / 0.7),
+                expected_calmar: Some(0.5 / 0.7),
             },
             TestCase {
                 // Test case 4
                 input_return: calmar_ratio_returns_input(5, 0.24),
                 input_max_dd: -0.8,
-                expected_calmar: (0.24 / 0.8),
+                expected_calmar: Some(0.24 / 0.8),
             },
         ];
 
         for (index, test) in test_cases.into_iter().enumerate() {
             calmar.update(&test.input_return, test.input_max_dd);
-            let calmar_diff = calmar.calmar_ratio_per_trade - test.expected_calmar;
-            assert!(calmar_diff < 1e-10, "Test case: {:?}", index);
+            assert_ratio_eq(calmar.calmar_ratio_per_trade, test.expected_calmar, index);
         }
     }
 
@@ -451,4 +566,123 @@ mod tests {
             assert_eq!(actual, test.expected_annual)
         }
     }
+
+    #[test]
+    fn recovery_factor_update() {
+        let mut drawdown = DrawdownSummary::new(1000.0);
+        let mut recovery_factor = RecoveryFactor::init();
+
+        // Equity Curve   = [1000.0, 1100.0, 880.0, 1200.0]
+        // Max Drawdown   = 1100.0 - 880.0 = 220.0
+        // Net Profit     = [100.0, -120.0, 200.0]
+        // Recovery Factor = [0.0, -120.0 / 220.0, 200.0 / 220.0]
+        struct TestCase {
+            realised_profit_loss: f64,
+            expected_recovery_factor: Option<f64>,
+        }
+
+        let test_cases = [
+            TestCase {
+                // Test case 0: no drawdown yet
+                realised_profit_loss: 100.0,
+                expected_recovery_factor: None,
+            },
+            TestCase {
+                // Test case 1: ongoing drawdown
+                realised_profit_loss: -220.0,
+                expected_recovery_factor: Some(-120.0 / 220.0),
+            },
+            TestCase {
+                // Test case 2: drawdown recovered
+                realised_profit_loss: 320.0,
+                expected_recovery_factor: Some(200.0 / 220.0),
+            },
+        ];
+
+        let mut equity = 1000.0;
+        for (index, test) in test_cases.into_iter().enumerate() {
+            equity += test.realised_profit_loss;
+
+            let mut position = position();
+            position.realised_profit_loss = test.realised_profit_loss;
+            position.meta.exit_balance = Some(Balance {
+                time: Utc::now(),
+                total: equity,
+                available: equity,
+            });
+
+            drawdown.update(&position);
+            recovery_factor.update(&position, &drawdown);
+
+            assert_ratio_eq(
+                recovery_factor.recovery_factor,
+                test.expected_recovery_factor,
+                index,
+            );
+        }
+    }
+
+    fn omega_ratio_after(threshold_return: f64, returns: &[f64]) -> OmegaRatio {
+        let mut omega_ratio = OmegaRatio::init(threshold_return);
+        for pnl_return in returns {
+            let mut position = position();
+            position.enter_value_gross = 100.0;
+            position.realised_profit_loss = pnl_return * 100.0;
+            omega_ratio.update(&position);
+        }
+        omega_ratio
+    }
+
+    #[test]
+    fn omega_ratio_update() {
+        struct TestCase {
+            threshold_return: f64,
+            returns: Vec<f64>,
+            expected_omega_ratio: Option<f64>,
+        }
+
+        let test_cases = [
+            TestCase {
+                // Test case 0: no trades
+                threshold_return: 0.0,
+                returns: vec![],
+                expected_omega_ratio: None,
+            },
+            TestCase {
+                // Test case 1: all gains, so no losses relative to the threshold
+                threshold_return: 0.0,
+                returns: vec![0.1, 0.2, 0.05],
+                expected_omega_ratio: None,
+            },
+            TestCase {
+                // Test case 2: all losses
+                threshold_return: 0.0,
+                returns: vec![-0.1, -0.2],
+                expected_omega_ratio: Some(0.0),
+            },
+            TestCase {
+                // Test case 3: gains = 0.1 + 0.3, losses = 0.2
+                threshold_return: 0.0,
+                returns: vec![0.1, -0.2, 0.3],
+                expected_omega_ratio: Some(2.0),
+            },
+            TestCase {
+                // Test case 4: gains = 0.2, losses = 0.05 + 0.3 relative to a 0.1 threshold
+                threshold_return: 0.1,
+                returns: vec![0.05, -0.2, 0.3],
+                expected_omega_ratio: Some(0.2 / 0.35),
+            },
+            TestCase {
+                // Test case 5: all gains below a high threshold are losses relative to it
+                threshold_return: 0.5,
+                returns: vec![0.1, 0.2],
+                expected_omega_ratio: Some(0.0),
+            },
+        ];
+
+        for (index, test) in test_cases.into_iter().enumerate() {
+            let omega_ratio = omega_ratio_after(test.threshold_return, &test.returns);
+            assert_ratio_eq(omega_ratio.omega_ratio, test.expected_omega_ratio, index);
+        }
+    }
 }
@@ -0,0 +1,191 @@
+use crate::statistic::{
+    error::StatisticError,
+    summary::{MetricReporter, TableBuilder, TableFormat},
+};
+use prettytable::Row;
+use serde::{Deserialize, Serialize};
+
+/// Kind of per-trade return series the statistics accumulators are fed with.
+///
+/// A simple return (r = PnL / entry value) is the intuitive percentage return of a trade, whereas
+/// a log return (ln(1 + r)) is additive across trades. Log returns are preferred by some for
+/// volatility & Sharpe calculations, since the mean & variance of additive returns scale
+/// linearly with the number of trades, which is the assumption behind scaling per trade ratios
+/// to daily & annual figures by the square root of time. For simple returns this scaling is only
+/// an approximation.
+///
+/// A log return is always less than the equivalent simple return. The log return of a total loss
+/// (r = -1), or of a leveraged loss exceeding the entry value (r < -1), is undefined.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum ReturnKind {
+    #[default]
+    Simple,
+    Log,
+}
+
+impl ReturnKind {
+    /// Converts the provided simple return into a return of this [`ReturnKind`].
+    ///
+    /// Returns a [`StatisticError::UndefinedLogReturn`] if a [`ReturnKind::Log`] return is
+    /// requested for a simple return <= -1, rather than yielding -inf or NaN.
+    pub fn calculate(&self, simple_return: f64) -> Result<f64, StatisticError> {
+        match self {
+            ReturnKind::Simple => Ok(simple_return),
+            ReturnKind::Log if simple_return <= -1.0 => {
+                Err(StatisticError::UndefinedLogReturn(simple_return))
+            }
+            ReturnKind::Log => Ok(simple_return.ln_1p()),
+        }
+    }
+}
+
+/// Time-weighted return (TWR) of a Portfolio that is subject to external cash flows (deposits &
+/// withdrawals).
+///
+/// Raw equity change returns are distorted by cash flows, since a deposit increases equity without
+/// being performance. The TWR instead splits the session into sub-periods at each cash flow,
+/// and chains (compounds) the return of each sub-period:
+///
+/// TWR = (1 + r_1) * (1 + r_2) * ... * (1 + r_n) - 1
+///
+/// where r_i = end equity / start equity - 1 of sub-period i. This makes the TWR independent of
+/// the size & timing of cash flows, and the correct metric for evaluating a strategy on a funded
+/// account.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct TimeWeightedReturn {
+    /// Equity at the start of the current sub-period, including the cash flow that started it.
+    pub sub_period_start: f64,
+    /// Latest equity of the current sub-period.
+    pub current: f64,
+    /// Compounded growth factor (1 + r) of all completed sub-periods.
+    pub growth_factor: f64,
+    /// Number of completed sub-periods (ie/ cash flows applied).
+    pub sub_periods: u64,
+}
+
+impl Default for TimeWeightedReturn {
+    fn default() -> Self {
+        Self::init(0.0)
+    }
+}
+
+impl TableBuilder for TimeWeightedReturn {
+    fn titles(&self) -> Row {
+        row!["TWR"]
+    }
+
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
+        row![table_format.float(self.time_weighted_return())]
+    }
+}
+
+impl MetricReporter for TimeWeightedReturn {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![("TWR", self.time_weighted_return())]
+    }
+}
+
+impl TimeWeightedReturn {
+    /// Initialises a new [`TimeWeightedReturn`] using the starting equity.
+    pub fn init(starting_equity: f64) -> Self {
+        Self {
+            sub_period_start: starting_equity,
+            current: starting_equity,
+            growth_factor: 1.0,
+            sub_periods: 0,
+        }
+    }
+
+    /// Updates the latest equity of the current sub-period.
+    pub fn update(&mut self, equity: f64) {
+        self.current = equity;
+    }
+
+    /// Ends the current sub-period at the equity immediately before the cash flow, and starts a
+    /// new sub-period from that equity plus the cash flow amount (+ve deposit, -ve withdrawal).
+    pub fn apply_cash_flow(&mut self, equity_before: f64, amount: f64) {
+        self.growth_factor *= Self::sub_period_growth(self.sub_period_start, equity_before);
+        self.sub_periods += 1;
+        self.sub_period_start = equity_before + amount;
+        self.current = self.sub_period_start;
+    }
+
+    /// Calculates the time-weighted return, chaining the completed sub-periods with the
+    /// current one.
+    pub fn time_weighted_return(&self) -> f64 {
+        self.growth_factor * Self::sub_period_growth(self.sub_period_start, self.current) - 1.0
+    }
+
+    /// Growth factor (1 + r) of a sub-period. A sub-period starting with zero equity has an
+    /// undefined return, so it does not contribute (growth factor of 1.0).
+    fn sub_period_growth(start: f64, end: f64) -> f64 {
+        match start == 0.0 {
+            true => 1.0,
+            false => end / start,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn return_kind_calculate_rejects_undefined_log_returns() {
+        assert!((ReturnKind::Log.calculate(0.1).unwrap() - 1.1_f64.ln()).abs() < 1e-12);
+
+        for simple_return in [-1.0, -1.5] {
+            assert_eq!(
+                ReturnKind::Simple.calculate(simple_return).unwrap(),
+                simple_return
+            );
+            assert!(matches!(
+                ReturnKind::Log.calculate(simple_return),
+                Err(StatisticError::UndefinedLogReturn(actual)) if actual == simple_return
+            ));
+        }
+    }
+
+    #[test]
+    fn time_weighted_return_with_no_cash_flows() {
+        let mut twr = TimeWeightedReturn::init(100.0);
+        twr.update(110.0);
+        twr.update(121.0);
+
+        assert!((twr.time_weighted_return() - 0.21).abs() < 1e-10);
+    }
+
+    #[test]
+    fn time_weighted_return_is_independent_of_cash_flows() {
+        let mut twr = TimeWeightedReturn::init(100.0);
+
+        // Sub-period 1: +10%, then deposit 890.0
+        twr.update(110.0);
+        twr.apply_cash_flow(110.0, 890.0);
+
+        // Sub-period 2: -5%, then withdraw 450.0
+        twr.update(950.0);
+        twr.apply_cash_flow(950.0, -450.0);
+
+        // Sub-period 3: +20%
+        twr.update(600.0);
+
+        // Raw equity change would be 600.0 / 100.0 - 1 = 5.0
+        let expected = 1.1 * 0.95 * 1.2 - 1.0;
+        assert!((twr.time_weighted_return() - expected).abs() < 1e-10);
+        assert_eq!(twr.sub_periods, 2);
+    }
+
+    #[test]
+    fn time_weighted_return_ignores_sub_period_starting_with_zero_equity() {
+        let mut twr = TimeWeightedReturn::default();
+
+        // Fund an empty account, then +10%
+        twr.apply_cash_flow(0.0, 100.0);
+        twr.update(110.0);
+
+        assert!((twr.time_weighted_return() - 0.1).abs() < 1e-10);
+    }
+}
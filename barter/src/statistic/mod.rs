@@ -1,5 +1,6 @@
-use chrono::Duration;
-use serde::{Deserialize, Deserializer, Serializer};
+use barter_integration::model::MarketId;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub mod algorithm;
 pub mod dispersion;
@@ -7,6 +8,39 @@ pub mod error;
 pub mod metric;
 pub mod summary;
 
+/// Snapshot of a [`MarketId`]'s statistics after they change due to a
+/// [`Position`](crate::portfolio::position::Position) exit. Streamed as an
+/// [`Event::StatisticUpdate`](crate::event::Event) so consumers (eg/ a dashboard) do not have to
+/// poll the Portfolio.
+///
+/// The statistics type is generic to the Portfolio, so the snapshot is its JSON representation.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct StatisticUpdate {
+    /// Timestamp of the [`FillEvent`](crate::execution::FillEvent) that exited the Position.
+    pub time: DateTime<Utc>,
+    pub market_id: MarketId,
+    pub statistics: serde_json::Value,
+}
+
+/// Time window, inclusive of the start & exclusive of the end, during which per-market statistics
+/// are not accumulated (eg/ a warm up period, or a known-bad data window).
+///
+/// A [`Position`](crate::portfolio::position::Position) is excluded if the market time of it's
+/// exit [`FillEvent`](crate::execution::FillEvent) falls within the window. Excluded Positions
+/// still trade normally, and their realised P&L is still applied to the Portfolio balance.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct StatisticExclusion {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl StatisticExclusion {
+    /// Determines if the provided time falls within this [`StatisticExclusion`] window.
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
 /// Serialize a [`Duration`] into a `u64` representing the associated seconds.
 pub fn se_duration_as_secs<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -0,0 +1,171 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::summary::{
+        combine_with_format, win_loss::WinLossSummary, Initialiser, PositionSummariser, TableFormat,
+    },
+};
+use chrono::{Datelike, Timelike, Weekday};
+use prettytable::Table;
+use serde::{Deserialize, Serialize};
+
+/// Breakdown of closed trade performance by hour-of-day & day-of-week (UTC) of each
+/// [`Position`]'s entry time, with a [`WinLossSummary`] per time bucket. Useful for finding when
+/// a strategy works best. Open [`Position`]s are ignored.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct TimeBreakdown {
+    hours: [WinLossSummary; 24],
+    weekdays: [WinLossSummary; 7],
+}
+
+impl Initialiser for TimeBreakdown {
+    type Config = ();
+
+    fn init(_: Self::Config) -> Self {
+        Self::default()
+    }
+}
+
+impl PositionSummariser for TimeBreakdown {
+    fn update(&mut self, position: &Position) {
+        // Only update TimeBreakdown with closed Positions
+        if position.meta.exit_balance.is_none() {
+            return;
+        }
+
+        let enter_time = position.meta.enter_time;
+        self.hours[enter_time.hour() as usize].update_pnl(position.realised_profit_loss);
+        self.weekdays[enter_time.weekday().num_days_from_monday() as usize]
+            .update_pnl(position.realised_profit_loss);
+    }
+}
+
+impl TimeBreakdown {
+    /// [`WinLossSummary`]s indexed by hour-of-day (0-23).
+    pub fn hours(&self) -> &[WinLossSummary; 24] {
+        &self.hours
+    }
+
+    /// [`WinLossSummary`]s indexed by day-of-week, starting from Monday.
+    pub fn weekdays(&self) -> &[WinLossSummary; 7] {
+        &self.weekdays
+    }
+
+    /// [`WinLossSummary`] of the provided hour-of-day (0-23).
+    ///
+    /// Panics if the hour is not a valid hour-of-day.
+    pub fn hour(&self, hour: u32) -> WinLossSummary {
+        self.hours[hour as usize]
+    }
+
+    /// [`WinLossSummary`] of the provided day-of-week.
+    pub fn weekday(&self, weekday: Weekday) -> WinLossSummary {
+        self.weekdays[weekday.num_days_from_monday() as usize]
+    }
+
+    /// Compact [`Table`] with a row for every hour-of-day that has closed trades.
+    pub fn hour_table(&self, table_format: TableFormat) -> Table {
+        combine_with_format(
+            self.hours
+                .iter()
+                .enumerate()
+                .filter(|(_, bucket)| bucket.trades() > 0)
+                .map(|(hour, bucket)| (format!("{hour:02}:00"), *bucket)),
+            table_format,
+        )
+    }
+
+    /// Compact [`Table`] with a row for every day-of-week that has closed trades.
+    pub fn weekday_table(&self, table_format: TableFormat) -> Table {
+        let mut weekday = Weekday::Mon;
+        combine_with_format(
+            self.weekdays.iter().filter_map(|bucket| {
+                let row = (bucket.trades() > 0).then(|| (weekday.to_string(), *bucket));
+                weekday = weekday.succ();
+                row
+            }),
+            table_format,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::position;
+    use chrono::{TimeZone, Utc};
+
+    fn closed_position(day: u32, hour: u32, realised_profit_loss: f64) -> Position {
+        let mut position = crate::test_util::closed_position(realised_profit_loss);
+        position.meta.enter_time = Utc.with_ymd_and_hms(2024, 1, day, hour, 30, 0).unwrap();
+        position
+    }
+
+    #[test]
+    fn time_breakdown_buckets_closed_positions_by_entry_hour_and_weekday() {
+        let mut breakdown = TimeBreakdown::init(());
+
+        // 2024-01-01 is a Monday, 2024-01-02 is a Tuesday
+        breakdown.update(&closed_position(1, 9, 20.0));
+        breakdown.update(&closed_position(1, 9, -10.0));
+        breakdown.update(&closed_position(2, 14, 5.0));
+
+        // Open Positions are ignored
+        breakdown.update(&position());
+
+        assert_eq!(
+            breakdown.hour(9),
+            WinLossSummary {
+                wins: 1,
+                losses: 1,
+                gross_profit: 20.0,
+                gross_loss: 10.0,
+            }
+        );
+        assert_eq!(breakdown.hour(9).win_rate(), 0.5);
+        assert_eq!(breakdown.hour(14).trades(), 1);
+        assert_eq!(breakdown.hour(0), WinLossSummary::default());
+
+        assert_eq!(breakdown.weekday(Weekday::Mon).trades(), 2);
+        assert_eq!(breakdown.weekday(Weekday::Tue).win_rate(), 1.0);
+        assert_eq!(breakdown.weekday(Weekday::Sun).win_rate(), 0.0);
+        assert_eq!(
+            breakdown
+                .hours()
+                .iter()
+                .map(|bucket| bucket.trades())
+                .sum::<u64>(),
+            3
+        );
+    }
+
+    #[test]
+    fn time_breakdown_tables_only_include_buckets_with_trades() {
+        let mut breakdown = TimeBreakdown::init(());
+        breakdown.update(&closed_position(1, 9, 20.0));
+        breakdown.update(&closed_position(2, 14, 5.0));
+
+        let hour_table = breakdown.hour_table(TableFormat::default());
+        assert_eq!(hour_table.len(), 2);
+        assert_eq!(
+            hour_table
+                .get_row(1)
+                .unwrap()
+                .get_cell(0)
+                .unwrap()
+                .get_content(),
+            "14:00"
+        );
+
+        let weekday_table = breakdown.weekday_table(TableFormat::default());
+        assert_eq!(weekday_table.len(), 2);
+        assert_eq!(
+            weekday_table
+                .get_row(1)
+                .unwrap()
+                .get_cell(0)
+                .unwrap()
+                .get_content(),
+            "Tue"
+        );
+    }
+}
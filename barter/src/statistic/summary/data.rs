@@ -1,4 +1,8 @@
-use crate::statistic::{algorithm::welford_online, dispersion::Dispersion, summary::TableBuilder};
+use crate::statistic::{
+    algorithm::welford_online,
+    dispersion::Dispersion,
+    summary::{TableBuilder, TableFormat},
+};
 use prettytable::Row;
 use serde::{Deserialize, Serialize};
 
@@ -41,15 +45,15 @@ impl TableBuilder for DataSummary {
         ]
     }
 
-    fn row(&self) -> Row {
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
         row![
             self.count,
-            format!("{:.3}", self.sum),
-            format!("{:.3}", self.mean),
-            format!("{:.3}", self.dispersion.variance),
-            format!("{:.3}", self.dispersion.std_dev),
-            format!("{:.3}", self.dispersion.range.high),
-            format!("{:.3}", self.dispersion.range.low),
+            table_format.float(self.sum),
+            table_format.float(self.mean),
+            table_format.float(self.dispersion.variance),
+            table_format.float(self.dispersion.std_dev),
+            table_format.float(self.dispersion.range.high),
+            table_format.float(self.dispersion.range.low),
         ]
     }
 }
@@ -2,10 +2,10 @@ use crate::{
     portfolio::position::Position,
     statistic::{
         metric::{
-            drawdown::{AvgDrawdown, Drawdown, MaxDrawdown},
+            drawdown::{AvgDrawdown, Drawdown, MaxDrawdown, UlcerIndex},
             EquityPoint,
         },
-        summary::{PositionSummariser, TableBuilder},
+        summary::{MetricReporter, PositionSummariser, TableBuilder, TableFormat},
     },
 };
 use prettytable::Row;
@@ -16,6 +16,7 @@ pub struct DrawdownSummary {
     pub current_drawdown: Drawdown,
     pub avg_drawdown: AvgDrawdown,
     pub max_drawdown: MaxDrawdown,
+    pub ulcer_index: UlcerIndex,
 }
 
 impl PositionSummariser for DrawdownSummary {
@@ -27,6 +28,7 @@ impl PositionSummariser for DrawdownSummary {
         };
 
         // Updates
+        self.ulcer_index.update(equity_point);
         if let Some(ended_drawdown) = self.current_drawdown.update(equity_point) {
             self.avg_drawdown.update(&ended_drawdown);
             self.max_drawdown.update(&ended_drawdown);
@@ -41,15 +43,35 @@ impl TableBuilder for DrawdownSummary {
             "Max Drawdown Days",
             "Avg. Drawdown",
             "Avg. Drawdown Days",
+            "Ulcer Index",
         ]
     }
 
-    fn row(&self) -> Row {
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
         row![
-            format!("{:.3}", self.max_drawdown.drawdown.drawdown),
+            table_format.float(self.max_drawdown.drawdown.drawdown),
             self.max_drawdown.drawdown.duration.num_days().to_string(),
-            format!("{:.3}", self.avg_drawdown.mean_drawdown),
+            table_format.float(self.avg_drawdown.mean_drawdown),
             self.avg_drawdown.mean_duration.num_days().to_string(),
+            table_format.float(self.ulcer_index.ulcer_index),
+        ]
+    }
+}
+
+impl MetricReporter for DrawdownSummary {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("Max Drawdown", self.max_drawdown.drawdown.drawdown),
+            (
+                "Max Drawdown Days",
+                self.max_drawdown.drawdown.duration.num_days() as f64,
+            ),
+            ("Avg. Drawdown", self.avg_drawdown.mean_drawdown),
+            (
+                "Avg. Drawdown Days",
+                self.avg_drawdown.mean_duration.num_days() as f64,
+            ),
+            ("Ulcer Index", self.ulcer_index.ulcer_index),
         ]
     }
 }
@@ -60,6 +82,7 @@ impl DrawdownSummary {
             current_drawdown: Drawdown::init(starting_equity),
             avg_drawdown: AvgDrawdown::init(),
             max_drawdown: MaxDrawdown::init(),
+            ulcer_index: UlcerIndex::init(starting_equity),
         }
     }
 }
@@ -0,0 +1,177 @@
+use crate::statistic::summary::{MetricReporter, TableBuilder, TableFormat};
+use barter_integration::model::MarketId;
+use chrono::{DateTime, Utc};
+use prettytable::Row;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Time-in-market (exposure duration) measured in bars. Tracks the number of bars observed and
+/// the number of those bars during which at least one [`Position`](crate::portfolio::position::Position)
+/// was open.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct TimeInMarket {
+    pub bars: u64,
+    pub bars_in_market: u64,
+}
+
+impl TimeInMarket {
+    /// Update with the next bar, and whether a [`Position`](crate::portfolio::position::Position)
+    /// was open during it.
+    pub fn update(&mut self, in_market: bool) {
+        self.bars += 1;
+        if in_market {
+            self.bars_in_market += 1;
+        }
+    }
+
+    /// Fraction of bars during which a [`Position`](crate::portfolio::position::Position) was
+    /// open. Returns 0.0 if no bars have been observed.
+    pub fn fraction(&self) -> f64 {
+        match self.bars {
+            0 => 0.0,
+            bars => self.bars_in_market as f64 / bars as f64,
+        }
+    }
+}
+
+impl TableBuilder for TimeInMarket {
+    fn titles(&self) -> Row {
+        row!["Bars", "Bars In Market", "Time In Market"]
+    }
+
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
+        row![
+            self.bars.to_string(),
+            self.bars_in_market.to_string(),
+            table_format.float(self.fraction()),
+        ]
+    }
+}
+
+impl MetricReporter for TimeInMarket {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("Bars", self.bars as f64),
+            ("Bars In Market", self.bars_in_market as f64),
+            ("Time In Market", self.fraction()),
+        ]
+    }
+}
+
+/// [`TimeInMarket`] of a Portfolio per [`MarketId`], as well as in total.
+///
+/// Each market counts its own bars, and is in the market if it has an open
+/// [`Position`](crate::portfolio::position::Position). The total counts the union of the bars of
+/// every market (ie/ bars of different markets with the same time are one bar), and is in the
+/// market if any [`Position`](crate::portfolio::position::Position) is open during the bar. The
+/// total therefore never exceeds the session length.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct TimeInMarketSummary {
+    pub markets: HashMap<MarketId, TimeInMarket>,
+    pub total: TimeInMarket,
+    /// Time of the latest bar counted in the total, & whether it was counted in the market.
+    #[serde(default)]
+    total_bar: Option<(DateTime<Utc>, bool)>,
+}
+
+impl TimeInMarketSummary {
+    /// Update with the next bar of the provided market, which closed at the provided time.
+    pub fn update(
+        &mut self,
+        market_id: MarketId,
+        time: DateTime<Utc>,
+        market_in_market: bool,
+        any_in_market: bool,
+    ) {
+        self.markets
+            .entry(market_id)
+            .or_default()
+            .update(market_in_market);
+
+        // Bars of other markets at the time of the latest bar are the same bar of the total
+        match &mut self.total_bar {
+            Some((total_time, total_in_market)) if *total_time == time => {
+                if any_in_market && !*total_in_market {
+                    self.total.bars_in_market += 1;
+                    *total_in_market = true;
+                }
+            }
+            total_bar => {
+                self.total.update(any_in_market);
+                *total_bar = Some((time, any_in_market));
+            }
+        }
+    }
+
+    /// [`TimeInMarket`] of the provided [`MarketId`], or default if no bars have been observed.
+    pub fn market(&self, market_id: &MarketId) -> TimeInMarket {
+        self.markets.get(market_id).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_in_market_fraction_with_no_bars() {
+        assert_eq!(TimeInMarket::default().fraction(), 0.0);
+    }
+
+    #[test]
+    fn time_in_market_summary_update() {
+        let btc = MarketId("binance_btc_usdt_spot".to_owned());
+        let eth = MarketId("binance_eth_usdt_spot".to_owned());
+        let (first, second) = (Utc::now(), Utc::now() + chrono::Duration::minutes(1));
+
+        let mut summary = TimeInMarketSummary::default();
+
+        // btc Position open for one of its two bars, eth never in the market
+        summary.update(btc.clone(), first, true, true);
+        summary.update(eth.clone(), first, false, true);
+        summary.update(btc.clone(), second, false, false);
+        summary.update(eth.clone(), second, false, false);
+
+        assert_eq!(summary.market(&btc).fraction(), 0.5);
+        assert_eq!(summary.market(&eth).fraction(), 0.0);
+        assert_eq!(
+            summary.total,
+            TimeInMarket {
+                bars: 2,
+                bars_in_market: 1
+            }
+        );
+        assert_eq!(
+            summary.market(&MarketId("unknown".to_owned())),
+            TimeInMarket::default()
+        );
+    }
+
+    #[test]
+    fn time_in_market_summary_total_is_union_of_bars_of_every_market() {
+        let btc = MarketId("binance_btc_usdt_spot".to_owned());
+        let eth = MarketId("binance_eth_usdt_spot".to_owned());
+        let start = Utc::now();
+
+        let mut summary = TimeInMarketSummary::default();
+
+        // Both markets have an open Position on the same three bars, & neither on the fourth
+        for bar in 0..4 {
+            let time = start + chrono::Duration::minutes(bar);
+            let in_market = bar < 3;
+            summary.update(btc.clone(), time, in_market, in_market);
+            summary.update(eth.clone(), time, in_market, in_market);
+        }
+
+        assert_eq!(summary.market(&btc).bars_in_market, 3);
+        assert_eq!(summary.market(&eth).bars_in_market, 3);
+        assert_eq!(
+            summary.total,
+            TimeInMarket {
+                bars: 4,
+                bars_in_market: 3
+            }
+        );
+        assert_eq!(summary.total.fraction(), 0.75);
+    }
+}
@@ -1,10 +1,17 @@
+pub mod breakdown;
 pub mod data;
 pub mod drawdown;
+pub mod exposure;
 pub mod pnl;
+pub mod rolling;
+pub mod session;
 pub mod trading;
+pub mod win_loss;
 
 use crate::portfolio::position::Position;
 use prettytable::{Cell, Row, Table};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 pub trait Initialiser {
     type Config: Copy;
@@ -20,9 +27,99 @@ pub trait PositionSummariser: Copy {
     }
 }
 
+/// Reports the headline metrics of a summary by name, so that the [`SessionSummary`] of two
+/// trading sessions can be compared metric by metric (see [`SessionSummary::diff`]).
+///
+/// Metrics are named after their [`TableBuilder`] column, and undefined metrics are omitted.
+///
+/// [`SessionSummary`]: session::SessionSummary
+/// [`SessionSummary::diff`]: session::SessionSummary::diff
+pub trait MetricReporter {
+    /// Returns the (name, value) of every defined headline metric.
+    fn metrics(&self) -> Vec<(&'static str, f64)>;
+}
+
+impl<A, B> MetricReporter for (A, B)
+where
+    A: MetricReporter,
+    B: MetricReporter,
+{
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let mut metrics = self.0.metrics();
+        metrics.extend(self.1.metrics());
+        metrics
+    }
+}
+
+impl<T> MetricReporter for Option<T>
+where
+    T: MetricReporter,
+{
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        self.as_ref().map(T::metrics).unwrap_or_default()
+    }
+}
+
+/// Rendering settings for [`TableBuilder`] output. Only affects how values are printed, the
+/// underlying computed values are never rounded.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct TableFormat {
+    /// Number of decimal places floating point values are rendered with.
+    pub decimal_places: usize,
+    /// How undefined metrics are rendered.
+    #[serde(default)]
+    pub undefined: UndefinedFormat,
+}
+
+impl Default for TableFormat {
+    fn default() -> Self {
+        Self {
+            decimal_places: 3,
+            undefined: UndefinedFormat::default(),
+        }
+    }
+}
+
+impl TableFormat {
+    /// Render a floating point value with the configured number of decimal places. A NaN value
+    /// is undefined, and is rendered using the configured [`UndefinedFormat`].
+    pub fn float(&self, value: f64) -> String {
+        match value.is_nan() {
+            true => self.optional_float(None),
+            false => format!("{:.*}", self.decimal_places, value),
+        }
+    }
+
+    /// Render an optional floating point value, where `None` is an undefined metric (eg/ a ratio
+    /// with a zero denominator) rendered using the configured [`UndefinedFormat`].
+    pub fn optional_float(&self, value: Option<f64>) -> String {
+        match (value, self.undefined) {
+            (Some(value), _) => self.float(value),
+            (None, UndefinedFormat::NotAvailable) => "N/A".to_owned(),
+            (None, UndefinedFormat::Zero) => self.float(0.0),
+        }
+    }
+}
+
+/// Rendering of undefined metrics, such as the Sharpe Ratio of a trading session with no variance
+/// in returns.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum UndefinedFormat {
+    /// Render as "N/A".
+    #[default]
+    NotAvailable,
+    /// Render as zero.
+    Zero,
+}
+
 pub trait TableBuilder {
     fn titles(&self) -> Row;
-    fn row(&self) -> Row;
+    fn row(&self) -> Row {
+        self.row_with_format(TableFormat::default())
+    }
+    fn row_with_format(&self, table_format: TableFormat) -> Row;
     fn table(&self, id_cell: &str) -> Table {
         let mut table = Table::new();
 
@@ -55,7 +152,61 @@ pub trait TableBuilder {
     }
 }
 
+impl<A, B> TableBuilder for (A, B)
+where
+    A: TableBuilder,
+    B: TableBuilder,
+{
+    fn titles(&self) -> Row {
+        let mut titles = self.0.titles();
+        for title in &self.1.titles() {
+            titles.add_cell(title.clone())
+        }
+        titles
+    }
+
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
+        let mut cells = self.0.row_with_format(table_format);
+        for cell in &self.1.row_with_format(table_format) {
+            cells.add_cell(cell.clone())
+        }
+        cells
+    }
+}
+
+/// Optional [`TableBuilder`], where `None` renders a "-" cell under each of the default titles.
+/// Useful for combining rows that only some [`TableBuilder`]s can populate (eg/ Portfolio level
+/// metrics only available for the Total row).
+impl<T> TableBuilder for Option<T>
+where
+    T: TableBuilder + Default,
+{
+    fn titles(&self) -> Row {
+        match self {
+            Some(builder) => builder.titles(),
+            None => T::default().titles(),
+        }
+    }
+
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
+        match self {
+            Some(builder) => builder.row_with_format(table_format),
+            None => Row::new(vec![Cell::new("-"); self.titles().len()]),
+        }
+    }
+}
+
 pub fn combine<Iter, T>(builders: Iter) -> Table
+where
+    Iter: IntoIterator<Item = (String, T)>,
+    T: TableBuilder,
+{
+    combine_with_format(builders, TableFormat::default())
+}
+
+/// Combine the [`TableBuilder`]s into a single [`Table`], rendering the rows using the provided
+/// [`TableFormat`].
+pub fn combine_with_format<Iter, T>(builders: Iter, table_format: TableFormat) -> Table
 where
     Iter: IntoIterator<Item = (String, T)>,
     T: TableBuilder,
@@ -72,10 +223,59 @@ where
             }
 
             // Add rows for each builder
-            let mut row = builder.row();
+            let mut row = builder.row_with_format(table_format);
             row.insert_cell(0, Cell::new(&id));
             table.add_row(row);
 
             table
         })
 }
+
+/// Export the summaries as a JSON object keyed by id, with every value at full precision. Use
+/// alongside the rounded [`Table`] output when exact values are required (eg/ for further
+/// analysis).
+pub fn export_json<Iter, T>(summaries: Iter) -> Result<String, serde_json::Error>
+where
+    Iter: IntoIterator<Item = (String, T)>,
+    T: Serialize,
+{
+    serde_json::to_string_pretty(&summaries.into_iter().collect::<BTreeMap<_, _>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistic::summary::exposure::TimeInMarket;
+
+    fn time_in_market() -> TimeInMarket {
+        TimeInMarket {
+            bars: 3,
+            bars_in_market: 2,
+        }
+    }
+
+    #[test]
+    fn combine_with_format_renders_configured_decimal_places() {
+        let table = combine_with_format(
+            [("Total".to_owned(), time_in_market())],
+            TableFormat {
+                decimal_places: 5,
+                ..TableFormat::default()
+            },
+        );
+
+        assert_eq!(
+            table.get_row(0).unwrap().get_cell(3).unwrap().get_content(),
+            "0.66667"
+        );
+    }
+
+    #[test]
+    fn export_json_uses_full_precision() {
+        let json = export_json([("Total".to_owned(), (time_in_market(), 2.0 / 3.0))]).unwrap();
+
+        let actual: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual["Total"][0]["bars_in_market"], 2);
+        assert_eq!(actual["Total"][1], 2.0 / 3.0);
+    }
+}
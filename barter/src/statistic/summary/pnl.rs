@@ -1,14 +1,20 @@
 use crate::{
     portfolio::position::Position,
     statistic::{
-        de_duration_from_secs, se_duration_as_secs,
-        summary::{data::DataSummary, Initialiser, PositionSummariser, TableBuilder},
+        de_duration_from_secs,
+        metric::returns::ReturnKind,
+        se_duration_as_secs,
+        summary::{
+            data::DataSummary, Initialiser, MetricReporter, PositionSummariser, TableBuilder,
+            TableFormat,
+        },
     },
 };
 use barter_integration::model::Side;
 use chrono::{DateTime, Duration, Utc};
 use prettytable::Row;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct PnLReturnSummary {
@@ -18,9 +24,13 @@ pub struct PnLReturnSummary {
         serialize_with = "se_duration_as_secs"
     )]
     pub duration: Duration,
-    pub trades_per_day: f64,
+    /// Trades per day of the trading session, undefined (`None`) if the session has no duration.
+    pub trades_per_day: Option<f64>,
     pub total: DataSummary,
     pub losses: DataSummary,
+    /// [`ReturnKind`] of the Position PnL returns the total & losses are calculated from.
+    #[serde(default)]
+    pub return_kind: ReturnKind,
 }
 
 impl Initialiser for PnLReturnSummary {
@@ -36,9 +46,10 @@ impl Default for PnLReturnSummary {
         Self {
             time: Utc::now(),
             duration: Duration::zero(),
-            trades_per_day: 0.0,
+            trades_per_day: None,
             total: DataSummary::default(),
             losses: DataSummary::default(),
+            return_kind: ReturnKind::default(),
         }
     }
 }
@@ -54,8 +65,22 @@ impl PositionSummariser for PnLReturnSummary {
         self.update_trading_session_duration(position);
         self.update_trades_per_day();
 
-        // Calculate the Position PnL Return
-        let pnl_return = position.calculate_profit_loss_return();
+        // Calculate the Position PnL Return of the configured ReturnKind, skipping undefined
+        // returns (eg/ the log return of a leveraged Position losing more than it's entry value)
+        let pnl_return = match self
+            .return_kind
+            .calculate(position.calculate_profit_loss_return())
+        {
+            Ok(pnl_return) => pnl_return,
+            Err(error) => {
+                warn!(
+                    %error,
+                    position_id = %position.position_id,
+                    "PnLReturnSummary skipping Position with an undefined PnL return"
+                );
+                return;
+            }
+        };
 
         // Update Total PnL Returns
         self.total.update(pnl_return);
@@ -83,23 +108,43 @@ impl TableBuilder for PnLReturnSummary {
         ]
     }
 
-    fn row(&self) -> Row {
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
         let wins = self.total.count - self.losses.count;
         row![
             self.total.count.to_string(),
             wins,
             self.losses.count,
             self.duration.num_days().to_string(),
-            format!("{:.3}", self.trades_per_day),
-            format!("{:.3}", self.total.mean),
-            format!("{:.3}", self.total.dispersion.std_dev),
-            format!("{:.3}", self.losses.mean),
-            format!("{:.3}", self.total.dispersion.range.high),
-            format!("{:.3}", self.total.dispersion.range.low),
+            table_format.optional_float(self.trades_per_day),
+            table_format.float(self.total.mean),
+            table_format.float(self.total.dispersion.std_dev),
+            table_format.float(self.losses.mean),
+            table_format.float(self.total.dispersion.range.high),
+            table_format.float(self.total.dispersion.range.low),
         ]
     }
 }
 
+impl MetricReporter for PnLReturnSummary {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let mut metrics = vec![
+            ("Trades", self.total.count as f64),
+            ("Wins", (self.total.count - self.losses.count) as f64),
+            ("Losses", self.losses.count as f64),
+            ("Trading Days", self.duration.num_days() as f64),
+        ];
+        metrics.extend(self.trades_per_day.map(|value| ("Trades Per Day", value)));
+        metrics.extend([
+            ("Mean Return", self.total.mean),
+            ("Std. Dev. Return", self.total.dispersion.std_dev),
+            ("Loss Mean Return", self.losses.mean),
+            ("Biggest Win", self.total.dispersion.range.high),
+            ("Biggest Loss", self.total.dispersion.range.low),
+        ]);
+        metrics
+    }
+}
+
 impl PnLReturnSummary {
     const SECONDS_IN_DAY: f64 = 86400.0;
 
@@ -107,9 +152,19 @@ impl PnLReturnSummary {
         Self {
             time: Utc::now(),
             duration: Duration::zero(),
-            trades_per_day: 0.0,
+            trades_per_day: None,
             total: Default::default(),
             losses: Default::default(),
+            return_kind: ReturnKind::default(),
+        }
+    }
+
+    /// Constructs a new [`PnLReturnSummary`] calculated from Position PnL returns of the provided
+    /// [`ReturnKind`].
+    pub fn with_return_kind(return_kind: ReturnKind) -> Self {
+        Self {
+            return_kind,
+            ..Self::new()
         }
     }
 
@@ -124,8 +179,13 @@ impl PnLReturnSummary {
     }
 
     pub fn update_trades_per_day(&mut self) {
-        self.trades_per_day = self.total.count as f64
-            / (self.duration.num_seconds() as f64 / PnLReturnSummary::SECONDS_IN_DAY)
+        self.trades_per_day = match self.duration.num_seconds() > 0 {
+            true => Some(
+                self.total.count as f64
+                    / (self.duration.num_seconds() as f64 / PnLReturnSummary::SECONDS_IN_DAY),
+            ),
+            false => None,
+        }
     }
 }
 
@@ -133,31 +193,31 @@ impl PnLReturnSummary {
 pub struct ProfitLossSummary {
     pub long_contracts: f64,
     pub long_pnl: f64,
-    pub long_pnl_per_contract: f64,
+    pub long_pnl_per_contract: Option<f64>,
     pub short_contracts: f64,
     pub short_pnl: f64,
-    pub short_pnl_per_contract: f64,
+    pub short_pnl_per_contract: Option<f64>,
     pub total_contracts: f64,
     pub total_pnl: f64,
-    pub total_pnl_per_contract: f64,
+    pub total_pnl_per_contract: Option<f64>,
 }
 
 impl PositionSummariser for ProfitLossSummary {
     fn update(&mut self, position: &Position) {
         self.total_contracts += position.quantity.abs();
         self.total_pnl += position.realised_profit_loss;
-        self.total_pnl_per_contract = self.total_pnl / self.total_contracts;
+        self.total_pnl_per_contract = per_contract(self.total_pnl, self.total_contracts);
 
         match position.side {
             Side::Buy => {
                 self.long_contracts += position.quantity.abs();
                 self.long_pnl += position.realised_profit_loss;
-                self.long_pnl_per_contract = self.long_pnl / self.long_contracts;
+                self.long_pnl_per_contract = per_contract(self.long_pnl, self.long_contracts);
             }
             Side::Sell => {
                 self.short_contracts += position.quantity.abs();
                 self.short_pnl += position.realised_profit_loss;
-                self.short_pnl_per_contract = self.short_pnl / self.short_contracts;
+                self.short_pnl_per_contract = per_contract(self.short_pnl, self.short_contracts);
             }
         }
     }
@@ -178,17 +238,17 @@ impl TableBuilder for ProfitLossSummary {
         ]
     }
 
-    fn row(&self) -> Row {
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
         row![
-            format!("{:.3}", self.long_contracts),
-            format!("{:.3}", self.long_pnl),
-            format!("{:.3}", self.long_pnl_per_contract),
-            format!("{:.3}", self.short_contracts),
-            format!("{:.3}", self.short_pnl),
-            format!("{:.3}", self.short_pnl_per_contract),
-            format!("{:.3}", self.total_contracts),
-            format!("{:.3}", self.total_pnl),
-            format!("{:.3}", self.total_pnl_per_contract),
+            table_format.float(self.long_contracts),
+            table_format.float(self.long_pnl),
+            table_format.optional_float(self.long_pnl_per_contract),
+            table_format.float(self.short_contracts),
+            table_format.float(self.short_pnl),
+            table_format.optional_float(self.short_pnl_per_contract),
+            table_format.float(self.total_contracts),
+            table_format.float(self.total_pnl),
+            table_format.optional_float(self.total_pnl_per_contract),
         ]
     }
 }
@@ -199,6 +259,14 @@ impl ProfitLossSummary {
     }
 }
 
+/// PnL per contract, undefined (`None`) if no contracts have been traded.
+fn per_contract(pnl: f64, contracts: f64) -> Option<f64> {
+    match contracts == 0.0 {
+        true => None,
+        false => Some(pnl / contracts),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,6 +278,21 @@ mod tests {
         // Todo:
     }
 
+    #[test]
+    fn update_pnl_return_summary_skips_undefined_log_return() {
+        let mut summary = PnLReturnSummary::with_return_kind(ReturnKind::Log);
+
+        // Leveraged Position losing more than it's entry value
+        let mut input_position = position();
+        input_position.enter_value_gross = 100.0;
+        input_position.realised_profit_loss = -150.0;
+        summary.update(&input_position);
+
+        assert_eq!(summary.total.count, 0);
+        assert!(summary.total.mean.is_finite());
+        assert!(summary.losses.mean.is_finite());
+    }
+
     #[test]
     fn update_trading_session_duration_with_non_exited_position() {
         let base_time = Utc::now();
@@ -0,0 +1,175 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::summary::{
+        data::DataSummary, win_loss::WinLossSummary, Initialiser, MetricReporter,
+        PositionSummariser, TableBuilder, TableFormat,
+    },
+};
+use prettytable::Row;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Statistics of the trailing `TRADES` closed trades, used to detect strategy degradation by
+/// comparing recent performance with the whole session.
+///
+/// The realised PnL of each closed [`Position`] is held in a fixed size ring buffer, and the
+/// window statistics are recalculated from it after each trade. Open [`Position`]s are ignored.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct RollingTradeSummary<const TRADES: usize> {
+    #[serde(serialize_with = "se_ring", deserialize_with = "de_ring")]
+    ring: [f64; TRADES],
+    next: usize,
+    /// Realised PnL of the closed trades in the window.
+    pub pnl: DataSummary,
+    /// Win / loss record of the closed trades in the window.
+    pub win_loss: WinLossSummary,
+}
+
+impl<const TRADES: usize> Initialiser for RollingTradeSummary<TRADES> {
+    type Config = ();
+
+    fn init(_: Self::Config) -> Self {
+        Self::default()
+    }
+}
+
+impl<const TRADES: usize> Default for RollingTradeSummary<TRADES> {
+    fn default() -> Self {
+        Self {
+            ring: [0.0; TRADES],
+            next: 0,
+            pnl: DataSummary::default(),
+            win_loss: WinLossSummary::default(),
+        }
+    }
+}
+
+impl<const TRADES: usize> PositionSummariser for RollingTradeSummary<TRADES> {
+    fn update(&mut self, position: &Position) {
+        // Only update RollingTradeSummary with closed Positions
+        if position.meta.exit_balance.is_none() || TRADES == 0 {
+            return;
+        }
+
+        // Overwrite the oldest trade in the ring buffer once the window is full
+        self.ring[self.next] = position.realised_profit_loss;
+        self.next = (self.next + 1) % TRADES;
+        let trades = (self.pnl.count as usize + 1).min(TRADES);
+
+        // Recalculate the window statistics from the trades in the ring buffer
+        self.pnl = DataSummary::default();
+        self.win_loss = WinLossSummary::default();
+        for &pnl in self.ring.iter().take(trades) {
+            self.pnl.update(pnl);
+            self.win_loss.update_pnl(pnl);
+        }
+    }
+}
+
+impl<const TRADES: usize> TableBuilder for RollingTradeSummary<TRADES> {
+    fn titles(&self) -> Row {
+        row![
+            "Rolling Trades",
+            "Rolling Win Rate",
+            "Rolling Profit Factor",
+            "Rolling Mean PnL",
+        ]
+    }
+
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
+        row![
+            self.pnl.count.to_string(),
+            table_format.float(self.win_loss.win_rate()),
+            table_format.float(self.win_loss.profit_factor()),
+            table_format.float(self.pnl.mean),
+        ]
+    }
+}
+
+impl<const TRADES: usize> MetricReporter for RollingTradeSummary<TRADES> {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("Rolling Trades", self.pnl.count as f64),
+            ("Rolling Win Rate", self.win_loss.win_rate()),
+            ("Rolling Profit Factor", self.win_loss.profit_factor()),
+            ("Rolling Mean PnL", self.pnl.mean),
+        ]
+    }
+}
+
+impl<const TRADES: usize> RollingTradeSummary<TRADES> {
+    /// Number of trailing closed trades the window holds.
+    pub const fn window() -> usize {
+        TRADES
+    }
+}
+
+fn se_ring<S, const TRADES: usize>(ring: &[f64; TRADES], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(ring.iter())
+}
+
+fn de_ring<'de, D, const TRADES: usize>(deserializer: D) -> Result<[f64; TRADES], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let ring: Vec<f64> = Deserialize::deserialize(deserializer)?;
+    let len = ring.len();
+    ring.try_into()
+        .map_err(|_| Error::invalid_length(len, &"a ring buffer with one element per trade"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{closed_position, position};
+
+    #[test]
+    fn rolling_trade_summary_only_includes_trailing_trades() {
+        let mut summary = RollingTradeSummary::<3>::init(());
+
+        // Open Positions are ignored
+        summary.update(&position());
+        assert_eq!(summary.pnl.count, 0);
+
+        // Window not yet full: 2 wins & 1 loss
+        summary.update(&closed_position(30.0));
+        summary.update(&closed_position(10.0));
+        summary.update(&closed_position(-20.0));
+        assert_eq!(summary.pnl.count, 3);
+        assert!((summary.win_loss.win_rate() - 2.0 / 3.0).abs() < 1e-10);
+        assert_eq!(summary.win_loss.profit_factor(), 2.0);
+
+        // Oldest winning trades roll out of the window: 1 win & 2 losses
+        summary.update(&closed_position(-20.0));
+        summary.update(&closed_position(10.0));
+        assert_eq!(summary.pnl.count, 3);
+        assert!((summary.win_loss.win_rate() - 1.0 / 3.0).abs() < 1e-10);
+        assert_eq!(summary.win_loss.profit_factor(), 0.25);
+        assert!((summary.pnl.mean - (-10.0)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn rolling_trade_summary_profit_factor_without_losses() {
+        let mut summary = RollingTradeSummary::<2>::init(());
+        assert_eq!(summary.win_loss.profit_factor(), 0.0);
+
+        summary.update(&closed_position(10.0));
+        assert_eq!(summary.win_loss.win_rate(), 1.0);
+        assert_eq!(summary.win_loss.profit_factor(), f64::INFINITY);
+    }
+
+    #[test]
+    fn rolling_trade_summary_serde_round_trip() {
+        let mut summary = RollingTradeSummary::<2>::init(());
+        summary.update(&closed_position(10.0));
+        summary.update(&closed_position(-5.0));
+
+        let json = serde_json::to_string(&summary).unwrap();
+        let actual: RollingTradeSummary<2> = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, summary);
+
+        assert!(serde_json::from_str::<RollingTradeSummary<3>>(&json).is_err());
+    }
+}
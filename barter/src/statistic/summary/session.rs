@@ -0,0 +1,234 @@
+use crate::statistic::{
+    metric::returns::TimeWeightedReturn,
+    summary::{
+        combine_with_format, export_json, exposure::TimeInMarket, MetricReporter, TableBuilder,
+        TableFormat,
+    },
+};
+use prettytable::{Row, Table};
+use serde::{Deserialize, Serialize};
+
+/// Trading session summary of a [`Market`](barter_integration::model::Market) or the Total,
+/// combining the statistics with the time-in-market & (Total only) time-weighted return.
+pub type MarketSummary<Statistic> = (Statistic, (TimeInMarket, Option<TimeWeightedReturn>));
+
+/// Trading session summary generated by an [`Engine`](crate::engine::Engine), containing the
+/// [`MarketSummary`] of every market traded followed by the "Total".
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SessionSummary<Statistic> {
+    pub summaries: Vec<(String, MarketSummary<Statistic>)>,
+}
+
+impl<Statistic> SessionSummary<Statistic>
+where
+    Statistic: TableBuilder + Serialize + Clone,
+{
+    /// [`MarketSummary`] with the provided id (eg/ "Total").
+    pub fn get(&self, id: &str) -> Option<&MarketSummary<Statistic>> {
+        self.summaries
+            .iter()
+            .find_map(|(summary_id, summary)| (summary_id == id).then_some(summary))
+    }
+
+    /// Render the [`SessionSummary`] as a [`Table`] with a row per [`MarketSummary`].
+    pub fn table(&self, table_format: TableFormat) -> Table {
+        combine_with_format(self.summaries.iter().cloned(), table_format)
+    }
+
+    /// Export the full-precision [`SessionSummary`] as a JSON object keyed by id.
+    pub fn export_json(&self) -> Result<String, serde_json::Error> {
+        export_json(
+            self.summaries
+                .iter()
+                .map(|(id, summary)| (id.clone(), summary)),
+        )
+    }
+}
+
+impl<Statistic> SessionSummary<Statistic>
+where
+    Statistic: MetricReporter,
+{
+    /// Compare this (before) [`SessionSummary`] with another (after), returning a [`MetricDiff`]
+    /// for every headline metric (see [`MetricReporter`]) of every [`MarketSummary`] id present
+    /// in both. Metrics that are undefined or non-finite in either [`SessionSummary`] are not
+    /// compared.
+    pub fn diff(&self, other: &Self) -> SessionSummaryDiff {
+        let mut metrics = Vec::new();
+
+        for (id, before) in &self.summaries {
+            let after = match other
+                .summaries
+                .iter()
+                .find_map(|(after_id, after)| (after_id == id).then_some(after))
+            {
+                Some(after) => after.metrics(),
+                None => continue,
+            };
+
+            metrics.extend(before.metrics().into_iter().filter_map(|(metric, before)| {
+                let after = after.iter().find_map(|(after_metric, after)| {
+                    (*after_metric == metric).then_some(*after)
+                })?;
+
+                (before.is_finite() && after.is_finite())
+                    .then(|| MetricDiff::new(id.clone(), metric.to_owned(), before, after))
+            }));
+        }
+
+        SessionSummaryDiff { metrics }
+    }
+}
+
+/// Change in a metric between two [`SessionSummary`]s.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct MetricDiff {
+    /// Id of the [`MarketSummary`] the metric belongs to (eg/ "Total").
+    pub summary: String,
+    /// Name of the metric within the [`MarketSummary`] (eg/ "Mean Return").
+    pub metric: String,
+    pub before: f64,
+    pub after: f64,
+    pub delta: f64,
+    /// Percentage change relative to the magnitude of the before value, or `None` if the before
+    /// value is zero.
+    pub pct_change: Option<f64>,
+}
+
+impl MetricDiff {
+    /// Constructs a new [`MetricDiff`] from the before & after values of a metric.
+    pub fn new(summary: String, metric: String, before: f64, after: f64) -> Self {
+        let delta = after - before;
+        Self {
+            summary,
+            metric,
+            before,
+            after,
+            delta,
+            pct_change: (before != 0.0).then(|| delta / before.abs() * 100.0),
+        }
+    }
+}
+
+impl TableBuilder for MetricDiff {
+    fn titles(&self) -> Row {
+        row!["Metric", "Before", "After", "Delta", "% Change"]
+    }
+
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
+        row![
+            self.metric,
+            table_format.float(self.before),
+            table_format.float(self.after),
+            table_format.float(self.delta),
+            self.pct_change
+                .map_or_else(|| "-".to_owned(), |pct| table_format.float(pct)),
+        ]
+    }
+}
+
+/// Comparison of two [`SessionSummary`]s, detailing the [`MetricDiff`] of every shared metric.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct SessionSummaryDiff {
+    pub metrics: Vec<MetricDiff>,
+}
+
+impl SessionSummaryDiff {
+    /// [`MetricDiff`] of the provided [`MarketSummary`] id & metric path.
+    pub fn get(&self, summary: &str, metric: &str) -> Option<&MetricDiff> {
+        self.metrics
+            .iter()
+            .find(|diff| diff.summary == summary && diff.metric == metric)
+    }
+
+    /// Render the [`SessionSummaryDiff`] as a comparison [`Table`] with a row per [`MetricDiff`].
+    pub fn table(&self, table_format: TableFormat) -> Table {
+        combine_with_format(
+            self.metrics
+                .iter()
+                .map(|diff| (diff.summary.clone(), diff.clone())),
+            table_format,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistic::summary::win_loss::WinLossSummary;
+
+    fn session_summary(
+        bars_in_market: u64,
+        time_weighted_return: Option<f64>,
+    ) -> SessionSummary<WinLossSummary> {
+        let statistic = WinLossSummary {
+            wins: 3,
+            losses: 1,
+            gross_profit: 30.0,
+            gross_loss: 10.0,
+        };
+        let time_in_market = TimeInMarket {
+            bars: 10,
+            bars_in_market,
+        };
+        let time_weighted_return = time_weighted_return.map(|current| TimeWeightedReturn {
+            sub_period_start: 100.0,
+            current,
+            growth_factor: 1.0,
+            sub_periods: 0,
+        });
+
+        SessionSummary {
+            summaries: vec![
+                ("btc".to_owned(), (statistic, (time_in_market, None))),
+                (
+                    "Total".to_owned(),
+                    (statistic, (time_in_market, time_weighted_return)),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn session_summary_diff_returns_delta_and_pct_change_of_each_metric() {
+        let before = session_summary(4, Some(110.0));
+        let after = session_summary(5, Some(121.0));
+
+        let diff = before.diff(&after);
+
+        assert_eq!(
+            diff.get("btc", "Bars In Market"),
+            Some(&MetricDiff {
+                summary: "btc".to_owned(),
+                metric: "Bars In Market".to_owned(),
+                before: 4.0,
+                after: 5.0,
+                delta: 1.0,
+                pct_change: Some(25.0),
+            })
+        );
+        assert!((diff.get("Total", "TWR").unwrap().pct_change.unwrap() - 110.0).abs() < 1e-10);
+        assert_eq!(diff.get("Total", "Win Rate").unwrap().delta, 0.0);
+
+        // Metrics only present in one SessionSummary are not compared
+        assert!(diff.get("btc", "TWR").is_none());
+
+        // Internal state that is not a reported metric is not compared
+        assert!(diff.get("Total", "sub_period_start").is_none());
+        assert!(diff.get("Total", "gross_profit").is_none());
+
+        assert_eq!(diff.table(TableFormat::default()).len(), diff.metrics.len());
+    }
+
+    #[test]
+    fn session_summary_diff_skips_summaries_not_present_in_both() {
+        let before = session_summary(4, None);
+        let mut after = session_summary(5, None);
+        after.summaries.retain(|(id, _)| id == "Total");
+
+        let diff = before.diff(&after);
+
+        assert!(diff.metrics.iter().all(|diff| diff.summary == "Total"));
+        assert!(!diff.metrics.is_empty());
+    }
+}
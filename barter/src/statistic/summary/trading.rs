@@ -1,30 +1,50 @@
 use crate::{
     portfolio::position::Position,
     statistic::{
-        metric::ratio::{CalmarRatio, Ratio, SharpeRatio, SortinoRatio},
+        metric::{
+            ratio::{CalmarRatio, OmegaRatio, Ratio, RecoveryFactor, SharpeRatio, SortinoRatio},
+            returns::ReturnKind,
+        },
         summary::{
-            drawdown::DrawdownSummary, pnl::PnLReturnSummary, Initialiser, PositionSummariser,
-            TableBuilder,
+            drawdown::DrawdownSummary, pnl::PnLReturnSummary, rolling::RollingTradeSummary,
+            Initialiser, MetricReporter, PositionSummariser, TableBuilder, TableFormat,
         },
     },
 };
 use chrono::{DateTime, Duration, Utc};
 use prettytable::{Cell, Row};
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 /// Configuration for initialising a [`TradingSummary`] via the init() constructor method.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Config {
     pub starting_equity: f64,
     pub trading_days_per_year: usize,
+    /// Simple risk free return per trade, converted to the configured [`ReturnKind`].
     pub risk_free_return: f64,
+    /// Threshold return the [`OmegaRatio`] gains & losses are measured relative to.
+    #[serde(default)]
+    pub omega_threshold_return: f64,
+    /// [`ReturnKind`] of the per-trade return series the Sharpe, Sortino & Calmar ratios are
+    /// calculated from. Defaults to [`ReturnKind::Simple`].
+    ///
+    /// The ratios are scaled to daily & annual figures by the square root of time for either
+    /// [`ReturnKind`], since both the mean & standard deviation are of the same return series.
+    /// The [`OmegaRatio`] is always calculated from simple returns.
+    #[serde(default)]
+    pub return_kind: ReturnKind,
 }
 
+/// Number of trailing closed trades the [`TradingSummary`] rolling window statistics cover.
+pub const ROLLING_TRADES: usize = 20;
+
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct TradingSummary {
     pub pnl_returns: PnLReturnSummary,
     pub drawdown: DrawdownSummary,
     pub tear_sheet: TearSheet,
+    pub rolling: RollingTradeSummary<ROLLING_TRADES>,
 }
 
 impl Initialiser for TradingSummary {
@@ -32,9 +52,19 @@ impl Initialiser for TradingSummary {
 
     fn init(config: Self::Config) -> Self {
         Self {
-            pnl_returns: PnLReturnSummary::new(),
+            pnl_returns: PnLReturnSummary::with_return_kind(config.return_kind),
             drawdown: DrawdownSummary::new(config.starting_equity),
-            tear_sheet: TearSheet::new(config.risk_free_return),
+            tear_sheet: TearSheet::new(
+                config
+                    .return_kind
+                    .calculate(config.risk_free_return)
+                    .unwrap_or_else(|error| {
+                        warn!(%error, "TradingSummary using a zero risk free return");
+                        0.0
+                    }),
+                config.omega_threshold_return,
+            ),
+            rolling: RollingTradeSummary::init(()),
         }
     }
 }
@@ -43,7 +73,9 @@ impl PositionSummariser for TradingSummary {
     fn update(&mut self, position: &Position) {
         self.pnl_returns.update(position);
         self.drawdown.update(position);
-        self.tear_sheet.update(&self.pnl_returns, &self.drawdown);
+        self.tear_sheet
+            .update(position, &self.pnl_returns, &self.drawdown);
+        self.rolling.update(position);
     }
 }
 
@@ -63,21 +95,29 @@ impl TableBuilder for TradingSummary {
             titles.push(title.clone())
         }
 
+        for title in &self.rolling.titles() {
+            titles.push(title.clone())
+        }
+
         Row::new(titles)
     }
 
-    fn row(&self) -> Row {
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
         let mut cells = Vec::<Cell>::new();
 
-        for cell in &self.pnl_returns.row() {
+        for cell in &self.pnl_returns.row_with_format(table_format) {
             cells.push(cell.clone())
         }
 
-        for cell in &self.tear_sheet.row() {
+        for cell in &self.tear_sheet.row_with_format(table_format) {
             cells.push(cell.clone())
         }
 
-        for cell in &self.drawdown.row() {
+        for cell in &self.drawdown.row_with_format(table_format) {
+            cells.push(cell.clone())
+        }
+
+        for cell in &self.rolling.row_with_format(table_format) {
             cells.push(cell.clone())
         }
 
@@ -85,41 +125,85 @@ impl TableBuilder for TradingSummary {
     }
 }
 
+impl MetricReporter for TradingSummary {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let mut metrics = self.pnl_returns.metrics();
+        metrics.extend(self.tear_sheet.metrics());
+        metrics.extend(self.drawdown.metrics());
+        metrics.extend(self.rolling.metrics());
+        metrics
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct TearSheet {
     pub sharpe_ratio: SharpeRatio,
     pub sortino_ratio: SortinoRatio,
     pub calmar_ratio: CalmarRatio,
+    pub recovery_factor: RecoveryFactor,
+    pub omega_ratio: OmegaRatio,
 }
 
 impl TearSheet {
-    pub fn new(risk_free_return: f64) -> Self {
+    pub fn new(risk_free_return: f64, omega_threshold_return: f64) -> Self {
         Self {
             sharpe_ratio: SharpeRatio::init(risk_free_return),
             sortino_ratio: SortinoRatio::init(risk_free_return),
             calmar_ratio: CalmarRatio::init(risk_free_return),
+            recovery_factor: RecoveryFactor::init(),
+            omega_ratio: OmegaRatio::init(omega_threshold_return),
         }
     }
 
-    pub fn update(&mut self, pnl_returns: &PnLReturnSummary, drawdown: &DrawdownSummary) {
+    pub fn update(
+        &mut self,
+        position: &Position,
+        pnl_returns: &PnLReturnSummary,
+        drawdown: &DrawdownSummary,
+    ) {
         self.sharpe_ratio.update(pnl_returns);
         self.sortino_ratio.update(pnl_returns);
         self.calmar_ratio
             .update(pnl_returns, drawdown.max_drawdown.drawdown.drawdown);
+        self.recovery_factor.update(position, drawdown);
+        self.omega_ratio.update(position);
     }
 }
 
 impl TableBuilder for TearSheet {
     fn titles(&self) -> Row {
-        row!["Sharpe Ratio", "Sortino Ratio", "Calmar Ratio"]
+        row![
+            "Sharpe Ratio",
+            "Sortino Ratio",
+            "Calmar Ratio",
+            "Recovery Factor",
+            "Omega Ratio"
+        ]
     }
 
-    fn row(&self) -> Row {
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
         row![
-            format!("{:.3}", self.sharpe_ratio.daily()),
-            format!("{:.3}", self.sortino_ratio.daily()),
-            format!("{:.3}", self.calmar_ratio.daily()),
+            table_format.optional_float(self.sharpe_ratio.daily()),
+            table_format.optional_float(self.sortino_ratio.daily()),
+            table_format.optional_float(self.calmar_ratio.daily()),
+            table_format.optional_float(self.recovery_factor.recovery_factor),
+            table_format.optional_float(self.omega_ratio.omega_ratio),
+        ]
+    }
+}
+
+impl MetricReporter for TearSheet {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        [
+            ("Sharpe Ratio", self.sharpe_ratio.daily()),
+            ("Sortino Ratio", self.sortino_ratio.daily()),
+            ("Calmar Ratio", self.calmar_ratio.daily()),
+            ("Recovery Factor", self.recovery_factor.recovery_factor),
+            ("Omega Ratio", self.omega_ratio.omega_ratio),
         ]
+        .into_iter()
+        .filter_map(|(metric, value)| Some((metric, value?)))
+        .collect()
     }
 }
 
@@ -132,3 +216,158 @@ pub fn calculate_trading_duration(start_time: &DateTime<Utc>, position: &Positio
         Some(exit_balance) => exit_balance.time.signed_duration_since(*start_time),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::Balance,
+        statistic::summary::{combine_with_format, UndefinedFormat},
+        test_util::position,
+    };
+
+    fn trading_summary_config() -> Config {
+        Config {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+            omega_threshold_return: 0.0,
+            return_kind: ReturnKind::Simple,
+        }
+    }
+
+    fn trading_summary() -> TradingSummary {
+        TradingSummary::init(trading_summary_config())
+    }
+
+    fn rendered_cells(summary: TradingSummary, table_format: TableFormat) -> Vec<String> {
+        summary
+            .row_with_format(table_format)
+            .iter()
+            .map(|cell| cell.get_content())
+            .collect()
+    }
+
+    #[test]
+    fn trading_summary_with_zero_trades_renders_undefined_metrics_as_not_available() {
+        let summary = trading_summary();
+
+        let cells = rendered_cells(summary, TableFormat::default());
+        assert!(cells.iter().all(|cell| !cell.contains("NaN")));
+        assert!(cells.iter().any(|cell| cell == "N/A"));
+        assert_eq!(summary.tear_sheet.sharpe_ratio.daily(), None);
+
+        // Undefined metrics render as zero if configured
+        let cells = rendered_cells(
+            summary,
+            TableFormat {
+                undefined: UndefinedFormat::Zero,
+                ..TableFormat::default()
+            },
+        );
+        assert!(cells
+            .iter()
+            .all(|cell| cell != "N/A" && !cell.contains("NaN")));
+
+        // Full summary Table renders cleanly
+        let table = combine_with_format([("Total".to_owned(), summary)], TableFormat::default());
+        assert!(!table.to_string().contains("NaN"));
+    }
+
+    #[test]
+    fn trading_summary_with_single_trade_renders_undefined_metrics_as_not_available() {
+        let mut summary = trading_summary();
+
+        // Single trade with no trading session duration & no variance in returns
+        let mut position = position();
+        position.realised_profit_loss = 10.0;
+        position.meta.exit_balance = Some(Balance {
+            time: position.meta.enter_time,
+            total: 1010.0,
+            available: 1010.0,
+        });
+        summary.update(&position);
+
+        assert_eq!(summary.pnl_returns.total.count, 1);
+        assert_eq!(summary.pnl_returns.trades_per_day, None);
+        assert_eq!(summary.tear_sheet.sharpe_ratio.sharpe_ratio_per_trade, None);
+        assert_eq!(summary.tear_sheet.sharpe_ratio.daily(), None);
+        assert_eq!(summary.tear_sheet.sortino_ratio.daily(), None);
+        assert_eq!(summary.tear_sheet.calmar_ratio.daily(), None);
+        assert_eq!(summary.tear_sheet.recovery_factor.recovery_factor, None);
+        assert_eq!(summary.tear_sheet.omega_ratio.omega_ratio, None);
+
+        let cells = rendered_cells(summary, TableFormat::default());
+        assert!(cells.iter().all(|cell| !cell.contains("NaN")));
+        assert_eq!(cells[4], "N/A");
+    }
+
+    #[test]
+    fn trading_summary_with_log_returns_compared_to_simple_returns() {
+        // Trades entered at 100.0 & exited at 110.0, 90.0 & 120.0 over a day each
+        let positions = [110.0, 90.0, 120.0]
+            .into_iter()
+            .enumerate()
+            .map(|(day, exit_price)| {
+                let mut position = position();
+                position.meta.enter_time += Duration::days(day as i64);
+                position.enter_value_gross = 100.0;
+                position.realised_profit_loss = exit_price - 100.0;
+                position.meta.exit_balance = Some(Balance {
+                    time: position.meta.enter_time + Duration::days(1),
+                    total: 1000.0,
+                    available: 1000.0,
+                });
+                position
+            })
+            .collect::<Vec<_>>();
+
+        let summary = |return_kind: ReturnKind| {
+            let mut summary = TradingSummary::init(Config {
+                risk_free_return: 0.01,
+                return_kind,
+                ..trading_summary_config()
+            });
+            positions
+                .iter()
+                .for_each(|position| summary.update(position));
+            summary
+        };
+        let simple = summary(ReturnKind::Simple);
+        let log = summary(ReturnKind::Log);
+
+        // Mean & population standard deviation of each per-trade return series
+        let mean_std_dev = |returns: [f64; 3]| {
+            let mean = returns.iter().sum::<f64>() / 3.0;
+            let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 3.0;
+            (mean, variance.sqrt())
+        };
+        let (simple_mean, simple_std_dev) = mean_std_dev([0.1, -0.1, 0.2]);
+        let (log_mean, log_std_dev) = mean_std_dev([1.1_f64.ln(), 0.9_f64.ln(), 1.2_f64.ln()]);
+
+        assert!((simple.pnl_returns.total.mean - simple_mean).abs() < 1e-12);
+        assert!((log.pnl_returns.total.mean - log_mean).abs() < 1e-12);
+        assert!(log.pnl_returns.total.mean < simple.pnl_returns.total.mean);
+
+        // Risk free return is converted to the same ReturnKind as the return series
+        let simple_sharpe = simple
+            .tear_sheet
+            .sharpe_ratio
+            .sharpe_ratio_per_trade
+            .unwrap();
+        let log_sharpe = log.tear_sheet.sharpe_ratio.sharpe_ratio_per_trade.unwrap();
+        assert!((simple_sharpe - (simple_mean - 0.01) / simple_std_dev).abs() < 1e-12);
+        assert!((log_sharpe - (log_mean - 1.01_f64.ln()) / log_std_dev).abs() < 1e-12);
+
+        // Annualisation scales both per trade ratios consistently by the square root of time
+        let annual_scaling = |summary: &TradingSummary| {
+            summary.tear_sheet.sharpe_ratio.annual(365).unwrap()
+                / summary
+                    .tear_sheet
+                    .sharpe_ratio
+                    .sharpe_ratio_per_trade
+                    .unwrap()
+        };
+        assert!((annual_scaling(&simple) - annual_scaling(&log)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::summary::{
+        Initialiser, MetricReporter, PositionSummariser, TableBuilder, TableFormat,
+    },
+};
+use prettytable::Row;
+use serde::{Deserialize, Serialize};
+
+/// Win / loss record of closed trades, used to estimate the edge of a strategy (eg/ by a
+/// [`KellyAllocator`](crate::portfolio::allocator::KellyAllocator)), & as the accumulator of
+/// the [`RollingTradeSummary`](super::rolling::RollingTradeSummary) window and the
+/// [`TimeBreakdown`](super::breakdown::TimeBreakdown) buckets.
+///
+/// A trade is a loss if its realised PnL is negative, and otherwise a win, consistent with
+/// [`PnLReturnSummary`](super::pnl::PnLReturnSummary). Open [`Position`]s are ignored.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct WinLossSummary {
+    pub wins: u64,
+    pub losses: u64,
+    /// Sum of the realised PnL of the winning trades.
+    pub gross_profit: f64,
+    /// Absolute sum of the realised PnL of the losing trades.
+    pub gross_loss: f64,
+}
+
+impl Initialiser for WinLossSummary {
+    type Config = ();
+
+    fn init(_: Self::Config) -> Self {
+        Self::default()
+    }
+}
+
+impl PositionSummariser for WinLossSummary {
+    fn update(&mut self, position: &Position) {
+        // Only update WinLossSummary with closed Positions
+        if position.meta.exit_balance.is_none() {
+            return;
+        }
+
+        self.update_pnl(position.realised_profit_loss);
+    }
+}
+
+impl TableBuilder for WinLossSummary {
+    fn titles(&self) -> Row {
+        row!["Trades", "Wins", "Win Rate", "PnL"]
+    }
+
+    fn row_with_format(&self, table_format: TableFormat) -> Row {
+        row![
+            self.trades().to_string(),
+            self.wins.to_string(),
+            table_format.float(self.win_rate()),
+            table_format.float(self.pnl()),
+        ]
+    }
+}
+
+impl MetricReporter for WinLossSummary {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("Trades", self.trades() as f64),
+            ("Wins", self.wins as f64),
+            ("Win Rate", self.win_rate()),
+            ("PnL", self.pnl()),
+        ]
+    }
+}
+
+impl WinLossSummary {
+    /// Update with the realised PnL of the next closed trade.
+    pub fn update_pnl(&mut self, pnl: f64) {
+        match pnl.is_sign_negative() {
+            true => {
+                self.losses += 1;
+                self.gross_loss += pnl.abs();
+            }
+            false => {
+                self.wins += 1;
+                self.gross_profit += pnl;
+            }
+        }
+    }
+
+    /// Total number of closed trades.
+    pub fn trades(&self) -> u64 {
+        self.wins + self.losses
+    }
+
+    /// Fraction of closed trades that were wins. Returns 0.0 if there have been no trades.
+    pub fn win_rate(&self) -> f64 {
+        match self.trades() {
+            0 => 0.0,
+            trades => self.wins as f64 / trades as f64,
+        }
+    }
+
+    /// Payoff ratio of the mean win to the mean absolute loss. Undefined (`None`) without both a
+    /// win & a loss, or if the losses sum to zero.
+    pub fn payoff_ratio(&self) -> Option<f64> {
+        if self.wins == 0 || self.losses == 0 || self.gross_loss == 0.0 {
+            return None;
+        }
+
+        let mean_win = self.gross_profit / self.wins as f64;
+        let mean_loss = self.gross_loss / self.losses as f64;
+        Some(mean_win / mean_loss)
+    }
+
+    /// Profit factor of the gross profit divided by the gross loss. It is 0.0 if there is no
+    /// gross profit, and infinite if there is gross profit but no gross loss.
+    pub fn profit_factor(&self) -> f64 {
+        match (self.gross_profit > 0.0, self.gross_loss > 0.0) {
+            (false, _) => 0.0,
+            (true, false) => f64::INFINITY,
+            (true, true) => self.gross_profit / self.gross_loss,
+        }
+    }
+
+    /// Cumulative realised PnL of the closed trades.
+    pub fn pnl(&self) -> f64 {
+        self.gross_profit - self.gross_loss
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{closed_position, position};
+
+    #[test]
+    fn win_loss_summary_win_rate_and_payoff_ratio() {
+        let mut summary = WinLossSummary::default();
+        assert_eq!(summary.win_rate(), 0.0);
+        assert_eq!(summary.payoff_ratio(), None);
+
+        // Open Positions are ignored
+        summary.update(&position());
+        assert_eq!(summary.trades(), 0);
+
+        summary.generate_summary(&[
+            closed_position(30.0),
+            closed_position(10.0),
+            closed_position(0.0),
+            closed_position(-10.0),
+        ]);
+        assert_eq!(summary.trades(), 4);
+        assert_eq!(summary.win_rate(), 0.75);
+
+        // Mean win of 40.0 / 3 vs mean loss of 10.0
+        assert!((summary.payoff_ratio().unwrap() - 4.0 / 3.0).abs() < 1e-10);
+        assert_eq!(summary.profit_factor(), 4.0);
+        assert_eq!(summary.pnl(), 30.0);
+    }
+
+    #[test]
+    fn win_loss_summary_profit_factor_without_losses() {
+        let mut summary = WinLossSummary::default();
+        assert_eq!(summary.profit_factor(), 0.0);
+
+        summary.update_pnl(10.0);
+        assert_eq!(summary.profit_factor(), f64::INFINITY);
+    }
+}
@@ -0,0 +1,119 @@
+use super::{Signal, SignalGenerator};
+use async_trait::async_trait;
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+use tokio::runtime::Handle;
+
+/// Asynchronous counterpart of [`SignalGenerator`] for strategies that must await external
+/// services to analyse a [`MarketEvent`] (eg/ scoring each bar with a remote ML model).
+///
+/// Use [`BlockingSignalGenerator`] to run an [`AsyncSignalGenerator`] in a
+/// [`Trader`](crate::engine::trader::Trader).
+#[async_trait]
+pub trait AsyncSignalGenerator {
+    /// Optionally return a [`Signal`] given input [`MarketEvent`].
+    async fn generate_signal(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Option<Signal>;
+}
+
+/// [`SignalGenerator`] adapter that awaits an [`AsyncSignalGenerator`] on the provided tokio
+/// runtime [`Handle`], enabling it to be used by a [`Trader`](crate::engine::trader::Trader).
+///
+/// ## Performance
+/// Each [`Trader`](crate::engine::trader::Trader) runs its trading loop on a dedicated OS thread
+/// and processes one [`MarketEvent`] at a time, so every bar blocks that thread until the awaited
+/// [`Signal`] resolves. The latency of the async call (eg/ a network round trip) is therefore
+/// added to every bar, and bounds the throughput of the trading loop. Prefer the synchronous
+/// [`SignalGenerator`] unless the strategy genuinely depends on async I/O, and consider caching
+/// or batching external requests where possible.
+///
+/// The future is driven by the runtime the [`Handle`] belongs to, so
+/// [`SignalGenerator::generate_signal`] must not be called from within an async context (it will
+/// panic). This is always the case for a [`Trader`](crate::engine::trader::Trader).
+#[derive(Debug)]
+pub struct BlockingSignalGenerator<Strategy>
+where
+    Strategy: AsyncSignalGenerator,
+{
+    strategy: Strategy,
+    runtime: Handle,
+}
+
+impl<Strategy> SignalGenerator for BlockingSignalGenerator<Strategy>
+where
+    Strategy: AsyncSignalGenerator,
+{
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        self.runtime.block_on(self.strategy.generate_signal(market))
+    }
+}
+
+impl<Strategy> BlockingSignalGenerator<Strategy>
+where
+    Strategy: AsyncSignalGenerator,
+{
+    /// Constructs a new [`BlockingSignalGenerator`] that awaits the provided
+    /// [`AsyncSignalGenerator`] on the runtime of the provided [`Handle`].
+    pub fn new(strategy: Strategy, runtime: Handle) -> Self {
+        Self { strategy, runtime }
+    }
+
+    /// Returns the wrapped [`AsyncSignalGenerator`].
+    pub fn into_inner(self) -> Strategy {
+        self.strategy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::MarketMeta, test_util::market_event_candle};
+    use chrono::Utc;
+    use std::time::Duration;
+
+    /// Strategy that awaits a simulated external service before generating a Signal.
+    struct DelayedStrategy;
+
+    #[async_trait]
+    impl AsyncSignalGenerator for DelayedStrategy {
+        async fn generate_signal(
+            &mut self,
+            market: &MarketEvent<Instrument, DataKind>,
+        ) -> Option<Signal> {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+
+            Some(Signal {
+                time: Utc::now(),
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                signals: Default::default(),
+                market_meta: MarketMeta {
+                    close: 100.0,
+                    time: market.exchange_time,
+                    volatility: None,
+                    volume: None,
+                    spread: None,
+                },
+                tag: None,
+                stop_loss: None,
+                take_profit: None,
+            })
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn blocking_signal_generator_awaits_async_strategy_on_trader_thread() {
+        let mut strategy =
+            BlockingSignalGenerator::new(DelayedStrategy, tokio::runtime::Handle::current());
+
+        // Trader trading loops run on a dedicated OS thread, outside of the async context
+        let market = market_event_candle();
+        let signal = std::thread::spawn(move || strategy.generate_signal(&market))
+            .join()
+            .unwrap();
+
+        assert!(signal.is_some());
+    }
+}
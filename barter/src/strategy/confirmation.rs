@@ -0,0 +1,183 @@
+use super::{error::StrategyError, Decision, Signal, SignalGenerator};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for constructing a [`ConfirmationStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Number of consecutive bars a [`Decision`] must persist for before it is forwarded. Must be
+    /// at least 1, where 1 forwards every [`Signal`] immediately.
+    pub confirmation_bars: usize,
+}
+
+/// [`SignalGenerator`] wrapper that debounces the inner strategy's [`Signal`]s to reduce
+/// whipsaws. A [`Signal`] is only forwarded once the same [`Decision`]s have been signalled for
+/// `confirmation_bars` consecutive bars, otherwise it is suppressed.
+///
+/// Confirmation resets if the inner strategy signals different [`Decision`]s, or no [`Signal`] at
+/// all, before the confirmation completes. Once confirmed, [`Signal`]s continue to be forwarded
+/// for as long as the [`Decision`]s persist.
+#[derive(Clone, Debug)]
+pub struct ConfirmationStrategy<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    strategy: Strategy,
+    confirmation_bars: usize,
+    pending: Option<(Vec<Decision>, usize)>,
+}
+
+impl<Strategy> SignalGenerator for ConfirmationStrategy<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        let signal = match self.strategy.generate_signal(market) {
+            Some(signal) => signal,
+            None => {
+                self.pending = None;
+                return None;
+            }
+        };
+
+        let mut decisions = signal.signals.keys().copied().collect::<Vec<_>>();
+        decisions.sort();
+
+        // Count consecutive bars with the same Decisions, restarting if they have changed
+        let consecutive_bars = match &mut self.pending {
+            Some((pending, count)) if *pending == decisions => {
+                *count += 1;
+                *count
+            }
+            pending => {
+                *pending = Some((decisions, 1));
+                1
+            }
+        };
+
+        match consecutive_bars >= self.confirmation_bars {
+            true => Some(signal),
+            false => None,
+        }
+    }
+}
+
+impl<Strategy> ConfirmationStrategy<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    /// Constructs a new [`ConfirmationStrategy`] wrapping the provided strategy.
+    ///
+    /// Returns a [`StrategyError::InvalidConfig`] if `confirmation_bars` is 0.
+    pub fn new(config: Config, strategy: Strategy) -> Result<Self, StrategyError> {
+        if config.confirmation_bars == 0 {
+            return Err(StrategyError::InvalidConfig(
+                "ConfirmationStrategy confirmation_bars must be at least 1",
+            ));
+        }
+
+        Ok(Self {
+            strategy,
+            confirmation_bars: config.confirmation_bars,
+            pending: None,
+        })
+    }
+
+    /// Returns the wrapped strategy.
+    pub fn into_inner(self) -> Strategy {
+        self.strategy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::MarketMeta, strategy::SignalStrength, test_util::market_event_candle};
+    use chrono::Utc;
+    use std::collections::VecDeque;
+
+    /// Strategy that generates a Signal for each of the scripted Decisions in turn.
+    struct ScriptedStrategy {
+        decisions: VecDeque<Option<Decision>>,
+    }
+
+    impl SignalGenerator for ScriptedStrategy {
+        fn generate_signal(
+            &mut self,
+            market: &MarketEvent<Instrument, DataKind>,
+        ) -> Option<Signal> {
+            let decision = self.decisions.pop_front().flatten()?;
+
+            Some(Signal {
+                time: Utc::now(),
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                signals: [(decision, SignalStrength(1.0))].into_iter().collect(),
+                market_meta: MarketMeta {
+                    close: 100.0,
+                    time: market.exchange_time,
+                    volatility: None,
+                    volume: None,
+                    spread: None,
+                },
+                tag: None,
+                stop_loss: None,
+                take_profit: None,
+            })
+        }
+    }
+
+    fn run(confirmation_bars: usize, decisions: &[Option<Decision>]) -> Vec<bool> {
+        let mut strategy = ConfirmationStrategy::new(
+            Config { confirmation_bars },
+            ScriptedStrategy {
+                decisions: decisions.iter().copied().collect(),
+            },
+        )
+        .unwrap();
+
+        decisions
+            .iter()
+            .map(|_| strategy.generate_signal(&market_event_candle()).is_some())
+            .collect()
+    }
+
+    #[test]
+    fn confirmation_strategy_forwards_decision_after_consecutive_bars() {
+        let long = Some(Decision::Long);
+        assert_eq!(
+            run(3, &[long, long, long, long]),
+            [false, false, true, true]
+        );
+    }
+
+    #[test]
+    fn confirmation_strategy_resets_when_decision_changes_before_confirmation() {
+        let (long, short) = (Some(Decision::Long), Some(Decision::Short));
+        assert_eq!(
+            run(2, &[long, short, long, long, None, long]),
+            [false, false, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn confirmation_strategy_with_one_confirmation_bar_forwards_every_signal() {
+        let (long, short) = (Some(Decision::Long), Some(Decision::Short));
+        assert_eq!(run(1, &[long, short, None]), [true, true, false]);
+    }
+
+    #[test]
+    fn confirmation_strategy_with_zero_confirmation_bars_returns_error() {
+        let strategy = ConfirmationStrategy::new(
+            Config {
+                confirmation_bars: 0,
+            },
+            ScriptedStrategy {
+                decisions: VecDeque::new(),
+            },
+        );
+
+        assert!(matches!(strategy, Err(StrategyError::InvalidConfig(_))));
+    }
+}
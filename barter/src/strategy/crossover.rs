@@ -0,0 +1,302 @@
+use super::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use crate::data::MarketMeta;
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, collections::HashMap};
+use ta::{
+    indicators::{ExponentialMovingAverage, SimpleMovingAverage},
+    Next,
+};
+
+/// Configuration for constructing a [`CrossoverStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub fast_period: usize,
+    pub slow_period: usize,
+    #[serde(default)]
+    pub moving_average: MovingAverageKind,
+}
+
+/// Kind of moving average tracked by a [`CrossoverStrategy`].
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum MovingAverageKind {
+    #[default]
+    Simple,
+    Exponential,
+}
+
+/// Moving average indicator of the configured [`MovingAverageKind`].
+#[derive(Clone, Debug)]
+enum MovingAverage {
+    Simple(SimpleMovingAverage),
+    Exponential(ExponentialMovingAverage),
+}
+
+impl MovingAverage {
+    /// Constructs a new [`MovingAverage`] of the provided [`MovingAverageKind`] & period.
+    fn new(kind: MovingAverageKind, period: usize) -> Result<Self, StrategyError> {
+        let invalid_period = |_| StrategyError::InvalidIndicatorPeriod {
+            indicator: match kind {
+                MovingAverageKind::Simple => "SMA",
+                MovingAverageKind::Exponential => "EMA",
+            },
+            period,
+        };
+
+        match kind {
+            MovingAverageKind::Simple => SimpleMovingAverage::new(period)
+                .map(Self::Simple)
+                .map_err(invalid_period),
+            MovingAverageKind::Exponential => ExponentialMovingAverage::new(period)
+                .map(Self::Exponential)
+                .map_err(invalid_period),
+        }
+    }
+
+    /// Calculate the next moving average value using the provided input.
+    fn next(&mut self, input: f64) -> f64 {
+        match self {
+            Self::Simple(sma) => sma.next(input),
+            Self::Exponential(ema) => ema.next(input),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Example moving average crossover (eg/ golden cross) strategy that implements
+/// [`SignalGenerator`].
+///
+/// A fast moving average crossing above the slow moving average advises [`Decision::Long`] &
+/// [`Decision::CloseShort`], and crossing below advises [`Decision::Short`] &
+/// [`Decision::CloseLong`]. The previous averages are held to detect the crossing event itself,
+/// so no [`Signal`] is generated while the relationship between the averages persists.
+///
+/// No [`Signal`] is generated until the slow moving average has warmed up with `slow_period`
+/// candles, avoiding spurious crossovers from partially filled averages.
+pub struct CrossoverStrategy {
+    fast: MovingAverage,
+    slow: MovingAverage,
+    slow_period: usize,
+    candles: usize,
+    prev_averages: Option<(f64, f64)>,
+}
+
+impl SignalGenerator for CrossoverStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Check if it's a MarketEvent with a candle
+        let (candle_close, candle_volume) = match &market.kind {
+            DataKind::Candle(candle) => (candle.close, candle.volume),
+            _ => return None,
+        };
+
+        // Calculate the next fast & slow moving averages using the new Candle close
+        let averages = (self.fast.next(candle_close), self.slow.next(candle_close));
+        self.candles += 1;
+        if self.candles < self.slow_period {
+            return None;
+        }
+        let prev_averages = self.prev_averages.replace(averages)?;
+
+        // Generate advisory signals map
+        let signals = CrossoverStrategy::generate_signals_map(prev_averages, averages);
+
+        // If signals map is empty, return no SignalEvent
+        if signals.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            time: Utc::now(),
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            market_meta: MarketMeta {
+                close: candle_close,
+                time: market.exchange_time,
+                volatility: None,
+                volume: Some(candle_volume),
+                spread: None,
+            },
+            signals,
+            tag: None,
+            stop_loss: None,
+            take_profit: None,
+        })
+    }
+}
+
+impl TryFrom<Config> for CrossoverStrategy {
+    type Error = StrategyError;
+
+    fn try_from(config: Config) -> Result<Self, Self::Error> {
+        Self::new(config)
+    }
+}
+
+impl CrossoverStrategy {
+    /// Constructs a new [`CrossoverStrategy`] component using the provided configuration struct.
+    ///
+    /// Returns a [`StrategyError::InvalidIndicatorPeriod`] if a moving average period is invalid
+    /// (eg/ 0), or a [`StrategyError::InvalidCrossoverPeriods`] if the fast period is not shorter
+    /// than the slow period.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        let fast = MovingAverage::new(config.moving_average, config.fast_period)?;
+        let slow = MovingAverage::new(config.moving_average, config.slow_period)?;
+
+        if config.fast_period >= config.slow_period {
+            return Err(StrategyError::InvalidCrossoverPeriods {
+                fast: config.fast_period,
+                slow: config.slow_period,
+            });
+        }
+
+        Ok(Self {
+            fast,
+            slow,
+            slow_period: config.slow_period,
+            candles: 0,
+            prev_averages: None,
+        })
+    }
+
+    /// Given the previous & latest (fast, slow) moving averages for a symbol, generates a map
+    /// containing the [`SignalStrength`] for [`Decision`] under consideration.
+    fn generate_signals_map(
+        (prev_fast, prev_slow): (f64, f64),
+        (fast, slow): (f64, f64),
+    ) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(2);
+        match (prev_fast.partial_cmp(&prev_slow), fast.partial_cmp(&slow)) {
+            (Some(Ordering::Less | Ordering::Equal), Some(Ordering::Greater)) => {
+                signals.insert(
+                    Decision::Long,
+                    CrossoverStrategy::calculate_signal_strength(),
+                );
+                signals.insert(
+                    Decision::CloseShort,
+                    CrossoverStrategy::calculate_signal_strength(),
+                );
+            }
+            (Some(Ordering::Greater | Ordering::Equal), Some(Ordering::Less)) => {
+                signals.insert(
+                    Decision::Short,
+                    CrossoverStrategy::calculate_signal_strength(),
+                );
+                signals.insert(
+                    Decision::CloseLong,
+                    CrossoverStrategy::calculate_signal_strength(),
+                );
+            }
+            _ => {}
+        }
+        signals
+    }
+
+    /// Calculates the [`SignalStrength`] of a particular [`Decision`].
+    fn calculate_signal_strength() -> SignalStrength {
+        SignalStrength(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle;
+
+    fn candle_event(close: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        if let DataKind::Candle(candle) = &mut event.kind {
+            candle.close = close;
+        }
+        event
+    }
+
+    fn decisions(signal: Option<Signal>) -> Vec<Decision> {
+        let mut decisions = signal
+            .map(|signal| signal.signals.into_keys().collect::<Vec<_>>())
+            .unwrap_or_default();
+        decisions.sort();
+        decisions
+    }
+
+    fn strategy(moving_average: MovingAverageKind) -> CrossoverStrategy {
+        CrossoverStrategy::new(Config {
+            fast_period: 2,
+            slow_period: 3,
+            moving_average,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn construct_crossover_strategy_with_invalid_periods_returns_error() {
+        assert_eq!(
+            CrossoverStrategy::new(Config {
+                fast_period: 0,
+                slow_period: 3,
+                moving_average: MovingAverageKind::Simple,
+            })
+            .unwrap_err(),
+            StrategyError::InvalidIndicatorPeriod {
+                indicator: "SMA",
+                period: 0
+            }
+        );
+        assert_eq!(
+            CrossoverStrategy::new(Config {
+                fast_period: 5,
+                slow_period: 5,
+                moving_average: MovingAverageKind::Exponential,
+            })
+            .unwrap_err(),
+            StrategyError::InvalidCrossoverPeriods { fast: 5, slow: 5 }
+        );
+    }
+
+    #[test]
+    fn crossover_strategy_generates_signals_only_on_crossing_events() {
+        let mut strategy = strategy(MovingAverageKind::Simple);
+
+        // Warm up: no signals until the slow SMA is filled, then the initial averages are held
+        for close in [10.0, 10.0, 10.0] {
+            assert_eq!(strategy.generate_signal(&candle_event(close)), None);
+        }
+
+        // Fast SMA crosses above slow SMA
+        assert_eq!(
+            decisions(strategy.generate_signal(&candle_event(13.0))),
+            vec![Decision::Long, Decision::CloseShort]
+        );
+
+        // Fast SMA remains above slow SMA, so no crossing event
+        assert_eq!(strategy.generate_signal(&candle_event(14.0)), None);
+
+        // Fast SMA crosses below slow SMA
+        assert_eq!(
+            decisions(strategy.generate_signal(&candle_event(5.0))),
+            vec![Decision::CloseLong, Decision::Short]
+        );
+        assert_eq!(strategy.generate_signal(&candle_event(4.0)), None);
+    }
+
+    #[test]
+    fn crossover_strategy_with_ema_detects_crossovers() {
+        let mut strategy = strategy(MovingAverageKind::Exponential);
+
+        for close in [10.0, 10.0, 10.0] {
+            assert_eq!(strategy.generate_signal(&candle_event(close)), None);
+        }
+
+        assert_eq!(
+            decisions(strategy.generate_signal(&candle_event(12.0))),
+            vec![Decision::Long, Decision::CloseShort]
+        );
+        assert_eq!(
+            decisions(strategy.generate_signal(&candle_event(8.0))),
+            vec![Decision::CloseLong, Decision::Short]
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use super::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+};
+
+/// Configuration for constructing an [`EnsembleStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub policy: CombinationPolicy,
+}
+
+/// Policy used by an [`EnsembleStrategy`] to combine the [`Signal`]s of it's member strategies
+/// into a single [`Signal`].
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub enum CombinationPolicy {
+    /// A [`Decision`] is advised if every member strategy advises it, with the mean
+    /// [`SignalStrength`] of the members.
+    Unanimous,
+    /// A [`Decision`] is advised if more than half of the member strategies advise it, with the
+    /// mean [`SignalStrength`] of the members advising it.
+    Majority,
+    /// A [`Decision`] is advised if the weighted [`SignalStrength`] of the members advising it,
+    /// divided by the total weight of every member, reaches the threshold. The advised
+    /// [`SignalStrength`] is that weighted score.
+    Weighted { threshold: f64 },
+}
+
+/// [`SignalGenerator`] that composes multiple member strategies (eg/ RSI & MACD) into one, only
+/// advising the [`Decision`]s the members agree on according to the [`CombinationPolicy`].
+///
+/// Every member strategy is updated with every [`MarketEvent`], so their indicators stay current
+/// even when they do not generate a [`Signal`]. A member that generates no [`Signal`] advises no
+/// [`Decision`]s. No [`Signal`] is generated if no [`Decision`] satisfies the
+/// [`CombinationPolicy`] (eg/ the members disagree under [`CombinationPolicy::Unanimous`]).
+pub struct EnsembleStrategy {
+    policy: CombinationPolicy,
+    members: Vec<(Box<dyn SignalGenerator + Send>, f64)>,
+}
+
+impl Debug for EnsembleStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnsembleStrategy")
+            .field("policy", &self.policy)
+            .field(
+                "weights",
+                &self
+                    .members
+                    .iter()
+                    .map(|(_, weight)| weight)
+                    .collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl SignalGenerator for EnsembleStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        let signals = self
+            .members
+            .iter_mut()
+            .map(|(strategy, weight)| (strategy.generate_signal(market), *weight))
+            .collect::<Vec<_>>();
+
+        // Use the MarketMeta of the first member Signal, since every member sees the same market
+        let market_meta = signals
+            .iter()
+            .find_map(|(signal, _)| signal.as_ref().map(|signal| signal.market_meta))?;
+
+        let combined = self.combine(&signals);
+        if combined.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            time: Utc::now(),
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            signals: combined,
+            market_meta,
+            tag: None,
+            stop_loss: None,
+            take_profit: None,
+        })
+    }
+}
+
+impl EnsembleStrategy {
+    /// Constructs a new [`EnsembleStrategy`] with no member strategies.
+    pub fn new(config: Config) -> Self {
+        Self {
+            policy: config.policy,
+            members: Vec::new(),
+        }
+    }
+
+    /// Add a member strategy with the provided weight. The weight is only used by
+    /// [`CombinationPolicy::Weighted`].
+    ///
+    /// Returns a [`StrategyError::InvalidConfig`] if the weight is negative or not finite.
+    pub fn with_strategy<Strategy>(
+        mut self,
+        strategy: Strategy,
+        weight: f64,
+    ) -> Result<Self, StrategyError>
+    where
+        Strategy: SignalGenerator + Send + 'static,
+    {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(StrategyError::InvalidConfig(
+                "EnsembleStrategy member weight must be finite & not negative",
+            ));
+        }
+
+        self.members.push((Box::new(strategy), weight));
+        Ok(self)
+    }
+
+    /// Combine the (optional [`Signal`], weight) of every member strategy into the advised
+    /// [`SignalStrength`] of each [`Decision`] satisfying the [`CombinationPolicy`].
+    fn combine(&self, signals: &[(Option<Signal>, f64)]) -> HashMap<Decision, SignalStrength> {
+        let total_weight = signals.iter().map(|(_, weight)| weight).sum::<f64>();
+
+        [
+            Decision::Long,
+            Decision::CloseLong,
+            Decision::Short,
+            Decision::CloseShort,
+        ]
+        .into_iter()
+        .filter_map(|decision| {
+            // (SignalStrength, weight) of each member advising the Decision
+            let advised = signals
+                .iter()
+                .filter_map(|(signal, weight)| {
+                    let strength = signal.as_ref()?.signals.get(&decision)?;
+                    Some((strength.0, *weight))
+                })
+                .collect::<Vec<_>>();
+
+            if advised.is_empty() {
+                return None;
+            }
+            let mean_strength =
+                advised.iter().map(|(strength, _)| strength).sum::<f64>() / advised.len() as f64;
+
+            let strength = match self.policy {
+                CombinationPolicy::Unanimous => {
+                    (advised.len() == signals.len()).then_some(mean_strength)
+                }
+                CombinationPolicy::Majority => {
+                    (advised.len() * 2 > signals.len()).then_some(mean_strength)
+                }
+                CombinationPolicy::Weighted { threshold } => {
+                    let score = match total_weight > 0.0 {
+                        true => {
+                            advised
+                                .iter()
+                                .map(|(strength, weight)| strength * weight)
+                                .sum::<f64>()
+                                / total_weight
+                        }
+                        false => 0.0,
+                    };
+                    (score > 0.0 && score >= threshold).then_some(score)
+                }
+            }?;
+
+            Some((decision, SignalStrength(strength)))
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::MarketMeta, test_util::market_event_candle};
+
+    /// Strategy that always generates a Signal advising the provided Decisions.
+    struct FixedStrategy {
+        decisions: Vec<(Decision, f64)>,
+    }
+
+    impl SignalGenerator for FixedStrategy {
+        fn generate_signal(
+            &mut self,
+            market: &MarketEvent<Instrument, DataKind>,
+        ) -> Option<Signal> {
+            if self.decisions.is_empty() {
+                return None;
+            }
+
+            Some(Signal {
+                time: Utc::now(),
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                signals: self
+                    .decisions
+                    .iter()
+                    .map(|(decision, strength)| (*decision, SignalStrength(*strength)))
+                    .collect(),
+                market_meta: MarketMeta {
+                    close: 100.0,
+                    time: market.exchange_time,
+                    volatility: None,
+                    volume: None,
+                    spread: None,
+                },
+                tag: None,
+                stop_loss: None,
+                take_profit: None,
+            })
+        }
+    }
+
+    fn member(decisions: &[(Decision, f64)]) -> FixedStrategy {
+        FixedStrategy {
+            decisions: decisions.to_vec(),
+        }
+    }
+
+    fn ensemble(policy: CombinationPolicy, members: Vec<(FixedStrategy, f64)>) -> EnsembleStrategy {
+        members.into_iter().fold(
+            EnsembleStrategy::new(Config { policy }),
+            |ensemble, (member, weight)| ensemble.with_strategy(member, weight).unwrap(),
+        )
+    }
+
+    fn signals(ensemble: &mut EnsembleStrategy) -> Option<HashMap<Decision, SignalStrength>> {
+        ensemble
+            .generate_signal(&market_event_candle())
+            .map(|signal| signal.signals)
+    }
+
+    #[test]
+    fn ensemble_strategy_unanimous_requires_every_member_to_agree() {
+        let mut agree = ensemble(
+            CombinationPolicy::Unanimous,
+            vec![
+                (
+                    member(&[(Decision::Long, 1.0), (Decision::CloseShort, 1.0)]),
+                    1.0,
+                ),
+                (member(&[(Decision::Long, 0.5)]), 1.0),
+            ],
+        );
+        assert_eq!(
+            signals(&mut agree),
+            Some(HashMap::from([(Decision::Long, SignalStrength(0.75))]))
+        );
+
+        let mut disagree = ensemble(
+            CombinationPolicy::Unanimous,
+            vec![
+                (member(&[(Decision::Long, 1.0)]), 1.0),
+                (member(&[(Decision::Short, 1.0)]), 1.0),
+            ],
+        );
+        assert_eq!(signals(&mut disagree), None);
+
+        let mut abstain = ensemble(
+            CombinationPolicy::Unanimous,
+            vec![(member(&[(Decision::Long, 1.0)]), 1.0), (member(&[]), 1.0)],
+        );
+        assert_eq!(signals(&mut abstain), None);
+    }
+
+    #[test]
+    fn ensemble_strategy_majority_requires_more_than_half_of_members() {
+        let mut ensemble = ensemble(
+            CombinationPolicy::Majority,
+            vec![
+                (member(&[(Decision::Long, 1.0)]), 1.0),
+                (member(&[(Decision::Long, 0.5)]), 1.0),
+                (member(&[(Decision::Short, 1.0)]), 1.0),
+            ],
+        );
+
+        assert_eq!(
+            signals(&mut ensemble),
+            Some(HashMap::from([(Decision::Long, SignalStrength(0.75))]))
+        );
+    }
+
+    #[test]
+    fn ensemble_strategy_weighted_aggregates_weighted_signal_strength() {
+        let members = || {
+            vec![
+                (member(&[(Decision::Long, 1.0)]), 3.0),
+                (member(&[(Decision::Short, 1.0)]), 1.0),
+            ]
+        };
+
+        let mut low_threshold =
+            ensemble(CombinationPolicy::Weighted { threshold: 0.25 }, members());
+        assert_eq!(
+            signals(&mut low_threshold),
+            Some(HashMap::from([
+                (Decision::Long, SignalStrength(0.75)),
+                (Decision::Short, SignalStrength(0.25)),
+            ]))
+        );
+
+        let mut high_threshold =
+            ensemble(CombinationPolicy::Weighted { threshold: 0.5 }, members());
+        assert_eq!(
+            signals(&mut high_threshold),
+            Some(HashMap::from([(Decision::Long, SignalStrength(0.75))]))
+        );
+
+        let mut unreachable = ensemble(CombinationPolicy::Weighted { threshold: 0.8 }, members());
+        assert_eq!(signals(&mut unreachable), None);
+    }
+
+    #[test]
+    fn ensemble_strategy_with_invalid_member_weight_returns_error() {
+        for weight in [-1.0, f64::NAN, f64::INFINITY] {
+            let ensemble = EnsembleStrategy::new(Config {
+                policy: CombinationPolicy::Majority,
+            })
+            .with_strategy(member(&[]), weight);
+
+            assert!(matches!(ensemble, Err(StrategyError::InvalidConfig(_))));
+        }
+    }
+}
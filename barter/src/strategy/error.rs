@@ -0,0 +1,33 @@
+use chrono::Duration;
+use thiserror::Error;
+
+/// All errors generated in the barter::strategy module.
+#[derive(Error, Copy, Clone, Debug, PartialEq)]
+pub enum StrategyError {
+    #[error("Failed to construct {indicator} indicator with invalid period: {period}")]
+    InvalidIndicatorPeriod {
+        indicator: &'static str,
+        period: usize,
+    },
+
+    #[error("Invalid RSI thresholds, expected 0 < oversold ({oversold}) <= overbought ({overbought}) < 100")]
+    InvalidThresholds { oversold: f64, overbought: f64 },
+
+    #[error("Invalid crossover periods, expected fast ({fast}) < slow ({slow})")]
+    InvalidCrossoverPeriods { fast: usize, slow: usize },
+
+    #[error("Invalid interval, expected a positive duration: {0}")]
+    InvalidInterval(Duration),
+
+    #[error("Invalid config: {0}")]
+    InvalidConfig(&'static str),
+
+    #[error(
+        "Failed to load {indicator} state with period {state_period}, expected period: {period}"
+    )]
+    IncompatibleState {
+        indicator: &'static str,
+        period: usize,
+        state_period: usize,
+    },
+}
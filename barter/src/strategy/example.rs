@@ -1,37 +1,110 @@
-use super::{Decision, Signal, SignalGenerator, SignalStrength};
+use super::{
+    error::StrategyError, Decision, PersistentState, Signal, SignalGenerator, SignalStrength,
+    TradeDirection,
+};
 use crate::data::MarketMeta;
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::instrument::Instrument;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use ta::{indicators::RelativeStrengthIndex, Next};
+use ta::{indicators::RelativeStrengthIndex, Next, Period};
 
 /// Configuration for constructing a [`RSIStrategy`] via the new() constructor method.
-#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Config {
     pub rsi_period: usize,
+    /// RSI below which the market is oversold (bullish). Defaults to 40.0.
+    #[serde(default = "default_oversold")]
+    pub oversold: f64,
+    /// RSI above which the market is overbought (bearish). Defaults to 60.0.
+    #[serde(default = "default_overbought")]
+    pub overbought: f64,
+    /// Scale the [`SignalStrength`] proportionally to how far past the threshold the RSI is,
+    /// rather than a constant 1.0. Enables an allocator to size positions by conviction.
+    /// Disabled by default.
+    #[serde(default)]
+    pub scale_signal_strength: bool,
+    /// Number of candles consumed to warm up the RSI before any [`Signal`] is generated.
+    /// Defaults to the `rsi_period`.
+    #[serde(default)]
+    pub warm_up: Option<usize>,
+    /// Direction(s) the strategy is permitted to trade in. [`Decision`]s in any other direction
+    /// are removed from the generated [`Signal`]. Defaults to [`TradeDirection::Both`].
+    #[serde(default)]
+    pub direction: TradeDirection,
+    /// Number of bars after an entry [`Decision`] (Long or Short) is advised during which no
+    /// further entry is advised, debouncing rapid re-entries in choppy markets. Exit
+    /// [`Decision`]s are unaffected. Defaults to 0 (disabled).
+    #[serde(default)]
+    pub cooldown_bars: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            rsi_period: 14,
+            oversold: default_oversold(),
+            overbought: default_overbought(),
+            scale_signal_strength: false,
+            warm_up: None,
+            direction: TradeDirection::default(),
+            cooldown_bars: 0,
+        }
+    }
+}
+
+fn default_oversold() -> f64 {
+    40.0
+}
+
+fn default_overbought() -> f64 {
+    60.0
 }
 
 #[derive(Clone, Debug)]
 /// Example RSI based strategy that implements [`SignalGenerator`].
+///
+/// No [`Signal`] is generated until the RSI has warmed up with the configured number of candles,
+/// avoiding spurious trades at the start of every backtest.
 pub struct RSIStrategy {
     rsi: RelativeStrengthIndex,
+    oversold: f64,
+    overbought: f64,
+    scale_signal_strength: bool,
+    warm_up: usize,
+    candles: usize,
+    direction: TradeDirection,
+    cooldown_bars: usize,
+    cooldown_remaining: usize,
 }
 
 impl SignalGenerator for RSIStrategy {
     fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
         // Check if it's a MarketEvent with a candle
-        let candle_close = match &market.kind {
-            DataKind::Candle(candle) => candle.close,
+        let (candle_close, candle_volume) = match &market.kind {
+            DataKind::Candle(candle) => (candle.close, candle.volume),
             _ => return None,
         };
 
         // Calculate the next RSI value using the new MarketEvent Candle data
         let rsi = self.rsi.next(candle_close);
+        self.candles += 1;
+        if self.candles < self.warm_up {
+            return None;
+        }
 
         // Generate advisory signals map
-        let signals = RSIStrategy::generate_signals_map(rsi);
+        let mut signals = self.generate_signals_map(rsi);
+
+        // Suppress entry Decisions during the cooldown following the previous entry
+        if self.cooldown_remaining > 0 {
+            self.cooldown_remaining -= 1;
+            signals.retain(|decision, _| !decision.is_entry());
+        }
+        if signals.keys().any(Decision::is_entry) {
+            self.cooldown_remaining = self.cooldown_bars;
+        }
 
         // If signals map is empty, return no SignalEvent
         if signals.is_empty() {
@@ -45,48 +118,405 @@ impl SignalGenerator for RSIStrategy {
             market_meta: MarketMeta {
                 close: candle_close,
                 time: market.exchange_time,
+                volatility: None,
+                volume: Some(candle_volume),
+                spread: None,
             },
             signals,
+            tag: None,
+            stop_loss: None,
+            take_profit: None,
         })
     }
 }
 
+/// Snapshot of the internal state of a [`RSIStrategy`], see [`PersistentState`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct State {
+    rsi: RelativeStrengthIndex,
+    candles: usize,
+    cooldown_remaining: usize,
+}
+
+impl PersistentState for RSIStrategy {
+    type State = State;
+
+    fn save_state(&self) -> Self::State {
+        State {
+            rsi: self.rsi.clone(),
+            candles: self.candles,
+            cooldown_remaining: self.cooldown_remaining,
+        }
+    }
+
+    fn load_state(&mut self, state: Self::State) -> Result<(), StrategyError> {
+        if state.rsi.period() != self.rsi.period() {
+            return Err(StrategyError::IncompatibleState {
+                indicator: "RSI",
+                period: self.rsi.period(),
+                state_period: state.rsi.period(),
+            });
+        }
+
+        self.rsi = state.rsi;
+        self.candles = state.candles;
+        self.cooldown_remaining = state.cooldown_remaining;
+        Ok(())
+    }
+}
+
+impl TryFrom<Config> for RSIStrategy {
+    type Error = StrategyError;
+
+    fn try_from(config: Config) -> Result<Self, Self::Error> {
+        Self::new(config)
+    }
+}
+
 impl RSIStrategy {
     /// Constructs a new [`RSIStrategy`] component using the provided configuration struct.
-    pub fn new(config: Config) -> Self {
-        let rsi_indicator = RelativeStrengthIndex::new(config.rsi_period)
-            .expect("Failed to construct RSI indicator");
+    ///
+    /// Returns a [`StrategyError::InvalidIndicatorPeriod`] if the RSI period is invalid (eg/ 0),
+    /// or a [`StrategyError::InvalidThresholds`] unless 0 < oversold <= overbought < 100.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        if !(0.0 < config.oversold
+            && config.oversold <= config.overbought
+            && config.overbought < 100.0)
+        {
+            return Err(StrategyError::InvalidThresholds {
+                oversold: config.oversold,
+                overbought: config.overbought,
+            });
+        }
+
+        let rsi_indicator = RelativeStrengthIndex::new(config.rsi_period).map_err(|_| {
+            StrategyError::InvalidIndicatorPeriod {
+                indicator: "RSI",
+                period: config.rsi_period,
+            }
+        })?;
 
-        Self { rsi: rsi_indicator }
+        Ok(Self {
+            rsi: rsi_indicator,
+            oversold: config.oversold,
+            overbought: config.overbought,
+            scale_signal_strength: config.scale_signal_strength,
+            warm_up: config.warm_up.unwrap_or(config.rsi_period),
+            candles: 0,
+            direction: config.direction,
+            cooldown_bars: config.cooldown_bars,
+            cooldown_remaining: 0,
+        })
     }
 
     /// Given the latest RSI value for a symbol, generates a map containing the [`SignalStrength`] for
     /// [`Decision`] under consideration.
-    fn generate_signals_map(rsi: f64) -> HashMap<Decision, SignalStrength> {
-        let mut signals = HashMap::with_capacity(4);
-        if rsi < 40.0 {
-            signals.insert(Decision::Long, RSIStrategy::calculate_signal_strength());
-        }
-        if rsi > 60.0 {
-            signals.insert(
-                Decision::CloseLong,
-                RSIStrategy::calculate_signal_strength(),
-            );
+    ///
+    /// Each RSI regime only advises self-consistent [`Decision`]s sharing the same directional
+    /// bias. An oversold RSI is bullish, so it advises entering a long ([`Decision::Long`]) or
+    /// exiting a short ([`Decision::CloseShort`]). Only one of these is acted upon, depending on
+    /// whether a [`Position`](crate::portfolio::position::Position) is open. An overbought RSI is
+    /// the bearish mirror image. A neutral RSI advises nothing.
+    ///
+    /// [`Decision`]s not permitted by the configured [`TradeDirection`] are removed.
+    fn generate_signals_map(&self, rsi: f64) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(2);
+        match rsi {
+            rsi if rsi < self.oversold => {
+                let strength = self.calculate_signal_strength(self.oversold - rsi, self.oversold);
+                signals.insert(Decision::Long, strength);
+                signals.insert(Decision::CloseShort, strength);
+            }
+            rsi if rsi > self.overbought => {
+                let strength =
+                    self.calculate_signal_strength(rsi - self.overbought, 100.0 - self.overbought);
+                signals.insert(Decision::Short, strength);
+                signals.insert(Decision::CloseLong, strength);
+            }
+            _ => {}
+        }
+        signals.retain(|decision, _| self.direction.permits(decision));
+        signals
+    }
+
+    /// Calculates the [`SignalStrength`] of a particular [`Decision`], given how far past the
+    /// threshold the RSI is, and the furthest the RSI could be past that threshold.
+    ///
+    /// If scaling is enabled, the [`SignalStrength`] is that fraction in the range (0, 1], else it
+    /// is a constant 1.0.
+    fn calculate_signal_strength(
+        &self,
+        past_threshold: f64,
+        max_past_threshold: f64,
+    ) -> SignalStrength {
+        match self.scale_signal_strength {
+            true => SignalStrength(past_threshold / max_past_threshold),
+            false => SignalStrength(1.0),
         }
-        if rsi > 60.0 {
-            signals.insert(Decision::Short, RSIStrategy::calculate_signal_strength());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{market_event_candle, market_event_trade};
+    use barter_integration::model::Side;
+
+    fn config(rsi_period: usize) -> Config {
+        Config {
+            rsi_period,
+            ..Config::default()
         }
-        if rsi < 40.0 {
-            signals.insert(
-                Decision::CloseShort,
-                RSIStrategy::calculate_signal_strength(),
+    }
+
+    #[test]
+    fn construct_rsi_strategy_with_invalid_period_returns_error() {
+        assert_eq!(
+            RSIStrategy::new(config(0)).unwrap_err(),
+            StrategyError::InvalidIndicatorPeriod {
+                indicator: "RSI",
+                period: 0
+            }
+        );
+        assert!(RSIStrategy::try_from(config(0)).is_err());
+        assert!(RSIStrategy::new(config(14)).is_ok());
+    }
+
+    #[test]
+    fn rsi_strategy_ignores_non_candle_market_events() {
+        let mut strategy = RSIStrategy::new(config(14)).unwrap();
+
+        assert_eq!(
+            strategy.generate_signal(&market_event_trade(Side::Buy)),
+            None
+        );
+    }
+
+    fn candle_event(close: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        if let DataKind::Candle(candle) = &mut event.kind {
+            candle.close = close;
+        }
+        event
+    }
+
+    #[test]
+    fn rsi_strategy_generates_no_signals_until_warmed_up() {
+        // Steadily falling closes keep the RSI oversold from the second candle onwards
+
+        let mut strategy = RSIStrategy::new(config(3)).unwrap();
+        assert_eq!(strategy.generate_signal(&candle_event(10.0)), None);
+        assert_eq!(strategy.generate_signal(&candle_event(9.0)), None);
+        assert!(strategy.generate_signal(&candle_event(8.0)).is_some());
+
+        let mut strategy = RSIStrategy::new(Config {
+            warm_up: Some(5),
+            ..config(3)
+        })
+        .unwrap();
+        for close in [10.0, 9.0, 8.0, 7.0] {
+            assert_eq!(strategy.generate_signal(&candle_event(close)), None);
+        }
+        assert!(strategy.generate_signal(&candle_event(6.0)).is_some());
+    }
+
+    #[test]
+    fn rsi_strategy_emits_no_entry_signals_during_cooldown() {
+        let mut strategy = RSIStrategy::new(Config {
+            warm_up: Some(1),
+            cooldown_bars: 2,
+            ..config(3)
+        })
+        .unwrap();
+        assert_eq!(strategy.generate_signal(&candle_event(20.0)), None);
+
+        // Steadily falling closes keep the RSI oversold, so every bar would advise an entry
+        let decisions = (1..=7)
+            .map(|bar| {
+                let signal = strategy.generate_signal(&candle_event(20.0 - bar as f64));
+                let mut decisions = signal
+                    .map(|signal| signal.signals.into_keys().collect::<Vec<_>>())
+                    .unwrap_or_default();
+                decisions.sort();
+                decisions
+            })
+            .collect::<Vec<_>>();
+
+        let entry = vec![Decision::Long, Decision::CloseShort];
+        let cooldown = vec![Decision::CloseShort];
+        assert_eq!(
+            decisions,
+            vec![
+                entry.clone(),
+                cooldown.clone(),
+                cooldown.clone(),
+                entry.clone(),
+                cooldown.clone(),
+                cooldown,
+                entry,
+            ]
+        );
+    }
+
+    #[test]
+    fn rsi_strategy_restored_from_saved_state_resumes_without_re_warming() {
+        let mut strategy = RSIStrategy::new(config(3)).unwrap();
+        for close in [10.0, 11.0, 12.0, 11.0] {
+            strategy.generate_signal(&candle_event(close));
+        }
+
+        let snapshot = serde_json::to_string(&strategy.save_state()).unwrap();
+        let mut restored = RSIStrategy::new(config(3)).unwrap();
+        restored
+            .load_state(serde_json::from_str(&snapshot).unwrap())
+            .unwrap();
+
+        for close in [9.0, 8.0, 12.0, 14.0] {
+            assert_eq!(
+                strategy
+                    .generate_signal(&candle_event(close))
+                    .map(|signal| signal.signals),
+                restored
+                    .generate_signal(&candle_event(close))
+                    .map(|signal| signal.signals)
             );
         }
-        signals
+
+        let mut incompatible = RSIStrategy::new(config(14)).unwrap();
+        assert_eq!(
+            incompatible.load_state(strategy.save_state()).unwrap_err(),
+            StrategyError::IncompatibleState {
+                indicator: "RSI",
+                period: 14,
+                state_period: 3
+            }
+        );
     }
 
-    /// Calculates the [`SignalStrength`] of a particular [`Decision`].
-    fn calculate_signal_strength() -> SignalStrength {
-        SignalStrength(1.0)
+    #[test]
+    fn rsi_strategy_signals_map_never_contains_contradictory_decisions() {
+        let contradictory = [
+            (Decision::Long, Decision::Short),
+            (Decision::Long, Decision::CloseLong),
+            (Decision::Short, Decision::CloseShort),
+            (Decision::CloseLong, Decision::CloseShort),
+        ];
+
+        let strategy = RSIStrategy::new(config(14)).unwrap();
+        for rsi in (0..=1000).map(|rsi| rsi as f64 / 10.0) {
+            let signals = strategy.generate_signals_map(rsi);
+            for (a, b) in contradictory {
+                assert!(
+                    !(signals.contains_key(&a) && signals.contains_key(&b)),
+                    "RSI {rsi} advised contradictory {a:?} & {b:?}"
+                );
+            }
+        }
+
+        let oversold = strategy.generate_signals_map(30.0);
+        assert!(
+            oversold.contains_key(&Decision::Long) && oversold.contains_key(&Decision::CloseShort)
+        );
+        let overbought = strategy.generate_signals_map(70.0);
+        assert!(
+            overbought.contains_key(&Decision::Short)
+                && overbought.contains_key(&Decision::CloseLong)
+        );
+        assert!(strategy.generate_signals_map(50.0).is_empty());
+    }
+
+    #[test]
+    fn rsi_strategy_with_default_config_advises_constant_signal_strength() {
+        let strategy = RSIStrategy::new(Config::default()).unwrap();
+
+        assert_eq!(
+            strategy.generate_signals_map(20.0).get(&Decision::Long),
+            Some(&SignalStrength(1.0))
+        );
+        assert_eq!(
+            strategy.generate_signals_map(39.0).get(&Decision::Long),
+            Some(&SignalStrength(1.0))
+        );
+        assert!(strategy.generate_signals_map(40.0).is_empty());
+        assert!(strategy.generate_signals_map(60.0).is_empty());
+    }
+
+    #[test]
+    fn rsi_strategy_scales_signal_strength_by_distance_past_threshold() {
+        let strategy = RSIStrategy::new(Config {
+            oversold: 30.0,
+            overbought: 80.0,
+            scale_signal_strength: true,
+            ..Config::default()
+        })
+        .unwrap();
+
+        // Custom thresholds are respected
+        assert!(strategy.generate_signals_map(35.0).is_empty());
+        assert!(strategy.generate_signals_map(75.0).is_empty());
+
+        // Oversold strength scales from 0 at the threshold to 1 at RSI 0
+        let strength = |rsi, decision| strategy.generate_signals_map(rsi)[&decision].0;
+        assert_eq!(strength(15.0, Decision::Long), 0.5);
+        assert_eq!(strength(15.0, Decision::CloseShort), 0.5);
+        assert!(strength(10.0, Decision::Long) > strength(29.0, Decision::Long));
+
+        // Overbought strength scales from 0 at the threshold to 1 at RSI 100
+        assert_eq!(strength(90.0, Decision::Short), 0.5);
+        assert_eq!(strength(100.0, Decision::CloseLong), 1.0);
+    }
+
+    #[test]
+    fn rsi_strategy_only_advises_decisions_in_configured_direction() {
+        let strategy = |direction| {
+            RSIStrategy::new(Config {
+                direction,
+                ..config(14)
+            })
+            .unwrap()
+        };
+        let decisions = |strategy: &RSIStrategy, rsi| {
+            let mut decisions = strategy
+                .generate_signals_map(rsi)
+                .into_keys()
+                .collect::<Vec<_>>();
+            decisions.sort();
+            decisions
+        };
+
+        let long_only = strategy(TradeDirection::LongOnly);
+        assert_eq!(decisions(&long_only, 20.0), vec![Decision::Long]);
+        assert_eq!(decisions(&long_only, 80.0), vec![Decision::CloseLong]);
+
+        let short_only = strategy(TradeDirection::ShortOnly);
+        assert_eq!(decisions(&short_only, 20.0), vec![Decision::CloseShort]);
+        assert_eq!(decisions(&short_only, 80.0), vec![Decision::Short]);
+
+        let both = strategy(TradeDirection::Both);
+        assert_eq!(
+            decisions(&both, 20.0),
+            vec![Decision::Long, Decision::CloseShort]
+        );
+    }
+
+    #[test]
+    fn construct_rsi_strategy_with_invalid_thresholds_returns_error() {
+        assert_eq!(
+            RSIStrategy::new(Config {
+                oversold: 70.0,
+                overbought: 30.0,
+                ..Config::default()
+            })
+            .unwrap_err(),
+            StrategyError::InvalidThresholds {
+                oversold: 70.0,
+                overbought: 30.0
+            }
+        );
+        assert!(RSIStrategy::new(Config {
+            overbought: 100.0,
+            ..Config::default()
+        })
+        .is_err());
     }
 }
@@ -0,0 +1,177 @@
+use super::{Signal, SignalGenerator};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Action taken by a [`LookAheadGuard`] when it detects a potential look-ahead violation.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum LookAheadMode {
+    /// Log the violation & continue.
+    Warn,
+    /// Panic on the violation. Useful in backtests & tests to fail fast.
+    Panic,
+}
+
+/// Configuration for constructing a [`LookAheadGuard`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub mode: LookAheadMode,
+}
+
+/// Debug [`SignalGenerator`] wrapper that asserts the inner strategy never acts on information
+/// from the future. Checks that:
+/// - Input [`MarketEvent`]s are provided in chronological `exchange_time` order.
+/// - Every generated [`Signal`] is derived from a bar that is not after the current
+///   [`MarketEvent`] (ie/ `signal.market_meta.time <= market.exchange_time`).
+#[derive(Clone, Debug)]
+pub struct LookAheadGuard<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    strategy: Strategy,
+    mode: LookAheadMode,
+    latest_time: Option<DateTime<Utc>>,
+}
+
+impl<Strategy> SignalGenerator for LookAheadGuard<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Check MarketEvents are not provided out of chronological order
+        if let Some(latest_time) = self.latest_time {
+            if market.exchange_time < latest_time {
+                self.violation(format!(
+                    "MarketEvent exchange_time {} is before previously seen {}",
+                    market.exchange_time, latest_time
+                ));
+            }
+        }
+        self.latest_time = Some(market.exchange_time);
+
+        // Check the Signal was not derived from a bar after the current MarketEvent
+        let signal = self.strategy.generate_signal(market)?;
+        if signal.market_meta.time > market.exchange_time {
+            self.violation(format!(
+                "Signal derived from bar at {} which is after current MarketEvent at {}",
+                signal.market_meta.time, market.exchange_time
+            ));
+        }
+
+        Some(signal)
+    }
+}
+
+impl<Strategy> LookAheadGuard<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    /// Constructs a new [`LookAheadGuard`] wrapping the provided strategy.
+    pub fn new(config: Config, strategy: Strategy) -> Self {
+        Self {
+            strategy,
+            mode: config.mode,
+            latest_time: None,
+        }
+    }
+
+    /// Returns the wrapped strategy.
+    pub fn into_inner(self) -> Strategy {
+        self.strategy
+    }
+
+    fn violation(&self, message: String) {
+        match self.mode {
+            LookAheadMode::Warn => warn!(%message, "strategy look-ahead violation detected"),
+            LookAheadMode::Panic => panic!("strategy look-ahead violation detected: {message}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::MarketMeta, test_util::market_event_candle};
+    use chrono::Duration;
+    use std::ops::Add;
+
+    /// Strategy that always generates a Signal with the configured offset from the MarketEvent.
+    struct OffsetStrategy {
+        offset: Duration,
+    }
+
+    impl SignalGenerator for OffsetStrategy {
+        fn generate_signal(
+            &mut self,
+            market: &MarketEvent<Instrument, DataKind>,
+        ) -> Option<Signal> {
+            Some(Signal {
+                time: Utc::now(),
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                signals: Default::default(),
+                market_meta: MarketMeta {
+                    close: 100.0,
+                    time: market.exchange_time.add(self.offset),
+                    volatility: None,
+                    volume: None,
+                    spread: None,
+                },
+                tag: None,
+                stop_loss: None,
+                take_profit: None,
+            })
+        }
+    }
+
+    fn guard(offset: Duration) -> LookAheadGuard<OffsetStrategy> {
+        LookAheadGuard::new(
+            Config {
+                mode: LookAheadMode::Panic,
+            },
+            OffsetStrategy { offset },
+        )
+    }
+
+    #[test]
+    fn look_ahead_guard_allows_signal_from_current_bar() {
+        let mut guard = guard(Duration::zero());
+        assert!(guard.generate_signal(&market_event_candle()).is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn look_ahead_guard_panics_on_signal_from_future_bar() {
+        let mut guard = guard(Duration::minutes(1));
+        guard.generate_signal(&market_event_candle());
+    }
+
+    #[test]
+    #[should_panic]
+    fn look_ahead_guard_panics_on_out_of_order_market_events() {
+        let mut guard = guard(Duration::zero());
+
+        let first = market_event_candle();
+        let mut second = first.clone();
+        second.exchange_time = first.exchange_time - Duration::minutes(1);
+
+        guard.generate_signal(&first);
+        guard.generate_signal(&second);
+    }
+
+    #[test]
+    fn look_ahead_guard_in_warn_mode_still_returns_signal() {
+        let mut guard = LookAheadGuard::new(
+            Config {
+                mode: LookAheadMode::Warn,
+            },
+            OffsetStrategy {
+                offset: Duration::minutes(1),
+            },
+        );
+
+        assert!(guard.generate_signal(&market_event_candle()).is_some());
+    }
+}
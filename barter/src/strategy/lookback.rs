@@ -0,0 +1,109 @@
+use super::{Signal, SignalGenerator};
+use crate::data::{lookback::CandleHistory, HistoryProvider};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+
+/// May generate an advisory [`Signal`] as a result of analysing an input [`MarketEvent`] together
+/// with the recent [`Candle`](barter_data::subscription::candle::Candle) history of it's market.
+/// Run in a [`Trader`](crate::engine::trader::Trader) via a [`LookbackStrategy`].
+pub trait HistorySignalGenerator {
+    /// Optionally return a [`Signal`] given input [`MarketEvent`] & the [`HistoryProvider`] of
+    /// recent candles, which already includes the input [`MarketEvent`] if it is a candle.
+    fn generate_signal_with_history(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+        history: &dyn HistoryProvider,
+    ) -> Option<Signal>;
+}
+
+/// [`SignalGenerator`] wrapper that owns a [`CandleHistory`] lookback window, updating it with
+/// every [`MarketEvent`] before providing it to the inner [`HistorySignalGenerator`].
+#[derive(Clone, Debug)]
+pub struct LookbackStrategy<Strategy>
+where
+    Strategy: HistorySignalGenerator,
+{
+    strategy: Strategy,
+    history: CandleHistory,
+}
+
+impl<Strategy> SignalGenerator for LookbackStrategy<Strategy>
+where
+    Strategy: HistorySignalGenerator,
+{
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        self.history.update(market);
+        self.strategy
+            .generate_signal_with_history(market, &self.history)
+    }
+}
+
+impl<Strategy> LookbackStrategy<Strategy>
+where
+    Strategy: HistorySignalGenerator,
+{
+    /// Constructs a new [`LookbackStrategy`] providing the [`CandleHistory`] to the wrapped
+    /// strategy.
+    pub fn new(history: CandleHistory, strategy: Strategy) -> Self {
+        Self { strategy, history }
+    }
+
+    /// [`CandleHistory`] of the [`MarketEvent`]s consumed so far.
+    pub fn history(&self) -> &CandleHistory {
+        &self.history
+    }
+
+    /// Returns the wrapped strategy.
+    pub fn into_inner(self) -> Strategy {
+        self.strategy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle;
+    use barter_integration::model::Market;
+
+    /// Strategy that records the closes of the recent candles it is provided.
+    #[derive(Default)]
+    struct RecordingStrategy {
+        closes: Vec<Vec<f64>>,
+    }
+
+    impl HistorySignalGenerator for RecordingStrategy {
+        fn generate_signal_with_history(
+            &mut self,
+            market: &MarketEvent<Instrument, DataKind>,
+            history: &dyn HistoryProvider,
+        ) -> Option<Signal> {
+            let market = Market::new(market.exchange.clone(), market.instrument.clone());
+            self.closes.push(
+                history
+                    .recent_bars(&market, 2)
+                    .map(|candle| candle.close)
+                    .collect(),
+            );
+            None
+        }
+    }
+
+    #[test]
+    fn lookback_strategy_provides_history_including_current_candle() {
+        let mut strategy =
+            LookbackStrategy::new(CandleHistory::new(2).unwrap(), RecordingStrategy::default());
+
+        for close in [1.0, 2.0, 3.0] {
+            let mut market = market_event_candle();
+            if let DataKind::Candle(candle) = &mut market.kind {
+                candle.close = close;
+            }
+            assert_eq!(strategy.generate_signal(&market), None);
+        }
+
+        assert_eq!(
+            strategy.into_inner().closes,
+            vec![vec![1.0], vec![1.0, 2.0], vec![2.0, 3.0]]
+        );
+    }
+}
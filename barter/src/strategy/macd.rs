@@ -0,0 +1,236 @@
+use super::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use crate::data::MarketMeta;
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ta::{indicators::MovingAverageConvergenceDivergence, Next};
+
+/// Configuration for constructing a [`MACDStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub fast_period: usize,
+    pub slow_period: usize,
+    pub signal_period: usize,
+}
+
+#[derive(Clone, Debug)]
+/// Example MACD signal-line crossover strategy that implements [`SignalGenerator`].
+///
+/// A bullish crossover (MACD crossing above the signal line) advises [`Decision::Long`] &
+/// [`Decision::CloseShort`], and a bearish crossover (MACD crossing below the signal line) advises
+/// [`Decision::Short`] & [`Decision::CloseLong`]. No [`Signal`] is generated between crossovers.
+pub struct MACDStrategy {
+    macd: MovingAverageConvergenceDivergence,
+    prev_histogram: Option<f64>,
+}
+
+impl SignalGenerator for MACDStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Check if it's a MarketEvent with a candle
+        let (candle_close, candle_volume) = match &market.kind {
+            DataKind::Candle(candle) => (candle.close, candle.volume),
+            _ => return None,
+        };
+
+        // Calculate the next MACD histogram (MACD - signal line) using the new Candle close
+        let histogram = self.macd.next(candle_close).histogram;
+        let prev_histogram = self.prev_histogram.replace(histogram)?;
+
+        // Generate advisory signals map
+        let signals = MACDStrategy::generate_signals_map(prev_histogram, histogram);
+
+        // If signals map is empty, return no SignalEvent
+        if signals.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            time: Utc::now(),
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            market_meta: MarketMeta {
+                close: candle_close,
+                time: market.exchange_time,
+                volatility: None,
+                volume: Some(candle_volume),
+                spread: None,
+            },
+            signals,
+            tag: None,
+            stop_loss: None,
+            take_profit: None,
+        })
+    }
+}
+
+impl TryFrom<Config> for MACDStrategy {
+    type Error = StrategyError;
+
+    fn try_from(config: Config) -> Result<Self, Self::Error> {
+        Self::new(config)
+    }
+}
+
+impl MACDStrategy {
+    /// Constructs a new [`MACDStrategy`] component using the provided configuration struct.
+    ///
+    /// Returns a [`StrategyError::InvalidIndicatorPeriod`] if any MACD period is invalid (eg/ 0).
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        let macd_indicator = MovingAverageConvergenceDivergence::new(
+            config.fast_period,
+            config.slow_period,
+            config.signal_period,
+        )
+        .map_err(|_| StrategyError::InvalidIndicatorPeriod {
+            indicator: "MACD",
+            period: [config.fast_period, config.slow_period, config.signal_period]
+                .into_iter()
+                .find(|period| *period == 0)
+                .unwrap_or_default(),
+        })?;
+
+        Ok(Self {
+            macd: macd_indicator,
+            prev_histogram: None,
+        })
+    }
+
+    /// Given the previous & latest MACD histogram values for a symbol, generates a map containing
+    /// the [`SignalStrength`] for [`Decision`] under consideration.
+    fn generate_signals_map(
+        prev_histogram: f64,
+        histogram: f64,
+    ) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(2);
+        if prev_histogram <= 0.0 && histogram > 0.0 {
+            signals.insert(Decision::Long, MACDStrategy::calculate_signal_strength());
+            signals.insert(
+                Decision::CloseShort,
+                MACDStrategy::calculate_signal_strength(),
+            );
+        }
+        if prev_histogram >= 0.0 && histogram < 0.0 {
+            signals.insert(Decision::Short, MACDStrategy::calculate_signal_strength());
+            signals.insert(
+                Decision::CloseLong,
+                MACDStrategy::calculate_signal_strength(),
+            );
+        }
+        signals
+    }
+
+    /// Calculates the [`SignalStrength`] of a particular [`Decision`].
+    fn calculate_signal_strength() -> SignalStrength {
+        SignalStrength(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{market_event_candle, market_event_trade};
+    use barter_integration::model::Side;
+
+    fn candle_event(close: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        if let DataKind::Candle(candle) = &mut event.kind {
+            candle.close = close;
+        }
+        event
+    }
+
+    fn decisions(signal: Option<Signal>) -> Vec<Decision> {
+        let mut decisions = signal
+            .map(|signal| signal.signals.into_keys().collect::<Vec<_>>())
+            .unwrap_or_default();
+        decisions.sort();
+        decisions
+    }
+
+    fn strategy() -> MACDStrategy {
+        MACDStrategy::new(Config {
+            fast_period: 3,
+            slow_period: 6,
+            signal_period: 4,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn construct_macd_strategy_with_invalid_period_returns_error() {
+        assert_eq!(
+            MACDStrategy::new(Config {
+                fast_period: 3,
+                slow_period: 0,
+                signal_period: 4,
+            })
+            .unwrap_err(),
+            StrategyError::InvalidIndicatorPeriod {
+                indicator: "MACD",
+                period: 0
+            }
+        );
+        assert!(MACDStrategy::try_from(Config {
+            fast_period: 12,
+            slow_period: 26,
+            signal_period: 9,
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn macd_strategy_generates_signals_on_signal_line_crossovers() {
+        let mut strategy = strategy();
+
+        // First candle initialises the MACD histogram
+        assert_eq!(strategy.generate_signal(&candle_event(2.0)), None);
+
+        // Bullish crossover as price starts rising
+        assert_eq!(
+            decisions(strategy.generate_signal(&candle_event(3.0))),
+            vec![Decision::Long, Decision::CloseShort]
+        );
+
+        // No further signals while MACD stays above the signal line
+        assert_eq!(strategy.generate_signal(&candle_event(4.2)), None);
+        assert_eq!(strategy.generate_signal(&candle_event(7.0)), None);
+
+        // Bearish crossover once price falls
+        let mut bearish = None;
+        for close in [6.0, 5.0, 4.0, 3.0] {
+            if let Some(signal) = strategy.generate_signal(&candle_event(close)) {
+                bearish = Some(signal);
+                break;
+            }
+        }
+        assert_eq!(
+            decisions(bearish),
+            vec![Decision::CloseLong, Decision::Short]
+        );
+
+        // Bullish crossover once price recovers
+        let mut bullish = None;
+        for close in [5.0, 7.0, 9.0, 11.0] {
+            if let Some(signal) = strategy.generate_signal(&candle_event(close)) {
+                bullish = Some(signal);
+                break;
+            }
+        }
+        assert_eq!(
+            decisions(bullish),
+            vec![Decision::Long, Decision::CloseShort]
+        );
+    }
+
+    #[test]
+    fn macd_strategy_ignores_non_candle_market_events() {
+        let mut strategy = strategy();
+        assert_eq!(
+            strategy.generate_signal(&market_event_trade(Side::Buy)),
+            None
+        );
+        assert_eq!(strategy.generate_signal(&candle_event(2.0)), None);
+    }
+}
@@ -1,19 +1,63 @@
+use self::error::StrategyError;
 use crate::data::MarketMeta;
 use barter_data::event::{DataKind, MarketEvent};
 use barter_integration::model::{instrument::Instrument, Exchange, Market};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Asynchronous [`SignalGenerator`] variant & an adapter for running it in a
+/// [`Trader`](crate::engine::trader::Trader).
+pub mod asynchronous;
+
+/// [`SignalGenerator`] wrapper that only forwards a [`Decision`] once it has persisted for a
+/// number of consecutive bars.
+pub mod confirmation;
+
+/// Barter example moving average crossover strategy [`SignalGenerator`] implementation.
+pub mod crossover;
+
+/// [`SignalGenerator`] that combines the [`Signal`]s of multiple member strategies.
+pub mod ensemble;
+
+/// Barter strategy module specific errors.
+pub mod error;
+
 /// Barter example RSI strategy [`SignalGenerator`] implementation.
 pub mod example;
 
+/// [`SignalGenerator`] wrapper providing a lookback window of recent candles to the inner
+/// strategy.
+pub mod lookback;
+
+/// Barter example MACD strategy [`SignalGenerator`] implementation.
+pub mod macd;
+
+/// Barter example multi-timeframe RSI strategy [`SignalGenerator`] implementation.
+pub mod multi_timeframe;
+
+/// Debug [`SignalGenerator`] wrapper that asserts a strategy does not look-ahead into the future.
+pub mod look_ahead;
+
 /// May generate an advisory [`Signal`] as a result of analysing an input [`MarketEvent`].
 pub trait SignalGenerator {
     /// Optionally return a [`Signal`] given input [`MarketEvent`].
     fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal>;
 }
 
+/// [`SignalGenerator`] whose internal state (eg/ warmed up indicators) can be snapshot & restored,
+/// enabling a live strategy to resume after a process restart without re-warming.
+pub trait PersistentState {
+    type State: Serialize + DeserializeOwned;
+
+    /// Snapshot the current internal state.
+    fn save_state(&self) -> Self::State;
+
+    /// Restore the internal state from a snapshot, returning a [`StrategyError`] if the snapshot
+    /// is incompatible with this strategy's configuration.
+    fn load_state(&mut self, state: Self::State) -> Result<(), StrategyError>;
+}
+
 /// Advisory [`Signal`] for a [`Market`] detailing the [`SignalStrength`] associated with each
 /// possible [`Decision`]. Interpreted by an [`OrderGenerator`](crate::portfolio::OrderGenerator).
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
@@ -24,6 +68,27 @@ pub struct Signal {
     pub signals: HashMap<Decision, SignalStrength>,
     /// Metadata propagated from the [`MarketEvent`] that yielded this [`Signal`].
     pub market_meta: MarketMeta,
+    /// Optional sub-strategy tag, enabling a Portfolio with a tag aware
+    /// [`PositionKey`](crate::portfolio::position::PositionKey) to hold a separate
+    /// [`Position`](crate::portfolio::position::Position) per tag in the same market.
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Optional stop-loss price level advised by the strategy (eg/ ATR based), enabling a risk
+    /// aware [`OrderGenerator`](crate::portfolio::OrderGenerator) to attach bracket orders.
+    #[serde(default)]
+    pub stop_loss: Option<f64>,
+    /// Optional take-profit price level advised by the strategy. See [`Signal::stop_loss`].
+    #[serde(default)]
+    pub take_profit: Option<f64>,
+}
+
+/// Outcome of an advisory [`Signal`], tagging whether it resulted in an
+/// [`OrderEvent`](crate::portfolio::OrderEvent) or was vetoed (eg/ by Portfolio allocation & risk
+/// management). Useful for measuring signal quality separately from execution & risk filtering.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SignalOutcome {
+    pub signal: Signal,
+    pub order_generated: bool,
 }
 
 /// Describes the type of advisory signal the strategy is endorsing.
@@ -63,6 +128,33 @@ impl Decision {
     }
 }
 
+/// Direction(s) a strategy is permitted to trade in (eg/ LongOnly for an account that cannot
+/// short).
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum TradeDirection {
+    LongOnly,
+    ShortOnly,
+    #[default]
+    Both,
+}
+
+impl TradeDirection {
+    /// Determines if a [`Decision`] is permitted by this [`TradeDirection`]. LongOnly only permits
+    /// [`Decision::Long`] & [`Decision::CloseLong`], and ShortOnly only permits [`Decision::Short`]
+    /// & [`Decision::CloseShort`].
+    pub fn permits(&self, decision: &Decision) -> bool {
+        match self {
+            TradeDirection::LongOnly => matches!(decision, Decision::Long | Decision::CloseLong),
+            TradeDirection::ShortOnly => {
+                matches!(decision, Decision::Short | Decision::CloseShort)
+            }
+            TradeDirection::Both => true,
+        }
+    }
+}
+
 /// Strength of an advisory [`Signal`] decision produced by [`SignalGenerator`] strategy.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SignalStrength(pub f64);
@@ -74,6 +166,10 @@ pub struct SignalForceExit {
     pub time: DateTime<Utc>,
     pub exchange: Exchange,
     pub instrument: Instrument,
+    /// Optional sub-strategy tag of the [`Position`](crate::portfolio::position::Position) to
+    /// exit. See [`Signal::tag`].
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
 impl<M> From<M> for SignalForceExit
@@ -99,6 +195,7 @@ impl SignalForceExit {
             time: Utc::now(),
             exchange: exchange.into(),
             instrument: instrument.into(),
+            tag: None,
         }
     }
 }
@@ -154,4 +251,27 @@ mod tests {
         let decision = Decision::Long;
         assert_eq!(decision.is_exit(), false)
     }
+
+    #[test]
+    fn deserialise_signal_without_risk_levels_defaults_to_none() {
+        let signal = Signal {
+            stop_loss: Some(95.0),
+            take_profit: Some(110.0),
+            ..crate::test_util::signal()
+        };
+
+        let mut json = serde_json::to_value(&signal).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("stop_loss");
+        fields.remove("take_profit");
+
+        assert_eq!(
+            serde_json::from_value::<Signal>(json).unwrap(),
+            Signal {
+                stop_loss: None,
+                take_profit: None,
+                ..signal
+            }
+        );
+    }
 }
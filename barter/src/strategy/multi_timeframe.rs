@@ -0,0 +1,287 @@
+use super::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use crate::{
+    data::MarketMeta,
+    statistic::{de_duration_from_secs, se_duration_as_secs},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_integration::model::instrument::Instrument;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ta::{indicators::RelativeStrengthIndex, Next};
+
+/// Configuration for constructing a [`MultiTimeframeStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Period of the RSI tracked on both the entry & trend timeframes.
+    pub rsi_period: usize,
+    /// Interval of the higher trend timeframe (eg/ 3600 seconds to confirm a 1h trend before
+    /// taking entries on a 5m candle feed).
+    #[serde(
+        deserialize_with = "de_duration_from_secs",
+        serialize_with = "se_duration_as_secs"
+    )]
+    pub trend_interval: Duration,
+}
+
+/// Trend timeframe [`Candle`](barter_data::subscription::candle::Candle) close being aggregated
+/// from the entry timeframe candles, and the RSI of the completed trend timeframe candles.
+#[derive(Clone, Debug)]
+struct TrendTimeframe {
+    interval: Duration,
+    rsi: RelativeStrengthIndex,
+    window: Option<(DateTime<Utc>, f64)>,
+    latest_rsi: Option<f64>,
+}
+
+impl TrendTimeframe {
+    /// Aggregates the next entry timeframe candle close into the trend timeframe, updating the
+    /// trend RSI once the current trend timeframe candle is completed by a candle closing in the
+    /// next interval.
+    fn update(&mut self, close_time: DateTime<Utc>, close: f64) {
+        let Ok(window_start) = close_time.duration_trunc(self.interval) else {
+            return;
+        };
+
+        if let Some((current_start, current_close)) = self.window {
+            if current_start < window_start {
+                self.latest_rsi = Some(self.rsi.next(current_close));
+            }
+        }
+
+        self.window = Some((window_start, close));
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Example multi-timeframe strategy that implements [`SignalGenerator`], confirming the trend on a
+/// higher timeframe before taking entries on the timeframe of the candle feed.
+///
+/// A [`SignalGenerator`] receives a single [`MarketEvent`] feed, so the higher trend timeframe is
+/// derived from the entry timeframe candles themselves: each candle is assigned to the trend
+/// interval containing its `close_time`, and a trend candle is only completed once a candle from
+/// the next interval arrives, so the trend RSI never looks ahead. An RSI instance is maintained
+/// for each timeframe.
+///
+/// An oversold entry RSI during a bullish trend (trend RSI above 50) advises [`Decision::Long`] &
+/// [`Decision::CloseShort`], and an overbought entry RSI during a bearish trend (trend RSI below
+/// 50) advises [`Decision::Short`] & [`Decision::CloseLong`]. No [`Signal`] is generated against
+/// the trend, or before the first trend candle has completed.
+pub struct MultiTimeframeStrategy {
+    entry_rsi: RelativeStrengthIndex,
+    trend: TrendTimeframe,
+}
+
+impl SignalGenerator for MultiTimeframeStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Check if it's a MarketEvent with a candle
+        let candle = match &market.kind {
+            DataKind::Candle(candle) => candle,
+            _ => return None,
+        };
+
+        // Calculate the next entry RSI value, and aggregate the Candle into the trend timeframe
+        let entry_rsi = self.entry_rsi.next(candle.close);
+        self.trend.update(candle.close_time, candle.close);
+        let trend_rsi = self.trend.latest_rsi?;
+
+        // Generate advisory signals map
+        let signals = MultiTimeframeStrategy::generate_signals_map(entry_rsi, trend_rsi);
+
+        // If signals map is empty, return no SignalEvent
+        if signals.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            time: Utc::now(),
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            market_meta: MarketMeta {
+                close: candle.close,
+                time: market.exchange_time,
+                volatility: None,
+                volume: Some(candle.volume),
+                spread: None,
+            },
+            signals,
+            tag: None,
+            stop_loss: None,
+            take_profit: None,
+        })
+    }
+}
+
+impl TryFrom<Config> for MultiTimeframeStrategy {
+    type Error = StrategyError;
+
+    fn try_from(config: Config) -> Result<Self, Self::Error> {
+        Self::new(config)
+    }
+}
+
+impl MultiTimeframeStrategy {
+    /// Constructs a new [`MultiTimeframeStrategy`] component using the provided configuration
+    /// struct.
+    ///
+    /// Returns a [`StrategyError::InvalidIndicatorPeriod`] if the RSI period is invalid (eg/ 0),
+    /// or a [`StrategyError::InvalidInterval`] if the trend interval is not positive.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        if config.trend_interval <= Duration::zero() {
+            return Err(StrategyError::InvalidInterval(config.trend_interval));
+        }
+
+        let rsi = || {
+            RelativeStrengthIndex::new(config.rsi_period).map_err(|_| {
+                StrategyError::InvalidIndicatorPeriod {
+                    indicator: "RSI",
+                    period: config.rsi_period,
+                }
+            })
+        };
+
+        Ok(Self {
+            entry_rsi: rsi()?,
+            trend: TrendTimeframe {
+                interval: config.trend_interval,
+                rsi: rsi()?,
+                window: None,
+                latest_rsi: None,
+            },
+        })
+    }
+
+    /// Given the latest entry & trend RSI values for a symbol, generates a map containing the
+    /// [`SignalStrength`] for [`Decision`] under consideration.
+    fn generate_signals_map(entry_rsi: f64, trend_rsi: f64) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(2);
+        if entry_rsi < 40.0 && trend_rsi > 50.0 {
+            signals.insert(
+                Decision::Long,
+                MultiTimeframeStrategy::calculate_signal_strength(),
+            );
+            signals.insert(
+                Decision::CloseShort,
+                MultiTimeframeStrategy::calculate_signal_strength(),
+            );
+        }
+        if entry_rsi > 60.0 && trend_rsi < 50.0 {
+            signals.insert(
+                Decision::Short,
+                MultiTimeframeStrategy::calculate_signal_strength(),
+            );
+            signals.insert(
+                Decision::CloseLong,
+                MultiTimeframeStrategy::calculate_signal_strength(),
+            );
+        }
+        signals
+    }
+
+    /// Calculates the [`SignalStrength`] of a particular [`Decision`].
+    fn calculate_signal_strength() -> SignalStrength {
+        SignalStrength(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle;
+
+    fn start() -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp(0, 0).unwrap()
+    }
+
+    /// Build a candle MarketEvent closing the provided number of minutes after the start.
+    fn candle_event(minutes: i64, close: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut event = market_event_candle();
+        event.exchange_time = start() + Duration::minutes(minutes);
+        if let DataKind::Candle(candle) = &mut event.kind {
+            candle.close_time = event.exchange_time;
+            candle.close = close;
+        }
+        event
+    }
+
+    fn strategy() -> MultiTimeframeStrategy {
+        MultiTimeframeStrategy::new(Config {
+            rsi_period: 3,
+            trend_interval: Duration::hours(1),
+        })
+        .unwrap()
+    }
+
+    /// Feed 20 minute candles with the provided closes, returning the Decisions of each Signal.
+    fn run(closes: &[f64]) -> Vec<Vec<Decision>> {
+        let mut strategy = strategy();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(bar, close)| {
+                let signal = strategy.generate_signal(&candle_event(bar as i64 * 20, *close));
+                let mut decisions = signal
+                    .map(|signal| signal.signals.into_keys().collect::<Vec<_>>())
+                    .unwrap_or_default();
+                decisions.sort();
+                decisions
+            })
+            .collect()
+    }
+
+    #[test]
+    fn construct_multi_timeframe_strategy_with_invalid_config_returns_error() {
+        assert_eq!(
+            MultiTimeframeStrategy::new(Config {
+                rsi_period: 0,
+                trend_interval: Duration::hours(1),
+            })
+            .unwrap_err(),
+            StrategyError::InvalidIndicatorPeriod {
+                indicator: "RSI",
+                period: 0
+            }
+        );
+        assert_eq!(
+            MultiTimeframeStrategy::new(Config {
+                rsi_period: 3,
+                trend_interval: Duration::zero(),
+            })
+            .unwrap_err(),
+            StrategyError::InvalidInterval(Duration::zero())
+        );
+    }
+
+    #[test]
+    fn multi_timeframe_strategy_only_takes_entries_with_the_trend() {
+        // Hourly uptrend, followed by a sharp dip on the entry timeframe
+        let decisions = run(&[100.0, 101.0, 102.0, 104.0, 105.0, 106.0, 108.0, 95.0]);
+
+        // No signals until the first trend candle completes, and none while entry RSI is neutral
+        assert!(decisions[..7].iter().all(Vec::is_empty));
+        assert_eq!(decisions[7], vec![Decision::Long, Decision::CloseShort]);
+
+        // Hourly downtrend, followed by the same dip: no entry against the trend
+        let decisions = run(&[108.0, 107.0, 106.0, 104.0, 103.0, 102.0, 100.0, 90.0]);
+        assert!(decisions.iter().all(Vec::is_empty));
+
+        // Hourly downtrend, followed by a sharp rally on the entry timeframe
+        let decisions = run(&[108.0, 107.0, 106.0, 104.0, 103.0, 102.0, 100.0, 115.0]);
+        assert_eq!(decisions[7], vec![Decision::CloseLong, Decision::Short]);
+    }
+
+    #[test]
+    fn multi_timeframe_strategy_trend_rsi_does_not_look_ahead() {
+        let mut strategy = strategy();
+
+        // Candles within the first hour do not complete a trend candle
+        for (minutes, close) in [(0, 100.0), (20, 101.0), (40, 102.0)] {
+            strategy.generate_signal(&candle_event(minutes, close));
+            assert_eq!(strategy.trend.latest_rsi, None);
+        }
+
+        // First candle of the second hour completes the first trend candle
+        strategy.generate_signal(&candle_event(60, 103.0));
+        assert!(strategy.trend.latest_rsi.is_some());
+    }
+}
@@ -53,6 +53,8 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
                 starting_equity: 10_000.0,
                 trading_days_per_year: 365,
                 risk_free_return: 0.0,
+                omega_threshold_return: 0.0,
+                return_kind: Default::default(),
             })
             .build_and_init()
             .expect("failed to build & initialise MetaPortfolio"),
@@ -74,13 +76,20 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
             .data(historical::MarketFeed::new(
                 [market_event_trade(Side::Buy)].into_iter(),
             ))
-            .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+            .strategy(
+                RSIStrategy::new(StrategyConfig {
+                    rsi_period: 14,
+                    ..StrategyConfig::default()
+                })
+                .unwrap(),
+            )
             .execution(SimulatedExecution::new(ExecutionConfig {
                 simulated_fees_pct: Fees {
                     exchange: 0.1,
                     slippage: 0.05,
                     network: 0.0,
                 },
+                ..Default::default()
             }))
             .build()
             .expect("failed to build trader"),
@@ -100,6 +109,8 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
             starting_equity: 1000.0,
             trading_days_per_year: 365,
             risk_free_return: 0.0,
+            omega_threshold_return: 0.0,
+            return_kind: Default::default(),
         }))
         .build()
         .expect("failed to build engine");